@@ -1,9 +1,10 @@
 //! Top level abstraction for buffer objects
 
 use alloc::prelude::v1::Box;
-use crate::{json_decode, json_flex::JSMAP, pointer::NP_Cursor_Parent, schema::{NP_Bytes_Data, NP_Map_List_Data, NP_String_Data, NP_Struct_Data, NP_Tuple_Data}};
+use core::marker::PhantomData;
+use crate::{json_decode, json_flex::JSMAP, pointer::NP_Cursor_Parent, schema::{NP_Bytes_Data, NP_Map_List_Data, NP_Portal_Data, NP_Schema, NP_String_Data, NP_Struct_Data, NP_Tuple_Data, NP_Uuid_Data}};
 use alloc::string::String;
-use crate::{NP_Size_Data, schema::NP_TypeKeys};
+use crate::{NP_Compact_Result, NP_Size_Data, schema::NP_TypeKeys};
 use crate::{memory::NP_Memory, utils::opt_err};
 use crate::collection::tuple::NP_Tuple;
 
@@ -17,6 +18,13 @@ use crate::{collection::{list::NP_List}};
 use crate::error::NP_Error;
 use crate::{json_flex::NP_JSON};
 use crate::alloc::borrow::ToOwned;
+use crate::pointer::dec::{NP_Dec, NP_DecReader, NP_RoundMode};
+use crate::pointer::dec128::NP_Dec128;
+use crate::pointer::uuid::NP_UUID;
+use crate::pointer::{bytes::NP_Bytes, date::NP_Date, geo::NP_Geo, option::NP_Enum, ulid::NP_ULID};
+use crate::pointer::sorted_uints::{NP_SortedUints, NP_SortedUintsIter};
+use alloc::string::ToString;
+use crate::pointer::numbers::{Endian, NP_Number, NP_NumberCast, NumericOverflowPolicy};
 
 /// The address location of the root pointer.
 #[doc(hidden)]
@@ -29,6 +37,11 @@ pub const VTABLE_SIZE: usize = 4;
 #[doc(hidden)]
 pub const VTABLE_BYTES: usize = 20;
 
+/// An owned path to a value in a buffer, one segment per collection level.
+/// 
+/// List and tuple indexes are stored as strings, so a path can be passed back into methods like `get` or `set`.
+pub type NP_Path = Vec<String>;
+
 
 /// Buffers contain the bytes of each object and allow you to perform reads, updates, deletes and compaction.
 /// 
@@ -39,7 +52,10 @@ pub struct NP_Buffer {
     memory: NP_Memory,
     /// Is this buffer mutable?
     pub mutable: bool,
-    cursor: NP_Cursor
+    cursor: NP_Cursor,
+    /// Read and write counts per path, see `access_stats`
+    #[cfg(feature = "access_stats")]
+    stats: core::cell::RefCell<alloc::collections::BTreeMap<NP_Path, (u64, u64)>>
 }
 
 unsafe impl Send for NP_Buffer {}
@@ -73,6 +89,44 @@ impl NP_Finished_Buffer {
     }
 }
 
+/// Read only view of a buffer that borrows it's bytes, created with [`NP_Factory::open_ref`](../struct.NP_Factory.html#method.open_ref).
+/// 
+/// Values are read straight out of the borrowed slice without copying it, which makes this a good fit for memory mapped files.  There are no methods to change the buffer.
+/// 
+#[derive(Debug)]
+pub struct NP_BufferRef<'buffer> {
+    buffer: NP_Buffer,
+    _bytes: PhantomData<&'buffer [u8]>
+}
+
+impl<'buffer> NP_BufferRef<'buffer> {
+
+    #[doc(hidden)]
+    pub fn _new(memory: NP_Memory) -> Self {
+        NP_BufferRef { buffer: NP_Buffer::_new(memory), _bytes: PhantomData }
+    }
+
+    /// Retrieve an inner value from the buffer, works the same as [`NP_Buffer::get`](struct.NP_Buffer.html#method.get).
+    pub fn get<'get, X: 'get>(&'get self, path: &[&str]) -> Result<Option<X>, NP_Error> where X: NP_Value<'get> + NP_Scalar<'get> {
+        self.buffer.get(path)
+    }
+
+    /// Get the length of a collection, works the same as [`NP_Buffer::get_length`](struct.NP_Buffer.html#method.get_length).
+    pub fn get_length(&self, path: &[&str]) -> Result<Option<usize>, NP_Error> {
+        self.buffer.get_length(path)
+    }
+
+    /// Copy a value and all it's children into JSON, works the same as [`NP_Buffer::json_encode`](struct.NP_Buffer.html#method.json_encode).
+    pub fn json_encode(&self, path: &[&str]) -> Result<NP_JSON, NP_Error> {
+        self.buffer.json_encode(path)
+    }
+
+    /// The borrowed bytes of this buffer
+    pub fn read_bytes(&self) -> &[u8] {
+        self.buffer.read_bytes()
+    }
+}
+
 impl NP_Buffer {
 
     #[doc(hidden)]
@@ -81,7 +135,47 @@ impl NP_Buffer {
         NP_Buffer {
             cursor: NP_Cursor::new(memory.root, 0, 0),
             mutable: memory.is_mutable,
-            memory: memory
+            memory: memory,
+            #[cfg(feature = "access_stats")]
+            stats: core::cell::RefCell::new(alloc::collections::BTreeMap::new())
+        }
+    }
+
+    /// Get how many times each path has been read with `get` and written on this buffer.
+    /// 
+    /// Every method that changes a value at a path counts as a write, including `set_with_json`, `list_push`, `next_seq`, `del` and the list builder.
+    /// 
+    /// Each entry is `(path, reads, writes)`, sorted by path.  Counting only happens with the `access_stats` feature enabled, so normal builds pay nothing for it.
+    /// 
+    /// ```
+    /// # #[cfg(feature = "access_stats")] {
+    /// use no_proto::NP_Factory;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("struct({fields: {age: u8()}})")?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.set(&["age"], 20u8)?;
+    /// new_buffer.get::<u8>(&["age"])?;
+    /// 
+    /// assert_eq!(new_buffer.access_stats(), vec![(vec![String::from("age")], 1, 1)]);
+    /// 
+    /// # }
+    /// # Ok::<(), no_proto::error::NP_Error>(()) 
+    /// ```
+    /// 
+    #[cfg(feature = "access_stats")]
+    pub fn access_stats(&self) -> Vec<(NP_Path, u64, u64)> {
+        self.stats.borrow().iter().map(|(path, (reads, writes))| (path.clone(), *reads, *writes)).collect()
+    }
+
+    #[cfg(feature = "access_stats")]
+    fn count_access(&self, path: &[&str], write: bool) {
+        let mut stats = self.stats.borrow_mut();
+        let counts = stats.entry(path.iter().map(|segment| segment.to_string()).collect()).or_insert((0, 0));
+        if write {
+            counts.1 += 1;
+        } else {
+            counts.0 += 1;
         }
     }
 
@@ -126,6 +220,240 @@ impl NP_Buffer {
 
     }
 
+    /// Export the whole buffer as indented JSON, each level is indented by `indent` spaces.
+    /// 
+    /// The output has the same shape as `json_encode(&[])`, but keys are always written in a stable order: struct fields follow the order they are declared in the schema and map keys are sorted.  The same data always gives the same string, which makes this useful for test fixtures and diffs.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new(r#"
+    ///     struct({fields: {
+    ///         name: string(),
+    ///         tags: map({value: u8()})
+    ///     }})
+    /// "#)?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.set(&["tags", "zeta"], 2u8)?;
+    /// new_buffer.set(&["tags", "alpha"], 1u8)?;
+    /// new_buffer.set(&["name"], "Jeb Kermin")?;
+    /// 
+    /// assert_eq!(new_buffer.to_json_pretty(2)?, r#"{
+    ///   "value": {
+    ///     "name": "Jeb Kermin",
+    ///     "tags": {
+    ///       "alpha": 1,
+    ///       "zeta": 2
+    ///     }
+    ///   }
+    /// }"#);
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn to_json_pretty(&self, indent: usize) -> Result<String, NP_Error> {
+        let json = match self.json_encode(&[])? {
+            NP_JSON::Dictionary(mut map) => {
+                let value = map.get_mut("value").map(|x| core::mem::replace(x, NP_JSON::Null)).unwrap_or(NP_JSON::Null);
+                let mut ordered = JSMAP::new();
+                ordered.insert(String::from("value"), NP_Buffer::_schema_order(0, value, self.cursor.schema_addr, self.memory.get_schemas()));
+                NP_JSON::Dictionary(ordered)
+            },
+            other => other
+        };
+
+        Ok(json.stringify_pretty(indent))
+    }
+
+    /// Is this buffer reading and writing with the given parsed schema?
+    pub(crate) fn _uses_schema(&self, schema: &Vec<NP_Parsed_Schema>) -> bool {
+        core::ptr::eq(self.memory.schema, schema)
+    }
+
+    /// Compare two buffers value by value, used by `NP_Factory::buffers_equal`.
+    /// 
+    /// The scalars of both buffers are walked in path order.  A scalar missing from one side (because it's collection or map key isn't there) is treated the same as an unset one.
+    pub(crate) fn _values_eq(a: &NP_Buffer, b: &NP_Buffer, semantic: bool) -> Result<bool, NP_Error> {
+        let a_leaves = a._sorted_leaves()?;
+        let b_leaves = b._sorted_leaves()?;
+
+        let (mut a_idx, mut b_idx) = (0usize, 0usize);
+
+        while a_idx < a_leaves.len() || b_idx < b_leaves.len() {
+            let order = match (a_leaves.get(a_idx), b_leaves.get(b_idx)) {
+                (Some(x), Some(y)) => x.0.cmp(&y.0),
+                (Some(_), None) => core::cmp::Ordering::Less,
+                _ => core::cmp::Ordering::Greater
+            };
+
+            let (a_leaf, b_leaf) = match order {
+                core::cmp::Ordering::Equal => {
+                    a_idx += 1;
+                    b_idx += 1;
+                    (Some(&a_leaves[a_idx - 1].1), Some(&b_leaves[b_idx - 1].1))
+                },
+                core::cmp::Ordering::Less => {
+                    a_idx += 1;
+                    (Some(&a_leaves[a_idx - 1].1), None)
+                },
+                core::cmp::Ordering::Greater => {
+                    b_idx += 1;
+                    (None, Some(&b_leaves[b_idx - 1].1))
+                }
+            };
+
+            if !NP_Buffer::_leaf_eq(a_leaf, &a.memory, b_leaf, &b.memory, semantic)? {
+                return Ok(false)
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Every scalar in the buffer with it's path, sorted by path.
+    fn _sorted_leaves(&self) -> Result<Vec<(NP_Path, NP_Cursor)>, NP_Error> {
+        let mut leaves: Vec<(NP_Path, NP_Cursor)> = Vec::new();
+        let mut path: NP_Path = Vec::new();
+
+        NP_Cursor::for_each_leaf(0, self.cursor.clone(), &self.memory, &mut path, &mut |leaf_path, leaf| {
+            leaves.push((leaf_path.clone(), leaf.clone()));
+            Ok(())
+        })?;
+
+        leaves.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Ok(leaves)
+    }
+
+    /// Compare the decoded values of two scalars at the same path, `None` is a scalar that isn't in the buffer.
+    fn _leaf_eq(a: Option<&NP_Cursor>, a_memory: &NP_Memory, b: Option<&NP_Cursor>, b_memory: &NP_Memory, semantic: bool) -> Result<bool, NP_Error> {
+        let schema_addr = match (a, b) {
+            (Some(x), _) => x.schema_addr,
+            (None, Some(y)) => y.schema_addr,
+            (None, None) => return Ok(true)
+        };
+
+        let kind = a_memory.get_schema(schema_addr).i;
+
+        // reads fill in the schema default for every other type, numbers and decimals only get it in semantic mode
+        let fill = semantic || !matches!(kind,
+            NP_TypeKeys::Int8 | NP_TypeKeys::Int16 | NP_TypeKeys::Int32 | NP_TypeKeys::Int64 |
+            NP_TypeKeys::Uint8 | NP_TypeKeys::Uint16 | NP_TypeKeys::Uint32 | NP_TypeKeys::Uint64 |
+            NP_TypeKeys::Float | NP_TypeKeys::Double | NP_TypeKeys::Decimal
+        );
+
+        macro_rules! leaf_eq {
+            ($t: ty) => {{
+                let (x, y) = NP_Buffer::_leaf_values::<$t>(a, a_memory, b, b_memory, schema_addr, fill)?;
+                x == y
+            }}
+        }
+
+        macro_rules! float_leaf_eq {
+            ($t: ty) => {{
+                match NP_Buffer::_leaf_values::<$t>(a, a_memory, b, b_memory, schema_addr, fill)? {
+                    // a NaN equals another NaN so a buffer always equals it's own copy
+                    (Some(x), Some(y)) => x == y || (x.is_nan() && y.is_nan()),
+                    (x, y) => x == y
+                }
+            }}
+        }
+
+        Ok(match kind {
+            NP_TypeKeys::UTF8String   => leaf_eq!(String),
+            NP_TypeKeys::Bytes        => leaf_eq!(NP_Bytes),
+            NP_TypeKeys::Int8         => leaf_eq!(i8),
+            NP_TypeKeys::Int16        => leaf_eq!(i16),
+            NP_TypeKeys::Int32        => leaf_eq!(i32),
+            NP_TypeKeys::Int64        => leaf_eq!(i64),
+            NP_TypeKeys::Uint8        => leaf_eq!(u8),
+            NP_TypeKeys::Uint16       => leaf_eq!(u16),
+            NP_TypeKeys::Uint32       => leaf_eq!(u32),
+            NP_TypeKeys::Uint64       => leaf_eq!(u64),
+            NP_TypeKeys::Float        => float_leaf_eq!(f32),
+            NP_TypeKeys::Double       => float_leaf_eq!(f64),
+            NP_TypeKeys::Decimal      => leaf_eq!(NP_Dec),
+            NP_TypeKeys::Decimal128   => leaf_eq!(NP_Dec128),
+            NP_TypeKeys::SortedUints  => leaf_eq!(NP_SortedUints),
+            NP_TypeKeys::Boolean      => leaf_eq!(bool),
+            NP_TypeKeys::Geo          => leaf_eq!(NP_Geo),
+            NP_TypeKeys::Uuid         => leaf_eq!(NP_UUID),
+            NP_TypeKeys::Ulid         => leaf_eq!(NP_ULID),
+            NP_TypeKeys::Date         => leaf_eq!(NP_Date),
+            NP_TypeKeys::Enum         => leaf_eq!(NP_Enum),
+            // collections are walked into, they never show up as leaves
+            NP_TypeKeys::None | NP_TypeKeys::Any | NP_TypeKeys::Struct | NP_TypeKeys::Map |
+            NP_TypeKeys::List | NP_TypeKeys::Tuple | NP_TypeKeys::Portal => true
+        })
+    }
+
+    /// Read the values of two scalars, with the schema default filled in for unset ones when `fill` is set.
+    fn _leaf_values<'value, T: NP_Value<'value>>(a: Option<&NP_Cursor>, a_memory: &'value NP_Memory, b: Option<&NP_Cursor>, b_memory: &'value NP_Memory, schema_addr: usize, fill: bool) -> Result<(Option<T>, Option<T>), NP_Error> {
+        let read = |cursor: Option<&NP_Cursor>, memory: &'value NP_Memory| -> Result<Option<T>, NP_Error> {
+            let value = match cursor {
+                Some(x) => T::into_value(x, memory)?,
+                None => None
+            };
+            Ok(match value {
+                Some(x) => Some(x),
+                None if fill => T::default_value(0, schema_addr, memory.get_schemas()),
+                None => None
+            })
+        };
+
+        Ok((read(a, a_memory)?, read(b, b_memory)?))
+    }
+
+    /// Put the keys of a JSON value into schema order, struct fields in declaration order and map keys sorted.
+    fn _schema_order(depth: usize, json: NP_JSON, schema_addr: usize, schemas: &Vec<NP_Parsed_Schema>) -> NP_JSON {
+
+        if depth > 255 { return json }
+
+        let schema = &schemas[schema_addr];
+
+        match (schema.i, json) {
+            (NP_TypeKeys::Struct, NP_JSON::Dictionary(mut map)) => {
+                let data = unsafe { &*(*schema.data as *const NP_Struct_Data) };
+                let mut ordered = JSMAP::new();
+                for field in data.fields.iter() {
+                    if let Some(value) = map.get_mut(field.col.as_str()) {
+                        let value = core::mem::replace(value, NP_JSON::Null);
+                        ordered.insert(field.col.clone(), NP_Buffer::_schema_order(depth + 1, value, field.schema, schemas));
+                    }
+                }
+                NP_JSON::Dictionary(ordered)
+            },
+            (NP_TypeKeys::Map, NP_JSON::Dictionary(mut map)) => {
+                let data = unsafe { &*(*schema.data as *const NP_Map_List_Data) };
+                map.values.sort_by(|a, b| a.0.cmp(&b.0));
+                map.values = map.values.into_iter().map(|(key, value)| {
+                    (key, NP_Buffer::_schema_order(depth + 1, value, data.child, schemas))
+                }).collect();
+                NP_JSON::Dictionary(map)
+            },
+            (NP_TypeKeys::List, NP_JSON::Array(items)) => {
+                let data = unsafe { &*(*schema.data as *const NP_Map_List_Data) };
+                NP_JSON::Array(items.into_iter().map(|item| NP_Buffer::_schema_order(depth + 1, item, data.child, schemas)).collect())
+            },
+            (NP_TypeKeys::Tuple, NP_JSON::Array(items)) => {
+                let data = unsafe { &*(*schema.data as *const NP_Tuple_Data) };
+                NP_JSON::Array(items.into_iter().enumerate().map(|(index, item)| {
+                    match data.values.get(index) {
+                        Some(value) => NP_Buffer::_schema_order(depth + 1, item, value.schema, schemas),
+                        None => item
+                    }
+                }).collect())
+            },
+            (NP_TypeKeys::Portal, json) => {
+                let data = unsafe { &*(*schema.data as *const NP_Portal_Data) };
+                NP_Buffer::_schema_order(depth + 1, json, data.schema, schemas)
+            },
+            (_, json) => json
+        }
+    }
+
     /// Finish the buffer.
     /// 
     /// If the buffer is an onwed type typically opened with `.open_buffer` or created with `.new_empty` you will get the bytes of the buffer returned from this method.
@@ -184,6 +512,41 @@ impl NP_Buffer {
         self.cursor = NP_Cursor::new(self.memory.root, 0, 0);
     }
 
+    /// Remove all values from the buffer while keeping it's allocated memory.
+    /// 
+    /// The buffer ends up in the same state as a new empty buffer, but the underlying `Vec<u8>` keeps it's capacity so filling it up again doesn't need to reallocate.  This is much cheaper than `compact`, which copies every value into new memory.
+    /// 
+    /// Your cursor location will be reset to the root.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("struct({fields: {name: string(), age: u8()}})")?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.set(&["name"], "Jeb Kermin")?;
+    /// new_buffer.set(&["age"], 30u8)?;
+    /// 
+    /// new_buffer.clear()?;
+    /// assert_eq!(new_buffer.get::<&str>(&["name"])?, None);
+    /// assert_eq!(new_buffer.read_bytes(), factory.new_buffer(None).read_bytes());
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn clear(&mut self) -> Result<(), NP_Error> {
+
+        if self.mutable == false {
+            return Err(NP_Error::MemoryReadOnly)
+        }
+
+        self.memory.clear()?;
+        self.cursor_to_root();
+
+        Ok(())
+    }
+
     /// Set the max value allowed for the specific data type at the given key.
     /// 
     /// String & Byte types only work if a `size` property is set in the schema.
@@ -235,6 +598,9 @@ impl NP_Buffer {
             return Err(NP_Error::MemoryReadOnly)
         }
 
+        #[cfg(feature = "access_stats")]
+        self.count_access(path, true);
+
         let value_cursor = NP_Cursor::select(&self.memory, self.cursor.clone(), self.mutable, false, path)?;
         match value_cursor {
             Some(x) => {
@@ -296,6 +662,9 @@ impl NP_Buffer {
             return Err(NP_Error::MemoryReadOnly)
         }
 
+        #[cfg(feature = "access_stats")]
+        self.count_access(path, true);
+
         let value_cursor = NP_Cursor::select(&self.memory, self.cursor.clone(), self.mutable, false, path)?;
         match value_cursor {
             Some(x) => {
@@ -305,6 +674,126 @@ impl NP_Buffer {
         }
     }
 
+    /// Get the address and length in bytes of the value stored for a scalar field, for debugging buffer layouts.
+    /// 
+    /// The address is the offset into `read_bytes()` where the value starts, the length is the number of bytes the value takes there.  If no value is stored at the path, because it was never set or it was removed with `del`, you'll get `None`.
+    /// 
+    /// Collections return an error, their values aren't stored in a single location.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("struct({fields: {age: u16(), name: string()}})")?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.set(&["age"], 2048u16)?;
+    /// 
+    /// let (address, length) = new_buffer.value_location(&["age"])?.unwrap();
+    /// assert_eq!(length, 2);
+    /// assert_eq!(&new_buffer.read_bytes()[address..(address + length)], &[8, 0]);
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn value_location(&self, path: &[&str]) -> Result<Option<(usize, usize)>, NP_Error> {
+
+        let value_cursor = match NP_Cursor::select(&self.memory, self.cursor.clone(), false, false, path)? {
+            Some(x) => x,
+            None => return Ok(None)
+        };
+
+        let schema_type = self.memory.get_schema(value_cursor.schema_addr).i;
+
+        match schema_type {
+            NP_TypeKeys::Struct | NP_TypeKeys::Tuple | NP_TypeKeys::List | NP_TypeKeys::Map => {
+                let mut err = "TypeError: Attempted to get value location of collection type (".to_owned();
+                err.push_str(schema_type.into_type_idx().0);
+                err.push_str(")\n");
+                return Err(NP_Error::new(err));
+            },
+            _ => { }
+        }
+
+        let value = value_cursor.get_value(&self.memory);
+
+        let value_addr = value.get_addr_value() as usize;
+
+        if value_addr == 0 {
+            return Ok(None);
+        }
+
+        // calc_size includes the pointer, only the value is wanted here
+        let value_size = NP_Cursor::calc_size(0, &value_cursor, &self.memory)? - value.get_size();
+
+        Ok(Some((value_addr, value_size)))
+    }
+
+    /// Get the smallest and largest values a scalar field can hold as JSON, returned as `(min, max)`.
+    /// 
+    /// These are the same values `set_min` and `set_max` would write, serialized the same way `json_encode` serializes the field.  Useful for driving range inputs in a UI.  Nothing is written to this buffer.
+    /// 
+    /// Collections and types without bounds return an error.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::json_flex::NP_JSON;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("struct({fields: {age: u8()}})")?;
+    /// 
+    /// let new_buffer = factory.new_buffer(None);
+    /// 
+    /// let (min, max) = new_buffer.bounds_json(&["age"])?;
+    /// assert_eq!(min.stringify(), "0");
+    /// assert_eq!(max.stringify(), "255");
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn bounds_json(&self, path: &[&str]) -> Result<(NP_JSON, NP_JSON), NP_Error> {
+
+        let value_cursor = match NP_Cursor::select(&self.memory, self.cursor.clone(), false, true, path)? {
+            Some(x) => x,
+            None => return Err(NP_Error::new("Path not found for bounds!"))
+        };
+
+        let schema_type = self.memory.get_schema(value_cursor.schema_addr).i;
+
+        match schema_type {
+            NP_TypeKeys::Struct | NP_TypeKeys::Tuple | NP_TypeKeys::List | NP_TypeKeys::Map => {
+                let mut err = "TypeError: Attempted to get bounds of collection type (".to_owned();
+                err.push_str(schema_type.into_type_idx().0);
+                err.push_str(")\n");
+                return Err(NP_Error::new(err));
+            },
+            _ => { }
+        }
+
+        // write each bound into an empty scratch buffer using the same schema, then read it back out
+        let bound_json = |max: bool| -> Result<NP_JSON, NP_Error> {
+            let scratch = self.memory.new_empty(Some(64))?;
+            let cursor = NP_Cursor::new(scratch.root, value_cursor.schema_addr, value_cursor.parent_schema_addr);
+
+            let has_bound = if max {
+                NP_Cursor::set_max(cursor.clone(), &scratch)?
+            } else {
+                NP_Cursor::set_min(cursor.clone(), &scratch)?
+            };
+
+            if has_bound == false {
+                let mut err = "TypeError: Schema of type (".to_owned();
+                err.push_str(schema_type.into_type_idx().0);
+                err.push_str(") doesn't have bounds\n");
+                return Err(NP_Error::new(err));
+            }
+
+            Ok(NP_Cursor::json_encode(0, &cursor, &scratch))
+        };
+
+        Ok((bound_json(false)?, bound_json(true)?))
+    }
+
     /// Used to set scalar values inside the buffer.
     /// 
     /// The type that you set with will be compared to the schema, if it doesn't match the schema the request will fail.
@@ -336,6 +825,9 @@ impl NP_Buffer {
             return Err(NP_Error::MemoryReadOnly);
         }
 
+        #[cfg(feature = "access_stats")]
+        self.count_access(path, true);
+
         let value_cursor = NP_Cursor::select(&self.memory, self.cursor.clone(), self.mutable, false, path)?;
         match value_cursor {
             Some(x) => {
@@ -361,697 +853,1819 @@ impl NP_Buffer {
         }
     }
 
-    /// Set value with JSON
-    /// 
-    /// This works with all types including portals.
+    /// Set a number only if the value currently in the buffer equals `expected`, returns `true` if the new value was written.
     /// 
-    /// Data that doesn't align with the schema will be ignored.  `Null` and `undefined` values will be ignored.
-    /// 
-    /// Partial updates just merge the provided values into the buffer, you only need to provide the values you'd like changed.  This method cannot be used to delete values.
+    /// Pass `None` as `expected` to only write when the value hasn't been set.  Schema defaults are ignored, only the value stored in the buffer is compared.
     /// 
-    /// Using the `.set()` method is far more performant.  I recommend only using this on the client side of your application.
+    /// This is a logical compare and set for building optimistic update checks, like bumping a version number only if it hasn't changed since it was read.  It is **not** an atomic operation across threads, buffers need `&mut` access to change anyway.
     /// 
     /// ```
     /// use no_proto::error::NP_Error;
     /// use no_proto::NP_Factory;
-    /// use no_proto::NP_Size_Data;
     /// 
-    /// let factory: NP_Factory = NP_Factory::new("list({of: string()})")?;
+    /// let factory: NP_Factory = NP_Factory::new("struct({fields: {version: u32()}})")?;
     /// 
     /// let mut new_buffer = factory.new_buffer(None);
-    /// new_buffer.set_with_json(&[], r#"{"value": ["foo", "bar", null, "baz"]}"#)?;
-    ///    
-    /// assert_eq!(new_buffer.get_length(&[])?, Some(4));
-    /// assert_eq!(new_buffer.get::<&str>(&["0"])?, Some("foo"));
-    /// assert_eq!(new_buffer.get::<&str>(&["1"])?, Some("bar"));
-    /// assert_eq!(new_buffer.get::<&str>(&["2"])?, None);
-    /// assert_eq!(new_buffer.get::<&str>(&["3"])?, Some("baz"));
     /// 
-    /// new_buffer.set_with_json(&["2"], r#"{"value": "bazzy"}"#)?;
-    /// assert_eq!(new_buffer.get::<&str>(&["2"])?, Some("bazzy"));
+    /// assert_eq!(new_buffer.compare_and_set(&["version"], None, 1u32)?, true);
+    /// assert_eq!(new_buffer.compare_and_set(&["version"], Some(1u32), 2u32)?, true);
     /// 
+    /// // someone else already moved to version 2
+    /// assert_eq!(new_buffer.compare_and_set(&["version"], Some(1u32), 2u32)?, false);
+    /// assert_eq!(new_buffer.get::<u32>(&["version"])?, Some(2));
     /// 
     /// # Ok::<(), NP_Error>(()) 
     /// ```
     /// 
-    pub fn set_with_json<S: Into<String>>(&mut self, path: &[&str], json_value: S) -> Result<bool, NP_Error> {
+    pub fn compare_and_set<X>(&mut self, path: &[&str], expected: Option<X>, new: X) -> Result<bool, NP_Error> where X: for<'x> NP_Value<'x> + for<'x> NP_Scalar<'x> + NP_Number + PartialEq {
 
         if self.mutable == false {
-            return Err(NP_Error::MemoryReadOnly)
+            return Err(NP_Error::MemoryReadOnly);
         }
 
-        let value_cursor = NP_Cursor::select(&self.memory, self.cursor.clone(), self.mutable, false, path)?;
-        match value_cursor {
-            Some(x) => {
-                let parsed = json_decode(json_value.into())?;
+        #[cfg(feature = "access_stats")]
+        self.count_access(path, true);
 
-                match parsed["value"] {
-                    NP_JSON::Null => {
-                        return Err(NP_Error::new(".set_with_json requires `value` property!"))
-                    },
-                    _ => {
-                        NP_Cursor::set_from_json(0, false, x, &self.memory, &Box::new(parsed["value"].clone()))?;
-                    }
-                }
-                
-                Ok(true)
-            }
-            None => Ok(false)
+        let value_cursor = match NP_Cursor::select(&self.memory, self.cursor.clone(), true, false, path)? {
+            Some(x) => x,
+            None => return Ok(false)
+        };
+
+        // type does not match schema
+        if X::type_idx().1 != self.memory.get_schema(value_cursor.schema_addr).i {
+            let mut err = "TypeError: Attempted to set value for type (".to_owned();
+            err.push_str(X::type_idx().0);
+            err.push_str(") into schema of type (");
+            err.push_str(self.memory.get_schema(value_cursor.schema_addr).i.into_type_idx().0);
+            err.push_str(")\n");
+            return Err(NP_Error::new(err));
+        }
+
+        if X::into_value(&value_cursor, &self.memory)? != expected {
+            return Ok(false);
+        }
+
+        if value_cursor.parent_type == NP_Cursor_Parent::Tuple {
+            self.memory.write_bytes()[value_cursor.buff_addr - 1] = 1;
         }
+
+        X::set_value(value_cursor, &self.memory, new)?;
+
+        Ok(true)
     }
 
-    
-    /// Get an iterator for a collection
+    /// Set any integer into a numeric field, converting it to the integer type declared in the schema.
     /// 
+    /// This lets the type of your Rust variable differ from the exact width in the schema, a `u16` can be stored in a `u32` field or an `i64` in a `u8` field as long as the value fits.  If the value is out of range for the schema type an error is returned and nothing is written.
+    /// 
+    /// Only integer schema types (`i8` through `u64`) are supported.
     /// 
-    /// ## List Example
     /// ```
     /// use no_proto::error::NP_Error;
     /// use no_proto::NP_Factory;
-    /// use no_proto::NP_Size_Data;
     /// 
-    /// let factory: NP_Factory = NP_Factory::new("list({of: string()})")?;
+    /// let factory: NP_Factory = NP_Factory::new("struct({fields: {count: u32(), small: u8()}})")?;
     /// 
     /// let mut new_buffer = factory.new_buffer(None);
-    /// // set value at 1 index
-    /// new_buffer.set(&["1"], "hello")?;
-    /// // set value at 4 index
-    /// new_buffer.set(&["4"], "world")?;
-    /// // push value onto the end
-    /// new_buffer.list_push(&[], "!")?;
     /// 
-    /// // get iterator of root (list item)
-    /// new_buffer.get_collection(&[])?.unwrap().into_iter().for_each(|item| {
-    ///     match item.index {
-    ///         0 => assert_eq!(item.get::<&str>().unwrap(), None),
-    ///         1 => assert_eq!(item.get::<&str>().unwrap(), Some("hello")),
-    ///         2 => assert_eq!(item.get::<&str>().unwrap(), None),
-    ///         3 => assert_eq!(item.get::<&str>().unwrap(), None),
-    ///         4 => assert_eq!(item.get::<&str>().unwrap(), Some("world")),
-    ///         5 => assert_eq!(item.get::<&str>().unwrap(), Some("!")),
-    ///         _ => panic!()
-    ///     };
-    /// });
+    /// new_buffer.set_numeric(&["count"], 500u16)?;
+    /// assert_eq!(new_buffer.get::<u32>(&["count"])?, Some(500));
+    /// 
+    /// assert!(new_buffer.set_numeric(&["small"], 300i64).is_err());
+    /// assert_eq!(new_buffer.get::<u8>(&["small"])?, None);
     /// 
     /// # Ok::<(), NP_Error>(()) 
     /// ```
     /// 
-    /// ## Struct Example
+    pub fn set_numeric<X: Into<i128>>(&mut self, path: &[&str], value: X) -> Result<(), NP_Error> {
+
+        if self.mutable == false {
+            return Err(NP_Error::MemoryReadOnly);
+        }
+
+        #[cfg(feature = "access_stats")]
+        self.count_access(path, true);
+
+        let value_cursor = match NP_Cursor::select(&self.memory, self.cursor.clone(), true, false, path)? {
+            Some(x) => x,
+            None => return Err(NP_Error::new("Path not found for numeric set!"))
+        };
+
+        let value: i128 = value.into();
+        let schema_type = self.memory.get_schema(value_cursor.schema_addr).i;
+
+        let out_of_range = || {
+            let mut err = "RangeError: Value (".to_owned();
+            err.push_str(value.to_string().as_str());
+            err.push_str(") does not fit into schema of type (");
+            err.push_str(schema_type.into_type_idx().0);
+            err.push_str(")\n");
+            NP_Error::new(err)
+        };
+
+        use core::convert::TryFrom;
+
+        // check the value fits before touching the buffer
+        match schema_type {
+            NP_TypeKeys::Int8   => { i8::try_from(value).map_err(|_| out_of_range())?; },
+            NP_TypeKeys::Int16  => { i16::try_from(value).map_err(|_| out_of_range())?; },
+            NP_TypeKeys::Int32  => { i32::try_from(value).map_err(|_| out_of_range())?; },
+            NP_TypeKeys::Int64  => { i64::try_from(value).map_err(|_| out_of_range())?; },
+            NP_TypeKeys::Uint8  => { u8::try_from(value).map_err(|_| out_of_range())?; },
+            NP_TypeKeys::Uint16 => { u16::try_from(value).map_err(|_| out_of_range())?; },
+            NP_TypeKeys::Uint32 => { u32::try_from(value).map_err(|_| out_of_range())?; },
+            NP_TypeKeys::Uint64 => { u64::try_from(value).map_err(|_| out_of_range())?; },
+            _ => {
+                let mut err = "TypeError: Attempted to set integer value into schema of type (".to_owned();
+                err.push_str(schema_type.into_type_idx().0);
+                err.push_str(")\n");
+                return Err(NP_Error::new(err));
+            }
+        }
+
+        if value_cursor.parent_type == NP_Cursor_Parent::Tuple {
+            self.memory.write_bytes()[value_cursor.buff_addr - 1] = 1;
+        }
+
+        // range was checked above, these casts are lossless
+        match schema_type {
+            NP_TypeKeys::Int8   => {  i8::set_value(value_cursor, &self.memory, value as i8)?; },
+            NP_TypeKeys::Int16  => { i16::set_value(value_cursor, &self.memory, value as i16)?; },
+            NP_TypeKeys::Int32  => { i32::set_value(value_cursor, &self.memory, value as i32)?; },
+            NP_TypeKeys::Int64  => { i64::set_value(value_cursor, &self.memory, value as i64)?; },
+            NP_TypeKeys::Uint8  => {  u8::set_value(value_cursor, &self.memory, value as u8)?; },
+            NP_TypeKeys::Uint16 => { u16::set_value(value_cursor, &self.memory, value as u16)?; },
+            NP_TypeKeys::Uint32 => { u32::set_value(value_cursor, &self.memory, value as u32)?; },
+            _                   => { u64::set_value(value_cursor, &self.memory, value as u64)?; }
+        }
+
+        Ok(())
+    }
+
+    /// Set any integer into an integer field, clamping it to the range of the type declared in the schema.
+    /// 
+    /// Works like `set_numeric`, but out of range values are stored as the closest value the field can hold instead of returning an error.  Storing `70000u32` in a `u16` field stores `65535`, `-5i32` in a `u8` field stores `0`.  This matches core's saturating conversions.
+    /// 
+    /// Only integer schema types (`i8` through `u64`) are supported.
+    /// 
     /// ```
     /// use no_proto::error::NP_Error;
     /// use no_proto::NP_Factory;
-    /// use no_proto::NP_Size_Data;
     /// 
-    /// let factory: NP_Factory = NP_Factory::new_json(r#"{
-    ///    "type": "struct",
-    ///    "fields": [
-    ///         ["age", {"type": "uint8"}],
-    ///         ["name", {"type": "string"}],
-    ///         ["job", {"type": "string"}],
-    ///         ["tags", {"type": "list", "of": {"type": "string"}}]
-    ///     ]
-    /// }"#)?;
+    /// let factory: NP_Factory = NP_Factory::new("struct({fields: {reading: u16()}})")?;
     /// 
     /// let mut new_buffer = factory.new_buffer(None);
-    /// // set value of age
-    /// new_buffer.set(&["age"], 20u8)?;
-    /// // set value of name
-    /// new_buffer.set(&["name"], "Bill Kerman")?;
-    /// // push value onto tags list
-    /// new_buffer.list_push(&["tags"], "rocket")?;
     /// 
-    /// // get iterator of root (table)
-    /// new_buffer.get_collection(&[])?.unwrap().into_iter().for_each(|item| {
-    ///     
-    ///     match item.key {
-    ///         "name" => assert_eq!(item.get::<&str>().unwrap(), Some("Bill Kerman")),
-    ///         "age" =>  assert_eq!(item.get::<u8>().unwrap(), Some(20)),
-    ///         "job" => assert_eq!(item.get::<&str>().unwrap(), None),
-    ///         "tags" => { /* tags field is list, can't do anything with it here */ },
-    ///         _ => { panic!() }
-    ///     };
-    /// });
+    /// new_buffer.set_saturating(&["reading"], 70000u32)?;
+    /// assert_eq!(new_buffer.get::<u16>(&["reading"])?, Some(u16::MAX));
     /// 
-    /// // we can also loop through items of the tags list
-    /// new_buffer.get_collection(&["tags"])?.unwrap().into_iter().for_each(|item| {
-    ///     assert_eq!(item.index, 0);
-    ///     assert_eq!(item.get::<&str>().unwrap(), Some("rocket"));
-    /// });
+    /// new_buffer.set_saturating(&["reading"], -5i32)?;
+    /// assert_eq!(new_buffer.get::<u16>(&["reading"])?, Some(0));
     /// 
     /// # Ok::<(), NP_Error>(()) 
     /// ```
     /// 
-    /// ## Map Example
-    /// ```
-    /// use no_proto::error::NP_Error;
-    /// use no_proto::NP_Factory;
-    /// use no_proto::NP_Size_Data;
-    /// 
-    /// let factory: NP_Factory = NP_Factory::new_json(r#"{
-    ///    "type": "map",
-    ///    "value": {"type": "string"}
-    /// }"#)?;
-    /// 
-    /// let mut new_buffer = factory.new_buffer(None);
-    /// // set value of color key
-    /// new_buffer.set(&["color"], "blue")?;
-    /// // set value of sport key
-    /// new_buffer.set(&["sport"], "soccor")?;
+    pub fn set_saturating<X: Into<i128>>(&mut self, path: &[&str], value: X) -> Result<(), NP_Error> {
+        self.set_integer_cast(path, value.into(), true)
+    }
+
+    /// Set any integer into an integer field, wrapping it around the range of the type declared in the schema.
     /// 
-    /// // get iterator of root (map)
-    /// new_buffer.get_collection(&[])?.unwrap().into_iter().for_each(|item| {
-    ///     
-    ///     match item.key {
-    ///         "color" => assert_eq!(item.get::<&str>().unwrap(), Some("blue")),
-    ///         "sport" => assert_eq!(item.get::<&str>().unwrap(), Some("soccor")),
-    ///         _ => panic!()
-    ///     }
-    /// });
+    /// Works like `set_numeric`, but out of range values keep their low bits like an `as` cast instead of returning an error.  Storing `70000u32` in a `u16` field stores `4464`, `-1i32` in a `u8` field stores `255`.  This matches core's wrapping conversions.
     /// 
-    /// # Ok::<(), NP_Error>(()) 
-    /// ```
+    /// Only integer schema types (`i8` through `u64`) are supported.
     /// 
-    /// ## Tuple Example
     /// ```
     /// use no_proto::error::NP_Error;
     /// use no_proto::NP_Factory;
-    /// use no_proto::NP_Size_Data;
     /// 
-    /// let factory: NP_Factory = NP_Factory::new_json(r#"{
-    ///    "type": "tuple",
-    ///     "values": [
-    ///         {"type": "string"},
-    ///         {"type": "u8"},
-    ///         {"type": "bool"}
-    ///     ]
-    /// }"#)?;
+    /// let factory: NP_Factory = NP_Factory::new("struct({fields: {counter: u8()}})")?;
     /// 
     /// let mut new_buffer = factory.new_buffer(None);
-    /// // set value at 0 index
-    /// new_buffer.set(&["0"], "hello")?;
-    /// // set value at 2 index
-    /// new_buffer.set(&["2"], false)?;
     /// 
-    /// // get iterator of root (tuple item)
-    /// new_buffer.get_collection(&[])?.unwrap().into_iter().for_each(|item| {
-    ///     match item.index {
-    ///         0 => assert_eq!(item.get::<&str>().unwrap(), Some("hello")),
-    ///         1 => assert_eq!(item.get::<u8>().unwrap(), None),
-    ///         2 => assert_eq!(item.get::<bool>().unwrap(), Some(false)),
-    ///         _ => panic!()
-    ///     };
-    /// });
+    /// new_buffer.set_wrapping(&["counter"], 256u32 + 7)?;
+    /// assert_eq!(new_buffer.get::<u8>(&["counter"])?, Some(7));
+    /// 
+    /// new_buffer.set_wrapping(&["counter"], -1i32)?;
+    /// assert_eq!(new_buffer.get::<u8>(&["counter"])?, Some(255));
     /// 
     /// # Ok::<(), NP_Error>(()) 
     /// ```
     /// 
-    pub fn get_collection<'iter>(&'iter self, path: &'iter [&str]) -> Result<Option<NP_Generic_Iterator<'iter>>, NP_Error> {
+    pub fn set_wrapping<X: Into<i128>>(&mut self, path: &[&str], value: X) -> Result<(), NP_Error> {
+        self.set_integer_cast(path, value.into(), false)
+    }
 
-        let value = NP_Cursor::select(&self.memory, self.cursor.clone(), false, false, path)?;
+    fn set_integer_cast(&mut self, path: &[&str], value: i128, saturate: bool) -> Result<(), NP_Error> {
 
-        let value = if let Some(x) = value {
-            x
-        } else {
-            return Ok(None);
+        if self.mutable == false {
+            return Err(NP_Error::MemoryReadOnly);
+        }
+
+        #[cfg(feature = "access_stats")]
+        self.count_access(path, true);
+
+        let value_cursor = match NP_Cursor::select(&self.memory, self.cursor.clone(), true, false, path)? {
+            Some(x) => x,
+            None => return Err(NP_Error::new("Path not found for numeric set!"))
         };
 
-        let value_data = value.get_value(&self.memory);
+        let schema_type = self.memory.get_schema(value_cursor.schema_addr).i;
+
+        let set: fn(NP_Cursor, &NP_Memory, i128) -> Result<NP_Cursor, NP_Error> = match (schema_type, saturate) {
+            (NP_TypeKeys::Int8, true)    => i8::set_saturating,
+            (NP_TypeKeys::Int8, false)   => i8::set_wrapping,
+            (NP_TypeKeys::Int16, true)   => i16::set_saturating,
+            (NP_TypeKeys::Int16, false)  => i16::set_wrapping,
+            (NP_TypeKeys::Int32, true)   => i32::set_saturating,
+            (NP_TypeKeys::Int32, false)  => i32::set_wrapping,
+            (NP_TypeKeys::Int64, true)   => i64::set_saturating,
+            (NP_TypeKeys::Int64, false)  => i64::set_wrapping,
+            (NP_TypeKeys::Uint8, true)   => u8::set_saturating,
+            (NP_TypeKeys::Uint8, false)  => u8::set_wrapping,
+            (NP_TypeKeys::Uint16, true)  => u16::set_saturating,
+            (NP_TypeKeys::Uint16, false) => u16::set_wrapping,
+            (NP_TypeKeys::Uint32, true)  => u32::set_saturating,
+            (NP_TypeKeys::Uint32, false) => u32::set_wrapping,
+            (NP_TypeKeys::Uint64, true)  => u64::set_saturating,
+            (NP_TypeKeys::Uint64, false) => u64::set_wrapping,
+            _ => {
+                let mut err = "TypeError: Attempted to set integer value into schema of type (".to_owned();
+                err.push_str(schema_type.into_type_idx().0);
+                err.push_str(")\n");
+                return Err(NP_Error::new(err));
+            }
+        };
 
-        // value doesn't exist
-        if value_data.get_addr_value() == 0 {
-            return Ok(None);
+        if value_cursor.parent_type == NP_Cursor_Parent::Tuple {
+            self.memory.write_bytes()[value_cursor.buff_addr - 1] = 1;
         }
 
-        Ok(Some(NP_Generic_Iterator::new(value, &self.memory)?))
+        set(value_cursor, &self.memory, value)?;
+
+        Ok(())
     }
 
-    /// Push a value onto the end of a list.
-    /// The path provided must resolve to a list type, and the type being pushed must match the schema
+    /// Increment a `u64` sequence and return the new value, the usual way to allocate the next id.
     /// 
-    /// This is the most efficient way to add values to a list type.
+    /// An unset value starts from the schema default, or zero if there isn't one.  If the sequence is already at `u64::MAX` an error is returned and the stored value is left alone.
     /// 
     /// ```
     /// use no_proto::error::NP_Error;
     /// use no_proto::NP_Factory;
-    /// use no_proto::NP_Size_Data;
-    /// 
-    /// let factory: NP_Factory = NP_Factory::new_json(r#"{
-    ///    "type": "list",
-    ///     "of": {"type": "string"}
-    /// }"#)?;
-    /// 
-    /// let mut new_buffer = factory.new_buffer(None);
-    /// new_buffer.set(&["3"], "launch")?;
-    /// new_buffer.list_push(&[], "this")?;
-    /// new_buffer.list_push(&[], "rocket")?;
     /// 
-    /// // get iterator of root (list item)
-    /// new_buffer.get_collection(&[])?.unwrap().into_iter().for_each(|item| {
-    ///     match item.index {
-    ///         0 => assert_eq!(item.get::<&str>().unwrap(), None),
-    ///         1 => assert_eq!(item.get::<&str>().unwrap(), None),
-    ///         2 => assert_eq!(item.get::<&str>().unwrap(), None),
-    ///         3 => assert_eq!(item.get::<&str>().unwrap(), Some("launch")),
-    ///         4 => assert_eq!(item.get::<&str>().unwrap(), Some("this")),
-    ///         5 => assert_eq!(item.get::<&str>().unwrap(), Some("rocket")),
-    ///         _ => panic!()
-    ///     };
-    /// });
+    /// let factory: NP_Factory = NP_Factory::new("struct({fields: {next_id: u64({default: 100})}})")?;
     /// 
     /// let mut new_buffer = factory.new_buffer(None);
-    /// new_buffer.list_push(&[], "launch")?;
-    /// new_buffer.list_push(&[], "this")?;
-    /// new_buffer.list_push(&[], "rocket")?;
-    /// 
-    /// // get iterator of root (list item)
-    /// new_buffer.get_collection(&[])?.unwrap().into_iter().for_each(|item| {
-    ///     match item.index {
-    ///         0 => assert_eq!(item.get::<&str>().unwrap(), Some("launch")),
-    ///         1 => assert_eq!(item.get::<&str>().unwrap(), Some("this")),
-    ///         2 => assert_eq!(item.get::<&str>().unwrap(), Some("rocket")),
-    ///         _ => panic!()
-    ///     };
-    /// });
+    /// assert_eq!(new_buffer.next_seq(&["next_id"])?, 101);
+    /// assert_eq!(new_buffer.next_seq(&["next_id"])?, 102);
+    /// assert_eq!(new_buffer.get::<u64>(&["next_id"])?, Some(102));
     /// 
     /// # Ok::<(), NP_Error>(()) 
     /// ```
     /// 
-    pub fn list_push<'push, X: 'push>(&mut self, path: &[&str], value: X) -> Result<Option<u16>, NP_Error> where X: NP_Value<'push> + NP_Scalar<'push> {
+    pub fn next_seq(&mut self, path: &[&str]) -> Result<u64, NP_Error> {
 
         if self.mutable == false {
-            return Err(NP_Error::MemoryReadOnly)
+            return Err(NP_Error::MemoryReadOnly);
         }
 
-        let list_cursor = if path.len() == 0 { self.cursor.clone() } else { match NP_Cursor::select(&self.memory, self.cursor.clone(), true, false, path)? {
+        #[cfg(feature = "access_stats")]
+        self.count_access(path, true);
+
+        let value_cursor = match NP_Cursor::select(&self.memory, self.cursor.clone(), true, false, path)? {
             Some(x) => x,
-            None => return Ok(None)
-        }};
+            None => return Err(NP_Error::new("Path not found for sequence increment!"))
+        };
 
-        let schema = self.memory.get_schema(list_cursor.schema_addr);
+        let schema_type = self.memory.get_schema(value_cursor.schema_addr).i;
 
-        match schema.i {
-            NP_TypeKeys::List => {
+        if schema_type != NP_TypeKeys::Uint64 {
+            let mut err = "TypeError: Attempted to increment sequence in schema of type (".to_owned();
+            err.push_str(schema_type.into_type_idx().0);
+            err.push_str(")\n");
+            return Err(NP_Error::new(err));
+        }
 
-                let data = unsafe { &*(*schema.data as *const NP_Map_List_Data) };
+        let current = match u64::into_value(&value_cursor, &self.memory)? {
+            Some(x) => x,
+            None => u64::default_value(0, value_cursor.schema_addr, &self.memory.get_schemas()).unwrap_or(0)
+        };
 
-                let of = data.child;
-                    
-                let of_schema = &self.memory.get_schema(of);
+        let next = match current.checked_add(1) {
+            Some(x) => x,
+            None => return Err(NP_Error::new("Sequence overflow, value is already u64::MAX!"))
+        };
 
-                // type does not match schema
-                if X::type_idx().1 != of_schema.i {
-                    let mut err = "TypeError: Attempted to set value for type (".to_owned();
-                    err.push_str(X::type_idx().0);
-                    err.push_str(") into schema of type (");
-                    err.push_str(of_schema.i.into_type_idx().0);
-                    err.push_str(")\n");
-                    return Err(NP_Error::new(err));
-                }
-            },
-            _ => return Err(NP_Error::new("Trying to push onto non list item!"))
+        if value_cursor.parent_type == NP_Cursor_Parent::Tuple {
+            self.memory.write_bytes()[value_cursor.buff_addr - 1] = 1;
         }
 
-        match NP_List::push(&list_cursor, &self.memory, None)? {
-            Some((index, new_item_addr)) => {
-                X::set_value(new_item_addr, &self.memory, value)?;
-                Ok(Some(index))
-            },
-            None => Ok(None)
-        }
-    }
+        u64::set_value(value_cursor, &self.memory, next)?;
 
+        Ok(next)
+    }
 
-    /// Get length of String, Bytes, Table, Tuple, List or Map Type
-    /// 
-    /// If the type found at the path provided does not support length operations, you'll get `None`.
-    /// 
-    /// If there is no value at the path provodid, you will get `None`.
+    /// Round a decimal to a whole number and store it in an integer field.
     /// 
-    /// If an item is found and it's length is zero, you can expect `Some(0)`.
+    /// The value is rounded with the provided `mode`, then stored the same way as `set_numeric`, so it must fit the integer type declared in the schema or an error is returned and nothing is written.
     /// 
-    /// ## String Example
     /// ```
     /// use no_proto::error::NP_Error;
     /// use no_proto::NP_Factory;
-    /// use no_proto::NP_Size_Data;
+    /// use no_proto::pointer::dec::{NP_Dec, NP_RoundMode};
     /// 
-    /// let factory: NP_Factory = NP_Factory::new("string()")?;
+    /// let factory: NP_Factory = NP_Factory::new("struct({fields: {qty: u8()}})")?;
     /// 
     /// let mut new_buffer = factory.new_buffer(None);
-    /// // set initial value
-    /// new_buffer.set(&[], "hello")?;
-    /// // get length of value at root (String)
-    /// assert_eq!(new_buffer.get_length(&[])?, Some(5));
     /// 
-    /// # Ok::<(), NP_Error>(()) 
-    /// ```
+    /// new_buffer.set_dec_as_int(&["qty"], NP_Dec::new(35, 1), NP_RoundMode::HalfEven)?;
+    /// assert_eq!(new_buffer.get::<u8>(&["qty"])?, Some(4));
     /// 
-    /// ## Collection (List) Example
+    /// assert!(new_buffer.set_dec_as_int(&["qty"], NP_Dec::new(2555, 1), NP_RoundMode::HalfUp).is_err());
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
     /// ```
-    /// use no_proto::error::NP_Error;
-    /// use no_proto::NP_Factory;
-    /// use no_proto::NP_Size_Data;
     /// 
-    /// let factory: NP_Factory = NP_Factory::new("list({ of: string() })")?;
+    pub fn set_dec_as_int(&mut self, path: &[&str], value: NP_Dec, mode: NP_RoundMode) -> Result<(), NP_Error> {
+        let mut whole = value;
+        whole.shift_exp_rounded(0, mode);
+        self.set_numeric(path, whole.num)
+    }
+
+    /// Set value with JSON
+    /// 
+    /// This works with all types including portals.
+    /// 
+    /// Data that doesn't align with the schema will be ignored.  `Null` and `undefined` values will be ignored.
+    /// 
+    /// Partial updates just merge the provided values into the buffer, you only need to provide the values you'd like changed.  This method cannot be used to delete values.
+    /// 
+    /// Using the `.set()` method is far more performant.  I recommend only using this on the client side of your application.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::NP_Size_Data;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("list({of: string()})")?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.set_with_json(&[], r#"{"value": ["foo", "bar", null, "baz"]}"#)?;
+    ///    
+    /// assert_eq!(new_buffer.get_length(&[])?, Some(4));
+    /// assert_eq!(new_buffer.get::<&str>(&["0"])?, Some("foo"));
+    /// assert_eq!(new_buffer.get::<&str>(&["1"])?, Some("bar"));
+    /// assert_eq!(new_buffer.get::<&str>(&["2"])?, None);
+    /// assert_eq!(new_buffer.get::<&str>(&["3"])?, Some("baz"));
+    /// 
+    /// new_buffer.set_with_json(&["2"], r#"{"value": "bazzy"}"#)?;
+    /// assert_eq!(new_buffer.get::<&str>(&["2"])?, Some("bazzy"));
+    /// 
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn set_with_json<S: Into<String>>(&mut self, path: &[&str], json_value: S) -> Result<bool, NP_Error> {
+        self.set_with_json_inner(path, json_value.into(), false)
+    }
+
+    /// Set value with JSON, applying `null` values
+    /// 
+    /// Works like `.set_with_json()` except `null` values are not ignored.  A `null` struct field is cleared, and a `null` number or decimal is set to the schema default if one exists or cleared otherwise.  Use `NP_Factory::with_null_clears` to always clear them instead.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("list({of: i32({default: 5})})")?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.set_with_json(&[], r#"{"value": [1, 2, 3]}"#)?;
+    /// new_buffer.set_with_json_apply_null(&[], r#"{"value": [null, 7]}"#)?;
+    ///    
+    /// assert_eq!(new_buffer.get::<i32>(&["0"])?, Some(5));
+    /// assert_eq!(new_buffer.get::<i32>(&["1"])?, Some(7));
+    /// assert_eq!(new_buffer.get::<i32>(&["2"])?, Some(3));
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn set_with_json_apply_null<S: Into<String>>(&mut self, path: &[&str], json_value: S) -> Result<bool, NP_Error> {
+        self.set_with_json_inner(path, json_value.into(), true)
+    }
+
+    /// Set value with JSON, rejecting anything that can't be stored exactly.
+    /// 
+    /// Works like `.set_with_json()` but for untrusted input.  Numbers out of range for their field, numbers with a fraction going into an integer field and values of the wrong JSON type all return an error instead of being wrapped, truncated or skipped.  The error names the path of the value and the value itself, like `Number out of range for type (uint8): 300 (at path: items.2)`.  The factory settings for overflow and strict JSON are ignored here.
+    /// 
+    /// `null` values are skipped.  Values written before an error was found stay in the buffer.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("struct({fields: {age: u8(), scores: list({of: i16()})}})")?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.set_with_json_checked(&[], r#"{"value": {"age": 30, "scores": [2, -40]}}"#)?;
+    /// assert_eq!(new_buffer.get::<i16>(&["scores", "1"])?, Some(-40));
+    /// 
+    /// assert!(new_buffer.set_with_json_checked(&["age"], r#"{"value": 300}"#).is_err());
+    /// assert!(new_buffer.set_with_json_checked(&["scores"], r#"{"value": [1.5]}"#).is_err());
+    /// assert_eq!(new_buffer.get::<u8>(&["age"])?, Some(30));
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn set_with_json_checked<S: Into<String>>(&mut self, path: &[&str], json_value: S) -> Result<bool, NP_Error> {
+
+        if self.mutable == false {
+            return Err(NP_Error::MemoryReadOnly)
+        }
+
+        #[cfg(feature = "access_stats")]
+        self.count_access(path, true);
+
+        let value_cursor = NP_Cursor::select(&self.memory, self.cursor.clone(), self.mutable, false, path)?;
+        match value_cursor {
+            Some(x) => {
+                let parsed = json_decode(json_value.into())?;
+
+                if parsed["value"] == NP_JSON::Null {
+                    return Err(NP_Error::new(".set_with_json_checked requires `value` property!"))
+                }
+
+                let options = self.memory.options;
+                self.memory.options.overflow_policy = NumericOverflowPolicy::Error;
+                self.memory.options.strict_json = true;
+                self.memory.options.lossless_json = true;
+
+                let mut json_path: Vec<String> = path.iter().map(|s| String::from(*s)).collect();
+                let result = NP_Cursor::set_from_json_with_path(0, x, &self.memory, &parsed["value"], &mut json_path);
+
+                self.memory.options = options;
+
+                result?;
+                Ok(true)
+            }
+            None => Ok(false)
+        }
+    }
+
+    /// Fill this buffer from a JSON document with strict JSON on, used by [`NP_Factory::buffer_from_json`](../struct.NP_Factory.html#method.buffer_from_json).
+    pub(crate) fn fill_from_json(&mut self, json: &NP_JSON) -> Result<(), NP_Error> {
+
+        if self.mutable == false {
+            return Err(NP_Error::MemoryReadOnly)
+        }
+
+        let options = self.memory.options;
+        self.memory.options.strict_json = true;
+        let result = NP_Cursor::set_from_json_with_path(0, self.cursor.clone(), &self.memory, json, &mut Vec::new());
+        self.memory.options = options;
+        result
+    }
+
+    fn set_with_json_inner(&mut self, path: &[&str], json_value: String, apply_null: bool) -> Result<bool, NP_Error> {
+
+        if self.mutable == false {
+            return Err(NP_Error::MemoryReadOnly)
+        }
+
+        #[cfg(feature = "access_stats")]
+        self.count_access(path, true);
+
+        let value_cursor = NP_Cursor::select(&self.memory, self.cursor.clone(), self.mutable, false, path)?;
+        match value_cursor {
+            Some(x) => {
+                let parsed = json_decode(json_value)?;
+
+                let has_value = match &*parsed {
+                    NP_JSON::Dictionary(map) => map.has("value"),
+                    _ => false
+                };
+
+                match parsed["value"] {
+                    NP_JSON::Null if !(apply_null && has_value) => {
+                        return Err(NP_Error::new(".set_with_json requires `value` property!"))
+                    },
+                    _ => {
+                        NP_Cursor::set_from_json(0, apply_null, x, &self.memory, &Box::new(parsed["value"].clone()))?;
+                    }
+                }
+                
+                Ok(true)
+            }
+            None => Ok(false)
+        }
+    }
+
+    
+    /// Get an iterator for a collection
+    /// 
+    /// 
+    /// ## List Example
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::NP_Size_Data;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("list({of: string()})")?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// // set value at 1 index
+    /// new_buffer.set(&["1"], "hello")?;
+    /// // set value at 4 index
+    /// new_buffer.set(&["4"], "world")?;
+    /// // push value onto the end
+    /// new_buffer.list_push(&[], "!")?;
+    /// 
+    /// // get iterator of root (list item)
+    /// new_buffer.get_collection(&[])?.unwrap().into_iter().for_each(|item| {
+    ///     match item.index {
+    ///         0 => assert_eq!(item.get::<&str>().unwrap(), None),
+    ///         1 => assert_eq!(item.get::<&str>().unwrap(), Some("hello")),
+    ///         2 => assert_eq!(item.get::<&str>().unwrap(), None),
+    ///         3 => assert_eq!(item.get::<&str>().unwrap(), None),
+    ///         4 => assert_eq!(item.get::<&str>().unwrap(), Some("world")),
+    ///         5 => assert_eq!(item.get::<&str>().unwrap(), Some("!")),
+    ///         _ => panic!()
+    ///     };
+    /// });
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    /// ## Struct Example
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::NP_Size_Data;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new_json(r#"{
+    ///    "type": "struct",
+    ///    "fields": [
+    ///         ["age", {"type": "uint8"}],
+    ///         ["name", {"type": "string"}],
+    ///         ["job", {"type": "string"}],
+    ///         ["tags", {"type": "list", "of": {"type": "string"}}]
+    ///     ]
+    /// }"#)?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// // set value of age
+    /// new_buffer.set(&["age"], 20u8)?;
+    /// // set value of name
+    /// new_buffer.set(&["name"], "Bill Kerman")?;
+    /// // push value onto tags list
+    /// new_buffer.list_push(&["tags"], "rocket")?;
+    /// 
+    /// // get iterator of root (table)
+    /// new_buffer.get_collection(&[])?.unwrap().into_iter().for_each(|item| {
+    ///     
+    ///     match item.key {
+    ///         "name" => assert_eq!(item.get::<&str>().unwrap(), Some("Bill Kerman")),
+    ///         "age" =>  assert_eq!(item.get::<u8>().unwrap(), Some(20)),
+    ///         "job" => assert_eq!(item.get::<&str>().unwrap(), None),
+    ///         "tags" => { /* tags field is list, can't do anything with it here */ },
+    ///         _ => { panic!() }
+    ///     };
+    /// });
+    /// 
+    /// // we can also loop through items of the tags list
+    /// new_buffer.get_collection(&["tags"])?.unwrap().into_iter().for_each(|item| {
+    ///     assert_eq!(item.index, 0);
+    ///     assert_eq!(item.get::<&str>().unwrap(), Some("rocket"));
+    /// });
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    /// ## Map Example
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::NP_Size_Data;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new_json(r#"{
+    ///    "type": "map",
+    ///    "value": {"type": "string"}
+    /// }"#)?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// // set value of color key
+    /// new_buffer.set(&["color"], "blue")?;
+    /// // set value of sport key
+    /// new_buffer.set(&["sport"], "soccor")?;
+    /// 
+    /// // get iterator of root (map)
+    /// new_buffer.get_collection(&[])?.unwrap().into_iter().for_each(|item| {
+    ///     
+    ///     match item.key {
+    ///         "color" => assert_eq!(item.get::<&str>().unwrap(), Some("blue")),
+    ///         "sport" => assert_eq!(item.get::<&str>().unwrap(), Some("soccor")),
+    ///         _ => panic!()
+    ///     }
+    /// });
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    /// ## Tuple Example
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::NP_Size_Data;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new_json(r#"{
+    ///    "type": "tuple",
+    ///     "values": [
+    ///         {"type": "string"},
+    ///         {"type": "u8"},
+    ///         {"type": "bool"}
+    ///     ]
+    /// }"#)?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// // set value at 0 index
+    /// new_buffer.set(&["0"], "hello")?;
+    /// // set value at 2 index
+    /// new_buffer.set(&["2"], false)?;
+    /// 
+    /// // get iterator of root (tuple item)
+    /// new_buffer.get_collection(&[])?.unwrap().into_iter().for_each(|item| {
+    ///     match item.index {
+    ///         0 => assert_eq!(item.get::<&str>().unwrap(), Some("hello")),
+    ///         1 => assert_eq!(item.get::<u8>().unwrap(), None),
+    ///         2 => assert_eq!(item.get::<bool>().unwrap(), Some(false)),
+    ///         _ => panic!()
+    ///     };
+    /// });
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn get_collection<'iter>(&'iter self, path: &'iter [&str]) -> Result<Option<NP_Generic_Iterator<'iter>>, NP_Error> {
+
+        let value = NP_Cursor::select(&self.memory, self.cursor.clone(), false, false, path)?;
+
+        let value = if let Some(x) = value {
+            x
+        } else {
+            return Ok(None);
+        };
+
+        let value_data = value.get_value(&self.memory);
+
+        // value doesn't exist
+        if value_data.get_addr_value() == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(NP_Generic_Iterator::new(value, &self.memory)?))
+    }
+
+    /// Push a value onto the end of a list.
+    /// The path provided must resolve to a list type, and the type being pushed must match the schema
+    /// 
+    /// This is the most efficient way to add values to a list type.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::NP_Size_Data;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new_json(r#"{
+    ///    "type": "list",
+    ///     "of": {"type": "string"}
+    /// }"#)?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.set(&["3"], "launch")?;
+    /// new_buffer.list_push(&[], "this")?;
+    /// new_buffer.list_push(&[], "rocket")?;
+    /// 
+    /// // get iterator of root (list item)
+    /// new_buffer.get_collection(&[])?.unwrap().into_iter().for_each(|item| {
+    ///     match item.index {
+    ///         0 => assert_eq!(item.get::<&str>().unwrap(), None),
+    ///         1 => assert_eq!(item.get::<&str>().unwrap(), None),
+    ///         2 => assert_eq!(item.get::<&str>().unwrap(), None),
+    ///         3 => assert_eq!(item.get::<&str>().unwrap(), Some("launch")),
+    ///         4 => assert_eq!(item.get::<&str>().unwrap(), Some("this")),
+    ///         5 => assert_eq!(item.get::<&str>().unwrap(), Some("rocket")),
+    ///         _ => panic!()
+    ///     };
+    /// });
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.list_push(&[], "launch")?;
+    /// new_buffer.list_push(&[], "this")?;
+    /// new_buffer.list_push(&[], "rocket")?;
+    /// 
+    /// // get iterator of root (list item)
+    /// new_buffer.get_collection(&[])?.unwrap().into_iter().for_each(|item| {
+    ///     match item.index {
+    ///         0 => assert_eq!(item.get::<&str>().unwrap(), Some("launch")),
+    ///         1 => assert_eq!(item.get::<&str>().unwrap(), Some("this")),
+    ///         2 => assert_eq!(item.get::<&str>().unwrap(), Some("rocket")),
+    ///         _ => panic!()
+    ///     };
+    /// });
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn list_push<'push, X: 'push>(&mut self, path: &[&str], value: X) -> Result<Option<u16>, NP_Error> where X: NP_Value<'push> + NP_Scalar<'push> {
+
+        if self.mutable == false {
+            return Err(NP_Error::MemoryReadOnly)
+        }
+
+        #[cfg(feature = "access_stats")]
+        self.count_access(path, true);
+
+        let list_cursor = if path.len() == 0 { self.cursor.clone() } else { match NP_Cursor::select(&self.memory, self.cursor.clone(), true, false, path)? {
+            Some(x) => x,
+            None => return Ok(None)
+        }};
+
+        let schema = self.memory.get_schema(list_cursor.schema_addr);
+
+        match schema.i {
+            NP_TypeKeys::List => {
+
+                let data = unsafe { &*(*schema.data as *const NP_Map_List_Data) };
+
+                let of = data.child;
+                    
+                let of_schema = &self.memory.get_schema(of);
+
+                // type does not match schema
+                if X::type_idx().1 != of_schema.i {
+                    let mut err = "TypeError: Attempted to set value for type (".to_owned();
+                    err.push_str(X::type_idx().0);
+                    err.push_str(") into schema of type (");
+                    err.push_str(of_schema.i.into_type_idx().0);
+                    err.push_str(")\n");
+                    return Err(NP_Error::new(err));
+                }
+            },
+            _ => return Err(NP_Error::new("Trying to push onto non list item!"))
+        }
+
+        match NP_List::push(&list_cursor, &self.memory, None)? {
+            Some((index, new_item_addr)) => {
+                X::set_value(new_item_addr, &self.memory, value)?;
+                Ok(Some(index))
+            },
+            None => Ok(None)
+        }
+    }
+
+    /// Get a builder that appends numbers to the end of a list.
+    /// 
+    /// This is much faster than calling `list_push` in a loop when you have lots of values to add.  The list is found and type checked once, then each item is written with one allocation for both it's pointer and value, without walking the list again.
+    /// 
+    /// The list head and tail are updated when the builder is finished or dropped.  The buffer can't be used while the builder is alive.
+    /// 
+    /// Lists can hold at most 65,536 items (indexes `0` through `65535`), pushing past that returns an error.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("list({of: u32()})")?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.list_push(&[], 7u32)?;
+    /// 
+    /// let mut builder = new_buffer.number_list_builder::<u32>(&[])?;
+    /// for x in 0..100u32 {
+    ///     builder.push(x * 2)?;
+    /// }
+    /// builder.finish();
+    /// 
+    /// assert_eq!(new_buffer.get_length(&[])?, Some(101));
+    /// assert_eq!(new_buffer.get::<u32>(&["0"])?, Some(7));
+    /// assert_eq!(new_buffer.get::<u32>(&["5"])?, Some(8));
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn number_list_builder<'builder, X: 'builder>(&'builder mut self, path: &[&str]) -> Result<NP_ListBuilder<'builder, X>, NP_Error> where X: NP_Value<'builder> + NP_Scalar<'builder> + NP_Number {
+
+        if self.mutable == false {
+            return Err(NP_Error::MemoryReadOnly)
+        }
+
+        #[cfg(feature = "access_stats")]
+        self.count_access(path, true);
+
+        let list_cursor = if path.len() == 0 { self.cursor.clone() } else { match NP_Cursor::select(&self.memory, self.cursor.clone(), true, false, path)? {
+            Some(x) => x,
+            None => return Err(NP_Error::new("Path not found for list builder!"))
+        }};
+
+        let schema = self.memory.get_schema(list_cursor.schema_addr);
+
+        let of = match schema.i {
+            NP_TypeKeys::List => {
+
+                let data = unsafe { &*(*schema.data as *const NP_Map_List_Data) };
+
+                let of_schema = &self.memory.get_schema(data.child);
+
+                // type does not match schema
+                if X::type_idx().1 != of_schema.i {
+                    let mut err = "TypeError: Attempted to set value for type (".to_owned();
+                    err.push_str(X::type_idx().0);
+                    err.push_str(") into schema of type (");
+                    err.push_str(of_schema.i.into_type_idx().0);
+                    err.push_str(")\n");
+                    return Err(NP_Error::new(err));
+                }
+
+                data.child
+            },
+            _ => return Err(NP_Error::new("Trying to build non list item!"))
+        };
+
+        if list_cursor.get_value(&self.memory).get_addr_value() == 0 {
+            NP_List::make_list(&list_cursor, &self.memory)?;
+        }
+
+        let list_data = NP_List::get_list(list_cursor.get_value(&self.memory).get_addr_value() as usize, &self.memory);
+
+        let head = list_data.get_head() as usize;
+        let tail = list_data.get_tail() as usize;
+
+        // continue after the last item already in the list
+        let next_index = if tail == 0 {
+            0
+        } else {
+            NP_Cursor::new(tail, of, list_cursor.schema_addr).get_value(&self.memory).get_index() as usize + 1
+        };
+
+        Ok(NP_ListBuilder {
+            memory: &self.memory,
+            list: list_cursor,
+            of,
+            head,
+            tail,
+            next_index,
+            _value: PhantomData
+        })
+    }
+
+
+    /// Get length of String, Bytes, Table, Tuple, List or Map Type
+    /// 
+    /// If the type found at the path provided does not support length operations, you'll get `None`.
+    /// 
+    /// If there is no value at the path provodid, you will get `None`.
+    /// 
+    /// If an item is found and it's length is zero, you can expect `Some(0)`.
+    /// 
+    /// ## String Example
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::NP_Size_Data;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("string()")?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// // set initial value
+    /// new_buffer.set(&[], "hello")?;
+    /// // get length of value at root (String)
+    /// assert_eq!(new_buffer.get_length(&[])?, Some(5));
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    /// ## Collection (List) Example
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::NP_Size_Data;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("list({ of: string() })")?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// // set value at 9th index
+    /// new_buffer.set(&["9"], "hello")?;
+    /// // get length of value at root (List)
+    /// assert_eq!(new_buffer.get_length(&[])?, Some(10));
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    /// ## Collection (Table) Example
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::NP_Size_Data;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new(r#"
+    ///     struct({fields: {
+    ///         age: u8(),
+    ///         name: string()
+    ///     }})
+    /// "#)?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// // get length of value at root (Table)
+    /// assert_eq!(new_buffer.get_length(&[])?, Some(2));
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    /// ## Collection (Map) Example
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::NP_Size_Data;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("map({value: string() })")?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// // set values
+    /// new_buffer.set(&["foo"], "bar")?;
+    /// new_buffer.set(&["foo2"], "bar2")?;
+    /// // get length of value at root (Map)
+    /// assert_eq!(new_buffer.get_length(&[])?, Some(2));
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    /// ## Collection (Tuple) Example
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::NP_Size_Data;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("tuple({values: [string(), string()]})")?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// // get length of value at root (Tuple)
+    /// assert_eq!(new_buffer.get_length(&[])?, Some(2));
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn get_length(&self, path: &[&str]) -> Result<Option<usize>, NP_Error> {
+        let value_cursor = NP_Cursor::select(&self.memory, self.cursor.clone(), false, false, path)?;
+
+        let found_cursor = if let Some(x) = value_cursor {
+            x
+        } else {
+            return Ok(None);
+        };
+
+        let addr_value = found_cursor.get_value(&self.memory).get_addr_value();
+
+        let schema = self.memory.get_schema(found_cursor.schema_addr);
+
+        match schema.i {
+            NP_TypeKeys::List => {
+                if addr_value == 0 {
+                    return Ok(None);
+                }
+
+                let data = unsafe { &*(*schema.data as *const NP_Map_List_Data) };
+
+                let of = data.child;
+
+                let list_data = NP_List::get_list(addr_value as usize, &self.memory);
+                let tail_addr = list_data.get_tail() as usize;
+                if tail_addr == 0 {
+                    Ok(Some(0))
+                } else {
+                    let tail_cursor = NP_Cursor::new(tail_addr, of, found_cursor.schema_addr);
+                    let cursor_data = tail_cursor.get_value(&self.memory);
+                    Ok(Some(cursor_data.get_index() as usize + 1))
+                }
+            },
+            NP_TypeKeys::Map => {
+                if addr_value == 0 {
+                    return Ok(None);
+                }
+                let mut count = 0usize;
+                {
+                    let mut map_iter = NP_Map::new_iter(&found_cursor, &self.memory);
+
+                    while let Some((_ikey, _item)) = map_iter.step_iter(&self.memory) {
+                        count += 1;
+                    }
+                }
+
+                Ok(Some(count))
+            },
+            NP_TypeKeys::Struct => {
+                let data = unsafe { &*(*schema.data as *const NP_Struct_Data) };
+                Ok(Some(data.fields.len()))
+            },
+            NP_TypeKeys::Tuple => {
+                let data = unsafe { &*(*schema.data as *const NP_Tuple_Data) };
+                Ok(Some(data.values.len()))
+            },
+            NP_TypeKeys::Bytes => {
+
+                let data = unsafe { &*(*schema.data as *const NP_Bytes_Data) };
+
+                let size = data.size;
+         
+                if size > 0 {
+                    Ok(Some(size as usize))
+                } else {
+                    let length_bytes = self.memory.get_4_bytes(addr_value as usize).unwrap_or(&[0u8; 4]);
+                    Ok(Some(u32::from_be_bytes(*length_bytes) as usize))
+                }
+               
+            },
+            NP_TypeKeys::UTF8String => {
+
+                let data = unsafe { &*(*schema.data as *const NP_String_Data) };
+
+                let size = data.size;
+            
+                if size > 0 {
+                    Ok(Some(size as usize))
+                } else {
+                    let length_bytes = self.memory.get_4_bytes(addr_value as usize).unwrap_or(&[0u8; 4]);
+                    Ok(Some(u32::from_be_bytes(*length_bytes) as usize))
+                }
+    
+            },
+            _ => {
+                Ok(None)
+            }
+        }
+  
+    }
+
+    /// Clear an inner value from the buffer.
+    /// This can also be used to clear deeply nested collection objects or scalar objects.
+    /// 
+    /// Returns `true` if it found a value to delete (and deleted it), `false` otherwise.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::NP_Size_Data;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("list({ of: string() })")?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// // set index 0
+    /// new_buffer.set(&["0"], "hello")?;
+    /// // del index 0
+    /// new_buffer.del(&["0"])?;
+    /// // value is gone now!
+    /// assert_eq!(None, new_buffer.get::<&str>(&["0"])?);
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn del(&mut self, path: &[&str]) -> Result<bool, NP_Error> {
+
+        if self.mutable == false {
+            return Err(NP_Error::MemoryReadOnly)
+        }
+
+        #[cfg(feature = "access_stats")]
+        self.count_access(path, true);
+
+        let value_cursor = NP_Cursor::select(&self.memory, self.cursor.clone(), false, false, path)?;
+        
+        match value_cursor {
+            Some(x) => {
+                NP_Cursor::delete(x, &self.memory)
+            }
+            None => Ok(false)
+        }
+    }
+
+    /// Retrieve the schema type at a given path.
+    /// 
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::schema::NP_TypeKeys;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("tuple({values: [ geo8(), dec({exp: 2}), string() ]})")?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// 
+    /// assert_eq!(new_buffer.get_schema_type(&[])?.unwrap(), NP_TypeKeys::Tuple);
+    /// assert_eq!(new_buffer.get_schema_type(&["0"])?.unwrap(), NP_TypeKeys::Geo);
+    /// assert_eq!(new_buffer.get_schema_type(&["1"])?.unwrap(), NP_TypeKeys::Decimal);
+    /// assert_eq!(new_buffer.get_schema_type(&["2"])?.unwrap(), NP_TypeKeys::UTF8String);
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn get_schema_type(&self, path: &[&str]) -> Result<Option<NP_TypeKeys>, NP_Error> {
+
+        match NP_Cursor::select(&self.memory, self.cursor.clone(), false, true, path)? {
+            Some(x) => {
+                Ok(Some(self.memory.get_schema(x.schema_addr).i))
+            }
+            None => Ok(None)
+        }
+    }
+
+    /// Retrieve the schema default at a given path.
+    /// 
+    /// This is useful for `geo` and `dec` data types where there is information about the value in the schema.
+    /// 
+    /// For example, when you create an `NP_Geo` type to put into a `geo` field, you must know the resolution (4/8/16).  If you use this method you can get an empty `NP_Geo` type that already has the correct resolution set based on the schema.
+    /// 
+    /// The type that you cast the request to will be compared to the schema, if it doesn't match the schema the request will fail.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// use no_proto::pointer::geo::NP_Geo;
+    /// 
+    /// // a list where each item is a map where each key has a value containing a list of strings
+    /// let factory: NP_Factory = NP_Factory::new(r#"
+    ///     tuple({values: [
+    ///         geo8(),
+    ///         dec({exp: 2})
+    ///     ]})
+    /// "#)?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// // Get an empty NP_Geo type that has the correct resolution for the schema
+    /// // 
+    /// let geo_default: NP_Geo = new_buffer.get_schema_default::<NP_Geo>(&["0"])?.unwrap();
+    /// assert_eq!(geo_default.size, 8); // geo is size 8 in schema
+    /// 
+    /// // Get an empty NP_Dec type that has the correct exp for the schema
+    /// // 
+    /// let dec_default: NP_Dec = new_buffer.get_schema_default::<NP_Dec>(&["1"])?.unwrap();
+    /// assert_eq!(dec_default.exp, 2); // exponent is 2 in schema
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn get_schema_default<'get, X: 'get>(&'get self, path: &[&str]) -> Result<Option<X>, NP_Error> where X: NP_Value<'get> + NP_Scalar<'get> {
+
+        match NP_Cursor::select(&self.memory, self.cursor.clone(), false, true, path)? {
+            Some(x) => {
+                                
+                // type does not match schema
+                if X::type_idx().1 != self.memory.get_schema(x.schema_addr).i {
+                    let mut err = "TypeError: Attempted to get schema for type (".to_owned();
+                    err.push_str(X::type_idx().0);
+                    err.push_str(") for schema of type (");
+                    err.push_str(self.memory.get_schema(x.schema_addr).i.into_type_idx().0);
+                    err.push_str(")\n");
+                    return Err(NP_Error::new(err));
+                }
+
+                Ok(X::schema_default(&self.memory.get_schema(x.schema_addr)))
+            }
+            None => Ok(None)
+        }
+    }
+  
+    /// Retrieve an inner value from the buffer. 
+    /// 
+    /// The type that you cast the request to will be compared to the schema, if it doesn't match the schema the request will fail.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::NP_Size_Data;
+    /// 
+    /// // a list where each item is a map where each key has a value containing a list of strings
+    /// let factory: NP_Factory = NP_Factory::new(r#"list({of: map({ value: list({of: string() }) })})"#)?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// // third item in the top level list -> key "alpha" of map at 3rd element -> 9th element of list at "alpha" key
+    /// // 
+    /// new_buffer.set(&["3", "alpha", "9"], "who would build a schema like this")?;
+    /// 
+    /// // get the same item we just set
+    /// let message = new_buffer.get::<&str>(&["3", "alpha", "9"])?;
+    /// 
+    /// assert_eq!(message, Some("who would build a schema like this"));
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn get<'get, X: 'get>(&'get self, path: &[&str]) -> Result<Option<X>, NP_Error> where X: NP_Value<'get> + NP_Scalar<'get> {
+        #[cfg(feature = "access_stats")]
+        self.count_access(path, false);
+
+        let value_cursor = NP_Cursor::select(&self.memory, self.cursor.clone(), false, false, path)?;
+
+        match value_cursor {
+            Some(x) => {
+                                
+                // type does not match schema
+                if X::type_idx().1 != self.memory.get_schema(x.schema_addr).i {
+                    let mut err = "TypeError: Attempted to get value for type (".to_owned();
+                    err.push_str(X::type_idx().0);
+                    err.push_str(") for schema of type (");
+                    err.push_str(self.memory.get_schema(x.schema_addr).i.into_type_idx().0);
+                    err.push_str(")\n");
+                    return Err(NP_Error::new(err));
+                }
+
+                match X::into_value(&x, &self.memory)? {
+                    Some(x) => {
+                        Ok(Some(x))
+                    },
+                    None => { // no value found here, return default from schema
+                        match X::default_value(0, x.schema_addr, &self.memory.get_schemas()) {
+                            Some(y) => {
+                                Ok(Some(y))
+                            },
+                            None => { // no default in schema, no value to provide
+                                Ok(None)
+                            }
+                        }                        
+                    }
+                }
+            }
+            None => Ok(None)
+        }
+    }
+
+    /// Retrieve an inner value with the path given as a single dotted string.
+    /// 
+    /// The path is split on `.` and passed to `get`, so `"items.0.name"` is the same as `&["items", "0", "name"]`.  Use `\.` for a dot inside a key and `\\` for a backslash.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new(r#"struct({fields: {items: list({of: map({value: u32()})})}})"#)?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.set_path("items.0.api\\.example\\.com", 443u32)?;
+    /// 
+    /// assert_eq!(new_buffer.get::<u32>(&["items", "0", "api.example.com"])?, Some(443));
+    /// assert_eq!(new_buffer.get_path::<u32>("items.0.api\\.example\\.com")?, Some(443));
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn get_path<'get, X: 'get>(&'get self, dotted: &str) -> Result<Option<X>, NP_Error> where X: NP_Value<'get> + NP_Scalar<'get> {
+        let segments = Self::split_dotted_path(dotted);
+        let path: Vec<&str> = segments.iter().map(|s| s.as_str()).collect();
+        self.get(&path)
+    }
+
+    /// Set a scalar value with the path given as a single dotted string, see [`get_path`](#method.get_path) for the path format.
+    pub fn set_path<'set, X: 'set>(&mut self, dotted: &str, value: X) -> Result<bool, NP_Error> where X: NP_Value<'set> + NP_Scalar<'set> {
+        let segments = Self::split_dotted_path(dotted);
+        let path: Vec<&str> = segments.iter().map(|s| s.as_str()).collect();
+        self.set(&path, value)
+    }
+
+    /// Split a dotted path into segments, `\.` and `\\` escape a literal dot or backslash.  Empty segments are dropped like `np_path!` does.
+    fn split_dotted_path(dotted: &str) -> Vec<String> {
+        let mut segments: Vec<String> = Vec::new();
+        let mut current = String::new();
+        let mut chars = dotted.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    match chars.next() {
+                        Some(escaped) => current.push(escaped),
+                        None => current.push('\\')
+                    }
+                },
+                '.' => {
+                    if current.len() > 0 {
+                        segments.push(core::mem::take(&mut current));
+                    }
+                },
+                _ => current.push(c)
+            }
+        }
+
+        if current.len() > 0 {
+            segments.push(current);
+        }
+
+        segments
+    }
+
+    /// Retrieve a number from the buffer, or `fallback` if it hasn't been set.
+    /// 
+    /// Unlike `get`, schema defaults are ignored, so the fallback is used whenever the buffer doesn't hold a value.  The type must match the schema or an error is returned.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new(r#"struct({fields: {retries: u32({default: 5}), timeout: u32()}})"#)?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// 
+    /// // unset values use the fallback, even if the schema has a default
+    /// assert_eq!(new_buffer.get_or(&["retries"], 3u32)?, 3);
+    /// assert_eq!(new_buffer.get_or(&["timeout"], 30u32)?, 30);
+    /// 
+    /// new_buffer.set(&["retries"], 10u32)?;
+    /// assert_eq!(new_buffer.get_or(&["retries"], 3u32)?, 10);
+    /// 
+    /// // type must match the schema
+    /// assert!(new_buffer.get_or(&["retries"], 3u8).is_err());
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn get_or<'get, X: 'get>(&'get self, path: &[&str], fallback: X) -> Result<X, NP_Error> where X: NP_Value<'get> + NP_Scalar<'get> + NP_Number {
+        let value_cursor = match NP_Cursor::select(&self.memory, self.cursor.clone(), false, false, path)? {
+            Some(x) => x,
+            None => return Ok(fallback)
+        };
+
+        // type does not match schema
+        if X::type_idx().1 != self.memory.get_schema(value_cursor.schema_addr).i {
+            let mut err = "TypeError: Attempted to get value for type (".to_owned();
+            err.push_str(X::type_idx().0);
+            err.push_str(") for schema of type (");
+            err.push_str(self.memory.get_schema(value_cursor.schema_addr).i.into_type_idx().0);
+            err.push_str(")\n");
+            return Err(NP_Error::new(err));
+        }
+
+        Ok(X::into_value(&value_cursor, &self.memory)?.unwrap_or(fallback))
+    }
+
+    /// Read a number from the buffer, decoding it's bytes with a specific byte order.
+    /// 
+    /// **This is an advanced escape hatch.** NoProto always writes numbers as big endian, so you should never need this for buffers written by this library.  It exists to recover data from producers that wrote numbers with the wrong byte order.
+    /// 
+    /// The type is checked against the schema the same way `get` checks it.  Unlike `get`, the schema default is NOT used for values that haven't been set, since the default is already a native value.
+    /// 
+    /// With `Endian::Big` this returns the same value as `get`.  With `Endian::Little` the bytes are decoded as little endian two's complement, without the sign bit flip NoProto applies to big endian signed values.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::pointer::numbers::Endian;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("i32()")?;
+    /// 
+    /// // a buffer from a buggy producer: header, root pointer to address 6, then -5 as little endian
+    /// let mut blob: Vec<u8> = vec![0, 0, 0, 0, 0, 6];
+    /// blob.extend_from_slice(&(-5i32).to_le_bytes());
+    /// 
+    /// let buffer = factory.open_buffer(blob);
+    /// assert_eq!(buffer.get_with_endian::<i32>(&[], Endian::Little)?, Some(-5));
+    /// assert_ne!(buffer.get::<i32>(&[])?, Some(-5));
+    /// 
+    /// // values written by NoProto read the same as `get` with big endian
+    /// let mut buffer = factory.new_buffer(None);
+    /// buffer.set(&[], -5i32)?;
+    /// assert_eq!(buffer.get_with_endian::<i32>(&[], Endian::Big)?, Some(-5));
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn get_with_endian<'get, X: 'get>(&'get self, path: &[&str], endian: Endian) -> Result<Option<X>, NP_Error> where X: NP_Number + NP_Value<'get> {
+        let value_cursor = NP_Cursor::select(&self.memory, self.cursor.clone(), false, false, path)?;
+
+        match value_cursor {
+            Some(x) => {
+
+                // type does not match schema
+                if X::type_idx().1 != self.memory.get_schema(x.schema_addr).i {
+                    let mut err = "TypeError: Attempted to get value for type (".to_owned();
+                    err.push_str(X::type_idx().0);
+                    err.push_str(") for schema of type (");
+                    err.push_str(self.memory.get_schema(x.schema_addr).i.into_type_idx().0);
+                    err.push_str(")\n");
+                    return Err(NP_Error::new(err));
+                }
+
+                let value_addr = x.get_value(&self.memory).get_addr_value() as usize;
+
+                if value_addr == 0 {
+                    return Ok(None);
+                }
+
+                let read_bytes = self.memory.read_bytes();
+
+                if value_addr >= read_bytes.len() {
+                    return Ok(None);
+                }
+
+                Ok(X::np_from_raw_bytes(&read_bytes[value_addr..], endian))
+            }
+            None => Ok(None)
+        }
+    }
+
+    /// Get a numeric, decimal or uuid value as a string.
     /// 
-    /// let mut new_buffer = factory.new_buffer(None);
-    /// // set value at 9th index
-    /// new_buffer.set(&["9"], "hello")?;
-    /// // get length of value at root (List)
-    /// assert_eq!(new_buffer.get_length(&[])?, Some(10));
+    /// Integers and floats use their `to_string` form, decimals are printed exactly (no trip through a float) and uuids use the hyphenated form.  Useful for sending large integers to systems that lose precision on big JSON numbers.
     /// 
-    /// # Ok::<(), NP_Error>(()) 
-    /// ```
+    /// Like `.get()`, the schema default is used if the value isn't set.  Other schema types return an error.
     /// 
-    /// ## Collection (Table) Example
     /// ```
     /// use no_proto::error::NP_Error;
     /// use no_proto::NP_Factory;
-    /// use no_proto::NP_Size_Data;
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// use no_proto::pointer::uuid::NP_UUID;
     /// 
     /// let factory: NP_Factory = NP_Factory::new(r#"
     ///     struct({fields: {
-    ///         age: u8(),
+    ///         id: u64(),
+    ///         price: dec({exp: 2}),
+    ///         tax: dec({exp: 3, default: 1.5}),
+    ///         uuid: uuid(),
     ///         name: string()
     ///     }})
     /// "#)?;
     /// 
     /// let mut new_buffer = factory.new_buffer(None);
-    /// // get length of value at root (Table)
-    /// assert_eq!(new_buffer.get_length(&[])?, Some(2));
+    /// new_buffer.set(&["id"], u64::MAX)?;
+    /// new_buffer.set(&["price"], NP_Dec::new(-5, 2))?;
+    /// new_buffer.set(&["uuid"], &NP_UUID::generate(22))?;
     /// 
-    /// # Ok::<(), NP_Error>(()) 
-    /// ```
+    /// assert_eq!(new_buffer.get_as_string(&["id"])?, Some(String::from("18446744073709551615")));
+    /// assert_eq!(new_buffer.get_as_string(&["price"])?, Some(String::from("-0.05")));
+    /// assert_eq!(new_buffer.get_as_string(&["tax"])?, Some(String::from("1.500")));
+    /// assert_eq!(new_buffer.get_as_string(&["uuid"])?, Some(NP_UUID::generate(22).to_string()));
+    /// assert!(new_buffer.get_as_string(&["name"]).is_err());
     /// 
-    /// ## Collection (Map) Example
+    /// # Ok::<(), NP_Error>(()) 
     /// ```
-    /// use no_proto::error::NP_Error;
-    /// use no_proto::NP_Factory;
-    /// use no_proto::NP_Size_Data;
-    /// 
-    /// let factory: NP_Factory = NP_Factory::new("map({value: string() })")?;
     /// 
-    /// let mut new_buffer = factory.new_buffer(None);
-    /// // set values
-    /// new_buffer.set(&["foo"], "bar")?;
-    /// new_buffer.set(&["foo2"], "bar2")?;
-    /// // get length of value at root (Map)
-    /// assert_eq!(new_buffer.get_length(&[])?, Some(2));
+    pub fn get_as_string(&self, path: &[&str]) -> Result<Option<String>, NP_Error> {
+
+        let type_key = match self.get_schema_type(path)? {
+            Some(x) => x,
+            None => return Ok(None)
+        };
+
+        Ok(match type_key {
+            NP_TypeKeys::Int8    => self.get::<i8>(path)?.map(|x| x.to_string()),
+            NP_TypeKeys::Int16   => self.get::<i16>(path)?.map(|x| x.to_string()),
+            NP_TypeKeys::Int32   => self.get::<i32>(path)?.map(|x| x.to_string()),
+            NP_TypeKeys::Int64   => self.get::<i64>(path)?.map(|x| x.to_string()),
+            NP_TypeKeys::Uint8   => self.get::<u8>(path)?.map(|x| x.to_string()),
+            NP_TypeKeys::Uint16  => self.get::<u16>(path)?.map(|x| x.to_string()),
+            NP_TypeKeys::Uint32  => self.get::<u32>(path)?.map(|x| x.to_string()),
+            NP_TypeKeys::Uint64  => self.get::<u64>(path)?.map(|x| x.to_string()),
+            NP_TypeKeys::Float   => self.get::<f32>(path)?.map(|x| x.to_string()),
+            NP_TypeKeys::Double  => self.get::<f64>(path)?.map(|x| x.to_string()),
+            NP_TypeKeys::Decimal => self.get::<NP_Dec>(path)?.map(|x| x.to_string()),
+            NP_TypeKeys::Decimal128 => self.get::<NP_Dec128>(path)?.map(|x| x.to_string()),
+            NP_TypeKeys::Uuid    => self.get::<&NP_UUID>(path)?.map(|x| x.to_string()),
+            _ => {
+                let mut err = "TypeError: Attempted to get string for schema of type (".to_owned();
+                err.push_str(type_key.into_type_idx().0);
+                err.push_str(")\n");
+                return Err(NP_Error::new(err));
+            }
+        })
+    }
+
+    /// Feed the content of the buffer into a hasher, for fingerprints that only change when the data does.
     /// 
-    /// # Ok::<(), NP_Error>(()) 
-    /// ```
+    /// Every scalar that holds a value is written to the hasher along with the path to it, in schema order with map keys sorted.  The raw value bytes are used, but free space, deleted values and where values happen to be allocated are not, so buffers with the same values give the same digest even if one of them was edited many times or compacted.  Defaults are not values, an unset field and a field set to it's default give different digests.
     /// 
-    /// ## Collection (Tuple) Example
     /// ```
     /// use no_proto::error::NP_Error;
     /// use no_proto::NP_Factory;
-    /// use no_proto::NP_Size_Data;
+    /// use core::hash::Hasher;
+    /// 
+    /// // a tiny FNV-1a hasher, any `Hasher` works
+    /// struct Fnv(u64);
+    /// impl Hasher for Fnv {
+    ///     fn finish(&self) -> u64 { self.0 }
+    ///     fn write(&mut self, bytes: &[u8]) {
+    ///         for byte in bytes { self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x100000001b3); }
+    ///     }
+    /// }
     /// 
-    /// let factory: NP_Factory = NP_Factory::new("tuple({values: [string(), string()]})")?;
+    /// let factory: NP_Factory = NP_Factory::new("map({value: string()})")?;
     /// 
-    /// let mut new_buffer = factory.new_buffer(None);
-    /// // get length of value at root (Tuple)
-    /// assert_eq!(new_buffer.get_length(&[])?, Some(2));
+    /// let mut a = factory.new_buffer(None);
+    /// a.set(&["x"], "hello")?;
+    /// a.set(&["y"], "world")?;
+    /// 
+    /// let mut b = factory.new_buffer(None);
+    /// b.set(&["y"], "a much longer string")?;
+    /// b.set(&["y"], "world")?;
+    /// b.set(&["x"], "hello")?;
+    /// 
+    /// let (mut hash_a, mut hash_b) = (Fnv(0xcbf29ce484222325), Fnv(0xcbf29ce484222325));
+    /// a.content_digest(&mut hash_a)?;
+    /// b.content_digest(&mut hash_b)?;
+    /// assert_eq!(hash_a.finish(), hash_b.finish());
     /// 
     /// # Ok::<(), NP_Error>(()) 
     /// ```
     /// 
-    pub fn get_length(&self, path: &[&str]) -> Result<Option<usize>, NP_Error> {
-        let value_cursor = NP_Cursor::select(&self.memory, self.cursor.clone(), false, false, path)?;
+    pub fn content_digest<H: core::hash::Hasher>(&self, hasher: &mut H) -> Result<(), NP_Error> {
+        let mut path: Vec<u8> = Vec::new();
+        NP_Buffer::_content_digest(0, self.cursor.clone(), &self.memory, &mut path, hasher)
+    }
 
-        let found_cursor = if let Some(x) = value_cursor {
-            x
-        } else {
-            return Ok(None);
-        };
+    /// Hash the set scalars at or below `cursor`, `path` holds the encoded segments leading to it.
+    /// 
+    /// Index segments are a `0` byte and a big endian u32, map keys are a `1` byte, a big endian u32 length and the key.
+    fn _content_digest<H: core::hash::Hasher>(depth: usize, cursor: NP_Cursor, memory: &NP_Memory, path: &mut Vec<u8>, hasher: &mut H) -> Result<(), NP_Error> {
 
-        let addr_value = found_cursor.get_value(&self.memory).get_addr_value();
+        if depth > 255 { return Err(NP_Error::RecursionLimit) }
 
-        let schema = self.memory.get_schema(found_cursor.schema_addr);
+        let schema = memory.get_schema(cursor.schema_addr);
 
         match schema.i {
-            NP_TypeKeys::List => {
-                if addr_value == 0 {
-                    return Ok(None);
-                }
-
-                let data = unsafe { &*(*schema.data as *const NP_Map_List_Data) };
-
-                let of = data.child;
-
-                let list_data = NP_List::get_list(addr_value as usize, &self.memory);
-                let tail_addr = list_data.get_tail() as usize;
-                if tail_addr == 0 {
-                    Ok(Some(0))
-                } else {
-                    let tail_cursor = NP_Cursor::new(tail_addr, of, found_cursor.schema_addr);
-                    let cursor_data = tail_cursor.get_value(&self.memory);
-                    Ok(Some(cursor_data.get_index() as usize + 1))
+            NP_TypeKeys::Struct | NP_TypeKeys::Tuple | NP_TypeKeys::List | NP_TypeKeys::Map => {
+                if cursor.get_value(memory).get_addr_value() == 0 {
+                    return Ok(())
                 }
             },
-            NP_TypeKeys::Map => {
-                if addr_value == 0 {
-                    return Ok(None);
-                }
-                let mut count = 0usize;
-                {
-                    let mut map_iter = NP_Map::new_iter(&found_cursor, &self.memory);
+            _ => {}
+        }
 
-                    while let Some((_ikey, _item)) = map_iter.step_iter(&self.memory) {
-                        count += 1;
-                    }
-                }
+        let path_len = path.len();
 
-                Ok(Some(count))
-            },
+        match schema.i {
+            NP_TypeKeys::None | NP_TypeKeys::Any => { },
             NP_TypeKeys::Struct => {
-                let data = unsafe { &*(*schema.data as *const NP_Struct_Data) };
-                Ok(Some(data.fields.len()))
+                let mut struc = NP_Struct::new_iter(&cursor, memory);
+                while let Some((index, _key, item)) = struc.step_iter(memory) {
+                    if let Some(item_cursor) = item {
+                        path.push(0);
+                        path.extend_from_slice(&(index as u32).to_be_bytes());
+                        NP_Buffer::_content_digest(depth + 1, item_cursor, memory, path, hasher)?;
+                        path.truncate(path_len);
+                    }
+                }
             },
             NP_TypeKeys::Tuple => {
-                let data = unsafe { &*(*schema.data as *const NP_Tuple_Data) };
-                Ok(Some(data.values.len()))
+                let mut tuple = NP_Tuple::new_iter(&cursor, memory);
+                while let Some((index, item)) = tuple.step_iter(memory, false) {
+                    if let Some(item_cursor) = item {
+                        path.push(0);
+                        path.extend_from_slice(&(index as u32).to_be_bytes());
+                        NP_Buffer::_content_digest(depth + 1, item_cursor, memory, path, hasher)?;
+                        path.truncate(path_len);
+                    }
+                }
             },
-            NP_TypeKeys::Bytes => {
-
-                let data = unsafe { &*(*schema.data as *const NP_Bytes_Data) };
-
-                let size = data.size;
-         
-                if size > 0 {
-                    Ok(Some(size as usize))
-                } else {
-                    let length_bytes = self.memory.get_4_bytes(addr_value as usize).unwrap_or(&[0u8; 4]);
-                    Ok(Some(u32::from_be_bytes(*length_bytes) as usize))
+            NP_TypeKeys::List => {
+                let mut list = NP_List::new_iter(&cursor, memory, true, 0);
+                while let Some((index, item)) = list.step_iter(memory) {
+                    if let Some(item_cursor) = item {
+                        path.push(0);
+                        path.extend_from_slice(&(index as u32).to_be_bytes());
+                        NP_Buffer::_content_digest(depth + 1, item_cursor, memory, path, hasher)?;
+                        path.truncate(path_len);
+                    }
                 }
-               
             },
-            NP_TypeKeys::UTF8String => {
-
-                let data = unsafe { &*(*schema.data as *const NP_String_Data) };
-
-                let size = data.size;
-            
-                if size > 0 {
-                    Ok(Some(size as usize))
-                } else {
-                    let length_bytes = self.memory.get_4_bytes(addr_value as usize).unwrap_or(&[0u8; 4]);
-                    Ok(Some(u32::from_be_bytes(*length_bytes) as usize))
+            NP_TypeKeys::Map => {
+                let mut items: Vec<(String, NP_Cursor)> = Vec::new();
+                let mut map = NP_Map::new_iter(&cursor, memory);
+                while let Some((key, item_cursor)) = map.step_iter(memory) {
+                    items.push((String::from(key), item_cursor));
                 }
-    
+                items.sort_by(|a, b| a.0.cmp(&b.0));
+                for (key, item_cursor) in items.into_iter() {
+                    path.push(1);
+                    path.extend_from_slice(&(key.len() as u32).to_be_bytes());
+                    path.extend_from_slice(key.as_bytes());
+                    NP_Buffer::_content_digest(depth + 1, item_cursor, memory, path, hasher)?;
+                    path.truncate(path_len);
+                }
+            },
+            NP_TypeKeys::Portal => {
+                let portal_data = unsafe { &*(*schema.data as *const NP_Portal_Data) };
+                let mut portal_cursor = cursor.clone();
+                portal_cursor.schema_addr = portal_data.schema;
+                portal_cursor.parent_schema_addr = portal_data.parent_schema;
+                NP_Buffer::_content_digest(depth + 1, portal_cursor, memory, path, hasher)?;
             },
             _ => {
-                Ok(None)
+                let value = cursor.get_value(memory);
+                let value_addr = value.get_addr_value() as usize;
+
+                if value_addr != 0 {
+                    // calc_size includes the pointer, only the value is wanted here
+                    let value_size = NP_Cursor::calc_size(depth, &cursor, memory)? - value.get_size();
+                    hasher.write(&(path.len() as u32).to_be_bytes());
+                    hasher.write(path);
+                    hasher.write(&(value_size as u32).to_be_bytes());
+                    hasher.write(&memory.read_bytes()[value_addr..(value_addr + value_size)]);
+                }
             }
         }
-  
+
+        Ok(())
     }
 
-    /// Clear an inner value from the buffer.
-    /// This can also be used to clear deeply nested collection objects or scalar objects.
+    /// Iterate over every decimal value set in the buffer, no matter how deeply it's nested.
     /// 
-    /// Returns `true` if it found a value to delete (and deleted it), `false` otherwise.
+    /// Each item is the path to the decimal and it's value at the `exp` of the schema.  Decimals that haven't been set are skipped, even if the schema provides a default for them.
+    /// 
+    /// The whole buffer is walked before anything is returned, the walk stops at the first decimal that can't be read or when the nesting is too deep and that error is returned instead.
     /// 
     /// ```
     /// use no_proto::error::NP_Error;
     /// use no_proto::NP_Factory;
-    /// use no_proto::NP_Size_Data;
+    /// use no_proto::pointer::dec::NP_Dec;
     /// 
-    /// let factory: NP_Factory = NP_Factory::new("list({ of: string() })")?;
+    /// let factory: NP_Factory = NP_Factory::new(r#"
+    ///     struct({fields: {
+    ///         name: string(),
+    ///         total: dec({exp: 2}),
+    ///         tax: dec({exp: 2, default: 1.5}),
+    ///         lines: list({of: struct({fields: {
+    ///             price: dec({exp: 3})
+    ///         }})})
+    ///     }})
+    /// "#)?;
     /// 
     /// let mut new_buffer = factory.new_buffer(None);
-    /// // set index 0
-    /// new_buffer.set(&["0"], "hello")?;
-    /// // del index 0
-    /// new_buffer.del(&["0"])?;
-    /// // value is gone now!
-    /// assert_eq!(None, new_buffer.get::<&str>(&["0"])?);
+    /// new_buffer.set(&["name"], "invoice")?;
+    /// new_buffer.set(&["total"], NP_Dec::new(2050, 2))?;
+    /// new_buffer.set(&["lines", "0", "price"], NP_Dec::new(1000, 3))?;
+    /// new_buffer.set(&["lines", "1", "price"], NP_Dec::new(1050, 3))?;
+    /// 
+    /// let decimals: Vec<(Vec<String>, (i64, u8))> = new_buffer.iter_decimals()?.map(|(path, dec)| (path, dec.export())).collect();
+    /// 
+    /// assert_eq!(decimals, vec![
+    ///     (vec![String::from("total")], (2050, 2)),
+    ///     (vec![String::from("lines"), String::from("0"), String::from("price")], (1000, 3)),
+    ///     (vec![String::from("lines"), String::from("1"), String::from("price")], (1050, 3)),
+    /// ]);
     /// 
     /// # Ok::<(), NP_Error>(()) 
     /// ```
     /// 
-    pub fn del(&mut self, path: &[&str]) -> Result<bool, NP_Error> {
+    pub fn iter_decimals(&self) -> Result<impl Iterator<Item = (NP_Path, NP_Dec)>, NP_Error> {
+        let mut decimals: Vec<(NP_Path, NP_Dec)> = Vec::new();
 
-        if self.mutable == false {
-            return Err(NP_Error::MemoryReadOnly)
-        }
+        let mut path: NP_Path = Vec::new();
 
-        let value_cursor = NP_Cursor::select(&self.memory, self.cursor.clone(), false, false, path)?;
-        
-        match value_cursor {
-            Some(x) => {
-                NP_Cursor::delete(x, &self.memory)
+        let memory = &self.memory;
+
+        NP_Cursor::for_each_leaf(0, self.cursor.clone(), memory, &mut path, &mut |leaf_path, leaf| {
+            if memory.get_schema(leaf.schema_addr).i == NP_TypeKeys::Decimal {
+                if let Some(dec) = NP_Dec::into_value(leaf, memory)? {
+                    decimals.push((leaf_path.clone(), dec));
+                }
             }
-            None => Ok(false)
-        }
+            Ok(())
+        })?;
+
+        Ok(decimals.into_iter())
     }
 
-    /// Retrieve the schema type at a given path.
+
+    /// Read the values of a list of decimals.
     /// 
+    /// Gives the same values as calling `get` on each index of the list, but the decimal schema is only looked up once so long lists are faster to read.  Items that aren't in the list are skipped.
+    /// 
+    /// Returns `None` if the list hasn't been set, and an error if the path doesn't point to a list of decimals.
     /// 
     /// ```
     /// use no_proto::error::NP_Error;
     /// use no_proto::NP_Factory;
-    /// use no_proto::schema::NP_TypeKeys;
+    /// use no_proto::pointer::dec::NP_Dec;
     /// 
-    /// let factory: NP_Factory = NP_Factory::new("tuple({values: [ geo8(), dec({exp: 2}), string() ]})")?;
+    /// let factory: NP_Factory = NP_Factory::new("list({of: dec({exp: 2})})")?;
     /// 
     /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.list_push(&[], NP_Dec::new(1050, 2))?;
+    /// new_buffer.list_push(&[], NP_Dec::new(250, 2))?;
     /// 
-    /// assert_eq!(new_buffer.get_schema_type(&[])?.unwrap(), NP_TypeKeys::Tuple);
-    /// assert_eq!(new_buffer.get_schema_type(&["0"])?.unwrap(), NP_TypeKeys::Geo);
-    /// assert_eq!(new_buffer.get_schema_type(&["1"])?.unwrap(), NP_TypeKeys::Decimal);
-    /// assert_eq!(new_buffer.get_schema_type(&["2"])?.unwrap(), NP_TypeKeys::UTF8String);
+    /// let total = new_buffer.dec_reader(&[])?.unwrap().fold(NP_Dec::new(0, 2), |sum, (_index, value)| {
+    ///     sum + value.unwrap()
+    /// });
+    /// 
+    /// assert_eq!(total.export(), (1300, 2));
     /// 
     /// # Ok::<(), NP_Error>(()) 
     /// ```
     /// 
-    pub fn get_schema_type(&self, path: &[&str]) -> Result<Option<NP_TypeKeys>, NP_Error> {
+    pub fn dec_reader<'reader>(&'reader self, path: &[&str]) -> Result<Option<NP_DecReader<'reader>>, NP_Error> {
 
-        match NP_Cursor::select(&self.memory, self.cursor.clone(), false, true, path)? {
-            Some(x) => {
-                Ok(Some(self.memory.get_schema(x.schema_addr).i))
-            }
-            None => Ok(None)
+        let list_cursor = match NP_Cursor::select(&self.memory, self.cursor.clone(), false, false, path)? {
+            Some(x) => x,
+            None => return Ok(None)
+        };
+
+        let reader = NP_DecReader::new(&list_cursor, &self.memory)?;
+
+        // list doesn't exist
+        if list_cursor.get_value(&self.memory).get_addr_value() == 0 {
+            return Ok(None);
         }
+
+        Ok(Some(reader))
     }
 
-    /// Retrieve the schema default at a given path.
-    /// 
-    /// This is useful for `geo` and `dec` data types where there is information about the value in the schema.
-    /// 
-    /// For example, when you create an `NP_Geo` type to put into a `geo` field, you must know the resolution (4/8/16).  If you use this method you can get an empty `NP_Geo` type that already has the correct resolution set based on the schema.
+
+    /// Push a value onto the end of a `sorted_uints` sequence, creating the sequence if it hasn't been set.
     /// 
-    /// The type that you cast the request to will be compared to the schema, if it doesn't match the schema the request will fail.
+    /// The value must be equal to or larger than the last value in the sequence and fit in the `base` type, otherwise an error is returned.
     /// 
     /// ```
     /// use no_proto::error::NP_Error;
     /// use no_proto::NP_Factory;
-    /// use no_proto::pointer::dec::NP_Dec;
-    /// use no_proto::pointer::geo::NP_Geo;
     /// 
-    /// // a list where each item is a map where each key has a value containing a list of strings
-    /// let factory: NP_Factory = NP_Factory::new(r#"
-    ///     tuple({values: [
-    ///         geo8(),
-    ///         dec({exp: 2})
-    ///     ]})
-    /// "#)?;
+    /// let factory: NP_Factory = NP_Factory::new(r#"struct({fields: {ids: sorted_uints({base: "u32"})}})"#)?;
     /// 
     /// let mut new_buffer = factory.new_buffer(None);
-    /// // Get an empty NP_Geo type that has the correct resolution for the schema
-    /// // 
-    /// let geo_default: NP_Geo = new_buffer.get_schema_default::<NP_Geo>(&["0"])?.unwrap();
-    /// assert_eq!(geo_default.size, 8); // geo is size 8 in schema
+    /// new_buffer.sorted_uints_push(&["ids"], 20)?;
+    /// new_buffer.sorted_uints_push(&["ids"], 25)?;
     /// 
-    /// // Get an empty NP_Dec type that has the correct exp for the schema
-    /// // 
-    /// let dec_default: NP_Dec = new_buffer.get_schema_default::<NP_Dec>(&["1"])?.unwrap();
-    /// assert_eq!(dec_default.exp, 2); // exponent is 2 in schema
+    /// // going backwards is an error
+    /// assert!(new_buffer.sorted_uints_push(&["ids"], 10).is_err());
+    /// 
+    /// let values: Vec<u64> = new_buffer.sorted_uints_iter(&["ids"])?.unwrap().collect::<Result<Vec<u64>, NP_Error>>()?;
+    /// assert_eq!(values, vec![20, 25]);
     /// 
     /// # Ok::<(), NP_Error>(()) 
     /// ```
     /// 
-    pub fn get_schema_default<'get, X: 'get>(&'get self, path: &[&str]) -> Result<Option<X>, NP_Error> where X: NP_Value<'get> + NP_Scalar<'get> {
+    pub fn sorted_uints_push(&mut self, path: &[&str], value: u64) -> Result<(), NP_Error> {
 
-        match NP_Cursor::select(&self.memory, self.cursor.clone(), false, true, path)? {
-            Some(x) => {
-                                
-                // type does not match schema
-                if X::type_idx().1 != self.memory.get_schema(x.schema_addr).i {
-                    let mut err = "TypeError: Attempted to get schema for type (".to_owned();
-                    err.push_str(X::type_idx().0);
-                    err.push_str(") for schema of type (");
-                    err.push_str(self.memory.get_schema(x.schema_addr).i.into_type_idx().0);
-                    err.push_str(")\n");
-                    return Err(NP_Error::new(err));
-                }
+        if self.mutable == false {
+            return Err(NP_Error::MemoryReadOnly)
+        }
 
-                Ok(X::schema_default(&self.memory.get_schema(x.schema_addr)))
-            }
-            None => Ok(None)
+        #[cfg(feature = "access_stats")]
+        self.count_access(path, true);
+
+        let cursor = match NP_Cursor::select(&self.memory, self.cursor.clone(), true, false, path)? {
+            Some(x) => x,
+            None => return Err(NP_Error::new("Path not found for sorted_uints push!"))
+        };
+
+        let schema = self.memory.get_schema(cursor.schema_addr);
+
+        if schema.i != NP_TypeKeys::SortedUints {
+            let mut err = "TypeError: Attempted to push sorted_uints value into schema of type (".to_owned();
+            err.push_str(schema.i.into_type_idx().0);
+            err.push_str(")\n");
+            return Err(NP_Error::new(err));
         }
+
+        NP_SortedUints::push(&cursor, &self.memory, value)
     }
-  
-    /// Retrieve an inner value from the buffer. 
+
+    /// Iterate the values of a `sorted_uints` sequence in order.
     /// 
-    /// The type that you cast the request to will be compared to the schema, if it doesn't match the schema the request will fail.
+    /// Values are decoded as the iterator moves forward so nothing is collected up front.  There's no random access, getting to the value at an index means reading every value before it.  Each item is a `Result`, if the stored data doesn't hold as many values as the sequence says it does the iterator yields an error and stops.
+    /// 
+    /// Returns `None` if the sequence hasn't been set, and an error if the path doesn't point to a `sorted_uints` value.
     /// 
     /// ```
     /// use no_proto::error::NP_Error;
     /// use no_proto::NP_Factory;
-    /// use no_proto::NP_Size_Data;
     /// 
-    /// // a list where each item is a map where each key has a value containing a list of strings
-    /// let factory: NP_Factory = NP_Factory::new(r#"list({of: map({ value: list({of: string() }) })})"#)?;
+    /// let factory: NP_Factory = NP_Factory::new(r#"sorted_uints({base: "u64"})"#)?;
     /// 
     /// let mut new_buffer = factory.new_buffer(None);
-    /// // third item in the top level list -> key "alpha" of map at 3rd element -> 9th element of list at "alpha" key
-    /// // 
-    /// new_buffer.set(&["3", "alpha", "9"], "who would build a schema like this")?;
+    /// assert!(new_buffer.sorted_uints_iter(&[])?.is_none());
     /// 
-    /// // get the same item we just set
-    /// let message = new_buffer.get::<&str>(&["3", "alpha", "9"])?;
+    /// new_buffer.sorted_uints_push(&[], 5)?;
+    /// new_buffer.sorted_uints_push(&[], 500)?;
     /// 
-    /// assert_eq!(message, Some("who would build a schema like this"));
+    /// let total: u64 = new_buffer.sorted_uints_iter(&[])?.unwrap().sum::<Result<u64, NP_Error>>()?;
+    /// assert_eq!(total, 505);
     /// 
     /// # Ok::<(), NP_Error>(()) 
     /// ```
     /// 
-    pub fn get<'get, X: 'get>(&'get self, path: &[&str]) -> Result<Option<X>, NP_Error> where X: NP_Value<'get> + NP_Scalar<'get> {
-        let value_cursor = NP_Cursor::select(&self.memory, self.cursor.clone(), false, false, path)?;
+    pub fn sorted_uints_iter<'iter>(&'iter self, path: &[&str]) -> Result<Option<NP_SortedUintsIter<'iter>>, NP_Error> {
 
-        match value_cursor {
-            Some(x) => {
-                                
-                // type does not match schema
-                if X::type_idx().1 != self.memory.get_schema(x.schema_addr).i {
-                    let mut err = "TypeError: Attempted to get value for type (".to_owned();
-                    err.push_str(X::type_idx().0);
-                    err.push_str(") for schema of type (");
-                    err.push_str(self.memory.get_schema(x.schema_addr).i.into_type_idx().0);
-                    err.push_str(")\n");
-                    return Err(NP_Error::new(err));
-                }
+        let cursor = match NP_Cursor::select(&self.memory, self.cursor.clone(), false, false, path)? {
+            Some(x) => x,
+            None => return Ok(None)
+        };
 
-                match X::into_value(&x, &self.memory)? {
-                    Some(x) => {
-                        Ok(Some(x))
-                    },
-                    None => { // no value found here, return default from schema
-                        match X::default_value(0, x.schema_addr, &self.memory.get_schemas()) {
-                            Some(y) => {
-                                Ok(Some(y))
-                            },
-                            None => { // no default in schema, no value to provide
-                                Ok(None)
-                            }
-                        }                        
-                    }
-                }
-            }
-            None => Ok(None)
+        let schema = self.memory.get_schema(cursor.schema_addr);
+
+        if schema.i != NP_TypeKeys::SortedUints {
+            let mut err = "TypeError: Attempted to read sorted_uints values from schema of type (".to_owned();
+            err.push_str(schema.i.into_type_idx().0);
+            err.push_str(")\n");
+            return Err(NP_Error::new(err));
         }
+
+        NP_SortedUints::iter(&cursor, &self.memory)
     }
 
 
@@ -1136,6 +2750,7 @@ impl NP_Buffer {
     /// use no_proto::error::NP_Error;
     /// use no_proto::NP_Factory;
     /// use no_proto::NP_Size_Data;
+    /// use no_proto::NP_Compact_Result;
     /// 
     /// let factory: NP_Factory = NP_Factory::new("string()")?;
     /// 
@@ -1157,7 +2772,12 @@ impl NP_Buffer {
     ///     wasted_bytes: 9
     /// }, new_buffer.calc_bytes()?);
     /// // compact to save space
-    /// new_buffer.compact(None)?;
+    /// let result = new_buffer.compact(None)?;
+    /// assert_eq!(result, NP_Compact_Result {
+    ///     bytes_before: 31,
+    ///     bytes_after: 22,
+    ///     reclaimed: 9
+    /// });
     /// // back down to 18 bytes with no wasted bytes
     /// assert_eq!(NP_Size_Data {
     ///     current_buffer: 22,
@@ -1168,12 +2788,14 @@ impl NP_Buffer {
     /// # Ok::<(), NP_Error>(()) 
     /// ```
     /// 
-    pub fn compact<'compact>(&mut self, new_capacity: Option<usize>) -> Result<(), NP_Error> {
+    pub fn compact<'compact>(&mut self, new_capacity: Option<usize>) -> Result<NP_Compact_Result, NP_Error> {
 
         if self.mutable == false {
             return Err(NP_Error::MemoryReadOnly)
         }
 
+        let bytes_before = self.memory.length();
+
         let capacity = Some(match new_capacity {
             Some(x) => { x as usize },
             None => self.memory.read_bytes().len()
@@ -1210,7 +2832,13 @@ impl NP_Buffer {
 
         self.cursor = NP_Cursor::new(self.memory.root, 0, 0);
 
-        Ok(())
+        let bytes_after = self.memory.length();
+
+        Ok(NP_Compact_Result {
+            bytes_before,
+            bytes_after,
+            reclaimed: bytes_before.saturating_sub(bytes_after)
+        })
     }
 
     /// Compact the current buffer into a new owned buffer.
@@ -1229,7 +2857,8 @@ impl NP_Buffer {
 
         let old_root = NP_Cursor::new(self.memory.root, 0, 0);
 
-        let new_bytes = NP_Memory::new(capacity, self.memory.get_schemas() as *const Vec<NP_Parsed_Schema>, self.memory.root);
+        let mut new_bytes = NP_Memory::new(capacity, self.memory.get_schemas() as *const Vec<NP_Parsed_Schema>, self.memory.root);
+        new_bytes.options = self.memory.options;
         let new_root  = NP_Cursor::new(self.memory.root, 0, 0);
 
         NP_Cursor::compact(0, old_root, &self.memory, new_root, &new_bytes)?;
@@ -1243,7 +2872,8 @@ impl NP_Buffer {
     /// 
     pub fn copy_buffer(&self) -> NP_Buffer {
         let copy_bytes = self.memory.read_bytes().to_vec();
-        let new_memory = NP_Memory::existing_owned(copy_bytes, self.memory.get_schemas() as *const Vec<NP_Parsed_Schema>, self.memory.root);
+        let mut new_memory = NP_Memory::existing_owned(copy_bytes, self.memory.get_schemas() as *const Vec<NP_Parsed_Schema>, self.memory.root);
+        new_memory.options = self.memory.options;
         NP_Buffer::_new(new_memory)
     }
 
@@ -1286,6 +2916,325 @@ impl NP_Buffer {
     }
 
 
+    /// Measure how many bytes each top level field of the buffer is using.
+    /// 
+    /// For structs the children are the fields, for lists and tuples the items and for maps the keys.  Each size includes the field's pointer, it's value and all of it's descendants.
+    /// 
+    /// The first entry has an empty path and holds every byte that doesn't belong to a child: the buffer header, the root pointer and the collection's own bookkeeping (struct vtables, list heads and so on).  If the root isn't a collection that entry is the only one.  All the entries add up to `calc_bytes()?.after_compaction`.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new(r#"
+    ///     struct({fields: {
+    ///         small: u8(),
+    ///         counter: u64(),
+    ///         name: string(),
+    ///         price: dec({exp: 2})
+    ///     }})
+    /// "#)?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.set(&["small"], 2u8)?;
+    /// new_buffer.set(&["counter"], 2u64)?;
+    /// new_buffer.set(&["name"], "hello world")?;
+    /// new_buffer.set(&["price"], NP_Dec::new(250, 2))?;
+    /// 
+    /// let sizes = new_buffer.size_breakdown()?;
+    /// let size_of = |field: &str| sizes.iter().find(|(path, _)| path == &[field]).unwrap().1;
+    /// 
+    /// // same pointer overhead, the u64 is 7 bytes bigger
+    /// assert_eq!(size_of("counter") - size_of("small"), 7);
+    /// // strings use 4 length bytes plus the utf8 bytes
+    /// assert_eq!(size_of("name") - size_of("small"), 14);
+    /// // decimals are stored as i64
+    /// assert_eq!(size_of("price"), size_of("counter"));
+    /// 
+    /// let total: usize = sizes.iter().map(|(_path, size)| size).sum();
+    /// assert_eq!(total, new_buffer.calc_bytes()?.after_compaction);
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn size_breakdown(&self) -> Result<Vec<(NP_Path, usize)>, NP_Error> {
+
+        let root = NP_Cursor::new(self.memory.root, 0, 0);
+        let memory = &self.memory;
+
+        let total = NP_Cursor::calc_size(0, &root, memory)? + memory.root;
+
+        // the root entry is filled in once the children are measured
+        let mut result: Vec<(NP_Path, usize)> = vec![(Vec::new(), total)];
+
+        let schema = memory.get_schema(root.schema_addr);
+
+        match schema.i {
+            NP_TypeKeys::Struct | NP_TypeKeys::Tuple | NP_TypeKeys::List | NP_TypeKeys::Map => {
+                if root.get_value(memory).get_addr_value() == 0 {
+                    return Ok(result)
+                }
+            },
+            _ => {
+                return Ok(result)
+            }
+        }
+
+        match schema.i {
+            NP_TypeKeys::Struct => {
+                let mut struc = NP_Struct::new_iter(&root, memory);
+                while let Some((_index, key, item)) = struc.step_iter(memory) {
+                    if let Some(item_cursor) = item {
+                        result.push((vec![String::from(key)], NP_Cursor::calc_size(1, &item_cursor, memory)?));
+                    }
+                }
+            },
+            NP_TypeKeys::Tuple => {
+                let mut tuple = NP_Tuple::new_iter(&root, memory);
+                while let Some((index, item)) = tuple.step_iter(memory, false) {
+                    if let Some(item_cursor) = item {
+                        result.push((vec![index.to_string()], NP_Cursor::calc_size(1, &item_cursor, memory)?));
+                    }
+                }
+            },
+            NP_TypeKeys::List => {
+                let mut list = NP_List::new_iter(&root, memory, true, 0);
+                while let Some((index, item)) = list.step_iter(memory) {
+                    if let Some(item_cursor) = item {
+                        result.push((vec![index.to_string()], NP_Cursor::calc_size(1, &item_cursor, memory)?));
+                    }
+                }
+            },
+            NP_TypeKeys::Map => {
+                let mut map = NP_Map::new_iter(&root, memory);
+                while let Some((key, item_cursor)) = map.step_iter(memory) {
+                    result.push((vec![String::from(key)], NP_Cursor::calc_size(1, &item_cursor, memory)?));
+                }
+            },
+            _ => { }
+        }
+
+        let children: usize = result[1..].iter().map(|(_path, size)| size).sum();
+        result[0].1 = match total.checked_sub(children) {
+            Some(x) => x,
+            None => return Err(NP_Error::new("Error calculating bytes!"))
+        };
+
+        Ok(result)
+    }
+
+    /// Check that every value marked `required` in the schema has been set.
+    /// 
+    /// On failure the paths of all missing values are returned.  Values with a default in the schema are never missing since reads return the default.
+    /// 
+    /// Required values are only checked when their parent collection has been set, except for values directly below the root which are always checked.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new(r#"
+    ///     struct({fields: {
+    ///         id: u32({required: true}),
+    ///         name: string({required: true}),
+    ///         status: u8({required: true, default: 1}),
+    ///         note: string()
+    ///     }})
+    /// "#)?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.set(&["name"], "hello")?;
+    /// 
+    /// let missing = new_buffer.assert_complete().unwrap_err();
+    /// assert_eq!(missing, vec![vec![String::from("id")]]);
+    /// 
+    /// new_buffer.set(&["id"], 20u32)?;
+    /// assert!(new_buffer.assert_complete().is_ok());
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn assert_complete(&self) -> Result<(), Vec<NP_Path>> {
+        let root = NP_Cursor::new(self.memory.root, 0, 0);
+
+        let mut missing: Vec<NP_Path> = Vec::new();
+
+        NP_Buffer::_find_missing(0, Some(root), 0, &self.memory, &mut Vec::new(), &mut missing);
+
+        if missing.len() == 0 {
+            Ok(())
+        } else {
+            Err(missing)
+        }
+    }
+
+    /// Recursively collect required values that aren't set
+    fn _find_missing(depth: usize, cursor: Option<NP_Cursor>, schema_addr: usize, memory: &NP_Memory, path: &mut NP_Path, missing: &mut Vec<NP_Path>) {
+
+        if depth > 255 { return }
+
+        let schema = memory.get_schema(schema_addr);
+
+        let is_set = match &cursor {
+            Some(x) => x.get_value(memory).get_addr_value() != 0,
+            None => false
+        };
+
+        if is_set == false {
+            if NP_Schema::is_required(schema) && NP_Schema::has_default(memory.get_schemas(), schema_addr) == false {
+                missing.push(path.clone());
+            }
+            // the root is always there, everything else has nothing below it
+            if depth > 0 {
+                return;
+            }
+        }
+
+        let cursor = if is_set { cursor } else { None };
+
+        match schema.i {
+            NP_TypeKeys::Struct => {
+                let data = unsafe { &*(*schema.data as *const NP_Struct_Data) };
+                for field in data.fields.iter() {
+                    let item = match &cursor {
+                        Some(x) => NP_Struct::select(x.clone(), schema, &field.col, false, false, memory).unwrap_or(None),
+                        None => None
+                    };
+                    path.push(field.col.clone());
+                    NP_Buffer::_find_missing(depth + 1, item, field.schema, memory, path, missing);
+                    path.pop();
+                }
+            },
+            NP_TypeKeys::Tuple => {
+                let data = unsafe { &*(*schema.data as *const NP_Tuple_Data) };
+                for (index, value) in data.values.iter().enumerate() {
+                    let item = match &cursor {
+                        Some(x) => NP_Tuple::select(x.clone(), schema, index, false, false, memory).unwrap_or(None),
+                        None => None
+                    };
+                    path.push(index.to_string());
+                    NP_Buffer::_find_missing(depth + 1, item, value.schema, memory, path, missing);
+                    path.pop();
+                }
+            },
+            NP_TypeKeys::List => {
+                if let Some(x) = &cursor {
+                    let mut list = NP_List::new_iter(x, memory, true, 0);
+                    while let Some((index, item)) = list.step_iter(memory) {
+                        if let Some(item_cursor) = item {
+                            path.push(index.to_string());
+                            NP_Buffer::_find_missing(depth + 1, Some(item_cursor), item_cursor.schema_addr, memory, path, missing);
+                            path.pop();
+                        }
+                    }
+                }
+            },
+            NP_TypeKeys::Map => {
+                if let Some(x) = &cursor {
+                    let mut map = NP_Map::new_iter(x, memory);
+                    while let Some((key, item_cursor)) = map.step_iter(memory) {
+                        path.push(String::from(key));
+                        NP_Buffer::_find_missing(depth + 1, Some(item_cursor), item_cursor.schema_addr, memory, path, missing);
+                        path.pop();
+                    }
+                }
+            },
+            NP_TypeKeys::Portal => {
+                let portal_data = unsafe { &*(*schema.data as *const NP_Portal_Data) };
+                let portal_cursor = cursor.map(|x| {
+                    let mut next = x.clone();
+                    next.schema_addr = portal_data.schema;
+                    next.parent_schema_addr = portal_data.parent_schema;
+                    next
+                });
+                NP_Buffer::_find_missing(depth + 1, portal_cursor, portal_data.schema, memory, path, missing);
+            },
+            _ => { }
+        }
+    }
+
+    /// Write a new UUID into every unset `uuid({auto: true})` field, returns how many were written.
+    /// 
+    /// `generate` is called once for each UUID needed.  Only fields reachable through structs and tuples from the buffer root are filled, items in lists and maps and values behind portals are left alone.  Fields that already have a value are not changed.
+    /// 
+    /// With the `getrandom` feature, `NP_Factory::new_buffer_with_defaults` creates a buffer and fills it with `NP_UUID::new_v4` in one step.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::pointer::uuid::NP_UUID;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new(r#"struct({fields: {id: uuid({auto: true}), parent: uuid()}})"#)?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// assert_eq!(new_buffer.fill_auto_uuids(|| NP_UUID::generate(20))?, 1);
+    /// 
+    /// assert_eq!(new_buffer.get::<NP_UUID>(&["id"])?, Some(NP_UUID::generate(20)));
+    /// assert_eq!(new_buffer.get::<NP_UUID>(&["parent"])?, None);
+    /// 
+    /// // already set, nothing to do
+    /// assert_eq!(new_buffer.fill_auto_uuids(|| NP_UUID::generate(30))?, 0);
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn fill_auto_uuids<F>(&mut self, mut generate: F) -> Result<usize, NP_Error> where F: FnMut() -> NP_UUID {
+
+        if self.mutable == false {
+            return Err(NP_Error::MemoryReadOnly)
+        }
+
+        let mut paths: Vec<NP_Path> = Vec::new();
+        NP_Buffer::_auto_uuid_paths(0, self.cursor.schema_addr, self.memory.get_schemas(), &mut Vec::new(), &mut paths);
+
+        let mut count = 0usize;
+
+        for path in paths.iter() {
+            let path: Vec<&str> = path.iter().map(|x| x.as_str()).collect();
+            if self.get::<NP_UUID>(&path)?.is_none() {
+                self.set(&path, generate())?;
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Collect the paths to every auto uuid field below a schema, only following structs and tuples
+    fn _auto_uuid_paths(depth: usize, schema_addr: usize, schemas: &Vec<NP_Parsed_Schema>, path: &mut NP_Path, paths: &mut Vec<NP_Path>) {
+
+        if depth > 255 { return }
+
+        let schema = &schemas[schema_addr];
+
+        match schema.i {
+            NP_TypeKeys::Uuid => {
+                let data = unsafe { &*(*schema.data as *const NP_Uuid_Data) };
+                if data.auto {
+                    paths.push(path.clone());
+                }
+            },
+            NP_TypeKeys::Struct => {
+                let data = unsafe { &*(*schema.data as *const NP_Struct_Data) };
+                for field in data.fields.iter() {
+                    path.push(field.col.clone());
+                    NP_Buffer::_auto_uuid_paths(depth + 1, field.schema, schemas, path, paths);
+                    path.pop();
+                }
+            },
+            NP_TypeKeys::Tuple => {
+                let data = unsafe { &*(*schema.data as *const NP_Tuple_Data) };
+                for (index, value) in data.values.iter().enumerate() {
+                    path.push(index.to_string());
+                    NP_Buffer::_auto_uuid_paths(depth + 1, value.schema, schemas, path, paths);
+                    path.pop();
+                }
+            },
+            _ => { }
+        }
+    }
+
     /// Set the maximum allowed of size of this buffer, in bytes.
     /// 
     /// Once this value is set, the buffer will not be allowed to grow beyond this size.
@@ -1303,6 +3252,97 @@ impl NP_Buffer {
     pub fn data_length(&self) -> usize {
         self.memory.length()
     }
+
+    /// How many times the underlying `Vec<u8>` has had to grow past it's capacity while data was written to this buffer.
+    /// 
+    /// Each one of these is a reallocation and copy of the whole buffer.  If you know roughly how large your buffer will get, passing that size into `new_buffer` will keep this at zero.
+    /// 
+    /// Compaction creates fresh memory, so this count is reset after compacting.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("list({of: string()})")?;
+    /// 
+    /// let mut small_buffer = factory.new_buffer(Some(16));
+    /// let mut sized_buffer = factory.new_buffer(Some(8192));
+    /// 
+    /// for x in 0..100 {
+    ///     small_buffer.set(&[x.to_string().as_str()], "hello, world")?;
+    ///     sized_buffer.set(&[x.to_string().as_str()], "hello, world")?;
+    /// }
+    /// 
+    /// assert_eq!(sized_buffer.alloc_count(), 0);
+    /// assert!(small_buffer.alloc_count() > sized_buffer.alloc_count());
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn alloc_count(&self) -> usize {
+        self.memory.alloc_count.get()
+    }
+}
+
+/// Appends numbers to the end of a list, see [`NP_Buffer::number_list_builder`](struct.NP_Buffer.html#method.number_list_builder).
+/// 
+/// The builder tracks the list tail itself, so the list head and tail in the buffer are only written when it's finished or dropped.
+pub struct NP_ListBuilder<'builder, X> {
+    memory: &'builder NP_Memory,
+    list: NP_Cursor,
+    of: usize,
+    head: usize,
+    tail: usize,
+    next_index: usize,
+    _value: PhantomData<X>
+}
+
+impl<'builder, X: 'builder> NP_ListBuilder<'builder, X> where X: NP_Value<'builder> + NP_Scalar<'builder> + NP_Number {
+
+    /// Push a value onto the end of the list, returns the index it was written to.
+    /// 
+    /// List indexes are stored as a `u16`, so this returns an error once the list already holds 65,536 items.
+    pub fn push(&mut self, value: X) -> Result<u16, NP_Error> {
+
+        if self.next_index > LIST_MAX_SIZE {
+            return Err(NP_Error::new("List can't hold more than 65,536 items!"))
+        }
+
+        // list item pointer and value in one allocation
+        let item_addr = self.memory.malloc_borrow(&[0u8; 18][..(10 + core::mem::size_of::<X>())])?;
+
+        let item = NP_Cursor::new(item_addr, self.of, self.list.schema_addr);
+        let item_value = item.get_value_mut(self.memory);
+        item_value.set_index(self.next_index as u16);
+        // value already has an address, so it's written in place right after the item
+        item_value.set_addr_value((item_addr + 10) as u32);
+
+        X::set_value(item, self.memory, value)?;
+
+        if self.tail == 0 {
+            self.head = item_addr;
+        } else {
+            NP_Cursor::new(self.tail, self.of, self.list.schema_addr).get_value_mut(self.memory).set_next_addr(item_addr as u32);
+        }
+
+        self.tail = item_addr;
+        self.next_index += 1;
+
+        Ok((self.next_index - 1) as u16)
+    }
+
+    /// Write the list head and tail into the buffer, dropping the builder does the same thing.
+    pub fn finish(self) { }
+}
+
+impl<'builder, X> Drop for NP_ListBuilder<'builder, X> {
+    fn drop(&mut self) {
+        if self.tail != 0 {
+            let list_data = NP_List::get_list(self.list.get_value(self.memory).get_addr_value() as usize, self.memory);
+            list_data.set_head(self.head as u32);
+            list_data.set_tail(self.tail as u32);
+        }
+    }
 }
 
 /// NP Item
@@ -304,6 +304,9 @@ impl<'value> NP_Value<'value> for NP_Map<'value> {
 
         let mut result = String::from("map({value: ");
         result.push_str(NP_Schema::_type_to_idl(&schema, data.child)?.as_str());
+        if NP_Schema::is_required(&schema[address]) {
+            result.push_str(", required: true");
+        }
         result.push_str("})");
         Ok(result)
          
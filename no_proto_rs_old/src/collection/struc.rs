@@ -270,7 +270,9 @@ impl<'value> NP_Value<'value> for NP_Struct<'value> {
             ]) as usize;
 
             let field_addr = schema_parsed.len();
-            let (_, schema) = NP_Schema::from_bytes(schema_parsed, offset + 2, bytes);
+            // each field only sees its own bytes, so types with optional trailing bytes can't read into the next field
+            let field_end = usize::min(bytes.len(), offset + 2 + schema_size);
+            let (_, schema) = NP_Schema::from_bytes(schema_parsed, offset + 2, &bytes[..field_end]);
             schema_parsed = schema;
             // parsed_fields.push((x as u8, col_name.to_string(), field_addr));
             parsed_fields.push(NP_Struct_Field { idx: x as u8, col: col_name.to_string(), schema: field_addr, offset: 0});
@@ -400,7 +402,11 @@ impl<'value> NP_Value<'value> for NP_Struct<'value> {
             }
         }
 
-        result.push_str("}})");
+        result.push_str("}");
+        if NP_Schema::is_required(&schema[address]) {
+            result.push_str(", required: true");
+        }
+        result.push_str(")");
         Ok(result)
         
     }
@@ -462,6 +468,10 @@ impl<'value> NP_Value<'value> for NP_Struct<'value> {
                 if field_name.len() > 255 {
                     return Err(NP_Error::new("Struct field names cannot be longer than 255 characters!"))
                 }
+
+                if fields.iter().any(|field| field.col == field_name) {
+                    return Err(NP_Error::new(duplicate_field_error(field_name)))
+                }
     
                 let field_schema_addr = schema_parsed.len();
                 // fields.push((x, String::from(field_name), field_schema_addr));
@@ -549,6 +559,10 @@ impl<'value> NP_Value<'value> for NP_Struct<'value> {
                 return Err(NP_Error::new("Struct field names cannot be longer than 255 characters!"))
             }
 
+            if fields.iter().any(|field| field.col == field_name) {
+                return Err(NP_Error::new(duplicate_field_error(&field_name)))
+            }
+
             let field_schema_addr = schema_parsed.len();
             // fields.push((x, field_name.clone(), field_schema_addr));
             fields.push(NP_Struct_Field { idx: x as u8, col: field_name.clone(), schema: field_schema_addr, offset: 0});
@@ -600,6 +614,13 @@ impl<'value> NP_Value<'value> for NP_Struct<'value> {
     }
 }
 
+/// Error for a struct schema that defines the same field name twice
+fn duplicate_field_error(field_name: &str) -> String {
+    let mut err = String::from("duplicate field '");
+    err.push_str(field_name);
+    err.push_str("' in schema");
+    err
+}
 
 #[test]
 fn schema_parsing_works_idl() -> Result<(), NP_Error> {
@@ -701,4 +722,19 @@ fn test_vtables() -> Result<(), NP_Error> {
     assert_eq!(buffer.get::<u8>(&["rating"])?.unwrap(), 20u8);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[test]
+fn duplicate_fields_rejected() -> Result<(), NP_Error> {
+    let err = crate::NP_Factory::new(r#"struct({fields: {age: u8(), name: string(), age: u16()}})"#).err().unwrap();
+    assert!(matches!(err, NP_Error::Custom { message } if message == "duplicate field 'age' in schema"));
+
+    let err = crate::NP_Factory::new_json(r#"{"type":"struct","fields":[["name",{"type":"string"}],["name",{"type":"string"}]]}"#).err().unwrap();
+    assert!(matches!(err, NP_Error::Custom { message } if message == "duplicate field 'name' in schema"));
+
+    // nested structs are checked too, same names in different structs are fine
+    assert!(crate::NP_Factory::new(r#"struct({fields: {inner: struct({fields: {a: u8(), a: u8()}})}})"#).is_err());
+    crate::NP_Factory::new(r#"struct({fields: {a: u8(), inner: struct({fields: {a: u8()}})}})"#)?;
+
+    Ok(())
+}
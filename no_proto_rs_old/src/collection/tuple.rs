@@ -259,6 +259,9 @@ impl<'value> NP_Value<'value> for NP_Tuple {
         if schema[address].sortable == true {
             result.push_str(", sorted: true");
         }
+        if NP_Schema::is_required(&schema[address]) {
+            result.push_str(", required: true");
+        }
         result.push_str("})");
         Ok(result)
          
@@ -493,7 +496,9 @@ impl<'value> NP_Value<'value> for NP_Tuple {
                 bytes[offset + 1]
             ]) as usize;
             let schema_len = working_schema.len();
-            let (_sortable, schema) = NP_Schema::from_bytes(working_schema, offset + 2, bytes);
+            // each value only sees its own bytes, so types with optional trailing bytes can't read into the next value
+            let value_end = usize::min(bytes.len(), offset + 2 + schema_size);
+            let (_sortable, schema) = NP_Schema::from_bytes(working_schema, offset + 2, &bytes[..value_end]);
             match schema[schema_len].val {
                 NP_Value_Kind::Pointer => {
                     tuple_values.push(NP_Tuple_Field { schema: schema_len, offset: data_offset, size: 0, fixed: false });
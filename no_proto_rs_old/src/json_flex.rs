@@ -104,14 +104,16 @@ impl JSMAP {
 }
 
 /// Represents an JSON value
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub enum NP_JSON {
     /// String JSON type
     String(String), 
     /// Integer JSON type
     Integer(i64), 
     /// Float JSON type
-    Float(f64), 
+    /// 
+    /// Floats parsed from JSON text keep the original number text in the second field, so exact types like decimals can read the digits that were written instead of the nearest `f64`.  It's `None` for floats created in code.
+    Float(f64, Option<String>), 
     /// Map JSON type
     Dictionary(JSMAP), 
     /// List JSON type
@@ -126,6 +128,23 @@ pub enum NP_JSON {
 
 
 
+// The original text of a float isn't part of it's value, `1.50` and `1.5` are equal.
+impl PartialEq for NP_JSON {
+    fn eq(&self, other: &NP_JSON) -> bool {
+        match (self, other) {
+            (NP_JSON::String(a), NP_JSON::String(b)) => a == b,
+            (NP_JSON::Integer(a), NP_JSON::Integer(b)) => a == b,
+            (NP_JSON::Float(a, _), NP_JSON::Float(b, _)) => a == b,
+            (NP_JSON::Dictionary(a), NP_JSON::Dictionary(b)) => a == b,
+            (NP_JSON::Array(a), NP_JSON::Array(b)) => a == b,
+            (NP_JSON::Null, NP_JSON::Null) => true,
+            (NP_JSON::False, NP_JSON::False) => true,
+            (NP_JSON::True, NP_JSON::True) => true,
+            _ => false
+        }
+    }
+}
+
 impl NP_JSON {
 
 
@@ -161,8 +180,8 @@ impl NP_JSON {
             NP_JSON::Integer(int) => {
                 NP_JSON::Integer(*int)
             },
-            NP_JSON::Float(num) => {
-                NP_JSON::Float(*num)
+            NP_JSON::Float(num, raw) => {
+                NP_JSON::Float(*num, raw.clone())
             },
             NP_JSON::Null => {
                 NP_JSON::Null
@@ -192,7 +211,7 @@ impl NP_JSON {
     /// Get this value as an f64
     pub fn into_f64(&self) -> Option<&f64> {
         match self {
-            &NP_JSON::Float(ref v) => Some(v),
+            &NP_JSON::Float(ref v, _) => Some(v),
             _ => None,
         }
     }
@@ -210,6 +229,17 @@ impl NP_JSON {
             _ => None,
         }
     }
+    /// Name of this value's JSON type, used in error messages
+    pub fn kind(&self) -> &'static str {
+        match self {
+            &NP_JSON::String(_) => "string",
+            &NP_JSON::Integer(_) | &NP_JSON::Float(_, _) => "number",
+            &NP_JSON::Dictionary(_) => "object",
+            &NP_JSON::Array(_) => "array",
+            &NP_JSON::Null => "null",
+            &NP_JSON::True | &NP_JSON::False => "boolean",
+        }
+    }
     /// Check if this value is null
     pub fn is_null(&self) -> bool {
         match self {
@@ -262,7 +292,7 @@ impl NP_JSON {
     /// Check if this value is float
     pub fn is_float(&self) -> bool {
         match self {
-            &NP_JSON::Float(_) => true,
+            &NP_JSON::Float(_, _) => true,
             _ => false,
         }
     }
@@ -283,7 +313,7 @@ impl NP_JSON {
     /// Get a reference to the f64 in this value if it's a f64
     pub fn unwrap_f64(&self) -> Option<&f64> {
         match self {
-            &NP_JSON::Float(ref v) => Some(v),
+            &NP_JSON::Float(ref v, _) => Some(v),
             _ => None,
         }
     }
@@ -311,7 +341,7 @@ impl NP_JSON {
                 string
             },
             &NP_JSON::Integer(ref v) => v.to_string(),
-            &NP_JSON::Float(ref v) => v.to_string(),
+            &NP_JSON::Float(ref v, _) => NP_JSON::float_to_string(*v),
             &NP_JSON::Dictionary(ref v) => {
                 let mut string: String = "{".to_owned();
                 let mut is_first = true;
@@ -351,6 +381,76 @@ impl NP_JSON {
             &NP_JSON::True => "true".to_owned(),
         }
     }
+
+    /// Text of a float that parses back as a float, whole values like `1.0` or `f64::MAX` keep a `.0` so they aren't read as integers.
+    fn float_to_string(value: f64) -> String {
+        let mut text = value.to_string();
+        if value.is_finite() && !text.contains('.') {
+            text.push_str(".0");
+        }
+        text
+    }
+
+    /// Stringify this JSON object and it's children with newlines, each level is indented by `indent` spaces.
+    /// 
+    /// Keys are written in the order they are stored in the map.
+    pub fn stringify_pretty(&self, indent: usize) -> String {
+        let mut string = String::new();
+        self.write_pretty(indent, 0, &mut string);
+        string
+    }
+
+    fn write_pretty(&self, indent: usize, level: usize, string: &mut String) {
+        let pad = |string: &mut String, level: usize| {
+            string.push('\n');
+            for _ in 0..(indent * level) { string.push(' '); }
+        };
+
+        match self {
+            &NP_JSON::Dictionary(ref v) => {
+                if v.values.len() == 0 {
+                    string.push_str("{}");
+                    return;
+                }
+                string.push('{');
+                let mut is_first = true;
+                for (k, v) in &v.values {
+                    if is_first {
+                        is_first = false;
+                    } else {
+                        string.push(',');
+                    }
+                    pad(string, level + 1);
+                    string.push('"');
+                    string.push_str(k.replace("\"", "\\\"").as_str());
+                    string.push_str("\": ");
+                    v.write_pretty(indent, level + 1, string);
+                }
+                pad(string, level);
+                string.push('}');
+            },
+            &NP_JSON::Array(ref v) => {
+                if v.len() == 0 {
+                    string.push_str("[]");
+                    return;
+                }
+                string.push('[');
+                let mut is_first = true;
+                for i in v {
+                    if is_first {
+                        is_first = false;
+                    } else {
+                        string.push(',');
+                    }
+                    pad(string, level + 1);
+                    i.write_pretty(indent, level + 1, string);
+                }
+                pad(string, level);
+                string.push(']');
+            },
+            _ => string.push_str(self.stringify().as_str())
+        }
+    }
 }
 
 impl Index<usize> for NP_JSON {
@@ -767,7 +867,7 @@ pub fn json_decode<'json>(text: String) -> Result<Box<NP_JSON>, NP_Error> {
                                     new_num = new_num.trim().to_string();
 
                                     match new_num.find('.') {
-                                        Some(_) => vv.push( NP_JSON::Float(f64::from_str(&new_num.clone())?) ),
+                                        Some(_) => vv.push( NP_JSON::Float(f64::from_str(&new_num.clone())?, Some(new_num.clone())) ),
                                         None    => vv.push( NP_JSON::Integer(i64::from_str(&new_num.clone())?) ),
                                     };
                                 }
@@ -1134,7 +1234,7 @@ pub fn json_decode<'json>(text: String) -> Result<Box<NP_JSON>, NP_Error> {
                                         NP_Error::unwrap(value.pop())?;
                                         value = value.trim().to_string();
                                         match value.find('.') {
-                                            Some(_) => vv.insert(key, NP_JSON::Float(f64::from_str(&value.clone())?)) ,
+                                            Some(_) => vv.insert(key, NP_JSON::Float(f64::from_str(&value.clone())?, Some(value.clone()))) ,
                                             None    => vv.insert(key, NP_JSON::Integer(i64::from_str(&value.clone())?)),
                                         };
                                     }
@@ -1447,7 +1547,7 @@ pub fn json_decode<'json>(text: String) -> Result<Box<NP_JSON>, NP_Error> {
 
                                     match new_num.find('.') {
                                         Some(_) => {
-                                            vv.push(NP_JSON::Float(f64::from_str(&new_num)?))
+                                            vv.push(NP_JSON::Float(f64::from_str(&new_num)?, Some(new_num.clone())))
                                         }
                                         None => {
                                             vv.push(NP_JSON::Integer(i64::from_str(&new_num)?))
@@ -1466,7 +1566,7 @@ pub fn json_decode<'json>(text: String) -> Result<Box<NP_JSON>, NP_Error> {
                                     match new_num.find('.') {
                                         Some(_) => {
                                             vv.insert(key,
-                                                      NP_JSON::Float(f64::from_str(&new_num)?))
+                                                      NP_JSON::Float(f64::from_str(&new_num)?, Some(new_num.clone())))
                                         }
                                         None => {
                                             vv.insert(key,
@@ -325,6 +325,9 @@
 #[macro_use]
 extern crate std;
 
+#[cfg(all(feature = "std", not(test)))]
+extern crate std;
+
 pub mod idl;
 pub mod pointer;
 pub mod collection;
@@ -345,15 +348,16 @@ extern crate alloc;
 
 use core::ops::{Deref, DerefMut};
 // use crate::buffer_ro::NP_Buffer_RO;
-use crate::memory::NP_Memory;
+use crate::memory::{NP_Memory, NP_Options};
+use crate::pointer::numbers::NumericOverflowPolicy;
 use crate::json_flex::NP_JSON;
 use crate::schema::NP_Schema;
 use crate::json_flex::json_decode;
 use crate::error::NP_Error;
-use buffer::{NP_Buffer, DEFAULT_ROOT_PTR_ADDR};
+use buffer::{NP_Buffer, NP_BufferRef, DEFAULT_ROOT_PTR_ADDR};
 use alloc::vec::Vec;
 use alloc::string::String;
-use schema::NP_Parsed_Schema;
+use schema::{NP_Parsed_Schema, NP_TypeKeys};
 
 /// Generate a path from a string.  The path must use dot notation between the path segments.
 /// 
@@ -397,6 +401,37 @@ macro_rules! np_path {
 }
 
 
+/// Create a factory from schema bytes compiled into the binary, so no IDL or JSON is parsed at runtime.
+/// 
+/// Parsing the IDL needs the full schema parser, which can't run inside `const` code or a declarative macro.  Instead the schema is compiled by a build script: it's parsed and validated there with `NP_Factory::new`, and a bad schema fails the build instead of the program.  The exported bytes are embedded with `include_bytes!` and loaded with `NP_Factory::new_bytes`, which only reads the compact byte layout.
+/// 
+/// In `build.rs`:
+/// ```ignore
+/// use no_proto::NP_Factory;
+/// 
+/// fn main() {
+///     let factory = NP_Factory::new("u32({default: 5})").expect("invalid schema");
+///     let out_dir = std::env::var("OUT_DIR").unwrap();
+///     std::fs::write(format!("{}/count.np", out_dir), factory.export_schema_bytes()).unwrap();
+/// }
+/// ```
+/// 
+/// Then, instead of `NP_Factory::new("u32({default: 5})")?` (the bytes for this schema are checked in at `fixtures/count.np`):
+/// ```ignore
+/// use no_proto::np_schema;
+/// 
+/// let factory = np_schema!(concat!(env!("OUT_DIR"), "/count.np"))?;
+/// let buffer = factory.new_buffer(None);
+/// assert_eq!(buffer.get::<u32>(&[])?, Some(5));
+/// ```
+/// 
+#[macro_export]
+macro_rules! np_schema {
+    ($path: expr) => {
+        $crate::NP_Factory::new_bytes(include_bytes!($path))
+    }
+}
+
 /// Factories are created from schemas.  Once you have a factory you can use it to create new buffers or open existing ones.
 /// 
 /// The easiest way to create a factory is to pass a JSON string schema into the static `new` method.  [Learn about schemas here.](./schema/index.html)
@@ -466,7 +501,8 @@ macro_rules! np_path {
 pub struct NP_Factory {
     /// schema data used by this factory
     pub schema: NP_Schema,
-    schema_bytes: Vec<u8>
+    schema_bytes: Vec<u8>,
+    options: NP_Options
 }
 
 unsafe impl Send for NP_Factory {}
@@ -483,6 +519,26 @@ pub struct NP_Size_Data {
     pub wasted_bytes: usize
 }
 
+/// Returned from `compact` on a buffer, describes how much space the compaction saved.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct NP_Compact_Result {
+    /// The size of the buffer before compaction
+    pub bytes_before: usize,
+    /// The size of the buffer after compaction
+    pub bytes_after: usize,
+    /// How many bytes compaction removed
+    pub reclaimed: usize
+}
+
+/// How `NP_Factory::buffers_equal` treats number and decimal fields that have a schema default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EqMode {
+    /// Compare the values a reader would see, an unset field reads as its default so it equals a field set to the default.
+    Semantic,
+    /// Compare only what was written, an unset field never equals a field set to the default.
+    Structural
+}
+
 impl NP_Factory {
 
     /// Generate a new factory from an ES6 schema
@@ -498,6 +554,7 @@ impl NP_Factory {
 
         Ok(Self {
             schema_bytes: schema_bytes,
+            options: NP_Options::default(),
             schema:  NP_Schema {
                 is_sortable: is_sortable,
                 parsed: schema
@@ -516,6 +573,7 @@ impl NP_Factory {
 
         Ok(Self {
             schema_bytes: Vec::from(schema_bytes),
+            options: NP_Options::default(),
             schema:  NP_Schema { 
                 is_sortable: is_sortable,
                 parsed: schema
@@ -523,6 +581,188 @@ impl NP_Factory {
         })
     }
 
+    /// Generate a new factory from a JSON schema read out of any `std::io::Read` source, like a file.
+    /// 
+    /// The JSON parser works on a complete string, so the reader is read to the end before the schema is parsed.  This saves loading the file yourself, it doesn't lower peak memory use compared to reading the file into a `String` first.
+    /// 
+    /// Only available with the `std` feature, `no_std` users can keep passing strings to the other constructors.
+    /// 
+    #[cfg(feature = "std")]
+    pub fn from_json_reader<R: std::io::Read>(mut reader: R) -> Result<Self, NP_Error> {
+        use alloc::string::ToString;
+
+        let mut json_bytes: Vec<u8> = Vec::new();
+
+        if let Err(e) = reader.read_to_end(&mut json_bytes) {
+            let mut err = String::from("Error reading JSON schema: ");
+            err.push_str(e.to_string().as_str());
+            return Err(NP_Error::new(err));
+        }
+
+        let parsed_value = json_decode(String::from_utf8(json_bytes)?)?;
+
+        let (is_sortable, schema_bytes, schema) = NP_Schema::from_json(Vec::new(), &parsed_value)?;
+
+        Ok(Self {
+            schema_bytes: schema_bytes,
+            options: NP_Options::default(),
+            schema:  NP_Schema {
+                is_sortable: is_sortable,
+                parsed: schema
+            }
+        })
+    }
+
+    /// Set how out of range numbers are handled by buffers created from this factory.
+    /// 
+    /// The policy applies when `set_with_json` writes a number that doesn't fit the schema type, like `300` into a `u8`.  The default is `NumericOverflowPolicy::Wrap`, which casts the value the same way `as` does.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::pointer::numbers::NumericOverflowPolicy;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("u8()")?.with_overflow_policy(NumericOverflowPolicy::Saturate);
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.set_with_json(&[], r#"{"value": 300}"#)?;
+    /// assert_eq!(new_buffer.get::<u8>(&[])?, Some(255));
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn with_overflow_policy(mut self, policy: NumericOverflowPolicy) -> Self {
+        self.options.overflow_policy = policy;
+        self
+    }
+
+    /// Make `set_with_json` on buffers from this factory return an error when a JSON value doesn't match the field type.
+    /// 
+    /// By default mismatched values (like a string that isn't a number for a number field) are ignored and the field is left alone.  With strict JSON on they return an error like `Can't parse number for type (uint8): twelve` or `expected number, got array` instead.  `null` is never an error.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("struct({fields: {age: u8(), name: string()}})")?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.set_with_json(&[], r#"{"value": {"age": "twelve"}}"#)?;
+    /// assert_eq!(new_buffer.get::<u8>(&["age"])?, None);
+    /// 
+    /// let factory = factory.with_strict_json(true);
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// assert!(new_buffer.set_with_json(&[], r#"{"value": {"age": "twelve"}}"#).is_err());
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn with_strict_json(mut self, strict: bool) -> Self {
+        self.options.strict_json = strict;
+        self
+    }
+
+    /// Make `json_encode` on buffers from this factory output `null` for number and decimal fields that haven't been set.
+    /// 
+    /// By default an unset field with a schema default is written out as that default, so a field set to the default and a field never set look the same.  Patch and merge consumers that need to tell them apart can turn this on.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::json_flex::NP_JSON;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("struct({fields: {retries: u8({default: 3})}})")?;
+    /// 
+    /// let new_buffer = factory.new_buffer(None);
+    /// assert_eq!(new_buffer.json_encode(&["retries"])?["value"], NP_JSON::Integer(3));
+    /// 
+    /// let factory = factory.with_emit_null_for_unset(true);
+    /// 
+    /// let new_buffer = factory.new_buffer(None);
+    /// assert_eq!(new_buffer.json_encode(&["retries"])?["value"], NP_JSON::Null);
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn with_emit_null_for_unset(mut self, emit_null: bool) -> Self {
+        self.options.emit_null_for_unset = emit_null;
+        self
+    }
+
+    /// Make `set_with_json_apply_null` on buffers from this factory clear number and decimal fields on `null`, even if the schema has a default.
+    /// 
+    /// By default a `null` writes the schema default into the buffer when there is one.  With this on the value is cleared instead.  `null` struct fields are always cleared.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("list({of: u8({default: 3})})")?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.set_with_json(&[], r#"{"value": [8]}"#)?;
+    /// new_buffer.set_with_json_apply_null(&[], r#"{"value": [null]}"#)?;
+    /// assert_eq!(new_buffer.get::<u8>(&["0"])?, Some(3));
+    /// 
+    /// let factory = factory.with_null_clears(true);
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.set_with_json(&[], r#"{"value": [8]}"#)?;
+    /// new_buffer.set_with_json_apply_null(&[], r#"{"value": [null]}"#)?;
+    /// assert_eq!(new_buffer.get::<u8>(&["0"])?, None);
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn with_null_clears(mut self, clear: bool) -> Self {
+        self.options.null_clears = clear;
+        self
+    }
+
+    /// Check if two buffers made with this factory hold the same data.
+    /// 
+    /// With `EqMode::Semantic` number and decimal fields are compared after defaults are filled in, so a field that was never set equals one set to the schema default.  With `EqMode::Structural` they only match if both are unset or both hold the same value.
+    /// 
+    /// Values are compared after they're decoded, so map keys are compared without regard to insertion order and floats are compared exactly no matter the `display_places` of their schema.  A NaN equals another NaN.  A collection or map key that isn't in one buffer is the same as one that's there with nothing set inside it.
+    /// 
+    /// Both buffers must have been made or opened by this factory, buffers from any other factory give an error even if the schemas match.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::{NP_Factory, EqMode};
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("struct({fields: {retries: u8({default: 3})}})")?;
+    /// 
+    /// let unset = factory.new_buffer(None);
+    /// let mut set = factory.new_buffer(None);
+    /// set.set(&["retries"], 3u8)?;
+    /// 
+    /// assert_eq!(factory.buffers_equal(&unset, &set, EqMode::Semantic)?, true);
+    /// assert_eq!(factory.buffers_equal(&unset, &set, EqMode::Structural)?, false);
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn buffers_equal(&self, a: &NP_Buffer, b: &NP_Buffer, mode: EqMode) -> Result<bool, NP_Error> {
+        let semantic = match mode {
+            EqMode::Semantic => true,
+            EqMode::Structural => false
+        };
+
+        if !a._uses_schema(&self.schema.parsed) || !b._uses_schema(&self.schema.parsed) {
+            return Err(NP_Error::new("Buffers passed to buffers_equal must come from this factory!"))
+        }
+
+        NP_Buffer::_values_eq(a, b, semantic)
+    }
+
+    /// Apply this factory's settings to buffer memory
+    fn with_policy(&self, mut memory: NP_Memory) -> NP_Memory {
+        memory.options = self.options;
+        memory
+    }
+
     /// Get a copy of the compiled schema byte array
     /// 
     pub fn export_schema_bytes(&self) -> &[u8] {
@@ -535,10 +775,61 @@ impl NP_Factory {
         self.schema.to_idl()
     }
 
+    /// Get every type used anywhere in this factory's schema, collections included.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::schema::NP_TypeKeys;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("list({of: uuid()})")?;
+    /// 
+    /// let used: Vec<NP_TypeKeys> = factory.type_keys_used().into_iter().collect();
+    /// assert_eq!(used, vec![NP_TypeKeys::Uuid, NP_TypeKeys::List]);
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn type_keys_used(&self) -> alloc::collections::BTreeSet<NP_TypeKeys> {
+        self.schema.parsed.iter().map(|schema| schema.i).collect()
+    }
+
     /// Open existing Vec<u8> as buffer for this factory.  
     /// 
     pub fn open_buffer(&self, bytes: Vec<u8>) -> NP_Buffer {
-        NP_Buffer::_new(NP_Memory::existing_owned(bytes, &self.schema.parsed, DEFAULT_ROOT_PTR_ADDR))
+        NP_Buffer::_new(self.with_policy(NP_Memory::existing_owned(bytes, &self.schema.parsed, DEFAULT_ROOT_PTR_ADDR)))
+    }
+
+    /// Open existing bytes as a read only view without copying them.
+    /// 
+    /// The returned [`NP_BufferRef`](buffer/struct.NP_BufferRef.html) borrows the slice, so it works well with memory mapped files.  It only has methods for reading values, so mutations aren't possible.
+    /// 
+    /// An error is returned if the slice is too short to hold a buffer.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("struct({fields: {count: u32()}})")?;
+    /// 
+    /// let mut new_buffer = factory.new_buffer(None);
+    /// new_buffer.set(&["count"], 42u32)?;
+    /// let bytes: Vec<u8> = new_buffer.finish().bytes();
+    /// 
+    /// let view = factory.open_ref(&bytes)?;
+    /// assert_eq!(view.get::<u32>(&["count"])?, Some(42));
+    /// 
+    /// assert!(factory.open_ref(&[0u8; 2]).is_err());
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn open_ref<'buffer>(&'buffer self, bytes: &'buffer [u8]) -> Result<NP_BufferRef<'buffer>, NP_Error> {
+        if bytes.len() < DEFAULT_ROOT_PTR_ADDR + 4 {
+            return Err(NP_Error::new("Bytes are too short to be a buffer!"));
+        }
+
+        Ok(NP_BufferRef::_new(self.with_policy(NP_Memory::existing_ref(bytes, &self.schema.parsed, DEFAULT_ROOT_PTR_ADDR))))
     }
 
     /// Open existing buffer as ready only ref, can much faster if you don't need to mutate anything.
@@ -548,7 +839,7 @@ impl NP_Factory {
     /// Also, read only buffers are `Sync` and `Send` so good for multithreaded environments.
     /// 
     pub fn open_buffer_ref<'buffer>(&'buffer self, bytes: &'buffer [u8]) -> NP_Buffer {
-        NP_Buffer::_new(NP_Memory::existing_ref(bytes, &self.schema.parsed, DEFAULT_ROOT_PTR_ADDR))
+        NP_Buffer::_new(self.with_policy(NP_Memory::existing_ref(bytes, &self.schema.parsed, DEFAULT_ROOT_PTR_ADDR)))
     }
 
     /// Open existing buffer as mutable ref, can be much faster to skip copying.  The `data_len` property is how many bytes the data in the buffer is using up.
@@ -561,16 +852,68 @@ impl NP_Factory {
     /// 
     /// 
     pub fn open_buffer_ref_mut<'buffer>(&'buffer self, bytes: &'buffer mut [u8], data_len: usize) -> NP_Buffer {
-        NP_Buffer::_new(NP_Memory::existing_ref_mut(bytes, data_len, &self.schema.parsed, DEFAULT_ROOT_PTR_ADDR))
+        NP_Buffer::_new(self.with_policy(NP_Memory::existing_ref_mut(bytes, data_len, &self.schema.parsed, DEFAULT_ROOT_PTR_ADDR)))
     }
 
     /// Generate a new empty buffer from this factory.
     /// 
     /// The first opional argument, capacity, can be used to set the space of the underlying Vec<u8> when it's created.  If you know you're going to be putting lots of data into the buffer, it's a good idea to set this to a large number comparable to the amount of data you're putting in.  The default is 1,024 bytes.
     /// 
+    /// The capacity is allocated up front, so writes that stay inside it never reallocate.  You can check how many reallocations a buffer has needed with `alloc_count`.
+    /// 
     /// 
     pub fn new_buffer<'buffer>(&'buffer self, capacity: Option<usize>) -> NP_Buffer {
-        NP_Buffer::_new(NP_Memory::new(capacity, &self.schema.parsed, DEFAULT_ROOT_PTR_ADDR))
+        NP_Buffer::_new(self.with_policy(NP_Memory::new(capacity, &self.schema.parsed, DEFAULT_ROOT_PTR_ADDR)))
+    }
+
+    /// Generate a new buffer from this factory and fill it from a JSON document that matches the schema.
+    /// 
+    /// This is the inverse of `json_encode`, the document is the value itself rather than the `{"value": ...}` wrapper `set_with_json` uses.  Values are checked the same way as `with_strict_json(true)`, the first value that doesn't match the schema is returned as an error with it's path, like `expected string, got number (at path: tags.1)`.  `null` values and unknown struct fields are skipped.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::json_flex::json_decode;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new("struct({fields: {name: string(), tags: list({of: string()})}})")?;
+    /// 
+    /// let json = json_decode(String::from(r#"{"name": "hello", "tags": ["a", "b"]}"#))?;
+    /// let new_buffer = factory.buffer_from_json(&json)?;
+    /// 
+    /// assert_eq!(new_buffer.get::<&str>(&["name"])?, Some("hello"));
+    /// assert_eq!(new_buffer.get::<&str>(&["tags", "1"])?, Some("b"));
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn buffer_from_json(&self, json: &NP_JSON) -> Result<NP_Buffer, NP_Error> {
+        let mut buffer = self.new_buffer(None);
+        buffer.fill_from_json(json)?;
+        Ok(buffer)
+    }
+
+    /// Generate a new buffer from this factory with every `uuid({auto: true})` field set to a new random v4 UUID.
+    /// 
+    /// Works the same as `new_buffer` followed by `fill_auto_uuids(NP_UUID::new_v4)` on the buffer, see that method for which fields are filled.
+    /// 
+    /// ```
+    /// use no_proto::error::NP_Error;
+    /// use no_proto::NP_Factory;
+    /// use no_proto::pointer::uuid::NP_UUID;
+    /// 
+    /// let factory: NP_Factory = NP_Factory::new(r#"struct({fields: {id: uuid({auto: true})}})"#)?;
+    /// 
+    /// let buffer = factory.new_buffer_with_defaults(None)?;
+    /// assert_eq!(buffer.get::<NP_UUID>(&["id"])?.unwrap().version(), 4);
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    #[cfg(feature = "getrandom")]
+    pub fn new_buffer_with_defaults<'buffer>(&'buffer self, capacity: Option<usize>) -> Result<NP_Buffer, NP_Error> {
+        let mut buffer = self.new_buffer(capacity);
+        buffer.fill_auto_uuids(crate::pointer::uuid::NP_UUID::new_v4)?;
+        Ok(buffer)
     }
 
     /// Generate a new empty buffer from this factory.
@@ -578,7 +921,7 @@ impl NP_Factory {
     /// Make sure the mutable slice is large enough to fit all the data you plan on putting into it.
     /// 
     pub fn new_buffer_ref_mut<'buffer>(&'buffer self, bytes: &'buffer mut [u8]) -> NP_Buffer {
-        NP_Buffer::_new(NP_Memory::new_ref_mut(bytes, &self.schema.parsed, DEFAULT_ROOT_PTR_ADDR))
+        NP_Buffer::_new(self.with_policy(NP_Memory::new_ref_mut(bytes, &self.schema.parsed, DEFAULT_ROOT_PTR_ADDR)))
     }
 
     // /// Convert a regular buffer into a packed buffer. A "packed" buffer contains the schema and the buffer data together.
@@ -693,4 +1036,763 @@ fn threading_works() {
         assert_eq!(6, b.calc_bytes().unwrap().current_buffer);
         assert_eq!(8, f.len());
     }).join().unwrap()
-}
\ No newline at end of file
+}
+#[test]
+fn root_schema_metadata_works() -> Result<(), NP_Error> {
+    let schema = r#"{"type":"struct","fields":[["age",{"type":"uint8"}]],"$id":"https://example.com/user.json","title":"User"}"#;
+    let factory = NP_Factory::new_json(schema)?;
+    assert_eq!(schema, factory.schema.to_json()?.stringify());
+
+    // metadata survives the trip through schema bytes
+    let factory2 = NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_json()?.stringify());
+
+    // and doesn't get in the way of the buffer
+    let mut buffer = factory2.new_buffer(None);
+    buffer.set(&["age"], 20u8)?;
+    assert_eq!(buffer.get::<u8>(&["age"])?, Some(20));
+
+    // schemas without metadata are unchanged
+    let schema = r#"{"type":"uint8"}"#;
+    let factory = NP_Factory::new_json(schema)?;
+    assert_eq!(factory.export_schema_bytes().len(), NP_Factory::new("u8()")?.export_schema_bytes().len());
+
+    // schema bytes that happen to end like the old metadata trailer aren't mistaken for metadata
+    let schema = r#"{"type":"struct","fields":[["a",{"type":"uint16","default":255}],["b",{"type":"uint16","default":65357}]]}"#;
+    let factory = NP_Factory::new_json(schema)?;
+    assert_eq!(&factory.export_schema_bytes()[(factory.export_schema_bytes().len() - 2)..], &[255, b'M']);
+    let factory2 = NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_json()?.stringify());
+
+    Ok(())
+}
+
+#[test]
+fn required_fields_work() -> Result<(), NP_Error> {
+    let schema = r#"{"type":"struct","fields":[["id",{"type":"uint32","required":true}],["status",{"type":"uint8","default":1,"required":true}],["tags",{"type":"list","of":{"type":"struct","fields":[["key",{"type":"string","required":true}],["value",{"type":"string"}]]}}]]}"#;
+    let factory = NP_Factory::new_json(schema)?;
+    assert_eq!(schema, factory.schema.to_json()?.stringify());
+
+    // the flag survives schema bytes and IDL
+    let factory2 = NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_json()?.stringify());
+    let idl = factory.schema.to_idl()?;
+    assert_eq!(idl, NP_Factory::new(idl.as_str())?.schema.to_idl()?);
+    for idl in [
+        "u32({required: true})",
+        "u32({default: 5, required: true})",
+        "bool({default: true, required: true})",
+        "date({required: true})",
+        "geo4({required: true})",
+        "any({required: true})",
+        "ulid({required: true})",
+        "dec({exp: 2, required: true})",
+        "enum({choices: [\"a\", \"b\"], required: true})",
+        "list({of: string({required: true}), required: true})",
+        "struct({fields: {a: bytes({required: true})}, required: true})",
+        "tuple({values: [uuid({required: true})], required: true})",
+        "struct({fields: {a: u8(), b: portal({to: \"a\", required: true})}})"
+    ].iter() {
+        assert_eq!(NP_Factory::new(idl)?.schema.to_idl()?, *idl);
+    }
+
+    let mut buffer = factory2.new_buffer(None);
+
+    // status is satisfied by it's default
+    assert_eq!(buffer.assert_complete().unwrap_err(), vec![vec![String::from("id")]]);
+    buffer.set(&["id"], 2u32)?;
+    assert!(buffer.assert_complete().is_ok());
+
+    // required values inside list items are checked for each item
+    buffer.set(&["tags", "0", "key"], "color")?;
+    buffer.set(&["tags", "1", "value"], "blue")?;
+    buffer.set(&["tags", "2", "key"], "size")?;
+    let missing = buffer.assert_complete().unwrap_err();
+    assert_eq!(missing, vec![vec![String::from("tags"), String::from("1"), String::from("key")]]);
+
+    Ok(())
+}
+
+#[test]
+fn to_json_pretty_is_stable() -> Result<(), NP_Error> {
+    let factory = NP_Factory::new(r#"struct({fields: {
+        id: u32(),
+        counts: map({value: list({of: tuple({values: [string(), struct({fields: {b: u8(), a: u8()}})]})})}),
+        name: string()
+    }})"#)?;
+
+    let mut first = factory.new_buffer(None);
+    first.set(&["name"], "hello")?;
+    first.set(&["counts", "zoo", "0", "0"], "z")?;
+    first.set(&["counts", "apple", "0", "1", "a"], 1u8)?;
+    first.set(&["counts", "apple", "0", "1", "b"], 2u8)?;
+    first.set(&["id"], 7u32)?;
+
+    // same data, set in a different order
+    let mut second = factory.new_buffer(None);
+    second.set(&["id"], 7u32)?;
+    second.set(&["counts", "apple", "0", "1", "b"], 2u8)?;
+    second.set(&["counts", "apple", "0", "1", "a"], 1u8)?;
+    second.set(&["counts", "zoo", "0", "0"], "z")?;
+    second.set(&["name"], "hello")?;
+
+    let expected = r#"{
+    "value": {
+        "id": 7,
+        "counts": {
+            "apple": [
+                [
+                    null,
+                    {
+                        "b": 2,
+                        "a": 1
+                    }
+                ]
+            ],
+            "zoo": [
+                [
+                    "z",
+                    null
+                ]
+            ]
+        },
+        "name": "hello"
+    }
+}"#;
+
+    assert_eq!(first.to_json_pretty(4)?, expected);
+    assert_eq!(second.to_json_pretty(4)?, expected);
+    assert_eq!(first.to_json_pretty(4)?, first.to_json_pretty(4)?);
+
+    // output can be read back in
+    let mut third = factory.new_buffer(None);
+    third.set_with_json(&[], first.to_json_pretty(2)?)?;
+    assert_eq!(third.to_json_pretty(4)?, expected);
+
+    Ok(())
+}
+
+#[test]
+fn clear_reuses_buffer() -> Result<(), NP_Error> {
+    let factory = NP_Factory::new("struct({fields: {id: u32(), tags: list({of: string()}), nested: struct({fields: {a: i64()}})}})")?;
+
+    let fill = |buffer: &mut NP_Buffer, round: u32| -> Result<(), NP_Error> {
+        buffer.set(&["id"], round)?;
+        buffer.set(&["nested", "a"], -5i64)?;
+        for x in 0..20 {
+            buffer.list_push(&["tags"], "a somewhat long tag value")?;
+            buffer.set(&["id"], x)?;
+        }
+        Ok(())
+    };
+
+    let mut buffer = factory.new_buffer(Some(8192));
+    fill(&mut buffer, 1)?;
+    assert_eq!(buffer.alloc_count(), 0);
+
+    for round in 2..10 {
+        buffer.clear()?;
+        assert_eq!(buffer.get::<u32>(&["id"])?, None);
+        assert_eq!(buffer.get_length(&["tags"])?, None);
+        fill(&mut buffer, round)?;
+    }
+
+    // same bytes as a fresh buffer, without ever growing the allocation
+    let mut fresh = factory.new_buffer(None);
+    fill(&mut fresh, 9)?;
+    assert_eq!(buffer.read_bytes(), fresh.read_bytes());
+    assert_eq!(buffer.alloc_count(), 0);
+
+    // read only buffers can't be cleared
+    let bytes = fresh.finish().bytes();
+    let mut read_only = factory.open_buffer_ref(&bytes);
+    assert!(read_only.clear().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn open_ref_works() -> Result<(), NP_Error> {
+    let factory = NP_Factory::new("struct({fields: {price: dec({exp: 2}), tags: list({of: string()})}})")?;
+
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&["price"], crate::pointer::dec::NP_Dec::new(1995, 2))?;
+    buffer.list_push(&["tags"], "sale")?;
+    let bytes = buffer.finish().bytes();
+
+    let owned = factory.open_buffer(bytes.clone());
+    let view = factory.open_ref(&bytes)?;
+
+    assert_eq!(view.get::<crate::pointer::dec::NP_Dec>(&["price"])?, owned.get::<crate::pointer::dec::NP_Dec>(&["price"])?);
+    assert_eq!(view.get::<&str>(&["tags", "0"])?, Some("sale"));
+    assert_eq!(view.get_length(&["tags"])?, Some(1));
+    assert_eq!(view.json_encode(&[])?, owned.json_encode(&[])?);
+
+    // the view reads from the slice, nothing is copied
+    assert_eq!(view.read_bytes().as_ptr(), bytes.as_ptr());
+
+    Ok(())
+}
+
+#[test]
+fn compact_result_works() -> Result<(), NP_Error> {
+    let factory = NP_Factory::new("struct({fields: {name: string(), tags: list({of: string()}), count: u32()}})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    for x in 0..10u32 {
+        buffer.set(&["name"], "a name that gets longer each time".repeat(x as usize + 1).as_str())?;
+        buffer.set(&["count"], x)?;
+        buffer.list_push(&["tags"], "tag")?;
+    }
+    buffer.del(&["tags"])?;
+
+    let before = buffer.calc_bytes()?;
+    assert!(before.wasted_bytes > 0);
+
+    let result = buffer.compact(None)?;
+    assert_eq!(result.bytes_before, before.current_buffer);
+    assert_eq!(result.bytes_after, before.after_compaction);
+    assert_eq!(result.reclaimed, before.wasted_bytes);
+    assert_eq!(buffer.calc_bytes()?.current_buffer, result.bytes_after);
+
+    // nothing left to reclaim
+    let result = buffer.compact(None)?;
+    assert_eq!(result.reclaimed, 0);
+    assert_eq!(result.bytes_before, result.bytes_after);
+
+    Ok(())
+}
+
+#[test]
+fn bounds_json_works() -> Result<(), NP_Error> {
+    let factory = NP_Factory::new("struct({fields: {small: i8(), price: dec({exp: 2}), id: uuid(), items: list({of: u8()}), list_item: list({of: i16()})}})")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&["small"], 5i8)?;
+    let before = buffer.read_bytes().to_vec();
+
+    let (min, max) = buffer.bounds_json(&["small"])?;
+    assert_eq!(min.stringify(), "-128");
+    assert_eq!(max.stringify(), "127");
+
+    let (min, max) = buffer.bounds_json(&["price"])?;
+    assert_eq!(min["parts"]["num"], NP_JSON::Integer(i64::MIN));
+    assert_eq!(max["parts"]["num"], NP_JSON::Integer(i64::MAX));
+    assert_eq!(max["parts"]["exp"], NP_JSON::Integer(2));
+
+    let (min, max) = buffer.bounds_json(&["id"])?;
+    assert_eq!(min.stringify(), "\"00000000-0000-0000-0000-000000000000\"");
+    assert_eq!(max.stringify(), "\"FFFFFFFF-FFFF-FFFF-FFFF-FFFFFFFFFFFF\"");
+
+    // list items resolve through the schema even when the list is empty
+    let (min, max) = buffer.bounds_json(&["list_item", "3"])?;
+    assert_eq!(min.stringify(), "-32768");
+    assert_eq!(max.stringify(), "32767");
+
+    assert!(buffer.bounds_json(&["items"]).is_err());
+    assert!(buffer.bounds_json(&["missing"]).is_err());
+
+    // buffer is untouched
+    assert_eq!(buffer.read_bytes(), &before[..]);
+
+    Ok(())
+}
+
+#[test]
+fn number_list_builder_works() -> Result<(), NP_Error> {
+    let factory = NP_Factory::new("struct({fields: {values: list({of: i64()}), names: list({of: string()})}})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    buffer.list_push(&["values"], -1i64)?;
+
+    // fill every remaining index the list format allows
+    let mut builder = buffer.number_list_builder::<i64>(&["values"])?;
+    for x in 1..65536i64 {
+        assert_eq!(builder.push(x * 3)?, x as u16);
+    }
+    assert!(matches!(builder.push(0), Err(NP_Error::Custom { message }) if message == "List can't hold more than 65,536 items!"));
+    builder.finish();
+
+    assert_eq!(buffer.get_length(&["values"])?, Some(65536));
+    assert_eq!(buffer.get::<i64>(&["values", "0"])?, Some(-1));
+    assert_eq!(buffer.get::<i64>(&["values", "200"])?, Some(600));
+
+    let mut count = 0usize;
+    for item in buffer.get_collection(&["values"])?.unwrap() {
+        let expected = if item.index == 0 { -1 } else { item.index as i64 * 3 };
+        assert_eq!(item.get::<i64>()?, Some(expected));
+        count += 1;
+    }
+    assert_eq!(count, 65536);
+
+    // dropping the builder also updates the list
+    {
+        let mut builder = buffer.number_list_builder::<i64>(&["values"])?;
+        assert!(builder.push(1).is_err());
+    }
+    assert!(buffer.number_list_builder::<u8>(&["values"]).is_err());
+    assert!(buffer.number_list_builder::<u8>(&["names"]).is_err());
+
+    // survives compaction
+    buffer.compact(None)?;
+    assert_eq!(buffer.get_length(&["values"])?, Some(65536));
+    assert_eq!(buffer.get::<i64>(&["values", "255"])?, Some(765));
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn from_json_reader_works() -> Result<(), NP_Error> {
+    let schema = r#"{"type":"struct","fields":[["id",{"type":"uuid"}],["price",{"type":"decimal","exp":2}],["tags",{"type":"list","of":{"type":"string"}}]]}"#;
+
+    let from_reader = NP_Factory::from_json_reader(std::io::Cursor::new(schema.as_bytes()))?;
+    let from_string = NP_Factory::new_json(schema)?;
+
+    assert_eq!(from_reader.export_schema_bytes(), from_string.export_schema_bytes());
+    assert_eq!(from_reader.schema.to_json()?.stringify(), schema);
+
+    assert!(NP_Factory::from_json_reader(std::io::Cursor::new(&[0xffu8, 0xfe][..])).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn value_location_works() -> Result<(), NP_Error> {
+    let factory = NP_Factory::new("struct({fields: {count: u32(), name: string(), tags: list({of: string()})}})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    // nothing in the buffer yet
+    assert_eq!(buffer.value_location(&["count"])?, None);
+
+    buffer.set(&["count"], 0x01020304u32)?;
+    buffer.set(&["name"], "hello")?;
+
+    let (address, length) = buffer.value_location(&["count"])?.unwrap();
+    assert!(address > 0);
+    assert_eq!(length, 4);
+    assert_eq!(&buffer.read_bytes()[address..(address + length)], &[1, 2, 3, 4]);
+
+    // strings include their length prefix
+    let (address, length) = buffer.value_location(&["name"])?.unwrap();
+    assert!(address > 0);
+    assert!(length >= 5);
+    assert_eq!(&buffer.read_bytes()[(address + length - 5)..(address + length)], b"hello");
+
+    // deleted values are gone too, the other field is still there
+    buffer.del(&["count"])?;
+    assert_eq!(buffer.value_location(&["count"])?, None);
+    assert!(buffer.value_location(&["name"])?.is_some());
+
+    assert!(buffer.value_location(&["tags"]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn buffer_from_json_works() -> Result<(), NP_Error> {
+    let factory = NP_Factory::new(r#"struct({fields: {
+        name: string(),
+        age: u8(),
+        active: bool(),
+        tags: list({of: string()}),
+        scores: map({value: i32()}),
+        address: struct({fields: {street: string(), zip: u32()}}),
+        point: tuple({values: [i16(), i16()]})
+    }})"#)?;
+
+    let json = json_decode(String::from(r#"{"name":"Billy","age":42,"active":true,"tags":["a","b"],"scores":{"math":90,"art":-3},"address":{"street":"Main","zip":90210},"point":[3,-4]}"#))?;
+
+    let buffer = factory.buffer_from_json(&json)?;
+    assert_eq!(buffer.get::<&str>(&["name"])?, Some("Billy"));
+    assert_eq!(buffer.get::<u32>(&["address", "zip"])?, Some(90210));
+    assert_eq!(buffer.get::<i16>(&["point", "1"])?, Some(-4));
+    assert_eq!(buffer.json_encode(&[])?, *json);
+
+    // the first mismatch is reported with it's path
+    let json = json_decode(String::from(r#"{"age":42,"address":{"street":"Main","zip":"nope"}}"#))?;
+    let err = factory.buffer_from_json(&json).unwrap_err();
+    assert!(matches!(err, NP_Error::Custom { message } if message == "Can't parse number for type (uint32): nope (at path: address.zip)"));
+
+    let json = json_decode(String::from(r#"{"address":{"zip":[90210]}}"#))?;
+    let err = factory.buffer_from_json(&json).unwrap_err();
+    assert!(matches!(err, NP_Error::Custom { message } if message == "expected number, got array (at path: address.zip)"));
+
+    let json = json_decode(String::from(r#"{"tags":["a",5]}"#))?;
+    let err = factory.buffer_from_json(&json).unwrap_err();
+    assert!(matches!(err, NP_Error::Custom { message } if message == "expected string, got number (at path: tags.1)"));
+
+    let json = json_decode(String::from(r#"["not", "a", "struct"]"#))?;
+    let err = factory.buffer_from_json(&json).unwrap_err();
+    assert!(matches!(err, NP_Error::Custom { message } if message == "expected object, got array (at path: root)"));
+
+    // the factory setting is left alone
+    let mut buffer = factory.new_buffer(None);
+    buffer.set_with_json(&[], r#"{"value": {"age": "twelve"}}"#)?;
+    assert_eq!(buffer.get::<u8>(&["age"])?, None);
+
+    Ok(())
+}
+
+#[test]
+fn type_keys_used_works() -> Result<(), NP_Error> {
+    let factory = NP_Factory::new("struct({fields: {a: u8(), b: u8(), c: dec({exp: 2}), d: list({of: uuid()}), e: f64()}})")?;
+
+    let mut expected = alloc::collections::BTreeSet::new();
+    expected.insert(NP_TypeKeys::Struct);
+    expected.insert(NP_TypeKeys::Uint8);
+    expected.insert(NP_TypeKeys::Decimal);
+    expected.insert(NP_TypeKeys::List);
+    expected.insert(NP_TypeKeys::Uuid);
+    expected.insert(NP_TypeKeys::Double);
+
+    assert_eq!(factory.type_keys_used(), expected);
+
+    // also works on schemas loaded from bytes
+    let factory = NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(factory.type_keys_used(), expected);
+
+    Ok(())
+}
+
+#[cfg(feature = "access_stats")]
+#[test]
+fn access_stats_works() -> Result<(), NP_Error> {
+    let factory = NP_Factory::new("struct({fields: {age: u8(), name: string(), tags: list({of: string()})}})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    buffer.set(&["age"], 20u8)?;
+    buffer.set(&["age"], 21u8)?;
+    buffer.set(&["tags", "0"], "rust")?;
+    buffer.get::<u8>(&["age"])?;
+    buffer.get::<u8>(&["age"])?;
+    buffer.get::<u8>(&["age"])?;
+    buffer.get::<&str>(&["name"])?;
+
+    // failed calls still count as an access
+    assert!(buffer.set(&["name"], 5u8).is_err());
+
+    assert_eq!(buffer.access_stats(), vec![
+        (vec![String::from("age")], 3, 2),
+        (vec![String::from("name")], 1, 1),
+        (vec![String::from("tags"), String::from("0")], 0, 1)
+    ]);
+
+    // copies start fresh
+    assert_eq!(buffer.copy_buffer().access_stats(), vec![]);
+
+    // writers other than `set` are counted too
+    let mut buffer = factory.new_buffer(None);
+    buffer.set_with_json(&["name"], r#"{"value": "bob"}"#)?;
+    buffer.set_numeric(&["age"], 5)?;
+    assert!(buffer.compare_and_set(&["age"], Some(5u8), 6u8)?);
+    buffer.list_push(&["tags"], "a")?;
+    buffer.del(&["name"])?;
+
+    assert_eq!(buffer.access_stats(), vec![
+        (vec![String::from("age")], 0, 2),
+        (vec![String::from("name")], 0, 2),
+        (vec![String::from("tags")], 0, 1)
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn np_schema_macro_works() -> Result<(), NP_Error> {
+    // fixtures/count.np holds the exported bytes of `u32({default: 5})`
+    let factory = np_schema!("../fixtures/count.np")?;
+    assert_eq!(factory.export_schema_bytes(), NP_Factory::new("u32({default: 5})")?.export_schema_bytes());
+
+    let mut buffer = factory.new_buffer(None);
+    assert_eq!(buffer.get::<u32>(&[])?, Some(5));
+    buffer.set(&[], 20u32)?;
+    assert_eq!(buffer.get::<u32>(&[])?, Some(20));
+
+    Ok(())
+}
+
+#[test]
+fn schema_bytes_builder_works() -> Result<(), NP_Error> {
+    use crate::schema::NP_SchemaBytesBuilder;
+    use crate::pointer::dec::{NP_Dec, NP_RoundMode};
+    use crate::pointer::numbers::{NP_NumberBound, NP_TimestampFormat};
+
+    let built = NP_SchemaBytesBuilder::integer(NP_TypeKeys::Uint16, Some(300))?.finish();
+    let from_json = NP_Factory::new_json(r#"{"type": "u16", "default": 300}"#)?;
+    assert_eq!(&built[..], from_json.export_schema_bytes());
+    assert_eq!(NP_Factory::new_bytes(&built)?.schema.to_json()?.stringify(), from_json.schema.to_json()?.stringify());
+
+    let built = NP_SchemaBytesBuilder::decimal(2, Some(NP_Dec::new(2500, 3)), NP_RoundMode::HalfUp)?.required().finish();
+    let from_json = NP_Factory::new_json(r#"{"type": "decimal", "exp": 2, "default": 2.5, "rounding": "half_up", "required": true}"#)?;
+    assert_eq!(&built[..], from_json.export_schema_bytes());
+    assert_eq!(NP_Factory::new_bytes(&built)?.schema.to_json()?.stringify(), from_json.schema.to_json()?.stringify());
+
+    assert!(NP_SchemaBytesBuilder::integer(NP_TypeKeys::Uint8, Some(256)).is_err());
+    assert!(NP_SchemaBytesBuilder::integer(NP_TypeKeys::Utf8String, None).is_err());
+    assert!(NP_SchemaBytesBuilder::decimal(2, Some(NP_Dec::new(2055, 3)), NP_RoundMode::Truncate).is_err());
+
+    let built = NP_SchemaBytesBuilder::integer(NP_TypeKeys::Int16, None)?.range(Some(-300), Some(-10))?.default_bound(NP_NumberBound::Min)?.finish();
+    let from_json = NP_Factory::new_json(r#"{"type": "i16", "min": -300, "max": -10, "default": "min"}"#)?;
+    assert_eq!(&built[..], from_json.export_schema_bytes());
+
+    let built = NP_SchemaBytesBuilder::integer(NP_TypeKeys::Int64, Some(5))?.timestamp_format(NP_TimestampFormat::UnixMillis)?.range(None, Some(1000))?.finish();
+    let from_json = NP_Factory::new_json(r#"{"type": "i64", "default": 5, "format": "unix_millis", "max": 1000}"#)?;
+    assert_eq!(&built[..], from_json.export_schema_bytes());
+    assert_eq!(NP_Factory::new_bytes(&built)?.schema.to_json()?.stringify(), from_json.schema.to_json()?.stringify());
+
+    assert!(NP_SchemaBytesBuilder::integer(NP_TypeKeys::Uint8, Some(50))?.range(Some(10), Some(20)).is_err());
+    assert!(NP_SchemaBytesBuilder::integer(NP_TypeKeys::Uint8, None)?.range(Some(-1), None).is_err());
+    assert!(NP_SchemaBytesBuilder::integer(NP_TypeKeys::Uint8, None)?.default_bound(NP_NumberBound::Max)?.range(None, Some(20)).is_err());
+    assert!(NP_SchemaBytesBuilder::integer(NP_TypeKeys::Int32, None)?.timestamp_format(NP_TimestampFormat::UnixSeconds).is_err());
+    assert!(NP_SchemaBytesBuilder::float(None, false, None)?.range(Some(1), None).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn dotted_paths_work() -> Result<(), NP_Error> {
+    let factory = NP_Factory::new("struct({fields: {user: struct({fields: {name: string(), tags: list({of: string()})}}), hosts: map({value: u16()})}})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    buffer.set(&["user", "name"], "ferris")?;
+    buffer.set_path("user.tags.2", "crab")?;
+    buffer.set_path("hosts.example\\.com", 8080u16)?;
+
+    assert_eq!(buffer.get_path::<&str>("user.name")?, buffer.get::<&str>(&["user", "name"])?);
+    assert_eq!(buffer.get_path::<&str>("user.name")?, Some("ferris"));
+    assert_eq!(buffer.get::<&str>(&["user", "tags", "2"])?, Some("crab"));
+    assert_eq!(buffer.get_path::<&str>("user.tags.2")?, Some("crab"));
+    assert_eq!(buffer.get::<u16>(&["hosts", "example.com"])?, Some(8080));
+    assert_eq!(buffer.get_path::<u16>("hosts.example\\.com")?, Some(8080));
+
+    // leading, trailing and doubled dots are ignored
+    assert_eq!(buffer.get_path::<&str>(".user..name.")?, Some("ferris"));
+
+    Ok(())
+}
+
+#[test]
+fn set_with_json_checked_works() -> Result<(), NP_Error> {
+    let factory = NP_Factory::new("struct({fields: {age: u8(), offset: i8(), ratio: f32(), items: list({of: struct({fields: {qty: u16()}})})}})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    buffer.set_with_json_checked(&[], r#"{"value": {"age": 255, "offset": -128, "ratio": 0.25, "items": [{"qty": 2.0}]}}"#)?;
+    assert_eq!(buffer.get::<u8>(&["age"])?, Some(255));
+    assert_eq!(buffer.get::<i8>(&["offset"])?, Some(-128));
+    assert_eq!(buffer.get::<f32>(&["ratio"])?, Some(0.25));
+    assert_eq!(buffer.get::<u16>(&["items", "0", "qty"])?, Some(2));
+
+    let err = buffer.set_with_json_checked(&[], r#"{"value": {"age": 300}}"#).unwrap_err();
+    assert!(matches!(err, NP_Error::Custom { message } if message == "Number out of range for type (uint8): 300 (at path: age)"));
+
+    let err = buffer.set_with_json_checked(&["offset"], r#"{"value": -129}"#).unwrap_err();
+    assert!(matches!(err, NP_Error::Custom { message } if message == "Number out of range for type (int8): -129 (at path: offset)"));
+
+    let err = buffer.set_with_json_checked(&[], r#"{"value": {"age": -1}}"#).unwrap_err();
+    assert!(matches!(err, NP_Error::Custom { message } if message == "Number out of range for type (uint8): -1 (at path: age)"));
+
+    let err = buffer.set_with_json_checked(&["items"], r#"{"value": [{"qty": 1}, {"qty": 2.5}]}"#).unwrap_err();
+    assert!(matches!(err, NP_Error::Custom { message } if message == "Number can't be stored exactly in type (uint16): 2.5 (at path: items.1.qty)"));
+
+    assert!(buffer.set_with_json_checked(&[], r#"{"value": {"age": "ten"}}"#).is_err());
+
+    // nothing was wrapped or truncated
+    assert_eq!(buffer.get::<u8>(&["age"])?, Some(255));
+    assert_eq!(buffer.get::<i8>(&["offset"])?, Some(-128));
+
+    // the factory settings are back in place afterwards
+    buffer.set_with_json(&["age"], r#"{"value": 300}"#)?;
+    assert_eq!(buffer.get::<u8>(&["age"])?, Some(44));
+
+    Ok(())
+}
+
+#[test]
+fn size_breakdown_works() -> Result<(), NP_Error> {
+    let schemas = [
+        "struct({fields: {a: u8(), b: string(), c: list({of: u16()}), d: u64(), e: bool()}})",
+        "list({of: string()})",
+        "map({value: u32()})",
+        "tuple({values: [u8(), string()]})",
+        "u32()"
+    ];
+
+    for schema in schemas.iter() {
+        let factory = NP_Factory::new(schema)?;
+        let mut buffer = factory.new_buffer(None);
+
+        let sum = |buffer: &NP_Buffer| -> Result<usize, NP_Error> {
+            Ok(buffer.size_breakdown()?.iter().map(|(_path, size)| size).sum())
+        };
+
+        // nothing set yet
+        assert_eq!(sum(&buffer)?, buffer.calc_bytes()?.after_compaction);
+
+        buffer.set_with_json(&[], match *schema {
+            "list({of: string()})" => r#"{"value": ["one", "two", "three"]}"#,
+            "map({value: u32()})" => r#"{"value": {"x": 1, "y": 2}}"#,
+            "tuple({values: [u8(), string()]})" => r#"{"value": [1, "hello"]}"#,
+            "u32()" => r#"{"value": 20}"#,
+            _ => r#"{"value": {"a": 1, "b": "hello", "c": [1, 2, 3], "d": 4, "e": true}}"#
+        })?;
+        assert_eq!(sum(&buffer)?, buffer.calc_bytes()?.after_compaction);
+
+        // compacted buffers have no wasted bytes, so the parts add up to the whole buffer
+        buffer.compact(None)?;
+        assert_eq!(sum(&buffer)?, buffer.calc_bytes()?.current_buffer);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn buffers_equal_works() -> Result<(), NP_Error> {
+    use crate::pointer::dec::NP_Dec;
+
+    let factory = NP_Factory::new(r#"struct({fields: {
+        retries: u8({default: 3}),
+        price: dec({exp: 2, default: 1.50}),
+        tags: map({value: u8()})
+    }})"#)?;
+
+    let mut a = factory.new_buffer(None);
+    let mut b = factory.new_buffer(None);
+    assert!(factory.buffers_equal(&a, &b, EqMode::Semantic)?);
+    assert!(factory.buffers_equal(&a, &b, EqMode::Structural)?);
+
+    // unset in one, set to the default in the other
+    b.set(&["retries"], 3u8)?;
+    assert!(factory.buffers_equal(&a, &b, EqMode::Semantic)?);
+    assert!(!factory.buffers_equal(&a, &b, EqMode::Structural)?);
+    a.set(&["retries"], 3u8)?;
+    assert!(factory.buffers_equal(&a, &b, EqMode::Structural)?);
+
+    a.set(&["price"], NP_Dec::new(150, 2))?;
+    assert!(factory.buffers_equal(&a, &b, EqMode::Semantic)?);
+    assert!(!factory.buffers_equal(&a, &b, EqMode::Structural)?);
+    b.set(&["price"], NP_Dec::new(150, 2))?;
+    assert!(factory.buffers_equal(&a, &b, EqMode::Structural)?);
+
+    // map insertion order doesn't matter, values do
+    a.set(&["tags", "x"], 1u8)?;
+    a.set(&["tags", "y"], 2u8)?;
+    b.set(&["tags", "y"], 2u8)?;
+    b.set(&["tags", "x"], 1u8)?;
+    assert!(factory.buffers_equal(&a, &b, EqMode::Structural)?);
+    b.set(&["retries"], 4u8)?;
+    assert!(!factory.buffers_equal(&a, &b, EqMode::Semantic)?);
+
+    // the factory setting doesn't change the result
+    let nulls = NP_Factory::new("u8({default: 3})")?.with_emit_null_for_unset(true);
+    let unset = nulls.new_buffer(None);
+    let mut set = nulls.new_buffer(None);
+    set.set(&[], 3u8)?;
+    assert!(nulls.buffers_equal(&unset, &set, EqMode::Semantic)?);
+    assert!(!nulls.buffers_equal(&unset, &set, EqMode::Structural)?);
+
+    // buffers from another factory are refused, even with the same schema
+    let other = NP_Factory::new("u8({default: 3})")?;
+    let err = nulls.buffers_equal(&unset, &other.new_buffer(None), EqMode::Semantic).unwrap_err();
+    assert!(matches!(err, NP_Error::Custom { message } if message == "Buffers passed to buffers_equal must come from this factory!"));
+
+    // NaN equals NaN
+    let floats = NP_Factory::new("list({of: double()})")?;
+    let mut a = floats.new_buffer(None);
+    a.list_push(&[], f64::NAN)?;
+    let b = a.copy_buffer();
+    assert!(floats.buffers_equal(&a, &b, EqMode::Structural)?);
+    a.list_push(&[], 1.0f64)?;
+    assert!(!floats.buffers_equal(&a, &b, EqMode::Structural)?);
+
+    // floats that only differ past display_places aren't equal
+    let rounded = NP_Factory::new("struct({fields: {price: f64({display_places: 2}), tags: map({value: f32({display_places: 1})})}})")?;
+    let mut a = rounded.new_buffer(None);
+    let mut b = rounded.new_buffer(None);
+    a.set(&["price"], 1.001f64)?;
+    b.set(&["price"], 1.002f64)?;
+    assert_eq!(a.json_encode(&["price"])?.stringify(), b.json_encode(&["price"])?.stringify());
+    assert!(!rounded.buffers_equal(&a, &b, EqMode::Structural)?);
+    assert!(!rounded.buffers_equal(&a, &b, EqMode::Semantic)?);
+    b.set(&["price"], 1.001f64)?;
+    assert!(rounded.buffers_equal(&a, &b, EqMode::Structural)?);
+    a.set(&["tags", "x"], 0.51f32)?;
+    b.set(&["tags", "x"], 0.54f32)?;
+    assert!(!rounded.buffers_equal(&a, &b, EqMode::Structural)?);
+
+    Ok(())
+}
+
+#[test]
+fn content_digest_works() -> Result<(), NP_Error> {
+    use core::hash::Hasher;
+
+    struct Fnv(u64);
+    impl Hasher for Fnv {
+        fn finish(&self) -> u64 { self.0 }
+        fn write(&mut self, bytes: &[u8]) {
+            for byte in bytes { self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x100000001b3); }
+        }
+    }
+
+    let digest = |buffer: &NP_Buffer| -> Result<u64, NP_Error> {
+        let mut hasher = Fnv(0xcbf29ce484222325);
+        buffer.content_digest(&mut hasher)?;
+        Ok(hasher.finish())
+    };
+
+    let factory = NP_Factory::new(r#"struct({fields: {
+        name: string(),
+        age: u16({default: 20}),
+        tags: list({of: string()}),
+        scores: map({value: i32()}),
+        extra: u8()
+    }})"#)?;
+
+    let mut a = factory.new_buffer(None);
+    a.set(&["name"], "Billy")?;
+    a.set(&["age"], 42u16)?;
+    a.set(&["tags", "0"], "one")?;
+    a.set(&["tags", "2"], "three")?;
+    a.set(&["scores", "alpha"], 1i32)?;
+    a.set(&["scores", "beta"], 2i32)?;
+
+    // same values, different history and layout
+    let mut b = factory.new_buffer(None);
+    b.set(&["scores", "beta"], 500i32)?;
+    b.set(&["name"], "a name long enough to need a new allocation")?;
+    b.set(&["extra"], 9u8)?;
+    b.set(&["tags", "2"], "three")?;
+    b.set(&["scores", "alpha"], 1i32)?;
+    b.set(&["tags", "0"], "one")?;
+    b.set(&["scores", "beta"], 2i32)?;
+    b.set(&["name"], "Billy")?;
+    b.set(&["age"], 42u16)?;
+    b.del(&["extra"])?;
+    assert_ne!(a.read_bytes(), b.read_bytes());
+    assert_eq!(digest(&a)?, digest(&b)?);
+
+    b.compact(None)?;
+    assert_eq!(digest(&a)?, digest(&b)?);
+
+    // any change to a value, a key or where a value sits changes the digest
+    b.set(&["age"], 43u16)?;
+    assert_ne!(digest(&a)?, digest(&b)?);
+    b.set(&["age"], 42u16)?;
+    b.set(&["scores", "alphb"], 1i32)?;
+    b.del(&["scores", "alpha"])?;
+    assert_ne!(digest(&a)?, digest(&b)?);
+
+    let mut c = factory.new_buffer(None);
+    c.set(&["tags", "0"], "x")?;
+    let mut d = factory.new_buffer(None);
+    d.set(&["tags", "1"], "x")?;
+    assert_ne!(digest(&c)?, digest(&d)?);
+
+    // an unset default isn't content
+    let mut e = factory.new_buffer(None);
+    let empty = digest(&e)?;
+    e.set(&["age"], 20u16)?;
+    assert_ne!(empty, digest(&e)?);
+
+    Ok(())
+}
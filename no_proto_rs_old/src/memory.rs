@@ -2,8 +2,9 @@
 
 use crate::{schema::NP_Parsed_Schema};
 use crate::{error::NP_Error};
-use core::cell::UnsafeCell;
+use core::cell::{Cell, UnsafeCell};
 use alloc::vec::Vec;
+use crate::pointer::numbers::NumericOverflowPolicy;
 
 #[doc(hidden)]
 #[derive(PartialEq, Debug)]
@@ -14,6 +15,28 @@ pub enum NP_Memory_Kind {
 }
 
 
+/// Settings a factory passes down to every buffer it creates.
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NP_Options {
+    pub overflow_policy: NumericOverflowPolicy,
+    pub strict_json: bool,
+    pub emit_null_for_unset: bool,
+    pub lossless_json: bool,
+    pub null_clears: bool,
+}
+
+impl Default for NP_Options {
+    fn default() -> Self {
+        Self {
+            overflow_policy: NumericOverflowPolicy::Wrap,
+            strict_json: false,
+            emit_null_for_unset: false,
+            lossless_json: false,
+            null_clears: false
+        }
+    }
+}
 
 #[doc(hidden)]
 #[derive(Debug)]
@@ -23,6 +46,8 @@ pub struct NP_Memory {
     pub schema: *const Vec<NP_Parsed_Schema>,
     pub max_size: usize,
     pub is_mutable: bool,
+    pub alloc_count: Cell<usize>,
+    pub options: NP_Options,
 }
 
 unsafe impl Send for NP_Memory {}
@@ -34,7 +59,9 @@ impl Clone for NP_Memory {
             max_size: self.max_size,
             bytes: UnsafeCell::new(NP_Memory_Kind::Owned { vec: self.read_bytes().to_vec() }),
             schema: self.schema.clone(),
-            is_mutable: true
+            is_mutable: true,
+            alloc_count: Cell::new(0),
+            options: self.options
         }
     }
 }
@@ -50,7 +77,9 @@ impl NP_Memory {
             max_size: u32::MAX as usize,
             bytes: UnsafeCell::new(NP_Memory_Kind::Owned { vec: bytes }),
             schema: schema,
-            is_mutable: true
+            is_mutable: true,
+            alloc_count: Cell::new(0),
+            options: NP_Options::default()
         }
     }
 
@@ -62,7 +91,9 @@ impl NP_Memory {
             max_size: 0,
             bytes: UnsafeCell::new(NP_Memory_Kind::Ref { vec: bytes }),
             schema: schema,
-            is_mutable: false
+            is_mutable: false,
+            alloc_count: Cell::new(0),
+            options: NP_Options::default()
         }
     }
 
@@ -74,7 +105,9 @@ impl NP_Memory {
             max_size: usize::min(u32::MAX as usize, len),
             bytes: UnsafeCell::new(NP_Memory_Kind::RefMut { vec: bytes, len: len }),
             schema: schema,
-            is_mutable: true
+            is_mutable: true,
+            alloc_count: Cell::new(0),
+            options: NP_Options::default()
         }
     }
 
@@ -95,7 +128,9 @@ impl NP_Memory {
             max_size: u32::MAX as usize,
             bytes: UnsafeCell::new(NP_Memory_Kind::Owned { vec: new_bytes }),
             schema: schema,
-            is_mutable: true
+            is_mutable: true,
+            alloc_count: Cell::new(0),
+            options: NP_Options::default()
         }
     }
 
@@ -107,7 +142,9 @@ impl NP_Memory {
             max_size: u32::MAX as usize,
             bytes: UnsafeCell::new(NP_Memory_Kind::RefMut { vec: bytes, len: 6 }),
             schema: schema,
-            is_mutable: true
+            is_mutable: true,
+            alloc_count: Cell::new(0),
+            options: NP_Options::default()
         }
     }
 
@@ -127,7 +164,9 @@ impl NP_Memory {
             max_size: u32::MAX as usize,
             bytes: UnsafeCell::new(NP_Memory_Kind::Owned { vec: new_bytes }),
             schema: self.schema,
-            is_mutable: true
+            is_mutable: true,
+            alloc_count: Cell::new(0),
+            options: self.options
         })
     }
 
@@ -162,6 +201,32 @@ impl NP_Memory {
         
     }
 
+    /// Drop everything after the root pointer and zero it, keeping the allocation
+    pub fn clear(&mut self) -> Result<(), NP_Error> {
+
+        let root_end = self.root + 4;
+
+        let self_bytes = unsafe { &mut *self.bytes.get() };
+
+        match self_bytes {
+            NP_Memory_Kind::Owned { vec } => {
+                vec.truncate(root_end);
+            },
+            NP_Memory_Kind::Ref { .. } => {
+                return Err(NP_Error::MemoryReadOnly)
+            },
+            NP_Memory_Kind::RefMut { len, .. } => {
+                *len = root_end;
+            }
+        }
+
+        for byte in self.write_bytes()[self.root..root_end].iter_mut() {
+            *byte = 0;
+        }
+
+        Ok(())
+    }
+
     pub fn set_max_length(&mut self, len: usize) {
 
         let self_bytes = unsafe { &*self.bytes.get() };
@@ -213,6 +278,10 @@ impl NP_Memory {
 
         match self_bytes {
             NP_Memory_Kind::Owned { vec } => {
+                // the vec is about to grow past it's capacity, so this write will reallocate
+                if vec.capacity() < vec.len() + bytes.len() {
+                    self.alloc_count.set(self.alloc_count.get() + 1);
+                }
                 vec.extend_from_slice(bytes);
             },
             NP_Memory_Kind::Ref { .. } => {
@@ -274,82 +343,68 @@ impl NP_Memory {
 
     #[inline(always)]
     pub fn get_2_bytes(&self, address: usize) -> Option<&[u8; 2]> {
-
-        // empty value
-        if address == 0 {
-            return None;
-        }
-
-        let self_bytes = self.read_bytes();
-
-        if self_bytes.len() < address + 2 {
-            return None;
-        }
-
-        let slice = &self_bytes[address..(address + 2)];
-
-        Some(unsafe { &*(slice as *const [u8] as *const [u8; 2]) })
+        self.get_fixed::<2>(address)
     }
 
     #[inline(always)]
     pub fn get_4_bytes(&self, address: usize) -> Option<&[u8; 4]> {
-
-        // empty value
-        if address == 0 {
-            return None;
-        }
-
-        let self_bytes = self.read_bytes();
-
-        if self_bytes.len() < address + 4 {
-            return None;
-        }
-
-        let slice = &self_bytes[address..(address + 4)];
-
-        Some(unsafe { &*(slice as *const [u8] as *const [u8; 4]) })
+        self.get_fixed::<4>(address)
     }
 
     #[inline(always)]
     pub fn get_8_bytes(&self, address: usize) -> Option<&[u8; 8]> {
-
-        // empty value
-        if address == 0 {
-            return None;
-        }
-
-        let self_bytes = self.read_bytes();
-
-        if self_bytes.len() < address + 8 {
-            return None;
-        }
-
-        let slice = &self_bytes[address..(address + 8)];
-
-        Some(unsafe { &*(slice as *const [u8] as *const [u8; 8]) })
+        self.get_fixed::<8>(address)
     }
 
     #[inline(always)]
     pub fn get_16_bytes(&self, address: usize) -> Option<&[u8; 16]> {
+        self.get_fixed::<16>(address)
+    }
 
-        // empty value
-        if address == 0 {
-            return None;
-        }
-
-        let self_bytes = self.read_bytes();
+    #[inline(always)]
+    pub fn get_32_bytes(&self, address: usize) -> Option<&[u8; 32]> {
+        self.get_fixed::<32>(address)
+    }
 
-        if self_bytes.len() < address + 16 {
-            return None;
+    pub fn dump(self) -> Vec<u8> {
+        let bytes = self.bytes.into_inner();
+        match bytes {
+            NP_Memory_Kind::Owned { vec } => vec,
+            NP_Memory_Kind::Ref { vec } => Vec::from(unsafe { &*vec }),
+            NP_Memory_Kind::RefMut { vec, ..  } => Vec::from(unsafe { &*vec })
         }
-
-        let slice = &self_bytes[address..(address + 16)];
-
-        Some(unsafe { &*(slice as *const [u8] as *const [u8; 16]) })
     }
+}
 
+impl NP_Memory {
+    /// Read a fixed size scalar out of the buffer.
+    /// 
+    /// Returns `None` if the address is zero (no value) or the read would go past the end of the buffer.  This is the same accessor the built in fixed size types (decimals, uuids, etc) use, so custom scalar types can read their bytes the same way.
+    /// 
+    /// ```
+    /// use no_proto::memory::NP_Memory;
+    /// 
+    /// // a tiny custom 4 byte scalar
+    /// #[derive(Debug, PartialEq)]
+    /// struct Rgba { r: u8, g: u8, b: u8, a: u8 }
+    /// 
+    /// impl Rgba {
+    ///     fn read(memory: &NP_Memory, address: usize) -> Option<Rgba> {
+    ///         let bytes = memory.get_fixed::<4>(address)?;
+    ///         Some(Rgba { r: bytes[0], g: bytes[1], b: bytes[2], a: bytes[3] })
+    ///     }
+    /// }
+    /// 
+    /// let memory = NP_Memory::existing_owned(vec![0, 0, 255, 128, 0, 255], core::ptr::null(), 0);
+    /// 
+    /// assert_eq!(Rgba::read(&memory, 2), Some(Rgba { r: 255, g: 128, b: 0, a: 255 }));
+    /// // zero address means no value
+    /// assert_eq!(Rgba::read(&memory, 0), None);
+    /// // out of bounds
+    /// assert_eq!(Rgba::read(&memory, 3), None);
+    /// ```
     #[inline(always)]
-    pub fn get_32_bytes(&self, address: usize) -> Option<&[u8; 32]> {
+    pub fn get_fixed<const N: usize>(&self, address: usize) -> Option<&[u8; N]> {
 
         // empty value
         if address == 0 {
@@ -358,21 +413,12 @@ impl NP_Memory {
 
         let self_bytes = self.read_bytes();
 
-        if self_bytes.len() < address + 32 {
+        if self_bytes.len() < address + N {
             return None;
         }
 
-        let slice = &self_bytes[address..(address + 32)];
+        let slice = &self_bytes[address..(address + N)];
 
-        Some(unsafe { &*(slice as *const [u8] as *const [u8; 32]) })
+        Some(unsafe { &*(slice as *const [u8] as *const [u8; N]) })
     }
-
-    pub fn dump(self) -> Vec<u8> {
-        let bytes = self.bytes.into_inner();
-        match bytes {
-            NP_Memory_Kind::Owned { vec } => vec,
-            NP_Memory_Kind::Ref { vec } => Vec::from(unsafe { &*vec }),
-            NP_Memory_Kind::RefMut { vec, ..  } => Vec::from(unsafe { &*vec })
-        }
-    }
-}
\ No newline at end of file
+}
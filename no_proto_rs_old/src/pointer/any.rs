@@ -2,7 +2,7 @@ use alloc::{string::String, sync::Arc};
 use crate::{idl::{JS_AST, JS_Schema}, json_flex::{JSMAP}, schema::{NP_Parsed_Schema, NP_Value_Kind, NULL}};
 use alloc::vec::Vec;
 use crate::error::NP_Error;
-use crate::{schema::{NP_TypeKeys}, pointer::NP_Value, json_flex::NP_JSON};
+use crate::{schema::{NP_Schema, NP_TypeKeys}, pointer::NP_Value, json_flex::NP_JSON};
 
 
 use alloc::boxed::Box;
@@ -27,8 +27,12 @@ impl<'value> NP_Value<'value> for NP_Any {
         Ok(NP_JSON::Dictionary(schema_json))
     }
 
-    fn schema_to_idl(_schema: &Vec<NP_Parsed_Schema>, _address: usize)-> Result<String, NP_Error> {
-        Ok(String::from("any()"))
+    fn schema_to_idl(schema: &Vec<NP_Parsed_Schema>, address: usize)-> Result<String, NP_Error> {
+        if NP_Schema::is_required(&schema[address]) {
+            Ok(String::from("any({required: true})"))
+        } else {
+            Ok(String::from("any()"))
+        }
     }
 
     fn from_idl_to_schema(schema: Vec<NP_Parsed_Schema>, _name: &str, _idl: &JS_Schema, _args: &Vec<JS_AST>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
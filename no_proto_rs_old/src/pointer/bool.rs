@@ -19,7 +19,7 @@ use alloc::sync::Arc;
 use alloc::string::String;
 use crate::{idl::{JS_AST, JS_Schema}, json_flex::JSMAP, schema::{NP_Bool_Data, NP_Parsed_Schema, NP_Value_Kind}};
 use crate::error::NP_Error;
-use crate::{schema::{NP_TypeKeys}, pointer::NP_Value, json_flex::NP_JSON};
+use crate::{schema::{NP_Schema, NP_TypeKeys}, pointer::NP_Value, json_flex::NP_JSON};
 
 use alloc::vec::Vec;
 use alloc::boxed::Box;
@@ -79,7 +79,7 @@ impl<'value> NP_Value<'value> for bool {
             NP_JSON::False => {
                 Self::set_value(cursor, memory, false)?;
             },
-            _ => {}
+            _ => { NP_Cursor::json_type_mismatch(memory, "boolean", value)?; }
         }
 
         Ok(())
@@ -185,14 +185,24 @@ impl<'value> NP_Value<'value> for bool {
 
         let data = unsafe { &*(*schema[address].data as *const NP_Bool_Data) };
         
-        let mut result = String::from("bool(");
+        let mut properties: Vec<String> = Vec::new();
+
         if let Some(x) = data.default {
-            result.push_str("{default: ");
             if x == true {
-                result.push_str("true");
+                properties.push(String::from("default: true"));
             } else {
-                result.push_str("false");
+                properties.push(String::from("default: false"));
             }
+        }
+
+        if NP_Schema::is_required(&schema[address]) {
+            properties.push(String::from("required: true"));
+        }
+
+        let mut result = String::from("bool(");
+        if properties.len() > 0 {
+            result.push_str("{");
+            result.push_str(properties.join(", ").as_str());
             result.push_str("}");
         }
         result.push_str(")");
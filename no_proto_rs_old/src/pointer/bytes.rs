@@ -19,7 +19,7 @@
 use alloc::{string::String, sync::Arc};
 use crate::{idl::{JS_AST, JS_Schema}, json_flex::JSMAP, schema::{NP_Bytes_Data, NP_Parsed_Schema, NP_Value_Kind}};
 use crate::error::NP_Error;
-use crate::{schema::{NP_TypeKeys}, pointer::NP_Value, json_flex::NP_JSON};
+use crate::{schema::{NP_Schema, NP_TypeKeys}, pointer::NP_Value, json_flex::NP_JSON};
 
 use alloc::vec::Vec;
 use alloc::boxed::Box;
@@ -157,6 +157,10 @@ impl<'value> NP_Value<'value> for NP_Bytes {
             properties.push(def);
         }
 
+        if NP_Schema::is_required(&schema[address]) {
+            properties.push(String::from("required: true"));
+        }
+
         Ok(if properties.len() == 0 {
             String::from("bytes()")
         } else {
@@ -265,7 +269,7 @@ impl<'value> NP_Value<'value> for NP_Bytes {
                         NP_JSON::Integer(x) => {
                             target.push(*x as u8);
                         },
-                        NP_JSON::Float(x) => {
+                        NP_JSON::Float(x, _) => {
                             target.push(*x as u8);
                         },
                         _ => {
@@ -276,7 +280,7 @@ impl<'value> NP_Value<'value> for NP_Bytes {
 
                 Self::set_value(cursor, memory, target)?;
             },
-            _ => { }
+            _ => { NP_Cursor::json_type_mismatch(memory, "array", value)?; }
         }
 
         Ok(())
@@ -368,7 +372,7 @@ impl<'value> NP_Value<'value> for NP_Bytes {
                 schema_data.extend((x as u32).to_be_bytes().to_vec());
                 x as u32
             },
-            NP_JSON::Float(x) => {
+            NP_JSON::Float(x, _) => {
                 has_fixed_size = true;
                 if x < 1.0 {
                     return Err(NP_Error::new("Fixed size for bytes must be larger than 1!"));
@@ -22,7 +22,7 @@ use alloc::{string::String, sync::Arc};
 use crate::{idl::{JS_AST, JS_Schema}, schema::{NP_Parsed_Schema, NP_Value_Kind, NP_u64_Data}};
 use alloc::vec::Vec;
 use crate::json_flex::{JSMAP, NP_JSON};
-use crate::schema::{NP_TypeKeys};
+use crate::schema::{NP_Schema, NP_TypeKeys};
 use crate::{pointer::NP_Value, error::NP_Error};
 use core::{fmt::{Debug, Formatter}};
 
@@ -112,8 +112,11 @@ impl<'value> NP_Value<'value> for NP_Date {
     fn set_from_json<'set>(_depth: usize, _apply_null: bool, cursor: NP_Cursor, memory: &'set NP_Memory, value: &Box<NP_JSON>) -> Result<(), NP_Error> where Self: 'set + Sized {
         let value = match &**value {
             NP_JSON::Integer(x) => *x as u64,
-            NP_JSON::Float(x) => *x as u64,
-            _ => 0
+            NP_JSON::Float(x, _) => *x as u64,
+            _ => {
+                NP_Cursor::json_type_mismatch(memory, "number", value)?;
+                0
+            }
         };
 
         Self::set_value(cursor, memory, NP_Date::new(value))?;
@@ -206,10 +209,22 @@ impl<'value> NP_Value<'value> for NP_Date {
     fn schema_to_idl(schema: &Vec<NP_Parsed_Schema>, address: usize)-> Result<String, NP_Error> {
         let data = unsafe { &*(*schema[address].data as *const NP_u64_Data) };
 
-        let mut result = String::from("date(");
+        let mut properties: Vec<String> = Vec::new();
+
         if let Some(x) = data.default {
-            result.push_str("{default: ");
-            result.push_str(x.to_string().as_str());
+            let mut def = String::from("default: ");
+            def.push_str(x.to_string().as_str());
+            properties.push(def);
+        }
+
+        if NP_Schema::is_required(&schema[address]) {
+            properties.push(String::from("required: true"));
+        }
+
+        let mut result = String::from("date(");
+        if properties.len() > 0 {
+            result.push_str("{");
+            result.push_str(properties.join(", ").as_str());
             result.push_str("}");
         }
         result.push_str(")");
@@ -265,7 +280,7 @@ impl<'value> NP_Value<'value> for NP_Date {
             val: NP_Value_Kind::Fixed(8),
             i: NP_TypeKeys::Date,
             sortable: true,
-            data: Arc::new(Box::into_raw(Box::new(NP_u64_Data { default })) as *const u8)
+            data: Arc::new(Box::into_raw(Box::new(NP_u64_Data { default, default_bound: None, min: None, max: None })) as *const u8)
         });
 
         return Ok((true, schema_data, schema));
@@ -292,7 +307,7 @@ impl<'value> NP_Value<'value> for NP_Date {
         schema.push(NP_Parsed_Schema {
             val: NP_Value_Kind::Fixed(8),
             i: NP_TypeKeys::Date,
-            data: Arc::new(Box::into_raw(Box::new(NP_u64_Data { default })) as *const u8),
+            data: Arc::new(Box::into_raw(Box::new(NP_u64_Data { default, default_bound: None, min: None, max: None })) as *const u8),
             sortable: true
         });
 
@@ -317,7 +332,7 @@ impl<'value> NP_Value<'value> for NP_Date {
             val: NP_Value_Kind::Fixed(8),
             i: NP_TypeKeys::Date,
             sortable: true,
-            data: Arc::new(Box::into_raw(Box::new(NP_u64_Data { default })) as *const u8)
+            data: Arc::new(Box::into_raw(Box::new(NP_u64_Data { default, default_bound: None, min: None, max: None })) as *const u8)
         });
         (true, schema)
     }
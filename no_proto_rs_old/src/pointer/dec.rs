@@ -19,10 +19,12 @@
 //! 
 //! Essentially, increaseing the `exp` factor decreases the maximum range of possible values that can be stored in exchange for increased decimal precision.
 //! 
-//! `NP_Dec` values can safely be multiplied, added, devided, subtracted or compared with eachother.  It's a good idea to manually shift the `exp` values of two `NP_Dec` to match before performing any operation between them, otherwise the operation might not do what you expect.
+//! `NP_Dec` values can safely be multiplied, added, devided, subtracted, negated, compared or used for remainders with eachother.  It's a good idea to manually shift the `exp` values of two `NP_Dec` to match before performing any operation between them, otherwise the operation might not do what you expect.
 //! 
 //! When `NP_Dec` values are pulled out of a buffer, the `num` property is pulled from the buffer contents and the `exp` property comes from the schema.
 //! 
+//! In JSON output a decimal is an object like `{"value": 0.1, "exact": "0.10", "parts": {"num": 10, "exp": 2}}`.  The `value` float is convenient but can be lossy, `exact` is the stored value written out in full.
+//! 
 //! ```
 //! use no_proto::pointer::dec::NP_Dec;
 //! 
@@ -62,12 +64,13 @@
 
 use alloc::{string::String, sync::Arc};
 use alloc::prelude::v1::Box;
-use crate::{idl::{JS_AST, JS_Schema}, schema::{NP_Dec_Data, NP_Value_Kind}, utils::to_signed};
+use crate::{idl::{JS_AST, JS_Schema}, schema::{NP_Dec_Data, NP_Map_List_Data, NP_Value_Kind}, utils::to_signed};
+use crate::collection::list::NP_List;
 use crate::schema::{NP_Parsed_Schema};
 use alloc::vec::Vec;
 use crate::utils::to_unsigned;
 use crate::json_flex::{JSMAP, NP_JSON};
-use crate::schema::{NP_TypeKeys};
+use crate::schema::{NP_Schema, NP_TypeKeys};
 use crate::{pointer::NP_Value, error::NP_Error};
 use core::{fmt::{Debug}};
 
@@ -80,7 +83,10 @@ use alloc::string::ToString;
 /// 
 /// Check out documentation [here](../dec/index.html).
 /// 
+/// With the `serde` feature NP_Dec can be serialized, it's written as it's parts like `{"num": 2039756, "exp": 3}` so no precision is lost.
+/// 
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NP_Dec {
     /// The number being stored, does not include decimal point data
     pub num: i64,
@@ -88,6 +94,87 @@ pub struct NP_Dec {
     pub exp: u8
 }
 
+/// How to round an NP_Dec when digits have to be dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NP_RoundMode {
+    /// Drop the extra digits, rounding toward zero
+    Truncate,
+    /// Round to the nearest value, halves round away from zero
+    HalfUp,
+    /// Round to the nearest value, halves round to the nearest even digit (bankers rounding)
+    HalfEven
+}
+
+impl NP_RoundMode {
+    /// Round a truncated magnitude (no sign) given the digits that were dropped from it.
+    fn round_magnitude(&self, truncated: u128, first_dropped: u8, rest_nonzero: bool) -> u128 {
+        let round_up = match self {
+            NP_RoundMode::Truncate => false,
+            NP_RoundMode::HalfUp => first_dropped >= 5,
+            NP_RoundMode::HalfEven => first_dropped > 5 || (first_dropped == 5 && (rest_nonzero || truncated % 2 == 1))
+        };
+
+        if round_up { truncated + 1 } else { truncated }
+    }
+
+    /// Name of this mode in schemas
+    pub(crate) fn schema_name(&self) -> &'static str {
+        match self {
+            NP_RoundMode::Truncate => "truncate",
+            NP_RoundMode::HalfUp => "half_up",
+            NP_RoundMode::HalfEven => "half_even"
+        }
+    }
+
+    /// Parse the name of a mode from a schema
+    pub(crate) fn from_schema_name(name: &str) -> Result<Self, NP_Error> {
+        match name {
+            "truncate" => Ok(NP_RoundMode::Truncate),
+            "half_up" => Ok(NP_RoundMode::HalfUp),
+            "half_even" => Ok(NP_RoundMode::HalfEven),
+            _ => Err(NP_Error::new("Decimal 'rounding' property must be one of 'truncate', 'half_up' or 'half_even'!"))
+        }
+    }
+
+    /// Mode stored in the schema bytes
+    pub(crate) fn to_schema_bits(&self) -> u8 {
+        match self {
+            NP_RoundMode::Truncate => 0,
+            NP_RoundMode::HalfUp => 1,
+            NP_RoundMode::HalfEven => 2
+        }
+    }
+
+    /// Mode read from the schema bytes, unknown values truncate
+    pub(crate) fn from_schema_bits(bits: u8) -> Self {
+        match bits {
+            1 => NP_RoundMode::HalfUp,
+            2 => NP_RoundMode::HalfEven,
+            _ => NP_RoundMode::Truncate
+        }
+    }
+}
+
+/// Schema byte after `exp` holds the default flag in bit 0 and the rounding mode above it
+pub(crate) const ROUNDING_SHIFT: u8 = 1;
+
+/// Convert a magnitude and sign into an i64, saturating at the i64 bounds.
+fn saturating_from_magnitude(magnitude: u128, negative: bool) -> i64 {
+    if negative {
+        if magnitude > (i64::MAX as u128) + 1 {
+            i64::MIN
+        } else {
+            (-(magnitude as i128)) as i64
+        }
+    } else {
+        if magnitude > i64::MAX as u128 {
+            i64::MAX
+        } else {
+            magnitude as i64
+        }
+    }
+}
+
 impl<'value> super::NP_Scalar<'value> for NP_Dec {
     fn schema_default(schema: &NP_Parsed_Schema) -> Option<Self> where Self: Sized {
         let data = unsafe { &*(*schema.data as *const NP_Dec_Data) };
@@ -129,6 +216,57 @@ impl NP_Dec {
         m / s
     }
 
+    /// Convert an NP_Dec into a native floating point value, also returning if the float is exactly equal to the decimal.
+    /// 
+    /// Most decimals can't be stored exactly in an `f64`, `0.1` for example becomes the closest binary value to it.  The float returned is always the same as `to_float`, which is within 1 ULP of the true value when `exp` is 15 or less.  The `bool` is `true` only when no rounding happened at all, so you can tell when a displayed float isn't the real value.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// assert_eq!(NP_Dec::new(25, 2).to_float_checked(), (0.25, true));
+    /// assert_eq!(NP_Dec::new(1, 1).to_float_checked(), (0.1, false));
+    /// 
+    /// // 2^53 + 1 doesn't fit in the f64 mantissa
+    /// assert_eq!(NP_Dec::new(9_007_199_254_740_993, 0).to_float_checked().1, false);
+    /// ```
+    /// 
+    pub fn to_float_checked(&self) -> (f64, bool) {
+        let float = self.to_float();
+
+        // drop trailing zeros, value is now num / 10^exp with the fewest digits
+        let mut num = self.num as i128;
+        let mut exp = self.exp;
+        while exp > 0 && num % 10 == 0 {
+            num /= 10;
+            exp -= 1;
+        }
+
+        // num / (2^exp * 5^exp) only has a finite binary form if the 5s divide out
+        for _ in 0..exp {
+            if num % 5 != 0 {
+                return (float, false);
+            }
+            num /= 5;
+        }
+
+        // what's left must fit in the 53 bit mantissa
+        let mut odd = num.unsigned_abs();
+        while odd != 0 && odd % 2 == 0 {
+            odd /= 2;
+        }
+        if odd >= (1u128 << 53) {
+            return (float, false);
+        }
+
+        // build the exact value by halving, every step is exact
+        let mut exact = num as f64;
+        for _ in 0..exp {
+            exact *= 0.5;
+        }
+
+        (float, float == exact)
+    }
+
     /// Shift the exponent of this NP_Dec to a new value.
     /// 
     /// If the new `exp` value is higher than the old `exp` value, there may be an overflow of the i64 value.
@@ -169,6 +307,79 @@ impl NP_Dec {
         *self
     }
 
+    /// Shift the exponent of this NP_Dec to a new value, clamping instead of overflowing.
+    /// 
+    /// Works like `shift_exp`, except when raising the `exp` would overflow the i64 value.  In that case `num` is clamped to `i64::MAX` (or `i64::MIN` for negative values) and `exp` is set to the exponent reached when the overflow happened, so the value stays as large as can be represented instead of wrapping.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// let mut my_num = NP_Dec::new(2203, 3); // value is 2.203
+    /// my_num.saturating_shift_exp(5);
+    /// assert_eq!(my_num.export(), (220300, 5));
+    /// 
+    /// let mut big_num = NP_Dec::new(i64::MAX / 50, 0);
+    /// big_num.saturating_shift_exp(4); // the second multiply by 10 would overflow
+    /// assert_eq!(big_num.export(), (i64::MAX, 2));
+    /// ```
+    pub fn saturating_shift_exp(&mut self, new_exp: u8) -> NP_Dec {
+
+        if new_exp <= self.exp { return self.shift_exp(new_exp) }
+
+        while self.exp < new_exp {
+            self.exp += 1;
+            match self.num.checked_mul(10) {
+                Some(num) => { self.num = num },
+                None => {
+                    self.num = if self.num < 0 { i64::MIN } else { i64::MAX };
+                    break;
+                }
+            }
+        }
+
+        *self
+    }
+
+    /// Shift the exponent like `shift_exp`, rounding with `mode` when digits are dropped.
+    /// 
+    /// `NP_RoundMode::Truncate` gives the same result as `shift_exp`.  Rounding works on the magnitude, so negative values round the same way as positive ones, and a carry can add a digit to `num`.  Results that don't fit an i64 saturate.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::{NP_Dec, NP_RoundMode};
+    /// 
+    /// assert_eq!(NP_Dec::new(2675, 3).shift_exp_rounded(2, NP_RoundMode::Truncate).export(), (267, 2));
+    /// assert_eq!(NP_Dec::new(2675, 3).shift_exp_rounded(2, NP_RoundMode::HalfUp).export(), (268, 2));
+    /// assert_eq!(NP_Dec::new(2665, 3).shift_exp_rounded(2, NP_RoundMode::HalfEven).export(), (266, 2));
+    /// assert_eq!(NP_Dec::new(-2675, 3).shift_exp_rounded(2, NP_RoundMode::HalfUp).export(), (-268, 2));
+    /// 
+    /// // 0.99 rounds up to 1.0
+    /// assert_eq!(NP_Dec::new(99, 2).shift_exp_rounded(1, NP_RoundMode::HalfUp).export(), (10, 1));
+    /// ```
+    pub fn shift_exp_rounded(&mut self, new_exp: u8, mode: NP_RoundMode) -> NP_Dec {
+
+        if new_exp >= self.exp || mode == NP_RoundMode::Truncate { return self.shift_exp(new_exp) }
+
+        let negative = self.num < 0;
+        let mut magnitude = (self.num as i128).abs() as u128;
+        let mut first_dropped = 0u8;
+        let mut rest_nonzero = false;
+
+        let mut step = self.exp - new_exp;
+        while step > 0 {
+            rest_nonzero = rest_nonzero || first_dropped != 0;
+            first_dropped = (magnitude % 10) as u8;
+            magnitude /= 10;
+            step -= 1;
+        }
+
+        let magnitude = mode.round_magnitude(magnitude, first_dropped, rest_nonzero);
+
+        self.num = saturating_from_magnitude(magnitude, negative);
+        self.exp = new_exp;
+
+        *self
+    }
+
     /// Generate a new NP_Dec value
     /// 
     /// First argument is the `num` value, second is the `exp` or exponent.
@@ -227,874 +438,3146 @@ impl NP_Dec {
         other_copy
     }
 
-    /// Export NP_Dec to it's component parts.
-    /// 
+    /// Returns a copy of this NP_Dec shifted to the `exp` value of another NP_Dec.
+    ///
+    /// This is the same operation as `other.match_exp(&self)`, but reads in the direction you'd expect: the value comes from `self`, the scale comes from `other`.
+    ///
+    /// Precision is truncated the same way as `shift_exp` if `other.exp` is lower than `self.exp`.
+    ///
     /// ```
     /// use no_proto::pointer::dec::NP_Dec;
-    /// 
-    /// let my_num = NP_Dec::new(2203, 3); // value is 2.203
-    /// 
-    /// assert_eq!(my_num.export(), (2203i64, 3u8));
+    ///
+    /// let price = NP_Dec::new(2203, 3); // value is 2.203
+    /// let qty = NP_Dec::new(50, 1); // value is 5.0
+    ///
+    /// // `with_exp_of` keeps the value of `self`, takes the `exp` of the argument
+    /// let a = price.with_exp_of(&qty);
+    /// assert_eq!(a.export(), (22i64, 1u8)); // 2.2
+    ///
+    /// // `match_exp` keeps the value of the argument, takes the `exp` of `self`
+    /// let b = price.match_exp(&qty);
+    /// assert_eq!(b.export(), (5000i64, 3u8)); // 5.000
+    ///
+    /// // these two are the same operation
+    /// assert_eq!(price.with_exp_of(&qty).export(), qty.match_exp(&price).export());
     /// ```
-    pub fn export(&self) -> (i64, u8) {
-        (self.num, self.exp)
+    ///
+    pub fn with_exp_of(&self, other: &NP_Dec) -> NP_Dec {
+        other.match_exp(self)
     }
-}
 
-/// Check if two NP_Dec are equal or not equal
-/// 
-/// If the two `exp` values are not identical, unexpected results may occur due to rounding.
-/// 
-/// ```
-/// use no_proto::pointer::dec::NP_Dec;
-/// 
-/// let result = NP_Dec::new(202, 1) == NP_Dec::new(202, 1);
-/// assert_eq!(result, true);
-/// 
-/// let result = NP_Dec::new(202, 1) != NP_Dec::new(200, 1);
-/// assert_eq!(result, true);
-/// 
-/// let result = NP_Dec::new(202, 1) == NP_Dec::new(2020, 2);
-/// assert_eq!(result, true);
-/// 
-/// let result = NP_Dec::new(203, 1) != NP_Dec::new(2020, 2);
-/// assert_eq!(result, true);
-/// 
-/// ```
-impl core::cmp::PartialEq for NP_Dec {
-    fn ne(&self, other: &NP_Dec) -> bool {
-        if self.exp == other.exp {
-            return self.num != other.num;
-        } else {
+    /// Create an NP_Dec from a float, rounding it to a specific `exp`.
+    /// 
+    /// Unlike the `Into<NP_Dec>` impl for `f64`, this never guesses the precision.  The float is read as the shortest decimal that represents it (the same digits you get when printing it), then rounded to `exp` decimal places with the provided mode.  This means `2.675` is rounded as the decimal `2.675`, not as the binary value `2.67499999...` it's actually stored as.
+    /// 
+    /// Values too large for the `exp` saturate at the i64 bounds, infinities do the same and `NaN` becomes zero.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::{NP_Dec, NP_RoundMode};
+    /// 
+    /// assert_eq!(NP_Dec::from_float(0.1, 2, NP_RoundMode::HalfEven).export(), (10, 2));
+    /// 
+    /// assert_eq!(NP_Dec::from_float(2.675, 2, NP_RoundMode::Truncate).export(), (267, 2));
+    /// assert_eq!(NP_Dec::from_float(2.675, 2, NP_RoundMode::HalfUp).export(), (268, 2));
+    /// assert_eq!(NP_Dec::from_float(2.665, 2, NP_RoundMode::HalfEven).export(), (266, 2));
+    /// ```
+    /// 
+    pub fn from_float(value: f64, exp: u8, mode: NP_RoundMode) -> NP_Dec {
 
-            let new_exp = u8::max(self.exp, other.exp);
-            let new_self = if new_exp == self.exp { *self } else { self.clone().shift_exp(new_exp) };
-            let new_other = if new_exp == other.exp { *other } else { other.clone().shift_exp(new_exp) };
+        if value.is_nan() {
+            return NP_Dec::new(0, exp);
+        }
 
-            return new_self.num != new_other.num;
+        if value.is_infinite() {
+            return NP_Dec::new(if value > 0.0 { i64::MAX } else { i64::MIN }, exp);
         }
-    }
-    fn eq(&self, other: &NP_Dec) -> bool { 
-        if self.exp == other.exp {
-            return self.num == other.num;
-        } else {
 
-            let new_exp = u8::max(self.exp, other.exp);
-            let new_self = if new_exp == self.exp { *self } else { self.clone().shift_exp(new_exp) };
-            let new_other = if new_exp == other.exp { *other } else { other.clone().shift_exp(new_exp) };
+        let negative = value < 0.0;
+
+        let float_str = format!("{}", if negative { -value } else { value });
+
+        let mut parts = float_str.split('.');
+        let int_part = parts.next().unwrap_or("0");
+        let frac_part = parts.next().unwrap_or("");
+
+        // integer digits followed by exactly `exp` fraction digits, padded with zeros
+        let kept_digits = int_part.bytes().chain(frac_part.bytes().chain(core::iter::repeat(b'0')).take(exp as usize));
 
-            return new_self.num == new_other.num;
+        let mut magnitude: u128 = 0;
+
+        for digit in kept_digits {
+            match magnitude.checked_mul(10).and_then(|m| m.checked_add((digit - b'0') as u128)) {
+                Some(m) => magnitude = m,
+                None => {
+                    return NP_Dec::new(if negative { i64::MIN } else { i64::MAX }, exp);
+                }
+            }
         }
+
+        let dropped = frac_part.as_bytes().get((exp as usize)..).unwrap_or(&[]);
+        let first_dropped = dropped.first().map(|d| d - b'0').unwrap_or(0);
+        let rest_nonzero = dropped.iter().skip(1).any(|d| *d != b'0');
+
+        let magnitude = mode.round_magnitude(magnitude, first_dropped, rest_nonzero);
+
+        NP_Dec::new(saturating_from_magnitude(magnitude, negative), exp)
     }
-}
 
-/// Compare two NP_Dec
-/// 
-/// If the two `exp` values are not identical, unexpected results may occur due to rounding.
-/// 
-/// ```
-/// use no_proto::pointer::dec::NP_Dec;
-/// 
-/// let result = NP_Dec::new(203, 1) > NP_Dec::new(202, 1);
-/// assert_eq!(result, true);
-/// 
-/// let result = NP_Dec::new(202, 1) < NP_Dec::new(203, 1);
-/// assert_eq!(result, true);
-/// 
-/// let result = NP_Dec::new(20201, 2) > NP_Dec::new(202, 0);
-/// assert_eq!(result, true);
-/// 
-/// let result = NP_Dec::new(20201, 2) == NP_Dec::new(2020100, 4);
-/// assert_eq!(result, true);
-/// ```
-impl core::cmp::PartialOrd for NP_Dec {
+    /// Create an NP_Dec from an amount in the minor units of a currency.
+    /// 
+    /// `minor_units` is how many digits the currency has after the decimal point: 2 for USD, 0 for JPY, 3 for BHD.  This is the same as `NP_Dec::new(amount, minor_units)`, it just reads better in currency code.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// let usd = NP_Dec::from_minor_units(1999, 2); // $19.99
+    /// assert_eq!(usd.to_float(), 19.99f64);
+    /// ```
+    /// 
+    pub fn from_minor_units(amount: i64, minor_units: u8) -> NP_Dec {
+        NP_Dec::new(amount, minor_units)
+    }
 
-    fn lt(&self, other: &NP_Dec) -> bool {
-        if self.exp == other.exp {
-            return self.num < other.num;
+    /// Get the amount of this NP_Dec in the minor units of a currency.
+    /// 
+    /// Returns `None` if the value can't be represented exactly at that precision, either because the amount would overflow an i64 or because digits would be lost.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// let price = NP_Dec::new(1950, 2); // 19.50
+    /// 
+    /// assert_eq!(price.to_minor_units(3), Some(19500)); // 19.500
+    /// assert_eq!(price.to_minor_units(1), Some(195)); // 19.5
+    /// assert_eq!(price.to_minor_units(0), None); // 19 would lose the .5
+    /// ```
+    /// 
+    pub fn to_minor_units(&self, minor_units: u8) -> Option<i64> {
+        let mut num = self.num;
+
+        if minor_units > self.exp {
+            for _ in 0..(minor_units - self.exp) {
+                num = num.checked_mul(10)?;
+            }
         } else {
-            let new_other = self.match_exp(other);
-            return self.num < new_other.num;
+            for _ in 0..(self.exp - minor_units) {
+                if num % 10 != 0 {
+                    return None;
+                }
+                num /= 10;
+            }
         }
+
+        Some(num)
     }
 
-    fn le(&self, other: &NP_Dec) -> bool {
-        if self.exp == other.exp {
-            return self.num <= other.num;
-        } else {
-            let new_other = self.match_exp(other);
-            return self.num <= new_other.num;
-        }
+    /// Add two NP_Dec without losing precision.
+    /// 
+    /// The `+` operator rescales `other` to the `exp` of `self`, so digits finer than `self.exp` are dropped.  This method moves both values to the larger of the two `exp` values first and returns the sum at that `exp`.  Returns `None` if the rescaling or the sum overflows.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// let a = NP_Dec::new(15, 1); // 1.5
+    /// let b = NP_Dec::new(5, 3); // 0.005
+    /// 
+    /// assert_eq!((a + b).export(), (15, 1)); // 1.5, the 0.005 is lost
+    /// assert_eq!(a.add_preserve(b).unwrap().export(), (1505, 3)); // 1.505
+    /// 
+    /// assert_eq!(NP_Dec::new(i64::MAX, 0).add_preserve(NP_Dec::new(1, 1)), None);
+    /// ```
+    /// 
+    pub fn add_preserve(self, other: NP_Dec) -> Option<NP_Dec> {
+        let exp = u8::max(self.exp, other.exp);
+        let num = self.to_minor_units(exp)?.checked_add(other.to_minor_units(exp)?)?;
+        Some(NP_Dec::new(num, exp))
     }
 
-    fn gt(&self, other: &NP_Dec) -> bool {
-        if self.exp == other.exp {
-            return self.num > other.num;
-        } else {
-            let new_other = self.match_exp(other);
-            return self.num > new_other.num;
+    /// Checked addition, returns `None` instead of overflowing.
+    /// 
+    /// Gives the same result as the `+` operator, `other` is moved to the `exp` of `self` first.  Rescaling `other` is checked too.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// assert_eq!(NP_Dec::new(15, 1).checked_add(NP_Dec::new(2, 0)).unwrap().export(), (35, 1)); // 3.5
+    /// assert_eq!(NP_Dec::new(i64::MAX, 0).checked_add(NP_Dec::new(1, 0)), None);
+    /// assert_eq!(NP_Dec::new(1, 2).checked_add(NP_Dec::new(i64::MAX, 0)), None);
+    /// ```
+    /// 
+    pub fn checked_add(self, other: NP_Dec) -> Option<NP_Dec> {
+        let num = self.num.checked_add(self.checked_match_exp(&other)?)?;
+        Some(NP_Dec::new(num, self.exp))
+    }
+
+    /// Checked subtraction, returns `None` instead of overflowing.
+    /// 
+    /// Gives the same result as the `-` operator, `other` is moved to the `exp` of `self` first.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// assert_eq!(NP_Dec::new(15, 1).checked_sub(NP_Dec::new(2, 0)).unwrap().export(), (-5, 1)); // -0.5
+    /// assert_eq!(NP_Dec::new(i64::MIN, 0).checked_sub(NP_Dec::new(1, 0)), None);
+    /// ```
+    /// 
+    pub fn checked_sub(self, other: NP_Dec) -> Option<NP_Dec> {
+        let num = self.num.checked_sub(self.checked_match_exp(&other)?)?;
+        Some(NP_Dec::new(num, self.exp))
+    }
+
+    /// Checked multiplication, returns `None` instead of overflowing.
+    /// 
+    /// Gives the same result as the `*` operator, `other` is moved to the `exp` of `self` first and the `num` values are multiplied.  Rescaling `other` is checked too.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// assert_eq!(NP_Dec::new(15, 1).checked_mul(NP_Dec::new(2, 1)).unwrap().export(), (30, 1));
+    /// assert_eq!(NP_Dec::new(15, 1).checked_mul(NP_Dec::new(2, 0)).unwrap().export(), (300, 1));
+    /// assert_eq!(NP_Dec::new(i64::MAX / 2, 0).checked_mul(NP_Dec::new(3, 0)), None);
+    /// assert_eq!(NP_Dec::new(1, 2).checked_mul(NP_Dec::new(i64::MAX, 0)), None);
+    /// ```
+    /// 
+    pub fn checked_mul(self, other: NP_Dec) -> Option<NP_Dec> {
+        let num = self.num.checked_mul(self.checked_match_exp(&other)?)?;
+        Some(NP_Dec::new(num, self.exp))
+    }
+
+    /// Checked division, returns `None` if `other` is zero at the `exp` of `self` or the result overflows.
+    /// 
+    /// Gives the same result as the `/` operator, `other` is moved to the `exp` of `self` first and the `num` values are divided.  Rescaling `other` is checked too.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// assert_eq!(NP_Dec::new(60, 1).checked_div(NP_Dec::new(2, 1)).unwrap().export(), (30, 1));
+    /// assert_eq!(NP_Dec::new(60, 1).checked_div(NP_Dec::new(2, 0)).unwrap().export(), (3, 1));
+    /// assert_eq!(NP_Dec::new(60, 1).checked_div(NP_Dec::new(0, 0)), None);
+    /// assert_eq!(NP_Dec::new(60, 0).checked_div(NP_Dec::new(5, 1)), None); // 0.5 is 0 at exp 0
+    /// assert_eq!(NP_Dec::new(i64::MIN, 0).checked_div(NP_Dec::new(-1, 0)), None);
+    /// ```
+    /// 
+    pub fn checked_div(self, other: NP_Dec) -> Option<NP_Dec> {
+        let num = self.num.checked_div(self.checked_match_exp(&other)?)?;
+        Some(NP_Dec::new(num, self.exp))
+    }
+
+    /// Checked remainder, returns `None` if `other` is zero or the two values can't be lined up.
+    /// 
+    /// Unlike the `%` operator the remainder is found at the larger of the two `exp` values, so a small `other` isn't truncated to zero first.  The result keeps the `exp` of `self`, digits past it are truncated.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// assert_eq!(NP_Dec::new(2049, 2).checked_rem(NP_Dec::new(1, 0)).unwrap().export(), (49, 2)); // 20.49 % 1 = 0.49
+    /// assert_eq!(NP_Dec::new(1000, 3).checked_rem(NP_Dec::new(15, 3)).unwrap().export(), (10, 3)); // 1.000 % 0.015 = 0.010
+    /// assert_eq!(NP_Dec::new(2049, 2).checked_rem(NP_Dec::new(5, 3)).unwrap().export(), (0, 2)); // 20.49 % 0.005 = 0
+    /// assert_eq!(NP_Dec::new(2049, 2).checked_rem(NP_Dec::new(0, 0)), None);
+    /// assert_eq!(NP_Dec::new(i64::MIN, 0).checked_rem(NP_Dec::new(-1, 0)).unwrap().export(), (0, 0));
+    /// ```
+    /// 
+    pub fn checked_rem(self, other: NP_Dec) -> Option<NP_Dec> {
+        if other.num == 0 {
+            return None;
+        }
+
+        let exp = u8::max(self.exp, other.exp);
+
+        let mut num = self.num as i128;
+        let mut divisor = other.num as i128;
+        for _ in self.exp..exp {
+            num = num.checked_mul(10)?;
         }
+        for _ in other.exp..exp {
+            divisor = divisor.checked_mul(10)?;
+        }
+
+        // the remainder is never larger than `num`, so it fits once it's back at the exp of `self`
+        let mut rem = num.checked_rem(divisor)?;
+        for _ in self.exp..exp {
+            rem /= 10;
+        }
+
+        NP_Dec::checked_num(rem, self.exp)
     }
 
-    fn ge(&self, other: &NP_Dec) -> bool {
-        if self.exp == other.exp {
-            return self.num >= other.num;
-        } else {
-            let new_other = self.match_exp(other);
-            return self.num >= new_other.num;
+    /// Build an NP_Dec from an i128 `num`, `None` if it doesn't fit in an i64.
+    fn checked_num(num: i128, exp: u8) -> Option<NP_Dec> {
+        if num > i64::MAX as i128 || num < i64::MIN as i128 {
+            return None;
         }
+
+        Some(NP_Dec::new(num as i64, exp))
     }
 
-    fn partial_cmp(&self, other: &NP_Dec) -> Option<core::cmp::Ordering> { 
+    /// The `num` of `other` at the `exp` of `self`, like `match_exp` but `None` if raising the `exp` overflows.
+    fn checked_match_exp(&self, other: &NP_Dec) -> Option<i64> {
+        let mut num = other.num;
 
-        let (a, b) = if self.exp == other.exp {
-            (self.num, other.num)
+        if self.exp > other.exp {
+            for _ in 0..(self.exp - other.exp) {
+                num = num.checked_mul(10)?;
+            }
         } else {
-            let new_other = self.match_exp(other);
-            (self.num, new_other.num)
-        };
-
-        if a > b {
-            return Some(core::cmp::Ordering::Greater);
-        } else if a < b {
-            return Some(core::cmp::Ordering::Less);
-        } else if a == b {
-            return Some(core::cmp::Ordering::Equal);
+            for _ in 0..(other.exp - self.exp) {
+                num /= 10;
+            }
         }
 
-        return None;
+        Some(num)
     }
-}
-
 
-/// Converts an NP_Dec into an Int32, rounds to nearest whole number
-/// ```
+    /// Check if two NP_Dec have the exact same representation, meaning both `num` and `exp` are identical.
+    /// 
+    /// This is NOT the same as `==`.  The `==` operator compares values, so `2.5` and `2.50` are equal even though they are stored differently.  `repr_eq` only returns `true` if the stored parts match.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// let a = NP_Dec::new(25, 1); // 2.5
+    /// let b = NP_Dec::new(250, 2); // 2.50
+    /// 
+    /// assert_eq!(a == b, true);
+    /// assert_eq!(a.repr_eq(&b), false);
+    /// assert_eq!(a.repr_eq(&NP_Dec::new(25, 1)), true);
+    /// ```
+    /// 
+    pub fn repr_eq(&self, other: &NP_Dec) -> bool {
+        self.num == other.num && self.exp == other.exp
+    }
+
+    /// Get the bytes NoProto uses to store and sort this value in a buffer.
+    /// 
+    /// The `num` is written as big endian bytes with the sign bit flipped, so comparing the bytes of two NP_Dec values gives the same order as comparing the values.  This is useful for building external indexes that need to sort the same way NoProto does.
+    /// 
+    /// **The `exp` is not part of the key.**  Keys only sort correctly against other keys made from NP_Dec values with the same `exp`, use `shift_exp` or `with_exp_of` first if you aren't sure.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// let low = NP_Dec::new(-250, 2);
+    /// let high = NP_Dec::new(125, 2);
+    /// 
+    /// assert!(low.sort_key_bytes() < high.sort_key_bytes());
+    /// ```
+    /// 
+    pub fn sort_key_bytes(&self) -> [u8; 8] {
+        let mut be_bytes = self.num.to_be_bytes();
+        be_bytes[0] = to_unsigned(be_bytes[0]);
+        be_bytes
+    }
+
+    /// Restrict this NP_Dec to the range `min..=max`.
+    /// 
+    /// The comparison is exact across different `exp` values.  If `min` or `max` is returned it's moved to the `exp` of `self`, rounding towards the inside of the range so the result never lands outside it.  If no value at the `exp` of `self` fits in the range (or the bound doesn't fit an i64 at that `exp`) the bound is returned at it's own `exp` instead.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `min` is greater than `max`.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// let rate = NP_Dec::new(1250, 3); // 1.250
+    /// 
+    /// assert_eq!(rate.clamp(NP_Dec::new(0, 0), NP_Dec::new(1, 0)).export(), (1000, 3));
+    /// assert_eq!(NP_Dec::new(-5, 2).clamp(NP_Dec::new(0, 0), NP_Dec::new(1, 0)).export(), (0, 2));
+    /// assert_eq!(NP_Dec::new(75, 2).clamp(NP_Dec::new(0, 0), NP_Dec::new(1, 0)).export(), (75, 2));
+    /// 
+    /// // 1.5 rounds up to 2 so the result stays above min
+    /// assert_eq!(NP_Dec::new(0, 0).clamp(NP_Dec::new(15, 1), NP_Dec::new(25, 1)).export(), (2, 0));
+    /// ```
+    pub fn clamp(self, min: NP_Dec, max: NP_Dec) -> NP_Dec {
+        assert!(min.cmp_aligned(&max) != core::cmp::Ordering::Greater, "NP_Dec::clamp called with min greater than max");
+
+        let (bound, step) = if self.cmp_aligned(&min) == core::cmp::Ordering::Less {
+            (min, 1)
+        } else if self.cmp_aligned(&max) == core::cmp::Ordering::Greater {
+            (max, -1)
+        } else {
+            return self
+        };
+
+        // rescaling truncates towards zero, which can step outside the range
+        let rescaled = self.checked_match_exp(&bound).and_then(|num| {
+            let truncated = NP_Dec::new(num, self.exp);
+            if truncated.cmp_aligned(&bound) == (if step > 0 { core::cmp::Ordering::Less } else { core::cmp::Ordering::Greater }) {
+                Some(NP_Dec::new(num.checked_add(step)?, self.exp))
+            } else {
+                Some(truncated)
+            }
+        });
+
+        match rescaled {
+            Some(x) if x.cmp_aligned(&min) != core::cmp::Ordering::Less && x.cmp_aligned(&max) != core::cmp::Ordering::Greater => x,
+            _ => bound
+        }
+    }
+
+    /// The distance between two NP_Dec values, always zero or positive.
+    /// 
+    /// The result uses the larger `exp` of the two so no decimal places are lost, the values are lined up as `i128` before subtracting.  If the distance doesn't fit in an `i64` at that `exp` the result saturates at `i64::MAX` instead of overflowing.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// let paid = NP_Dec::new(10, 0);    // 10
+    /// let owed = NP_Dec::new(9995, 3);  // 9.995
+    /// 
+    /// assert_eq!(paid.abs_diff(&owed).export(), (5, 3));
+    /// assert_eq!(owed.abs_diff(&paid).export(), (5, 3));
+    /// ```
+    pub fn abs_diff(&self, other: &NP_Dec) -> NP_Dec {
+        let exp = if self.exp > other.exp { self.exp } else { other.exp };
+
+        let scale = |value: &NP_Dec| -> Option<i128> {
+            let mut scaled = value.num as i128;
+            for _ in value.exp..exp {
+                if scaled == 0 { break; }
+                scaled = scaled.checked_mul(10)?;
+            }
+            Some(scaled)
+        };
+
+        let diff = match (scale(self), scale(other)) {
+            (Some(a), Some(b)) => a.checked_sub(b).and_then(|x| x.checked_abs()),
+            _ => None
+        };
+
+        match diff {
+            Some(x) if x <= i64::MAX as i128 => NP_Dec::new(x as i64, exp),
+            _ => NP_Dec::new(i64::MAX, exp)
+        }
+    }
+
+    /// The absolute value of this NP_Dec, keeping it's `exp`.
+    /// 
+    /// `i64::MIN` has no positive counterpart, it saturates at `i64::MAX` instead of overflowing.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// assert_eq!(NP_Dec::new(-2039, 2).abs().export(), (2039, 2));
+    /// assert_eq!(NP_Dec::new(2039, 2).abs().export(), (2039, 2));
+    /// assert_eq!(NP_Dec::new(i64::MIN, 2).abs().export(), (i64::MAX, 2));
+    /// ```
+    pub fn abs(self) -> NP_Dec {
+        NP_Dec::new(self.num.saturating_abs(), self.exp)
+    }
+
+    /// The sign of this NP_Dec, `-1` if it's negative, `0` if it's zero and `1` if it's positive.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// assert_eq!(NP_Dec::new(-5, 3).signum(), -1);
+    /// assert_eq!(NP_Dec::new(0, 3).signum(), 0);
+    /// assert_eq!(NP_Dec::new(5, 3).signum(), 1);
+    /// ```
+    pub fn signum(self) -> i64 {
+        self.num.signum()
+    }
+
+    /// Check if this NP_Dec is below zero.
+    pub fn is_negative(&self) -> bool {
+        self.num < 0
+    }
+
+    /// Check if this NP_Dec is above zero, zero is neither positive nor negative.
+    pub fn is_positive(&self) -> bool {
+        self.num > 0
+    }
+
+    /// Round this NP_Dec to the nearest multiple of `10^powers_of_ten`, like rounding `12345` to the nearest hundred.
+    /// 
+    /// `exp` can't go below zero, so this is how to keep fewer significant digits than the whole number has.  Any decimal places are dropped along the way and the result keeps the `exp` of this value.  Halves round away from zero the same as `NP_RoundMode::HalfUp`, a result too large for an `i64` saturates.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// assert_eq!(NP_Dec::new(12345, 0).round_to_magnitude(2).export(), (12300, 0));
+    /// assert_eq!(NP_Dec::new(1235067, 2).round_to_magnitude(2).export(), (1240000, 2)); // 12350.67 -> 12400.00
+    /// ```
+    pub fn round_to_magnitude(&self, powers_of_ten: u8) -> NP_Dec {
+        let negative = self.num < 0;
+        let mut magnitude = (self.num as i128).abs() as u128;
+        let mut first_dropped = 0u8;
+        let mut rest_nonzero = false;
+
+        let drop = self.exp as u16 + powers_of_ten as u16;
+
+        for _ in 0..drop {
+            rest_nonzero = rest_nonzero || first_dropped != 0;
+            first_dropped = (magnitude % 10) as u8;
+            magnitude /= 10;
+        }
+
+        let mut rounded = NP_RoundMode::HalfUp.round_magnitude(magnitude, first_dropped, rest_nonzero);
+
+        // scale back up to the original exp
+        for _ in 0..drop {
+            if rounded == 0 { break; }
+            rounded = match rounded.checked_mul(10) {
+                Some(x) => x,
+                None => u128::MAX
+            };
+        }
+
+        NP_Dec::new(saturating_from_magnitude(rounded, negative), self.exp)
+    }
+
+    /// Add up a list of decimals and round the total once to `result_exp`.
+    /// 
+    /// The values are added exactly at the largest `exp` in the list and only the total is rounded with `mode`, so rounding errors don't pile up the way they do when each value is rounded first.  This is the right way to total line items for an invoice.
+    /// 
+    /// Returns `None` if the total doesn't fit an `i64` at `result_exp`, or the values can't be lined up at a common `exp` without overflowing.  An empty list sums to zero.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::{NP_Dec, NP_RoundMode};
+    /// 
+    /// let items = [NP_Dec::new(1005, 3), NP_Dec::new(1005, 3), NP_Dec::new(1005, 3)]; // 1.005 three times
+    /// 
+    /// // 3.015 rounds once to 3.02
+    /// assert_eq!(NP_Dec::sum_rounded(&items, 2, NP_RoundMode::HalfEven).unwrap().export(), (302, 2));
+    /// 
+    /// // rounding each item to 1.00 first would total 3.00
+    /// ```
+    pub fn sum_rounded(values: &[NP_Dec], result_exp: u8, mode: NP_RoundMode) -> Option<NP_Dec> {
+        let exp = values.iter().map(|x| x.exp).max().unwrap_or(result_exp);
+
+        let mut total = 0i128;
+        for value in values {
+            let mut scaled = value.num as i128;
+            for _ in value.exp..exp {
+                scaled = scaled.checked_mul(10)?;
+            }
+            total = total.checked_add(scaled)?;
+        }
+
+        let negative = total < 0;
+        // `wrapping_neg` keeps i128::MIN's magnitude intact as a u128
+        let mut magnitude = if negative { (total as u128).wrapping_neg() } else { total as u128 };
+
+        if result_exp >= exp {
+            for _ in exp..result_exp {
+                magnitude = magnitude.checked_mul(10)?;
+            }
+        } else {
+            let mut first_dropped = 0u8;
+            let mut rest_nonzero = false;
+
+            for _ in result_exp..exp {
+                rest_nonzero = rest_nonzero || first_dropped != 0;
+                first_dropped = (magnitude % 10) as u8;
+                magnitude /= 10;
+            }
+
+            magnitude = mode.round_magnitude(magnitude, first_dropped, rest_nonzero);
+        }
+
+        let num = if negative {
+            if magnitude > i64::MAX as u128 + 1 { return None }
+            (magnitude as i128).wrapping_neg() as i64
+        } else {
+            if magnitude > i64::MAX as u128 { return None }
+            magnitude as i64
+        };
+
+        Some(NP_Dec::new(num, result_exp))
+    }
+
+    /// Split this NP_Dec into it's whole number part and fractional remainder.
+    /// 
+    /// The whole part is truncated toward zero and the remainder keeps the `exp` of this value, so adding them back together always gives the original.  Both parts carry the sign of the original value, `-2.75` splits into `-2` and `-0.75`.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// let (whole, frac) = NP_Dec::new(-275, 2).split();
+    /// 
+    /// assert_eq!(whole, -2);
+    /// assert_eq!(frac.export(), (-75, 2));
+    /// assert_eq!(frac + NP_Dec::new(whole, 0), NP_Dec::new(-275, 2));
+    /// ```
+    pub fn split(&self) -> (i64, NP_Dec) {
+        // i64 values have at most 19 digits, past that everything is fractional
+        if self.exp > 18 {
+            return (0, *self);
+        }
+
+        let mut divisor = 1i64;
+        for _ in 0..self.exp {
+            divisor *= 10;
+        }
+
+        // integer division and remainder both truncate toward zero, so they share the sign of `num`
+        (self.num / divisor, NP_Dec::new(self.num % divisor, self.exp))
+    }
+
+    /// Check if this NP_Dec is a whole number, trailing zero decimal places don't count.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// assert_eq!(NP_Dec::new(1200, 2).is_integer(), true); // 12.00
+    /// assert_eq!(NP_Dec::new(1250, 2).is_integer(), false); // 12.50
+    /// ```
+    pub fn is_integer(&self) -> bool {
+        self.split().1.num == 0
+    }
+
+    /// Get just the fractional part of this NP_Dec at the same `exp`, it has the sign of the original value.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// assert_eq!(NP_Dec::new(1250, 2).frac().export(), (50, 2)); // 12.50 -> 0.50
+    /// assert_eq!(NP_Dec::new(-1250, 2).frac().export(), (-50, 2)); // -12.50 -> -0.50
+    /// ```
+    pub fn frac(&self) -> NP_Dec {
+        self.split().1
+    }
+
+    /// Get the integer value of this NP_Dec if it has no fractional part, `None` otherwise.
+    /// 
+    /// Useful when migrating decimal columns that only ever hold whole numbers to a more compact integer or varint encoding.  Trailing zero decimal places don't count as a fraction, `12.00` is whole.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// assert_eq!(NP_Dec::new(1200, 2).to_varint_if_whole(), Some(12)); // 12.00
+    /// assert_eq!(NP_Dec::new(1250, 2).to_varint_if_whole(), None); // 12.50
+    /// ```
+    pub fn to_varint_if_whole(&self) -> Option<i64> {
+        let (whole, frac) = self.split();
+
+        if frac.num == 0 { Some(whole) } else { None }
+    }
+
+    /// Get the whole number part of this NP_Dec as an i32, clamped to `i32::MIN` or `i32::MAX` if it doesn't fit.
+    /// 
+    /// The fraction is truncated toward zero like `Into<i32>`, which wraps on overflow instead.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// assert_eq!(NP_Dec::new(-2599, 2).to_i32_saturating(), -25);
+    /// assert_eq!(NP_Dec::new(5_000_000_000, 0).to_i32_saturating(), i32::MAX);
+    /// assert_eq!(NP_Dec::new(-5_000_000_000, 0).to_i32_saturating(), i32::MIN);
+    /// ```
+    pub fn to_i32_saturating(&self) -> i32 {
+        let whole = self.split().0;
+
+        if whole > i32::MAX as i64 {
+            i32::MAX
+        } else if whole < i32::MIN as i64 {
+            i32::MIN
+        } else {
+            whole as i32
+        }
+    }
+
+    /// Get the whole number part of this NP_Dec as an i64, the fraction is truncated toward zero.
+    /// 
+    /// The whole part of an NP_Dec always fits an i64, so this never actually clamps.  It's here to match `to_i32_saturating`.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// assert_eq!(NP_Dec::new(-2599, 2).to_i64_saturating(), -25);
+    /// assert_eq!(NP_Dec::new(i64::MIN, 0).to_i64_saturating(), i64::MIN);
+    /// ```
+    pub fn to_i64_saturating(&self) -> i64 {
+        self.split().0
+    }
+
+    /// Export NP_Dec to it's component parts.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// let my_num = NP_Dec::new(2203, 3); // value is 2.203
+    /// 
+    /// assert_eq!(my_num.export(), (2203i64, 3u8));
+    /// ```
+    pub fn export(&self) -> (i64, u8) {
+        (self.num, self.exp)
+    }
+
+    /// Print this NP_Dec the way Rust prints the same value as an `f64` with `{:?}`.
+    /// 
+    /// Trailing zeros after the decimal point are dropped and whole numbers always end in `.0`, so `2.50` prints as `2.5` and `5` prints as `5.0`.  Useful when a float field is replaced with a decimal and log output should stay the same.
+    /// 
+    /// The output matches `f64` for values with 15 significant digits or less between `0.0001` and `1e16`, outside of that range floats switch to exponent notation.  Note that `f64::to_string` leaves off the `.0` on whole numbers, only `{:?}` keeps it.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// assert_eq!(NP_Dec::new(250, 2).to_string_like_float(), "2.5");
+    /// assert_eq!(NP_Dec::new(500, 2).to_string_like_float(), "5.0");
+    /// assert_eq!(NP_Dec::new(-5, 0).to_string_like_float(), "-5.0");
+    /// assert_eq!(NP_Dec::new(12345, 3).to_string_like_float(), "12.345");
+    /// ```
+    /// 
+    pub fn to_string_like_float(&self) -> String {
+        let mut value = self.to_string();
+
+        if value.contains('.') {
+            let trimmed = value.trim_end_matches('0').len();
+            value.truncate(trimmed);
+            if value.ends_with('.') {
+                value.push('0');
+            }
+        } else {
+            value.push_str(".0");
+        }
+
+        value
+    }
+
+    /// Print this NP_Dec with exactly `places` digits after the decimal point, right aligned in a field of `total_width` characters.
+    /// 
+    /// Extra digits are rounded off with `NP_RoundMode::HalfUp`, missing ones are filled with zeros.  Values wider than `total_width` are never cut, the text is just longer than the field.
+    /// 
+    /// ```
+    /// use no_proto::pointer::dec::NP_Dec;
+    /// 
+    /// assert_eq!(NP_Dec::new(2039756, 3).to_aligned_string(10, 2), "   2039.76");
+    /// assert_eq!(NP_Dec::new(-5, 1).to_aligned_string(8, 3), "  -0.500");
+    /// assert_eq!(NP_Dec::new(123456, 0).to_aligned_string(4, 1), "123456.0");
+    /// ```
+    /// 
+    pub fn to_aligned_string(&self, total_width: usize, places: u8) -> String {
+        let mut value = *self;
+
+        if places < value.exp {
+            value.shift_exp_rounded(places, NP_RoundMode::HalfUp);
+        }
+
+        let mut text = value.to_string();
+
+        // widen as text so large values can't overflow the i64
+        if places > value.exp {
+            if value.exp == 0 {
+                text.push('.');
+            }
+            for _ in value.exp..places {
+                text.push('0');
+            }
+        }
+
+        format!("{:>width$}", text, width = total_width)
+    }
+}
+
+/// Print the exact decimal value of an NP_Dec
+/// 
+/// The value is printed with exactly `exp` digits after the decimal point, so no precision is lost.
+/// 
+/// ```
 /// use no_proto::pointer::dec::NP_Dec;
 /// 
-/// let x = NP_Dec::new(10123, 2);
-/// let y: i32 = x.into();
+/// assert_eq!(NP_Dec::new(2039756, 3).to_string(), "2039.756");
+/// assert_eq!(NP_Dec::new(-5, 2).to_string(), "-0.05");
+/// assert_eq!(NP_Dec::new(100, 0).to_string(), "100");
 /// 
-/// assert_eq!(y, 101i32);
 /// ```
-impl Into<i32> for NP_Dec {
-    fn into(self) -> i32 { 
-        let mut change_value = self.num;
-        let mut loop_val = self.exp;
-        while loop_val > 0 {
-            change_value /= 10;
-            loop_val -= 1;
+impl core::fmt::Display for NP_Dec {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let digits = (self.num as i128).abs().to_string();
+        let exp = self.exp as usize;
+
+        if self.num < 0 {
+            f.write_str("-")?;
+        }
+
+        if exp == 0 {
+            return f.write_str(&digits);
+        }
+
+        if digits.len() > exp {
+            let (whole, frac) = digits.split_at(digits.len() - exp);
+            f.write_str(whole)?;
+            f.write_str(".")?;
+            f.write_str(frac)
+        } else {
+            f.write_str("0.")?;
+            for _ in digits.len()..exp {
+                f.write_str("0")?;
+            }
+            f.write_str(&digits)
         }
-        change_value as i32
     }
 }
 
-/// Converts an Int32 into a NP_Dec
+/// Parse an exact decimal string into an NP_Dec
+/// 
+/// The `exp` of the result is the number of digits after the decimal point, so `"12.50"` parses to `num: 1250, exp: 2`.  Leading and trailing whitespace is trimmed and a leading `+` or `-` is allowed.
+/// 
+/// Parsing is strict, anything that isn't a plain decimal number is an error instead of a partial value.  Empty strings, a lone sign or point, more than one point, thousands separators, exponents and values that don't fit in an i64 are all rejected.
+/// 
 /// ```
 /// use no_proto::pointer::dec::NP_Dec;
+/// use no_proto::error::NP_Error;
 /// 
-/// let x = 101i32;
-/// let y: NP_Dec = x.into();
+/// assert_eq!("-2039.756".parse::<NP_Dec>()?.export(), (-2039756, 3));
+/// assert_eq!(" +12.50 ".parse::<NP_Dec>()?.export(), (1250, 2));
+/// assert_eq!("42".parse::<NP_Dec>()?.export(), (42, 0));
+/// assert!("1,000".parse::<NP_Dec>().is_err());
 /// 
-/// assert_eq!(y.num as i32, x);
+/// # Ok::<(), NP_Error>(())
 /// ```
-impl Into<NP_Dec> for i32 {
-    fn into(self) -> NP_Dec { 
-        NP_Dec::new(self as i64, 0)
+impl core::str::FromStr for NP_Dec {
+    type Err = NP_Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+
+        let parse_err = || {
+            let mut err = String::from("Can't parse decimal value: ");
+            err.push_str(value);
+            NP_Error::new(err)
+        };
+
+        let (negative, digits) = match value.as_bytes().first() {
+            Some(b'-') => (true, &value[1..]),
+            Some(b'+') => (false, &value[1..]),
+            _ => (false, value)
+        };
+
+        let (whole, frac) = match digits.find('.') {
+            Some(idx) => (&digits[..idx], &digits[(idx + 1)..]),
+            None => (digits, "")
+        };
+
+        if (whole.len() == 0 && frac.len() == 0) || !whole.bytes().chain(frac.bytes()).all(|x| x.is_ascii_digit()) {
+            return Err(parse_err());
+        }
+
+        if frac.len() > u8::MAX as usize {
+            return Err(NP_Error::new("Decimal value has more than 255 digits after the point!"));
+        }
+
+        let overflow = || NP_Error::new("Decimal value is too large for an i64!");
+
+        // accumulate toward the sign so i64::MIN can be parsed
+        let mut num: i64 = 0;
+        for digit in whole.bytes().chain(frac.bytes()) {
+            let digit = (digit - b'0') as i64;
+            num = num.checked_mul(10).ok_or_else(overflow)?;
+            num = if negative { num.checked_sub(digit) } else { num.checked_add(digit) }.ok_or_else(overflow)?;
+        }
+
+        Ok(NP_Dec::new(num, frac.len() as u8))
+    }
+}
+
+/// Check if two NP_Dec are equal or not equal
+/// 
+/// Values with different `exp` values are compared exactly, the smaller `exp` is widened to an `i128` so lining them up never overflows.
+/// 
+/// ```
+/// use no_proto::pointer::dec::NP_Dec;
+/// 
+/// let result = NP_Dec::new(202, 1) == NP_Dec::new(202, 1);
+/// assert_eq!(result, true);
+/// 
+/// let result = NP_Dec::new(202, 1) != NP_Dec::new(200, 1);
+/// assert_eq!(result, true);
+/// 
+/// let result = NP_Dec::new(202, 1) == NP_Dec::new(2020, 2);
+/// assert_eq!(result, true);
+/// 
+/// let result = NP_Dec::new(203, 1) != NP_Dec::new(2020, 2);
+/// assert_eq!(result, true);
+/// 
+/// ```
+impl core::cmp::PartialEq for NP_Dec {
+    fn eq(&self, other: &NP_Dec) -> bool { 
+        self.cmp_aligned(other) == core::cmp::Ordering::Equal
+    }
+}
+
+/// Compare two NP_Dec
+/// 
+/// Values with different `exp` values are compared exactly, the smaller `exp` is widened to an `i128` so lining them up never overflows.
+/// 
+/// ```
+/// use no_proto::pointer::dec::NP_Dec;
+/// 
+/// let result = NP_Dec::new(203, 1) > NP_Dec::new(202, 1);
+/// assert_eq!(result, true);
+/// 
+/// let result = NP_Dec::new(202, 1) < NP_Dec::new(203, 1);
+/// assert_eq!(result, true);
+/// 
+/// let result = NP_Dec::new(20201, 2) > NP_Dec::new(202, 0);
+/// assert_eq!(result, true);
+/// 
+/// let result = NP_Dec::new(20201, 2) == NP_Dec::new(2020100, 4);
+/// assert_eq!(result, true);
+/// 
+/// // 1 vs 0.000000000000000000001, lining these up doesn't fit in an i64
+/// let result = NP_Dec::new(1, 0) > NP_Dec::new(1, 21);
+/// assert_eq!(result, true);
+/// ```
+impl core::cmp::PartialOrd for NP_Dec {
+    fn partial_cmp(&self, other: &NP_Dec) -> Option<core::cmp::Ordering> { 
+        Some(self.cmp_aligned(other))
+    }
+}
+
+impl core::cmp::Eq for NP_Dec { }
+
+/// Total ordering for NP_Dec, the same exact comparison used by `PartialOrd`.
+/// 
+/// This lets NP_Dec values be sorted or used as `BTreeMap` keys, values with different `exp` that are equal land in the same spot.
+/// 
+/// ```
+/// use no_proto::pointer::dec::NP_Dec;
+/// 
+/// let mut values = vec![NP_Dec::new(2020, 2), NP_Dec::new(-5, 0), NP_Dec::new(2021, 3)];
+/// values.sort();
+/// assert_eq!(values[0].export(), (-5, 0));
+/// assert_eq!(values[1].export(), (2021, 3));
+/// assert_eq!(values[2].export(), (2020, 2));
+/// ```
+impl core::cmp::Ord for NP_Dec {
+    fn cmp(&self, other: &NP_Dec) -> core::cmp::Ordering { 
+        self.cmp_aligned(other)
+    }
+}
+
+/// Hash an NP_Dec by value, so values that are `==` always hash the same.
+/// 
+/// Trailing zeros are stripped from `num` before hashing, `NP_Dec::new(202, 1)` and `NP_Dec::new(2020, 2)` hash as the same value.
+impl core::hash::Hash for NP_Dec {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        let mut num = self.num;
+        let mut exp = self.exp;
+
+        if num == 0 {
+            exp = 0;
+        }
+
+        while exp > 0 && num % 10 == 0 {
+            num /= 10;
+            exp -= 1;
+        }
+
+        num.hash(state);
+        exp.hash(state);
+    }
+}
+
+impl NP_Dec {
+    /// Compare the values of two NP_Dec without losing precision or overflowing.
+    fn cmp_aligned(&self, other: &NP_Dec) -> core::cmp::Ordering {
+        if self.exp == other.exp {
+            return self.num.cmp(&other.num);
+        }
+
+        // scale the side with the smaller exp up to the larger exp
+        let (low, high, flipped) = if self.exp < other.exp { (self, other, false) } else { (other, self, true) };
+
+        let mut scaled = low.num as i128;
+        let mut step = high.exp - low.exp;
+        let mut overflowed = false;
+
+        while step > 0 && scaled != 0 {
+            match scaled.checked_mul(10) {
+                Some(x) => scaled = x,
+                None => { overflowed = true; break; }
+            }
+            step -= 1;
+        }
+
+        let ordering = if overflowed {
+            // scaled is bigger than any i64 could be, only the sign matters
+            if scaled > 0 { core::cmp::Ordering::Greater } else { core::cmp::Ordering::Less }
+        } else {
+            scaled.cmp(&(high.num as i128))
+        };
+
+        if flipped { ordering.reverse() } else { ordering }
+    }
+}
+
+
+/// Converts an NP_Dec into an Int32, rounds to nearest whole number
+/// ```
+/// use no_proto::pointer::dec::NP_Dec;
+/// 
+/// let x = NP_Dec::new(10123, 2);
+/// let y: i32 = x.into();
+/// 
+/// assert_eq!(y, 101i32);
+/// ```
+impl Into<i32> for NP_Dec {
+    fn into(self) -> i32 { 
+        let mut change_value = self.num;
+        let mut loop_val = self.exp;
+        while loop_val > 0 {
+            change_value /= 10;
+            loop_val -= 1;
+        }
+        change_value as i32
+    }
+}
+
+/// Converts an Int32 into a NP_Dec
+/// ```
+/// use no_proto::pointer::dec::NP_Dec;
+/// 
+/// let x = 101i32;
+/// let y: NP_Dec = x.into();
+/// 
+/// assert_eq!(y.num as i32, x);
+/// ```
+impl Into<NP_Dec> for i32 {
+    fn into(self) -> NP_Dec { 
+        NP_Dec::new(self as i64, 0)
+    }
+}
+
+
+/// Converts an NP_Dec into an Int64, rounds to nearest whole number
+/// ```
+/// use no_proto::pointer::dec::NP_Dec;
+/// 
+/// let x = NP_Dec::new(10123, 2);
+/// let y: i64 = x.into();
+/// 
+/// assert_eq!(y, 101i64);
+/// ```
+impl Into<i64> for NP_Dec {
+    fn into(self) -> i64 { 
+        let mut change_value = self.num;
+        let mut loop_val = self.exp;
+        while loop_val > 0 {
+            change_value /= 10;
+            loop_val -= 1;
+        }
+        change_value
+    }
+}
+
+/// Converts an Int64 into a NP_Dec
+/// ```
+/// use no_proto::pointer::dec::NP_Dec;
+/// 
+/// let x = 101i64;
+/// let y: NP_Dec = x.into();
+/// 
+/// assert_eq!(y.num, x);
+/// ```
+impl Into<NP_Dec> for i64 {
+    fn into(self) -> NP_Dec { 
+        NP_Dec::new(self, 0)
+    }
+}
+
+
+
+fn round_f64(n: f64) -> f64 {
+    let value = if n < 0.0 { n - 0.5 } else { n + 0.5 };
+
+    let bounds_value = value.max(core::i64::MIN as f64).min(core::i64::MAX as f64);
+
+    (bounds_value as i64) as f64
+}
+
+fn round_f32(n: f32) -> f32 {
+    let value = if n < 0.0 { n - 0.5 } else { n + 0.5 };
+
+    let bounds_value = value.max(core::i64::MIN as f32).min(core::i64::MAX as f32);
+
+    (bounds_value as i64) as f32
+}
+
+fn round(n: f64, precision: u32) -> f64 {
+    round_f64(n * 10_u32.pow(precision) as f64) / 10_i32.pow(precision) as f64
+}
+
+fn precision(x: f64) -> Option<u32> {
+    for digits in 0..core::f64::DIGITS {
+        if round(x, digits) == x {
+            return Some(digits);
+        }
+    }
+    None
+}
+
+fn round32(n: f32, precision: u32) -> f32 {
+    round_f32(n * 10_u32.pow(precision) as f32) / 10_i32.pow(precision) as f32
+}
+
+fn precision32(x: f32) -> Option<u32> {
+    for digits in 0..core::f64::DIGITS {
+        if round32(x, digits) == x {
+            return Some(digits);
+        }
+    }
+    None
+}
+
+/// Converts a NP_Dec into a Float64
+/// ```
+/// use no_proto::pointer::dec::NP_Dec;
+/// 
+/// let x = NP_Dec::new(10023, 2);
+/// let y: f64 = x.into();
+/// 
+/// assert_eq!(y, x.to_float());
+/// ```
+impl Into<f64> for NP_Dec {
+    fn into(self) -> f64 { 
+        self.to_float()
+    }
+}
+
+/// Converts a Float64 into a NP_Dec
+/// ```
+/// use no_proto::pointer::dec::NP_Dec;
+/// 
+/// let x = 100.238f64;
+/// let y: NP_Dec = x.into();
+/// 
+/// assert_eq!(y.to_float(), x);
+/// ```
+impl Into<NP_Dec> for f64 {
+    fn into(self) -> NP_Dec { 
+        match precision(self) {
+            Some(x) => {
+                let max_decimal_places = u8::min(x as u8, 18);
+                let mut new_self = self.clone();
+                let mut loop_exp = max_decimal_places;
+                while loop_exp > 0 {
+                    new_self *= 10f64;
+                    loop_exp -= 1;
+                }
+                let value = round_f64(new_self) as i64;
+                return NP_Dec::new(value, max_decimal_places as u8);
+            },
+            None => { // this should be impossible, but just incase
+                let value = round_f64(self) as i64;
+                return NP_Dec::new(value, 0);
+            }
+        }
+    }
+}
+
+/// Converts a NP_Dec into a Float32
+/// ```
+/// use no_proto::pointer::dec::NP_Dec;
+/// 
+/// let x = NP_Dec::new(10023, 2);
+/// let y: f32 = x.into();
+/// 
+/// assert_eq!(y, x.to_float() as f32);
+/// ```
+impl Into<f32> for NP_Dec {
+    fn into(self) -> f32 { 
+        self.to_float() as f32
+    }
+}
+
+/// Converts a Float32 into a NP_Dec
+/// ```
+/// use no_proto::pointer::dec::NP_Dec;
+/// 
+/// let x = 100.238f32;
+/// let y: NP_Dec = x.into();
+/// 
+/// assert_eq!(y.to_float() as f32, x);
+/// ```
+impl Into<NP_Dec> for f32 {
+    fn into(self) -> NP_Dec { 
+        match precision32(self) {
+            Some(x) => {
+                let max_decimal_places = u8::min(x as u8, 18);
+                let mut new_self = self.clone();
+                let mut loop_exp = max_decimal_places;
+                while loop_exp > 0 {
+                    new_self *= 10f32;
+                    loop_exp -= 1;
+                }
+                let value = round_f32(new_self) as i64;
+                return NP_Dec::new(value, max_decimal_places as u8);
+            },
+            None => { // this should be impossible, but just incase
+                let value = round_f32(self) as i64;
+                return NP_Dec::new(value, 0);
+            }
+        }
+    }
+}
+
+impl core::ops::DivAssign for NP_Dec { // a /= b
+    fn div_assign(&mut self, other: NP_Dec) { 
+        if self.exp != other.exp {
+            let other_copy = self.match_exp(&other);
+            self.num = self.num / other_copy.num;
+        } else {
+            self.num = self.num / other.num;
+        }
+    }
+}
+
+impl core::ops::Div for NP_Dec { // a / b
+    type Output = NP_Dec;
+    fn div(mut self, other: NP_Dec) -> <Self as core::ops::Sub<NP_Dec>>::Output { 
+        if self.exp != other.exp {
+            let other_copy = self.match_exp(&other);
+            self.num = self.num / other_copy.num;
+        } else {
+            self.num = self.num / other.num;
+        }
+        return self;
+    }
+}
+
+impl core::ops::SubAssign for NP_Dec { // a -= b
+    fn sub_assign(&mut self, other: NP_Dec) { 
+        if self.exp != other.exp {
+            let other_copy = self.match_exp(&other);
+            self.num = self.num - other_copy.num;
+        } else {
+            self.num = self.num - other.num;
+        }
+    }
+}
+
+impl core::ops::Sub for NP_Dec { // a - b
+    type Output = NP_Dec;
+    fn sub(mut self, other: NP_Dec) -> <Self as core::ops::Sub<NP_Dec>>::Output { 
+        if self.exp != other.exp {
+            let other_copy = self.match_exp(&other);
+            self.num = self.num - other_copy.num;
+        } else {
+            self.num = self.num - other.num;
+        }
+        return self;
+    }
+}
+
+impl core::ops::AddAssign for NP_Dec { // a += b
+    fn add_assign(&mut self, other: NP_Dec) { 
+        if self.exp != other.exp {
+            let other_copy = self.match_exp(&other);
+            self.num = self.num + other_copy.num;
+        } else {
+            self.num = self.num + other.num;
+        }
+    }
+}
+
+impl core::ops::Add for NP_Dec { // a + b
+    type Output = NP_Dec;
+    fn add(mut self, other: NP_Dec) -> <Self as core::ops::Add<NP_Dec>>::Output { 
+        if self.exp != other.exp {
+            let other_copy = self.match_exp(&other);
+            self.num = self.num + other_copy.num;
+        } else {
+            self.num = self.num + other.num;
+        }
+        return self;
+    }
+}
+
+impl core::ops::MulAssign for NP_Dec { // a *= b
+    fn mul_assign(&mut self, other: NP_Dec) { 
+        if self.exp != other.exp {
+            let other_copy = self.match_exp(&other);
+            self.num = self.num * other_copy.num;
+        } else {
+            self.num = self.num * other.num;
+        }
+    }
+}
+
+impl core::ops::Mul for NP_Dec { // a * b
+    type Output = NP_Dec;
+    fn mul(mut self, other: NP_Dec) -> <Self as core::ops::Mul<NP_Dec>>::Output { 
+
+        if self.exp != other.exp {
+            let other_copy = self.match_exp(&other);
+            self.num = self.num * other_copy.num;
+        } else {
+            self.num = self.num * other.num;
+        }
+        return self;
+    }
+}
+
+impl core::ops::RemAssign for NP_Dec { // a %= b
+    fn rem_assign(&mut self, other: NP_Dec) { 
+        if self.exp != other.exp {
+            let other_copy = self.match_exp(&other);
+            self.num = self.num % other_copy.num;
+        } else {
+            self.num = self.num % other.num;
+        }
+    }
+}
+
+/// The remainder keeps the `exp` of the left operand, the same as `Div` and `Mul`.  The right operand is moved to that `exp` first, use `checked_rem` to find the remainder without truncating it.
+/// 
+/// # Panics
+/// 
+/// Panics if the right operand is zero at the `exp` of the left one, like `20.49 % 0.005`, or for `i64::MIN % -1`.  `checked_rem` returns `None` instead.
+/// 
+/// ```
+/// use no_proto::pointer::dec::NP_Dec;
+/// 
+/// // cents past the nearest dollar
+/// let price = NP_Dec::new(2049, 2); // 20.49
+/// assert_eq!((price % NP_Dec::new(1, 0)).export(), (49, 2));
+/// 
+/// // sign follows the left operand
+/// assert_eq!((NP_Dec::new(-2049, 2) % NP_Dec::new(100, 2)).export(), (-49, 2));
+/// ```
+impl core::ops::Rem for NP_Dec { // a % b
+    type Output = NP_Dec;
+    fn rem(mut self, other: NP_Dec) -> <Self as core::ops::Rem<NP_Dec>>::Output { 
+        if self.exp != other.exp {
+            let other_copy = self.match_exp(&other);
+            self.num = self.num % other_copy.num;
+        } else {
+            self.num = self.num % other.num;
+        }
+        return self;
+    }
+}
+
+/// Negating `i64::MIN` saturates at `i64::MAX`, the same as `abs`.
+/// 
+/// ```
+/// use no_proto::pointer::dec::NP_Dec;
+/// 
+/// assert_eq!((-NP_Dec::new(2049, 2)).export(), (-2049, 2));
+/// assert_eq!((-NP_Dec::new(i64::MIN, 2)).export(), (i64::MAX, 2));
+/// ```
+impl core::ops::Neg for NP_Dec { // -a
+    type Output = NP_Dec;
+    fn neg(mut self) -> <Self as core::ops::Neg>::Output { 
+        self.num = self.num.checked_neg().unwrap_or(i64::MAX);
+        return self;
+    }
+}
+
+impl Default for NP_Dec {
+    fn default() -> Self { 
+        NP_Dec::new(0,0)
+     }
+}
+
+/// Converts a database style `(mantissa, scale)` pair into an NP_Dec
+/// 
+/// The scale becomes the `exp`.  A negative scale is applied to the mantissa, so `(12, -3)` becomes `12000` with an `exp` of 0.  Errors if the scale is larger than `u8::MAX` or a negative scale overflows the `i64` mantissa.
+/// 
+/// ```
+/// use no_proto::pointer::dec::NP_Dec;
+/// use core::convert::TryFrom;
+/// 
+/// let num = NP_Dec::try_from((12345i64, 2i32)).unwrap(); // 123.45
+/// assert_eq!(num.export(), (12345, 2));
+/// 
+/// let num = NP_Dec::try_from((12i64, -3i32)).unwrap(); // 12000
+/// assert_eq!(num.export(), (12000, 0));
+/// 
+/// assert!(NP_Dec::try_from((1i64, 300i32)).is_err());
+/// ```
+impl core::convert::TryFrom<(i64, i32)> for NP_Dec {
+    type Error = NP_Error;
+
+    fn try_from((mantissa, scale): (i64, i32)) -> Result<Self, Self::Error> {
+        if scale > u8::MAX as i32 {
+            let mut err = String::from("Scale is too large for NP_Dec: ");
+            err.push_str(scale.to_string().as_str());
+            return Err(NP_Error::new(err));
+        }
+
+        if scale >= 0 {
+            return Ok(NP_Dec::new(mantissa, scale as u8));
+        }
+
+        let mut num = mantissa;
+        let mut step = -(scale as i64);
+
+        while step > 0 && num != 0 {
+            num = match num.checked_mul(10) {
+                Some(x) => x,
+                None => {
+                    let mut err = String::from("Negative scale overflows NP_Dec: ");
+                    err.push_str(scale.to_string().as_str());
+                    return Err(NP_Error::new(err));
+                }
+            };
+            step -= 1;
+        }
+
+        Ok(NP_Dec::new(num, 0))
+    }
+}
+
+/// Converts an NP_Dec into a database style `(mantissa, scale)` pair
+/// 
+/// ```
+/// use no_proto::pointer::dec::NP_Dec;
+/// 
+/// let pair: (i64, i32) = NP_Dec::new(12345, 2).into();
+/// assert_eq!(pair, (12345, 2));
+/// ```
+impl From<NP_Dec> for (i64, i32) {
+    fn from(dec: NP_Dec) -> Self {
+        (dec.num, dec.exp as i32)
+    }
+}
+
+/// Running total of NP_Dec values kept at a fixed `exp`.
+/// 
+/// Each value added is rescaled to the accumulator's `exp` (extra precision is truncated like `shift_exp`) and added with overflow checks, so long sums don't need to re-match exponents on every step.
+/// 
+/// ```
+/// use no_proto::pointer::dec::{NP_Dec, NP_DecAccumulator};
+/// use no_proto::error::NP_Error;
+/// 
+/// let mut ledger = NP_DecAccumulator::new(2);
+/// 
+/// ledger.add(NP_Dec::new(1050, 2))?; // 10.50
+/// ledger.add(NP_Dec::new(-25, 1))?; // -2.5
+/// ledger.add(NP_Dec::new(3, 0))?; // 3
+/// 
+/// assert_eq!(ledger.balance().export(), (1100, 2)); // 11.00
+/// 
+/// // overflow is reported instead of wrapping
+/// ledger.add(NP_Dec::new(i64::MAX, 2)).unwrap_err();
+/// assert_eq!(ledger.balance().export(), (1100, 2));
+/// 
+/// # Ok::<(), NP_Error>(()) 
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct NP_DecAccumulator {
+    /// The exponent every value is rescaled to
+    pub exp: u8,
+    /// The running total, as the `num` of an NP_Dec with `exp`
+    pub total: i64
+}
+
+impl NP_DecAccumulator {
+
+    /// Create a new accumulator starting at zero
+    pub fn new(exp: u8) -> Self {
+        NP_DecAccumulator { exp, total: 0 }
+    }
+
+    /// Rescale a value to this accumulator's `exp` and add it to the total
+    /// 
+    /// If rescaling or adding would overflow an error is returned and the total is left unchanged.
+    pub fn add(&mut self, d: NP_Dec) -> Result<(), NP_Error> {
+
+        let mut num = d.num;
+
+        if d.exp < self.exp {
+            for _ in d.exp..self.exp {
+                num = match num.checked_mul(10) {
+                    Some(x) => x,
+                    None => return Err(NP_Error::new("Decimal overflow while rescaling to accumulator exp!"))
+                };
+            }
+        } else {
+            for _ in self.exp..d.exp {
+                num /= 10;
+            }
+        }
+
+        self.total = match self.total.checked_add(num) {
+            Some(x) => x,
+            None => return Err(NP_Error::new("Decimal overflow while adding to accumulator!"))
+        };
+
+        Ok(())
+    }
+
+    /// Get the current total as an NP_Dec
+    pub fn balance(&self) -> NP_Dec {
+        NP_Dec::new(self.total, self.exp)
+    }
+}
+
+/// Reads every item of a decimal list, looking up the `exp` and default of the list's schema once instead of per item.
+/// 
+/// Created with [`NP_Buffer::dec_reader`](../../buffer/struct.NP_Buffer.html#method.dec_reader).  Each item is the list index and the same value `get` would return for that index.
+/// 
+pub struct NP_DecReader<'reader> {
+    list: NP_List,
+    memory: &'reader NP_Memory,
+    exp: u8,
+    default: Option<NP_Dec>
+}
+
+impl<'reader> NP_DecReader<'reader> {
+    #[doc(hidden)]
+    pub fn new(list_cursor: &NP_Cursor, memory: &'reader NP_Memory) -> Result<Self, NP_Error> {
+        let list_schema = memory.get_schema(list_cursor.schema_addr);
+
+        if list_schema.i != NP_TypeKeys::List {
+            return Err(NP_Error::new("Decimal reader can only be used on a list of decimals!"))
+        }
+
+        let of = unsafe { &*(*list_schema.data as *const NP_Map_List_Data) }.child;
+
+        if memory.get_schema(of).i != NP_TypeKeys::Decimal {
+            return Err(NP_Error::new("Decimal reader can only be used on a list of decimals!"))
+        }
+
+        let data = unsafe { &*(*memory.get_schema(of).data as *const NP_Dec_Data) };
+
+        Ok(Self {
+            list: NP_List::new_iter(list_cursor, memory, true, 0),
+            memory,
+            exp: data.exp,
+            default: data.default
+        })
+    }
+}
+
+impl<'reader> Iterator for NP_DecReader<'reader> {
+    type Item = (usize, Option<NP_Dec>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, item) = self.list.step_iter(self.memory)?;
+
+        let value = match item {
+            Some(cursor) => NP_Dec::read_at(cursor.get_value(self.memory).get_addr_value() as usize, self.exp, self.memory),
+            None => None
+        };
+
+        Some((index, value.or(self.default)))
+    }
+}
+
+
+impl NP_Dec {
+    /// Decode the 8 value bytes at an address, `None` if the address is empty or out of bounds
+    fn read_at(value_addr: usize, exp: u8, memory: &NP_Memory) -> Option<NP_Dec> {
+        if value_addr == 0 {
+            return None;
+        }
+
+        match memory.get_8_bytes(value_addr) {
+            Some(x) => {
+                let mut be_bytes = x.clone();
+                be_bytes[0] = to_signed(be_bytes[0]);
+                Some(NP_Dec::new(i64::from_be_bytes(be_bytes), exp))
+            },
+            None => None
+        }
+    }
+}
+
+impl NP_Dec {
+    /// Scale a schema default written as a whole number up to `exp`, erroring instead of overflowing
+    fn default_from_int(value: i64, exp: u8) -> Result<i64, NP_Error> {
+        if value == 0 { return Ok(0) }
+
+        match 10i64.checked_pow(exp as u32).and_then(|mult| value.checked_mul(mult)) {
+            Some(x) => Ok(x),
+            None => Err(NP_Dec::default_overflow(exp))
+        }
+    }
+
+    /// Scale a schema default written as a float up to `exp`, erroring if the result doesn't fit in an i64
+    fn default_from_float(value: f64, exp: u8) -> Result<i64, NP_Error> {
+        let mut mult = 1f64;
+        for _ in 0..exp {
+            mult *= 10f64;
+        }
+
+        let scaled = value * mult;
+
+        // i64::MAX isn't exact as a float, 2^63 is the first value out of range
+        if scaled.is_nan() || scaled >= 9223372036854775808f64 || scaled < -9223372036854775808f64 {
+            return Err(NP_Dec::default_overflow(exp));
+        }
+
+        Ok(scaled as i64)
+    }
+
+    /// Move a number read from JSON to the schema `exp`, extra decimal places are rounded with the schema mode and values too large for the `exp` are an error.
+    fn json_value_at_exp(value: NP_Dec, data: &NP_Dec_Data) -> Result<NP_Dec, NP_Error> {
+        if value.exp >= data.exp {
+            let mut value = value;
+            return Ok(value.shift_exp_rounded(data.exp, data.rounding));
+        }
+
+        match value.to_minor_units(data.exp) {
+            Some(num) => Ok(NP_Dec::new(num, data.exp)),
+            None => {
+                let mut err = String::from("Number is too large for a decimal with an 'exp' of ");
+                err.push_str(data.exp.to_string().as_str());
+                Err(NP_Error::new(err))
+            }
+        }
+    }
+
+    fn default_overflow(exp: u8) -> NP_Error {
+        let mut err = String::from("Decimal 'default' is too large for an 'exp' of ");
+        err.push_str(exp.to_string().as_str());
+        NP_Error::new(err)
+    }
+}
+
+impl<'value> NP_Value<'value> for NP_Dec {
+
+
+
+    fn type_idx() -> (&'value str, NP_TypeKeys) { ("decimal", NP_TypeKeys::Decimal) }
+    fn self_type_idx(&self) -> (&'value str, NP_TypeKeys) { ("decimal", NP_TypeKeys::Decimal) }
+
+    fn schema_to_json(schema: &Vec<NP_Parsed_Schema>, address: usize)-> Result<NP_JSON, NP_Error> {
+        let mut schema_json = JSMAP::new();
+        schema_json.insert("type".to_owned(), NP_JSON::String(Self::type_idx().0.to_string()));
+
+        let data = unsafe { &*(*schema[address].data as *const NP_Dec_Data) };
+
+        schema_json.insert("exp".to_owned(), NP_JSON::Integer(data.exp.clone() as i64));
+
+        if let Some(d) = data.default {
+            let value = NP_Dec::new(d.num.clone(), data.exp.clone());
+            schema_json.insert("default".to_owned(), NP_JSON::Float(value.into(), None));
+        }
+
+        if data.rounding != NP_RoundMode::Truncate {
+            schema_json.insert("rounding".to_owned(), NP_JSON::String(data.rounding.schema_name().to_owned()));
+        }
+
+        Ok(NP_JSON::Dictionary(schema_json))
+         
+    }
+
+    fn default_value(_depth: usize, addr: usize, schema: &Vec<NP_Parsed_Schema>) -> Option<Self> {
+        let data = unsafe { &*(*schema[addr].data as *const NP_Dec_Data) };
+    
+        if let Some(d) = data.default {
+            Some(d.clone())
+        } else {
+            None
+        }
+          
+    }
+
+    fn set_from_json<'set>(_depth: usize, apply_null: bool, cursor: NP_Cursor, memory: &'set NP_Memory, value: &Box<NP_JSON>) -> Result<(), NP_Error> where Self: 'set + Sized {
+        match &**value {
+            NP_JSON::Null => {
+                // null writes the schema default if there is one, otherwise clears the value
+                if apply_null {
+                    let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Dec_Data) };
+                    match (memory.options.null_clears, data.default) {
+                        (false, Some(default)) => { Self::set_value(cursor, memory, default)?; },
+                        _ => { NP_Cursor::delete(cursor, memory)?; }
+                    }
+                }
+            },
+            NP_JSON::Dictionary(map) => {
+                if let Some(NP_JSON::Dictionary(parts)) = map.get("parts") {
+                    if let Some(NP_JSON::Integer(num)) = parts.get("num") {
+                        if let Some(NP_JSON::Integer(exp)) = parts.get("exp") {
+                            Self::set_value(cursor, memory, NP_Dec::new(*num, *exp as u8))?;
+                        } else {
+                            return Err(NP_Error::new("Decimal types require a `parts.exp` property!"))
+                        }
+                    } else {
+                        return Err(NP_Error::new("Decimal types require a `parts.num` property!"))
+                    }
+                } else {
+                    return Err(NP_Error::new("Decimal types require a `parts` property!"))
+                }
+            },
+            NP_JSON::Integer(x) => {
+                let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Dec_Data) };
+                Self::set_value(cursor, memory, NP_Dec::json_value_at_exp(NP_Dec::new(*x, 0), data)?)?;
+            },
+            NP_JSON::Float(x, raw) => {
+                let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Dec_Data) };
+
+                // the original text is exact, the f64 is only the closest binary value to it
+                let exact = match raw {
+                    Some(text) => text.parse::<NP_Dec>().ok(),
+                    None => None
+                };
+
+                let value = match exact {
+                    Some(parsed) => NP_Dec::json_value_at_exp(parsed, data)?,
+                    None => NP_Dec::from_float(*x, data.exp, data.rounding)
+                };
+
+                Self::set_value(cursor, memory, value)?;
+            },
+            _ => { NP_Cursor::json_type_mismatch(memory, "number or object", value)?; }
+        }
+
+        Ok(())
+    }
+
+    fn set_value<'set>(cursor: NP_Cursor, memory: &'set NP_Memory, value: Self) -> Result<NP_Cursor, NP_Error> where Self: 'set + Sized {
+
+        let c_value = || { cursor.get_value(memory) };
+
+        let mut value_address = c_value().get_addr_value() as usize;
+
+        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Dec_Data) };
+
+        let exp = data.exp;
+
+        let mut cloned_value = (value).clone();
+        cloned_value.shift_exp_rounded(exp, data.rounding);
+
+        let i64_value = cloned_value.num;
+
+        if value_address != 0 { // existing value, replace
+            let mut bytes = i64_value.to_be_bytes();
+
+            // convert to unsigned
+            bytes[0] = to_unsigned(bytes[0]);
+
+            let write_bytes = memory.write_bytes();
+
+            // overwrite existing values in buffer
+            for x in 0..bytes.len() {
+                write_bytes[value_address + x] = bytes[x];
+            }
+
+        } else { // new value
+
+            let mut be_bytes = i64_value.to_be_bytes();
+
+            // convert to unsigned
+            be_bytes[0] = to_unsigned(be_bytes[0]);
+
+            value_address = memory.malloc_borrow(&be_bytes)?;
+            cursor.get_value_mut(memory).set_addr_value(value_address as u32);
+
+        }
+
+        Ok(cursor)
+    }
+
+    fn into_value(cursor: &NP_Cursor, memory: &'value NP_Memory) -> Result<Option<Self>, NP_Error> where Self: Sized {
+
+        let c_value = || { cursor.get_value(memory) };
+
+        let value_addr = c_value().get_addr_value() as usize;
+
+        // empty value
+        if value_addr == 0 {
+            return Ok(None);
+        }
+
+        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Dec_Data) };
+
+        Ok(NP_Dec::read_at(value_addr, data.exp, memory))
+    }
+
+    fn to_json(_depth:usize, cursor: &NP_Cursor, memory: &'value NP_Memory) -> NP_JSON {
+
+        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Dec_Data) };
+
+        let exp = data.exp;
+
+        match Self::into_value(cursor, memory) {
+            Ok(x) => {
+                match x {
+                    Some(y) => {
+                        let mut object = JSMAP::new();
+
+                        let mut parts = JSMAP::new();
+
+                        parts.insert("num".to_owned(), NP_JSON::Integer(y.num));
+                        parts.insert("exp".to_owned(), NP_JSON::Integer(exp as i64));
+                        object.insert("value".to_owned(), NP_JSON::Float(y.to_float(), None));
+                        object.insert("exact".to_owned(), NP_JSON::String(y.to_string()));
+                        object.insert("parts".to_owned(), NP_JSON::Dictionary(parts));
+                        
+                        NP_JSON::Dictionary(object)
+                    },
+                    None if memory.options.emit_null_for_unset => NP_JSON::Null,
+                    None => {
+                        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Dec_Data) };
+
+                        if let Some(d) = data.default {
+                            let mut object = JSMAP::new();
+                            let mut parts = JSMAP::new();
+
+                            parts.insert("num".to_owned(), NP_JSON::Integer(d.num.clone()));
+                            parts.insert("exp".to_owned(), NP_JSON::Integer(data.exp as i64));
+                            object.insert("value".to_owned(), NP_JSON::Float(d.to_float(), None));
+                            object.insert("exact".to_owned(), NP_JSON::String(d.to_string()));
+                            object.insert("parts".to_owned(), NP_JSON::Dictionary(parts));
+                            
+                            NP_JSON::Dictionary(object)
+                        } else {
+                            NP_JSON::Null
+                        }
+                         
+                    }
+                }
+            },
+            Err(_e) => {
+                NP_JSON::Null
+            }
+        }
+    }
+
+    fn get_size(_depth:usize, cursor: &NP_Cursor, memory: &NP_Memory) -> Result<usize, NP_Error> {
+        
+        let c_value = || { cursor.get_value(memory) };
+
+        if c_value().get_addr_value() == 0 {
+            Ok(0) 
+        } else {
+            Ok(core::mem::size_of::<i64>())
+        }
+    }
+
+
+    fn schema_to_idl(schema: &Vec<NP_Parsed_Schema>, address: usize)-> Result<String, NP_Error> {
+        let data = unsafe { &*(*schema[address].data as *const NP_Dec_Data) };
+
+        let mut result = String::from("dec({exp: ");
+        result.push_str(data.exp.to_string().as_str());
+        if let Some(x) = data.default {
+            result.push_str(", default: ");
+            result.push_str(x.to_float().to_string().as_str());
+        }
+        if data.rounding != NP_RoundMode::Truncate {
+            result.push_str(", rounding: \"");
+            result.push_str(data.rounding.schema_name());
+            result.push_str("\"");
+        }
+        if NP_Schema::is_required(&schema[address]) {
+            result.push_str(", required: true");
+        }
+        result.push_str("})");
+        Ok(result)
+       
+    }
+
+    fn from_idl_to_schema(mut schema: Vec<NP_Parsed_Schema>, _name: &str, idl: &JS_Schema, args: &Vec<JS_AST>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
+
+        let mut exp: Option<u8> = None;
+        let mut default: Option<f64> = None;
+        let mut rounding = NP_RoundMode::Truncate;
+        if args.len() > 0 {
+            match &args[0] {
+                JS_AST::object { properties } => {
+                    for (key, value) in properties {
+                        match idl.get_str(key).trim() {
+                            "exp" => {
+                                match value {
+                                    JS_AST::number { addr } => {
+                                        match idl.get_str(addr).trim().parse::<u8>() {
+                                            Ok(x) => {
+                                                exp = Some(x);
+                                            },
+                                            Err(_e) => return Err(NP_Error::new("Error parsing exponent of decimal value!"))
+                                        }
+                                    },
+                                    _ => { }
+                                }
+                            },
+                            "default" => {
+                                match value {
+                                    JS_AST::number { addr } => {
+                                        match idl.get_str(addr).trim().parse::<f64>() {
+                                            Ok(x) => {
+                                                default = Some(x);
+                                            },
+                                            Err(_e) => return Err(NP_Error::new("Error parsing exponent of decimal default!"))
+                                        }
+                                    },
+                                    _ => { }
+                                }
+                            },
+                            "rounding" => {
+                                match value {
+                                    JS_AST::string { addr } => {
+                                        rounding = NP_RoundMode::from_schema_name(idl.get_str(addr).trim())?;
+                                    },
+                                    _ => { }
+                                }
+                            },
+                            _ => { }
+                        }
+                    }
+                },
+                _ => { }
+            }
+        }
+
+        let mut schema_data: Vec<u8> = Vec::new();
+        schema_data.push(NP_TypeKeys::Decimal as u8);
+
+        
+
+        let exp = if let Some(x) = exp {
+            schema_data.push(x as u8);
+            x
+        } else {
+            return Err(NP_Error::new("Decimal type requires 'exp' property!"))
+        };
+
+        let default = match default {
+            Some(x) => {
+                schema_data.push(1 | rounding.to_schema_bits() << ROUNDING_SHIFT);
+                let value = NP_Dec::default_from_float(x, exp)?;
+                schema_data.extend(value.to_be_bytes().to_vec());
+                Some(NP_Dec::new(value, exp))
+            },
+            _ => {
+                schema_data.push(rounding.to_schema_bits() << ROUNDING_SHIFT);
+                None
+            }
+        };
+
+        schema.push(NP_Parsed_Schema {
+            val: NP_Value_Kind::Fixed(8),
+            i: NP_TypeKeys::Decimal,
+            sortable: true,
+            data: Arc::new(Box::into_raw(Box::new(NP_Dec_Data { exp, default, rounding })) as *const u8)
+        });
+
+        return Ok((true, schema_data, schema))
+
+    }
+
+    fn from_json_to_schema(mut schema: Vec<NP_Parsed_Schema>, json_schema: &Box<NP_JSON>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
+
+        let mut schema_data: Vec<u8> = Vec::new();
+        schema_data.push(NP_TypeKeys::Decimal as u8);
+
+        let exp_value: Option<i64> = match json_schema["exp"] {
+            NP_JSON::Integer(x) => Some(x),
+            // some schema sources emit every number as a string
+            NP_JSON::String(ref x) => x.trim().parse::<i64>().ok(),
+            _ => {
+                return Err(NP_Error::new("Decimal type requires 'exp' property!"))
+            }
+        };
+
+        let exp: u8 = match exp_value {
+            Some(x) if x >= 0 && x <= 255 => x as u8,
+            _ => return Err(NP_Error::new("Decimal 'exp' property must be between 0 and 255!"))
+        };
+        schema_data.push(exp);
+
+        let rounding = match &json_schema["rounding"] {
+            NP_JSON::String(x) => NP_RoundMode::from_schema_name(x.as_str())?,
+            _ => NP_RoundMode::Truncate
+        };
+
+        let default = match json_schema["default"] {
+            NP_JSON::Float(x, _) => {
+                schema_data.push(1 | rounding.to_schema_bits() << ROUNDING_SHIFT);
+                let value = NP_Dec::default_from_float(x, exp)?;
+                schema_data.extend(value.to_be_bytes().to_vec());
+                Some(NP_Dec::new(value, exp))
+            },
+            NP_JSON::Integer(x) => {
+                schema_data.push(1 | rounding.to_schema_bits() << ROUNDING_SHIFT);
+                let value = NP_Dec::default_from_int(x, exp)?;
+                schema_data.extend(value.to_be_bytes().to_vec());
+                Some(NP_Dec::new(value, exp))
+            },
+            NP_JSON::String(ref x) => {
+                // parsed exactly, going through a float would lose the precision string defaults are used for
+                let mut parsed = match x.parse::<NP_Dec>() {
+                    Ok(x) => x,
+                    Err(_e) => return Err(NP_Error::new("Error parsing decimal default!"))
+                };
+                let value = if parsed.exp >= exp {
+                    parsed.shift_exp_rounded(exp, rounding).num
+                } else {
+                    match parsed.to_minor_units(exp) {
+                        Some(x) => x,
+                        None => return Err(NP_Dec::default_overflow(exp))
+                    }
+                };
+                schema_data.push(1 | rounding.to_schema_bits() << ROUNDING_SHIFT);
+                schema_data.extend(value.to_be_bytes().to_vec());
+                Some(NP_Dec::new(value, exp))
+            },
+            _ => {
+                schema_data.push(rounding.to_schema_bits() << ROUNDING_SHIFT);
+                // schema_data.extend(0i64.to_be_bytes().to_vec())
+                None
+            }
+        };
+
+        schema.push(NP_Parsed_Schema {
+            val: NP_Value_Kind::Fixed(8),
+            i: NP_TypeKeys::Decimal,
+            sortable: true,
+            data: Arc::new(Box::into_raw(Box::new(NP_Dec_Data { exp, default, rounding })) as *const u8)
+        });
+
+        return Ok((true, schema_data, schema))
+   
     }
-}
 
+    fn from_bytes_to_schema(mut schema: Vec<NP_Parsed_Schema>, address: usize, bytes: &[u8]) -> (bool, Vec<NP_Parsed_Schema>) {
+        let exp = bytes[address + 1];
 
-/// Converts an NP_Dec into an Int64, rounds to nearest whole number
-/// ```
-/// use no_proto::pointer::dec::NP_Dec;
-/// 
-/// let x = NP_Dec::new(10123, 2);
-/// let y: i64 = x.into();
-/// 
-/// assert_eq!(y, 101i64);
-/// ```
-impl Into<i64> for NP_Dec {
-    fn into(self) -> i64 { 
-        let mut change_value = self.num;
-        let mut loop_val = self.exp;
-        while loop_val > 0 {
-            change_value /= 10;
-            loop_val -= 1;
-        }
-        change_value
-    }
-}
+        let rounding = NP_RoundMode::from_schema_bits(bytes[address + 2] >> ROUNDING_SHIFT);
 
-/// Converts an Int64 into a NP_Dec
-/// ```
-/// use no_proto::pointer::dec::NP_Dec;
-/// 
-/// let x = 101i64;
-/// let y: NP_Dec = x.into();
-/// 
-/// assert_eq!(y.num, x);
-/// ```
-impl Into<NP_Dec> for i64 {
-    fn into(self) -> NP_Dec { 
-        NP_Dec::new(self, 0)
+        let default = if bytes[address + 2] & 1 == 0 {
+            None
+        } else {
+            let mut slice = 0i64.to_be_bytes();
+            slice.copy_from_slice(&bytes[(address + 3)..address + 11]);
+            let value = i64::from_be_bytes(slice);
+            Some(NP_Dec::new(value, exp))
+        };
+
+        schema.push(NP_Parsed_Schema {
+            val: NP_Value_Kind::Fixed(8),
+            i: NP_TypeKeys::Decimal,
+            sortable: true,
+            data: Arc::new(Box::into_raw(Box::new(NP_Dec_Data { exp, default, rounding })) as *const u8)
+        });
+
+        (true, schema)
     }
 }
 
+#[test]
+fn schema_parsing_works_idl() -> Result<(), NP_Error> {
+    let schema = "dec({exp: 3, default: 203.293})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+
+    let schema = "dec({exp: 3})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+    
+    Ok(())
+}
 
+#[test]
+fn schema_parsing_works() -> Result<(), NP_Error> {
+    let schema = "{\"type\":\"decimal\",\"exp\":3,\"default\":203.293}";
+    let factory = crate::NP_Factory::new_json(schema)?;
+    assert_eq!(schema, factory.schema.to_json()?.stringify());
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_json()?.stringify());
 
-fn round_f64(n: f64) -> f64 {
-    let value = if n < 0.0 { n - 0.5 } else { n + 0.5 };
+    let schema = "{\"type\":\"decimal\",\"exp\":3}";
+    let factory = crate::NP_Factory::new_json(schema)?;
+    assert_eq!(schema, factory.schema.to_json()?.stringify());
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_json()?.stringify());
+    
+    Ok(())
+}
 
-    let bounds_value = value.max(core::i64::MIN as f64).min(core::i64::MAX as f64);
+#[test]
+fn default_value_works() -> Result<(), NP_Error> {
+    let schema = "{\"type\":\"decimal\",\"exp\":3,\"default\":203.293}";
+    let factory = crate::NP_Factory::new_json(schema)?;
+    let buffer = factory.new_buffer(None);
+    assert_eq!(buffer.get::<NP_Dec>(&[])?.unwrap(), NP_Dec::new(203293, 3));
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_json()?.stringify());
 
-    (bounds_value as i64) as f64
+    Ok(())
 }
 
-fn round_f32(n: f32) -> f32 {
-    let value = if n < 0.0 { n - 0.5 } else { n + 0.5 };
 
-    let bounds_value = value.max(core::i64::MIN as f32).min(core::i64::MAX as f32);
+#[test]
+fn set_clear_value_and_compaction_works() -> Result<(), NP_Error> {
+    let schema = "{\"type\":\"decimal\",\"exp\": 3}";
+    let factory = crate::NP_Factory::new_json(schema)?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&[], NP_Dec::new(203293, 3))?;
+    assert_eq!(buffer.get::<NP_Dec>(&[])?.unwrap(), NP_Dec::new(203293, 3));
+    buffer.del(&[])?;
+    assert_eq!(buffer.get::<NP_Dec>(&[])?, None);
 
-    (bounds_value as i64) as f32
-}
+    buffer.compact(None)?;
+    assert_eq!(buffer.calc_bytes()?.current_buffer, 6usize);
 
-fn round(n: f64, precision: u32) -> f64 {
-    round_f64(n * 10_u32.pow(precision) as f64) / 10_i32.pow(precision) as f64
+    Ok(())
 }
 
-fn precision(x: f64) -> Option<u32> {
-    for digits in 0..core::f64::DIGITS {
-        if round(x, digits) == x {
-            return Some(digits);
-        }
-    }
-    None
-}
+#[test]
+fn minor_units_works() -> Result<(), NP_Error> {
+    // JPY has no minor units
+    let yen = NP_Dec::from_minor_units(1500, 0);
+    assert_eq!(yen.export(), (1500, 0));
+    assert_eq!(yen.to_minor_units(0), Some(1500));
+    assert_eq!(NP_Dec::new(150050, 2).to_minor_units(0), None);
+    assert_eq!(NP_Dec::new(150000, 2).to_minor_units(0), Some(1500));
+
+    // BHD has 3 minor units
+    let dinar = NP_Dec::from_minor_units(12345, 3);
+    assert_eq!(dinar.to_float(), 12.345f64);
+    assert_eq!(dinar.to_minor_units(3), Some(12345));
+    assert_eq!(dinar.to_minor_units(2), None);
+    assert_eq!(NP_Dec::new(1234, 2).to_minor_units(3), Some(12340));
+
+    // overflow
+    assert_eq!(NP_Dec::new(i64::MAX, 0).to_minor_units(1), None);
+    assert_eq!(NP_Dec::new(i64::MIN, 0).to_minor_units(1), None);
 
-fn round32(n: f32, precision: u32) -> f32 {
-    round_f32(n * 10_u32.pow(precision) as f32) / 10_i32.pow(precision) as f32
+    Ok(())
 }
 
-fn precision32(x: f32) -> Option<u32> {
-    for digits in 0..core::f64::DIGITS {
-        if round32(x, digits) == x {
-            return Some(digits);
-        }
-    }
-    None
-}
+#[test]
+fn repr_eq_works() -> Result<(), NP_Error> {
+    let a = NP_Dec::new(25, 1);
+    let b = NP_Dec::new(2500, 3);
+    assert!(a == b);
+    assert!(!a.repr_eq(&b));
+    assert!(a.repr_eq(&b.with_exp_of(&a)));
 
-/// Converts a NP_Dec into a Float64
-/// ```
-/// use no_proto::pointer::dec::NP_Dec;
-/// 
-/// let x = NP_Dec::new(10023, 2);
-/// let y: f64 = x.into();
-/// 
-/// assert_eq!(y, x.to_float());
-/// ```
-impl Into<f64> for NP_Dec {
-    fn into(self) -> f64 { 
-        self.to_float()
-    }
+    Ok(())
 }
 
-/// Converts a Float64 into a NP_Dec
-/// ```
-/// use no_proto::pointer::dec::NP_Dec;
-/// 
-/// let x = 100.238f64;
-/// let y: NP_Dec = x.into();
-/// 
-/// assert_eq!(y.to_float(), x);
-/// ```
-impl Into<NP_Dec> for f64 {
-    fn into(self) -> NP_Dec { 
-        match precision(self) {
-            Some(x) => {
-                let max_decimal_places = u8::min(x as u8, 18);
-                let mut new_self = self.clone();
-                let mut loop_exp = max_decimal_places;
-                while loop_exp > 0 {
-                    new_self *= 10f64;
-                    loop_exp -= 1;
-                }
-                let value = round_f64(new_self) as i64;
-                return NP_Dec::new(value, max_decimal_places as u8);
-            },
-            None => { // this should be impossible, but just incase
-                let value = round_f64(self) as i64;
-                return NP_Dec::new(value, 0);
-            }
+#[test]
+fn sort_key_bytes_works() -> Result<(), NP_Error> {
+    let values = [i64::MIN, -1_000_000, -1, 0, 1, 25, 1_000_000, i64::MAX];
+
+    for a in values.iter() {
+        for b in values.iter() {
+            let dec_a = NP_Dec::new(*a, 2);
+            let dec_b = NP_Dec::new(*b, 2);
+            assert_eq!(dec_a.partial_cmp(&dec_b), Some(dec_a.sort_key_bytes().cmp(&dec_b.sort_key_bytes())));
         }
     }
-}
 
-/// Converts a NP_Dec into a Float32
-/// ```
-/// use no_proto::pointer::dec::NP_Dec;
-/// 
-/// let x = NP_Dec::new(10023, 2);
-/// let y: f32 = x.into();
-/// 
-/// assert_eq!(y, x.to_float() as f32);
-/// ```
-impl Into<f32> for NP_Dec {
-    fn into(self) -> f32 { 
-        self.to_float() as f32
-    }
-}
+    // matches the bytes written into a buffer
+    let factory = crate::NP_Factory::new("dec({exp: 2})")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&[], NP_Dec::new(-2050, 2))?;
+    let bytes = buffer.read_bytes();
+    assert_eq!(&bytes[(bytes.len() - 8)..], &NP_Dec::new(-2050, 2).sort_key_bytes());
 
-/// Converts a Float32 into a NP_Dec
-/// ```
-/// use no_proto::pointer::dec::NP_Dec;
-/// 
-/// let x = 100.238f32;
-/// let y: NP_Dec = x.into();
-/// 
-/// assert_eq!(y.to_float() as f32, x);
-/// ```
-impl Into<NP_Dec> for f32 {
-    fn into(self) -> NP_Dec { 
-        match precision32(self) {
-            Some(x) => {
-                let max_decimal_places = u8::min(x as u8, 18);
-                let mut new_self = self.clone();
-                let mut loop_exp = max_decimal_places;
-                while loop_exp > 0 {
-                    new_self *= 10f32;
-                    loop_exp -= 1;
-                }
-                let value = round_f32(new_self) as i64;
-                return NP_Dec::new(value, max_decimal_places as u8);
-            },
-            None => { // this should be impossible, but just incase
-                let value = round_f32(self) as i64;
-                return NP_Dec::new(value, 0);
-            }
-        }
-    }
+    Ok(())
 }
 
-impl core::ops::DivAssign for NP_Dec { // a /= b
-    fn div_assign(&mut self, other: NP_Dec) { 
-        if self.exp != other.exp {
-            let other_copy = self.match_exp(&other);
-            self.num = self.num / other_copy.num;
-        } else {
-            self.num = self.num / other.num;
-        }
-    }
+#[test]
+fn from_float_works() -> Result<(), NP_Error> {
+    assert_eq!(NP_Dec::from_float(0.1, 2, NP_RoundMode::HalfEven).export(), (10, 2));
+    assert_eq!(NP_Dec::from_float(0.1, 0, NP_RoundMode::HalfUp).export(), (0, 0));
+    assert_eq!(NP_Dec::from_float(25.0, 3, NP_RoundMode::Truncate).export(), (25000, 3));
+
+    // 2.675 is stored as 2.67499999999999982236431605997495353221893310546875
+    assert_eq!(NP_Dec::from_float(2.675, 2, NP_RoundMode::Truncate).export(), (267, 2));
+    assert_eq!(NP_Dec::from_float(2.675, 2, NP_RoundMode::HalfUp).export(), (268, 2));
+    assert_eq!(NP_Dec::from_float(2.675, 2, NP_RoundMode::HalfEven).export(), (268, 2));
+    assert_eq!(NP_Dec::from_float(-2.675, 2, NP_RoundMode::Truncate).export(), (-267, 2));
+    assert_eq!(NP_Dec::from_float(-2.675, 2, NP_RoundMode::HalfUp).export(), (-268, 2));
+
+    // halves only round to even
+    assert_eq!(NP_Dec::from_float(2.665, 2, NP_RoundMode::HalfUp).export(), (267, 2));
+    assert_eq!(NP_Dec::from_float(2.665, 2, NP_RoundMode::HalfEven).export(), (266, 2));
+    assert_eq!(NP_Dec::from_float(2.6651, 2, NP_RoundMode::HalfEven).export(), (267, 2));
+    assert_eq!(NP_Dec::from_float(2.5, 0, NP_RoundMode::HalfEven).export(), (2, 0));
+    assert_eq!(NP_Dec::from_float(3.5, 0, NP_RoundMode::HalfEven).export(), (4, 0));
+
+    // out of range values saturate
+    assert_eq!(NP_Dec::from_float(1e300, 2, NP_RoundMode::HalfUp).export(), (i64::MAX, 2));
+    assert_eq!(NP_Dec::from_float(-1e300, 2, NP_RoundMode::HalfUp).export(), (i64::MIN, 2));
+    assert_eq!(NP_Dec::from_float(f64::NAN, 2, NP_RoundMode::HalfUp).export(), (0, 2));
+
+    Ok(())
 }
 
-impl core::ops::Div for NP_Dec { // a / b
-    type Output = NP_Dec;
-    fn div(mut self, other: NP_Dec) -> <Self as core::ops::Sub<NP_Dec>>::Output { 
-        if self.exp != other.exp {
-            let other_copy = self.match_exp(&other);
-            self.num = self.num / other_copy.num;
-        } else {
-            self.num = self.num / other.num;
-        }
-        return self;
-    }
+#[test]
+fn apply_null_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("dec({exp: 2, default: 1.50})")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&[], NP_Dec::new(999, 2))?;
+    buffer.set_with_json_apply_null(&[], r#"{"value": null}"#)?;
+    assert_eq!(buffer.get::<NP_Dec>(&[])?, Some(NP_Dec::new(150, 2)));
+
+    let clears = factory.with_null_clears(true);
+    let mut buffer = clears.new_buffer(None);
+    buffer.set(&[], NP_Dec::new(999, 2))?;
+    buffer.set_with_json_apply_null(&[], r#"{"value": null}"#)?;
+    assert_eq!(buffer.get::<NP_Dec>(&[])?, None);
+
+    let factory = crate::NP_Factory::new("dec({exp: 2})")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&[], NP_Dec::new(999, 2))?;
+    buffer.set_with_json(&[], r#"{"value": {"parts": {"num": 5, "exp": 2}}}"#)?;
+    assert_eq!(buffer.get::<NP_Dec>(&[])?, Some(NP_Dec::new(5, 2)));
+    buffer.set_with_json_apply_null(&[], r#"{"value": null}"#)?;
+    assert_eq!(buffer.get::<NP_Dec>(&[])?, None);
+
+    Ok(())
 }
 
-impl core::ops::SubAssign for NP_Dec { // a -= b
-    fn sub_assign(&mut self, other: NP_Dec) { 
-        if self.exp != other.exp {
-            let other_copy = self.match_exp(&other);
-            self.num = self.num - other_copy.num;
-        } else {
-            self.num = self.num - other.num;
-        }
-    }
+#[test]
+fn saturating_shift_exp_works() -> Result<(), NP_Error> {
+    // lowering the exp behaves like shift_exp
+    let mut dec = NP_Dec::new(2203, 3);
+    assert_eq!(dec.saturating_shift_exp(1).export(), (22, 1));
+
+    // no overflow
+    let mut dec = NP_Dec::new(-15, 1);
+    assert_eq!(dec.saturating_shift_exp(4).export(), (-15000, 4));
+
+    // overflow on the first multiply clamps right away
+    let mut dec = NP_Dec::new(i64::MAX / 5, 2);
+    assert_eq!(dec.saturating_shift_exp(10).export(), (i64::MAX, 3));
+
+    let mut dec = NP_Dec::new(-(i64::MAX / 500), 0);
+    assert_eq!(dec.saturating_shift_exp(18).export(), (i64::MIN, 3));
+
+    Ok(())
 }
 
-impl core::ops::Sub for NP_Dec { // a - b
-    type Output = NP_Dec;
-    fn sub(mut self, other: NP_Dec) -> <Self as core::ops::Sub<NP_Dec>>::Output { 
-        if self.exp != other.exp {
-            let other_copy = self.match_exp(&other);
-            self.num = self.num - other_copy.num;
-        } else {
-            self.num = self.num - other.num;
+#[test]
+fn accumulator_works() -> Result<(), NP_Error> {
+    let mut acc = NP_DecAccumulator::new(3);
+
+    // 3000 deltas, +1.5 / -0.25 / +0.125, cycling through exps
+    for i in 0..3000 {
+        match i % 3 {
+            0 => acc.add(NP_Dec::new(15, 1))?,
+            1 => acc.add(NP_Dec::new(-25, 2))?,
+            _ => acc.add(NP_Dec::new(125, 3))?
         }
-        return self;
     }
+
+    // 1000 * (1.5 - 0.25 + 0.125) = 1375
+    assert_eq!(acc.balance().export(), (1375000, 3));
+    assert_eq!(acc.balance(), NP_Dec::new(1375, 0));
+
+    // extra precision is truncated
+    acc.add(NP_Dec::new(-19999, 5))?;
+    assert_eq!(acc.balance().export(), (1374801, 3));
+
+    // overflow while adding
+    let mut acc = NP_DecAccumulator::new(0);
+    acc.add(NP_Dec::new(i64::MAX, 0))?;
+    assert!(acc.add(NP_Dec::new(1, 0)).is_err());
+    assert_eq!(acc.total, i64::MAX);
+
+    // overflow while rescaling
+    let mut acc = NP_DecAccumulator::new(4);
+    assert!(acc.add(NP_Dec::new(i64::MAX / 100, 0)).is_err());
+    assert_eq!(acc.total, 0);
+
+    Ok(())
 }
 
-impl core::ops::AddAssign for NP_Dec { // a += b
-    fn add_assign(&mut self, other: NP_Dec) { 
-        if self.exp != other.exp {
-            let other_copy = self.match_exp(&other);
-            self.num = self.num + other_copy.num;
-        } else {
-            self.num = self.num + other.num;
-        }
-    }
+#[test]
+fn strict_json_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("dec({exp: 2})")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set_with_json(&[], r#"{"value": "2.5"}"#)?;
+    assert_eq!(buffer.get::<NP_Dec>(&[])?, None);
+
+    let factory = factory.with_strict_json(true);
+    let mut buffer = factory.new_buffer(None);
+    assert!(matches!(buffer.set_with_json(&[], r#"{"value": "2.5"}"#), Err(NP_Error::Custom { message }) if message == "expected number or object, got string"));
+    buffer.set_with_json(&[], r#"{"value": {"parts": {"num": 250, "exp": 2}}}"#)?;
+    assert_eq!(buffer.get::<NP_Dec>(&[])?, Some(NP_Dec::new(250, 2)));
+
+    Ok(())
 }
 
-impl core::ops::Add for NP_Dec { // a + b
-    type Output = NP_Dec;
-    fn add(mut self, other: NP_Dec) -> <Self as core::ops::Add<NP_Dec>>::Output { 
-        if self.exp != other.exp {
-            let other_copy = self.match_exp(&other);
-            self.num = self.num + other_copy.num;
-        } else {
-            self.num = self.num + other.num;
-        }
-        return self;
+#[test]
+fn dec_reader_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("struct({fields: {prices: list({of: dec({exp: 2, default: 1.5})}), name: string()}})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    // nothing to read yet
+    assert!(buffer.dec_reader(&["prices"])?.is_none());
+
+    buffer.set(&["prices", "0"], NP_Dec::new(1050, 2))?;
+    buffer.set(&["prices", "1"], NP_Dec::new(-25, 1))?;
+    buffer.set(&["prices", "4"], NP_Dec::new(i64::MAX, 2))?;
+    buffer.set(&["prices", "2"], NP_Dec::new(3, 0))?;
+    buffer.del(&["prices", "2"])?;
+
+    let read: Vec<(usize, Option<NP_Dec>)> = buffer.dec_reader(&["prices"])?.unwrap().collect();
+
+    let mut expected: Vec<(usize, Option<NP_Dec>)> = Vec::new();
+    for (index, _) in read.iter() {
+        expected.push((*index, buffer.get::<NP_Dec>(&["prices", index.to_string().as_str()])?));
     }
+
+    // deleted items fall back to the default just like `get`
+    assert_eq!(read.iter().map(|(index, _)| *index).collect::<Vec<usize>>(), vec![0, 1, 2, 4]);
+    assert_eq!(read[2].1, Some(NP_Dec::new(150, 2)));
+    assert_eq!(read, expected);
+
+    // only lists of decimals can be read
+    assert!(buffer.dec_reader(&["name"]).is_err());
+
+    Ok(())
 }
 
-impl core::ops::MulAssign for NP_Dec { // a *= b
-    fn mul_assign(&mut self, other: NP_Dec) { 
-        if self.exp != other.exp {
-            let other_copy = self.match_exp(&other);
-            self.num = self.num * other_copy.num;
-        } else {
-            self.num = self.num * other.num;
-        }
-    }
+#[test]
+fn compare_without_overflow_works() -> Result<(), NP_Error> {
+    // lining these up multiplies i64::MAX by 10^10, which doesn't fit in an i64
+    let big = NP_Dec::new(i64::MAX, 0);
+    let small = NP_Dec::new(1, 10);
+    assert!(big > small);
+    assert!(small < big);
+    assert!(big != small);
+
+    let neg_big = NP_Dec::new(i64::MIN, 0);
+    assert!(neg_big < small);
+    assert!(small > neg_big);
+
+    // the widest possible exp gap still orders correctly
+    assert!(NP_Dec::new(-1, 0) < NP_Dec::new(i64::MIN, 255));
+    assert!(NP_Dec::new(1, 0) > NP_Dec::new(i64::MAX, 255));
+    assert!(NP_Dec::new(0, 0) == NP_Dec::new(0, 255));
+    assert!(NP_Dec::new(0, 0) > NP_Dec::new(-1, 255));
+
+    // equal values at very different exps
+    assert!(NP_Dec::new(5, 0) == NP_Dec::new(50_000_000_000_000_000, 16));
+    assert_eq!(NP_Dec::new(5, 0).partial_cmp(&NP_Dec::new(50_000_000_000_000_001, 16)), Some(core::cmp::Ordering::Less));
+
+    // 1844674407370955162 * 10 wraps around to exactly 4 in i64, these must not compare equal
+    let wraps = NP_Dec::new(1_844_674_407_370_955_162, 0);
+    assert!(wraps != NP_Dec::new(4, 1));
+    assert!(wraps > NP_Dec::new(4, 1));
+    assert!(NP_Dec::new(4, 1) != wraps);
+
+    Ok(())
 }
 
-impl core::ops::Mul for NP_Dec { // a * b
-    type Output = NP_Dec;
-    fn mul(mut self, other: NP_Dec) -> <Self as core::ops::Mul<NP_Dec>>::Output { 
+#[test]
+fn mantissa_scale_works() -> Result<(), NP_Error> {
+    use core::convert::TryFrom;
 
-        if self.exp != other.exp {
-            let other_copy = self.match_exp(&other);
-            self.num = self.num * other_copy.num;
-        } else {
-            self.num = self.num * other.num;
-        }
-        return self;
-    }
+    // positive scale
+    let num = NP_Dec::try_from((-98765i64, 3i32))?;
+    assert!(num.repr_eq(&NP_Dec::new(-98765, 3)));
+    assert_eq!(<(i64, i32)>::from(num), (-98765, 3));
+
+    // zero scale
+    let num = NP_Dec::try_from((42i64, 0i32))?;
+    assert!(num.repr_eq(&NP_Dec::new(42, 0)));
+    assert_eq!(<(i64, i32)>::from(num), (42, 0));
+
+    // negative scale
+    let num = NP_Dec::try_from((7i64, -2i32))?;
+    assert!(num.repr_eq(&NP_Dec::new(700, 0)));
+
+    // out of range scales
+    assert!(NP_Dec::try_from((1i64, 256i32)).is_err());
+    assert!(NP_Dec::try_from((1i64, -19i32)).is_err());
+    assert!(NP_Dec::try_from((0i64, -100i32)).is_ok());
+
+    Ok(())
 }
 
-impl Default for NP_Dec {
-    fn default() -> Self { 
-        NP_Dec::new(0,0)
-     }
+#[test]
+fn add_preserve_works() -> Result<(), NP_Error> {
+    let coarse = NP_Dec::new(15, 1); // 1.5
+    let fine = NP_Dec::new(5, 3); // 0.005
+
+    // `+` truncates the finer side to the receiver's exp
+    assert!((coarse + fine).repr_eq(&NP_Dec::new(15, 1)));
+
+    // add_preserve keeps every digit regardless of order
+    assert!(coarse.add_preserve(fine).unwrap().repr_eq(&NP_Dec::new(1505, 3)));
+    assert!(fine.add_preserve(coarse).unwrap().repr_eq(&NP_Dec::new(1505, 3)));
+
+    // negatives and matching exps
+    assert!(NP_Dec::new(-25, 2).add_preserve(NP_Dec::new(1, 0)).unwrap().repr_eq(&NP_Dec::new(75, 2)));
+    assert!(NP_Dec::new(10, 1).add_preserve(NP_Dec::new(5, 1)).unwrap().repr_eq(&NP_Dec::new(15, 1)));
+
+    // overflow while rescaling or adding
+    assert_eq!(NP_Dec::new(i64::MAX / 5, 0).add_preserve(NP_Dec::new(1, 1)), None);
+    assert_eq!(NP_Dec::new(i64::MAX, 2).add_preserve(NP_Dec::new(1, 2)), None);
+
+    Ok(())
 }
 
+#[test]
+fn default_overflow_rejected() -> Result<(), NP_Error> {
+    // 10000000000 * 10^9 doesn't fit in an i64
+    assert!(crate::NP_Factory::new_json(r#"{"type":"decimal","exp":9,"default":10000000000}"#).is_err());
+    assert!(crate::NP_Factory::new_json(r#"{"type":"decimal","exp":9,"default":-10000000000}"#).is_err());
+    assert!(crate::NP_Factory::new_json(r#"{"type":"decimal","exp":9,"default":10000000000.5}"#).is_err());
+    assert!(crate::NP_Factory::new_json(r#"{"type":"decimal","exp":30,"default":1}"#).is_err());
+    assert!(crate::NP_Factory::new("dec({exp: 9, default: 10000000000.5})").is_err());
+
+    // the edges still work
+    let factory = crate::NP_Factory::new_json(r#"{"type":"decimal","exp":9,"default":9000000000}"#)?;
+    let buffer = factory.new_buffer(None);
+    assert!(buffer.get::<NP_Dec>(&[])?.unwrap().repr_eq(&NP_Dec::new(9_000_000_000_000_000_000, 9)));
 
-impl<'value> NP_Value<'value> for NP_Dec {
+    let factory = crate::NP_Factory::new_json(r#"{"type":"decimal","exp":30,"default":0}"#)?;
+    let buffer = factory.new_buffer(None);
+    assert!(buffer.get::<NP_Dec>(&[])?.unwrap().repr_eq(&NP_Dec::new(0, 30)));
 
+    Ok(())
+}
 
+#[test]
+fn to_float_checked_works() -> Result<(), NP_Error> {
+    use core::str::FromStr;
 
-    fn type_idx() -> (&'value str, NP_TypeKeys) { ("decimal", NP_TypeKeys::Decimal) }
-    fn self_type_idx(&self) -> (&'value str, NP_TypeKeys) { ("decimal", NP_TypeKeys::Decimal) }
+    // distance between two floats of the same sign in units of least precision
+    fn ulps(a: f64, b: f64) -> u64 {
+        let (a, b) = (a.to_bits() as i64, b.to_bits() as i64);
+        (a - b).unsigned_abs()
+    }
 
-    fn schema_to_json(schema: &Vec<NP_Parsed_Schema>, address: usize)-> Result<NP_JSON, NP_Error> {
-        let mut schema_json = JSMAP::new();
-        schema_json.insert("type".to_owned(), NP_JSON::String(Self::type_idx().0.to_string()));
+    let nums: [i64; 10] = [1, 7, 123456789, 999999999999999, 9007199254740991, 9007199254740993, 3333333333333333, i64::MAX, 5, 1234567890123456789];
 
-        let data = unsafe { &*(*schema[address].data as *const NP_Dec_Data) };
+    for num in nums.iter() {
+        for exp in 0..=15u8 {
+            for sign in [1i64, -1i64].iter() {
+                let dec = NP_Dec::new(num * sign, exp);
+                let (float, exact) = dec.to_float_checked();
+                assert_eq!(float, dec.to_float());
 
-        schema_json.insert("exp".to_owned(), NP_JSON::Integer(data.exp.clone() as i64));
+                // the core float parser is correctly rounded, use it as the reference
+                let mut reference = (num * sign).to_string();
+                reference.push_str("e-");
+                reference.push_str(exp.to_string().as_str());
+                let reference = f64::from_str(reference.as_str()).unwrap();
 
-        if let Some(d) = data.default {
-            let value = NP_Dec::new(d.num.clone(), data.exp.clone());
-            schema_json.insert("default".to_owned(), NP_JSON::Float(value.into()));
+                assert!(ulps(float, reference) <= 1, "{} e-{} was {} ulps off", num * sign, exp, ulps(float, reference));
+                if exact {
+                    assert_eq!(float, reference);
+                }
+            }
         }
-
-        Ok(NP_JSON::Dictionary(schema_json))
-         
     }
 
-    fn default_value(_depth: usize, addr: usize, schema: &Vec<NP_Parsed_Schema>) -> Option<Self> {
-        let data = unsafe { &*(*schema[addr].data as *const NP_Dec_Data) };
-    
-        if let Some(d) = data.default {
-            Some(d.clone())
+    // representable boundaries
+    assert_eq!(NP_Dec::new(9007199254740992, 0).to_float_checked(), (9007199254740992f64, true)); // 2^53
+    assert_eq!(NP_Dec::new(9007199254740991, 0).to_float_checked(), (9007199254740991f64, true)); // 2^53 - 1
+    assert_eq!(NP_Dec::new(9007199254740993, 0).to_float_checked().1, false); // 2^53 + 1
+    assert_eq!(NP_Dec::new(i64::MIN, 0).to_float_checked(), (-9223372036854775808f64, true)); // -2^63
+    assert_eq!(NP_Dec::new(5, 1).to_float_checked(), (0.5, true));
+    assert_eq!(NP_Dec::new(125, 3).to_float_checked(), (0.125, true));
+    assert_eq!(NP_Dec::new(1250000, 7).to_float_checked(), (0.125, true));
+    assert_eq!(NP_Dec::new(3, 1).to_float_checked().1, false);
+    assert_eq!(NP_Dec::new(-75, 2).to_float_checked(), (-0.75, true));
+    assert_eq!(NP_Dec::new(0, 40).to_float_checked(), (0.0, true));
+
+    // 2^-20 written out in full is exact
+    assert_eq!(NP_Dec::new(95367431640625, 20).to_float_checked(), (0.00000095367431640625, true));
+
+    Ok(())
+}
+
+#[test]
+fn to_string_like_float_works() -> Result<(), NP_Error> {
+    let values: [(i64, u8); 12] = [(5, 0), (500, 2), (-5, 0), (25, 1), (250, 2), (-1995, 2), (1, 4), (123456789012345, 5), (0, 0), (0, 3), (1000000, 3), (999999999999999, 0)];
+
+    for (num, exp) in values.iter() {
+        let dec = NP_Dec::new(*num, *exp);
+        let float = dec.to_float();
+
+        assert_eq!(dec.to_string_like_float(), alloc::format!("{:?}", float));
+
+        // to_string on a float is the same, without the trailing `.0`
+        let float_string = float.to_string();
+        if float == (float as i64) as f64 {
+            assert_eq!(dec.to_string_like_float(), float_string + ".0");
         } else {
-            None
+            assert_eq!(dec.to_string_like_float(), float_string);
         }
-          
     }
 
-    fn set_from_json<'set>(_depth: usize, _apply_null: bool, cursor: NP_Cursor, memory: &'set NP_Memory, value: &Box<NP_JSON>) -> Result<(), NP_Error> where Self: 'set + Sized {
-        match &**value {
-            NP_JSON::Dictionary(map) => {
-                if let Some(NP_JSON::Dictionary(parts)) = map.get("parts") {
-                    if let Some(NP_JSON::Integer(num)) = parts.get("num") {
-                        if let Some(NP_JSON::Integer(exp)) = parts.get("exp") {
-                            Self::set_value(cursor, memory, NP_Dec::new(*num, *exp as u8))?;
-                        } else {
-                            return Err(NP_Error::new("Decimal types require a `parts.exp` property!"))
-                        }
-                    } else {
-                        return Err(NP_Error::new("Decimal types require a `parts.num` property!"))
-                    }
-                } else {
-                    return Err(NP_Error::new("Decimal types require a `parts` property!"))
-                }
-            },
-            _ => {}
-        }
+    Ok(())
+}
 
-        Ok(())
+#[test]
+fn schema_rounding_works() -> Result<(), NP_Error> {
+    let schema = "dec({exp: 2, rounding: \"half_even\"})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+
+    let schema = "{\"type\":\"decimal\",\"exp\":2,\"default\":1.5,\"rounding\":\"half_up\"}";
+    let factory = crate::NP_Factory::new_json(schema)?;
+    assert_eq!(schema, factory.schema.to_json()?.stringify());
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_json()?.stringify());
+    assert_eq!(factory2.new_buffer(None).get::<NP_Dec>(&[])?, Some(NP_Dec::new(150, 2)));
+
+    assert!(crate::NP_Factory::new("dec({exp: 2, rounding: \"up\"})").is_err());
+
+    let truncate = crate::NP_Factory::new("dec({exp: 2})")?;
+    let half_even = crate::NP_Factory::new("dec({exp: 2, rounding: \"half_even\"})")?;
+
+    let cases: [(NP_Dec, i64, i64); 5] = [
+        (NP_Dec::new(12345, 3),  1234,  1234), // exact half, 4 is even
+        (NP_Dec::new(12355, 3),  1235,  1236), // exact half, 5 is odd
+        (NP_Dec::new(12346, 3),  1234,  1235),
+        (NP_Dec::new(-12355, 3), -1235, -1236),
+        (NP_Dec::new(999995, 4), 9999, 10000)  // carry into the next digit
+    ];
+
+    for (value, truncated, rounded) in cases.iter() {
+        let mut buffer = truncate.new_buffer(None);
+        buffer.set(&[], *value)?;
+        assert_eq!(buffer.get::<NP_Dec>(&[])?.unwrap().export(), (*truncated, 2));
+
+        let mut buffer = half_even.new_buffer(None);
+        buffer.set(&[], *value)?;
+        assert_eq!(buffer.get::<NP_Dec>(&[])?.unwrap().export(), (*rounded, 2));
     }
 
-    fn set_value<'set>(cursor: NP_Cursor, memory: &'set NP_Memory, value: Self) -> Result<NP_Cursor, NP_Error> where Self: 'set + Sized {
+    Ok(())
+}
 
-        let c_value = || { cursor.get_value(memory) };
+#[test]
+fn from_str_is_strict() -> Result<(), NP_Error> {
+    use core::str::FromStr;
+
+    let accepted: [(&str, i64, u8); 12] = [
+        ("0", 0, 0),
+        ("42", 42, 0),
+        ("+42", 42, 0),
+        ("-42", -42, 0),
+        ("  12.50\t", 1250, 2),
+        ("-0.05", -5, 2),
+        (".5", 5, 1),
+        ("5.", 5, 0),
+        ("007.10", 710, 2),
+        ("-0", 0, 0),
+        ("9223372036854775807", i64::MAX, 0),
+        ("-922337203685477.5808", i64::MIN, 4)
+    ];
+
+    for (input, num, exp) in accepted.iter() {
+        assert_eq!(NP_Dec::from_str(input)?.export(), (*num, *exp), "{}", input);
+    }
 
-        let mut value_address = c_value().get_addr_value() as usize;
+    let rejected: [&str; 18] = [
+        "",
+        "   ",
+        ".",
+        "-",
+        "+",
+        "-.",
+        "1.2.3",
+        "1,000",
+        "1 000",
+        "1e5",
+        "0x10",
+        "--1",
+        "+-1",
+        "12a",
+        "NaN",
+        "inf",
+        "9223372036854775808",
+        "99999999999999999999.5"
+    ];
+
+    for input in rejected.iter() {
+        assert!(NP_Dec::from_str(input).is_err(), "{}", input);
+    }
 
-        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Dec_Data) };
+    Ok(())
+}
 
-        let exp = data.exp;
+#[test]
+fn split_works() -> Result<(), NP_Error> {
+    let cases: [(NP_Dec, i64, i64); 9] = [
+        (NP_Dec::new(2203, 3), 2, 203),
+        (NP_Dec::new(-2203, 3), -2, -203),
+        (NP_Dec::new(500, 2), 5, 0),
+        (NP_Dec::new(-500, 2), -5, 0),
+        (NP_Dec::new(-5, 2), 0, -5),
+        (NP_Dec::new(42, 0), 42, 0),
+        (NP_Dec::new(0, 4), 0, 0),
+        (NP_Dec::new(i64::MIN, 18), -9, -223372036854775808),
+        (NP_Dec::new(i64::MAX, 19), 0, i64::MAX)
+    ];
+
+    for (value, whole, frac) in cases.iter() {
+        let (split_whole, split_frac) = value.split();
+        assert_eq!(split_whole, *whole);
+        assert_eq!(split_frac.export(), (*frac, value.exp));
+        assert!(split_frac + NP_Dec::new(split_whole, 0) == *value);
+    }
 
-        let mut cloned_value = (value).clone();
-        cloned_value.shift_exp(exp);
+    Ok(())
+}
 
-        let i64_value = cloned_value.num;
+#[test]
+fn clamp_works() -> Result<(), NP_Error> {
+    let min = NP_Dec::new(-15, 1);   // -1.5
+    let max = NP_Dec::new(2500, 3);  // 2.500
 
-        if value_address != 0 { // existing value, replace
-            let mut bytes = i64_value.to_be_bytes();
+    // below
+    assert_eq!(NP_Dec::new(-20, 1).clamp(min, max).export(), (-15, 1));
+    assert_eq!(NP_Dec::new(-1501, 3).clamp(min, max).export(), (-1500, 3));
+    assert_eq!(NP_Dec::new(i64::MIN, 0).clamp(min, max).export(), (-1, 0));
 
-            // convert to unsigned
-            bytes[0] = to_unsigned(bytes[0]);
+    // within, including the bounds themselves
+    assert_eq!(NP_Dec::new(-150, 2).clamp(min, max).export(), (-150, 2));
+    assert_eq!(NP_Dec::new(0, 4).clamp(min, max).export(), (0, 4));
+    assert_eq!(NP_Dec::new(25, 1).clamp(min, max).export(), (25, 1));
 
-            let write_bytes = memory.write_bytes();
+    // above
+    assert_eq!(NP_Dec::new(25001, 4).clamp(min, max).export(), (25000, 4));
+    assert_eq!(NP_Dec::new(3, 0).clamp(min, max).export(), (2, 0));
+    assert_eq!(NP_Dec::new(i64::MAX, 2).clamp(min, max).export(), (250, 2));
 
-            // overwrite existing values in buffer
-            for x in 0..bytes.len() {
-                write_bytes[value_address + x] = bytes[x];
-            }
+    // range with a single value
+    assert_eq!(NP_Dec::new(7, 0).clamp(NP_Dec::new(10, 1), NP_Dec::new(100, 2)).export(), (1, 0));
 
-        } else { // new value
+    // bounds between two values at the exp of self round inwards
+    assert_eq!(NP_Dec::new(0, 0).clamp(NP_Dec::new(15, 1), NP_Dec::new(25, 1)).export(), (2, 0));
+    assert_eq!(NP_Dec::new(9, 0).clamp(NP_Dec::new(15, 1), NP_Dec::new(25, 1)).export(), (2, 0));
+    assert_eq!(NP_Dec::new(0, 0).clamp(NP_Dec::new(-25, 1), NP_Dec::new(-15, 1)).export(), (-2, 0));
+    assert_eq!(NP_Dec::new(-9, 0).clamp(NP_Dec::new(-25, 1), NP_Dec::new(-15, 1)).export(), (-2, 0));
 
-            let mut be_bytes = i64_value.to_be_bytes();
+    // nothing at the exp of self fits, the bound keeps it's own exp
+    assert_eq!(NP_Dec::new(0, 0).clamp(NP_Dec::new(12, 1), NP_Dec::new(14, 1)).export(), (12, 1));
+    assert_eq!(NP_Dec::new(9, 0).clamp(NP_Dec::new(12, 1), NP_Dec::new(14, 1)).export(), (14, 1));
 
-            // convert to unsigned
-            be_bytes[0] = to_unsigned(be_bytes[0]);
+    // the bound doesn't fit at the exp of self
+    assert_eq!(NP_Dec::new(i64::MIN, 2).clamp(NP_Dec::new(i64::MIN / 2, 0), NP_Dec::new(0, 0)).export(), (i64::MIN / 2, 0));
 
-            value_address = memory.malloc_borrow(&be_bytes)?;
-            cursor.get_value_mut(memory).set_addr_value(value_address as u32);
+    Ok(())
+}
 
-        }
+#[test]
+#[should_panic(expected = "NP_Dec::clamp called with min greater than max")]
+fn clamp_rejects_inverted_range() {
+    NP_Dec::new(1, 0).clamp(NP_Dec::new(2, 0), NP_Dec::new(10, 1));
+}
 
-        Ok(cursor)
+#[test]
+fn schema_string_numbers_work() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new_json(r#"{"type":"decimal","exp":"3","default":"12.5"}"#)?;
+    assert_eq!(factory.schema.to_json()?.stringify(), r#"{"type":"decimal","exp":3,"default":12.5}"#);
+
+    let buffer = factory.new_buffer(None);
+    assert_eq!(buffer.get::<NP_Dec>(&[])?, Some(NP_Dec::new(12500, 3)));
+
+    assert!(crate::NP_Factory::new_json(r#"{"type":"decimal","exp":"256"}"#).is_err());
+    assert!(crate::NP_Factory::new_json(r#"{"type":"decimal","exp":"two"}"#).is_err());
+    assert!(crate::NP_Factory::new_json(r#"{"type":"decimal","exp":"2","default":"1.2.3"}"#).is_err());
+
+    // string defaults are exact, even past what a float can hold
+    let factory = crate::NP_Factory::new_json(r#"{"type":"decimal","exp":5,"default":"92233720368547.75807"}"#)?;
+    assert!(factory.new_buffer(None).get::<NP_Dec>(&[])?.unwrap().repr_eq(&NP_Dec::new(i64::MAX, 5)));
+    let factory = crate::NP_Factory::new_json(r#"{"type":"decimal","exp":2,"rounding":"half_up","default":"0.125"}"#)?;
+    assert!(factory.new_buffer(None).get::<NP_Dec>(&[])?.unwrap().repr_eq(&NP_Dec::new(13, 2)));
+    assert!(crate::NP_Factory::new_json(r#"{"type":"decimal","exp":5,"default":"92233720368547.75808"}"#).is_err());
+
+    // exp is range checked the same way in both forms
+    for exp in ["256", "-1", "\"256\"", "\"-1\""].iter() {
+        let schema = format!(r#"{{"type":"decimal","exp":{}}}"#, exp);
+        assert!(matches!(crate::NP_Factory::new_json(schema), Err(NP_Error::Custom { message }) if message == "Decimal 'exp' property must be between 0 and 255!"));
     }
 
-    fn into_value(cursor: &NP_Cursor, memory: &'value NP_Memory) -> Result<Option<Self>, NP_Error> where Self: Sized {
+    Ok(())
+}
 
-        let c_value = || { cursor.get_value(memory) };
+#[test]
+fn abs_diff_works() -> Result<(), NP_Error> {
+    let a = NP_Dec::new(1, 0);      // 1
+    let b = NP_Dec::new(5, 3);      // 0.005
 
-        let value_addr = c_value().get_addr_value() as usize;
+    // subtracting at the coarser exp drops the fraction
+    assert_eq!((a - b).export(), (1, 0));
+    assert_eq!(a.abs_diff(&b).export(), (995, 3));
+    assert_eq!(b.abs_diff(&a).export(), (995, 3));
 
-        // empty value
-        if value_addr == 0 {
-            return Ok(None);
-        }
+    assert_eq!(NP_Dec::new(-125, 2).abs_diff(&NP_Dec::new(3, 1)).export(), (155, 2));
+    assert_eq!(NP_Dec::new(42, 1).abs_diff(&NP_Dec::new(4200, 3)).export(), (0, 3));
 
-        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Dec_Data) };
+    // extreme values don't overflow
+    assert_eq!(NP_Dec::new(i64::MAX, 0).abs_diff(&NP_Dec::new(i64::MAX - 7, 0)).export(), (7, 0));
+    assert_eq!(NP_Dec::new(i64::MIN, 0).abs_diff(&NP_Dec::new(i64::MIN, 0)).export(), (0, 0));
+    assert_eq!(NP_Dec::new(i64::MAX, 0).abs_diff(&NP_Dec::new(i64::MIN, 0)).export(), (i64::MAX, 0));
+    assert_eq!(NP_Dec::new(i64::MAX, 0).abs_diff(&NP_Dec::new(1, 255)).export(), (i64::MAX, 255));
 
-        let exp = data.exp;
+    Ok(())
+}
 
-        Ok(match memory.get_8_bytes(value_addr) {
-            Some(x) => {
-                let mut be_bytes = x.clone();
-                be_bytes[0] = to_signed(be_bytes[0]);
-                Some(NP_Dec::new(i64::from_be_bytes(be_bytes), exp))
-            },
-            None => None
-        })
-    }
+#[test]
+fn round_to_magnitude_works() -> Result<(), NP_Error> {
+    assert_eq!(NP_Dec::new(12345, 0).round_to_magnitude(2).export(), (12300, 0));
+    assert_eq!(NP_Dec::new(12350, 0).round_to_magnitude(2).export(), (12400, 0));
+    assert_eq!(NP_Dec::new(-12350, 0).round_to_magnitude(2).export(), (-12400, 0));
+    assert_eq!(NP_Dec::new(12345, 0).round_to_magnitude(0).export(), (12345, 0));
 
-    fn to_json(_depth:usize, cursor: &NP_Cursor, memory: &'value NP_Memory) -> NP_JSON {
+    // decimal places count toward rounding, the exp is kept
+    assert_eq!(NP_Dec::new(1234567, 2).round_to_magnitude(2).export(), (1230000, 2));
+    assert_eq!(NP_Dec::new(1234550, 2).round_to_magnitude(0).export(), (1234600, 2));
 
-        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Dec_Data) };
+    // rounds to zero when the magnitude is bigger than the value
+    assert_eq!(NP_Dec::new(499, 0).round_to_magnitude(3).export(), (0, 0));
+    assert_eq!(NP_Dec::new(i64::MAX, 0).round_to_magnitude(30).export(), (0, 0));
 
-        let exp = data.exp;
+    // saturates instead of overflowing
+    assert_eq!(NP_Dec::new(i64::MAX, 0).round_to_magnitude(1).export(), (i64::MAX, 0));
+    assert_eq!(NP_Dec::new(i64::MIN, 0).round_to_magnitude(1).export(), (i64::MIN, 0));
 
-        match Self::into_value(cursor, memory) {
-            Ok(x) => {
-                match x {
-                    Some(y) => {
-                        let mut object = JSMAP::new();
+    Ok(())
+}
 
-                        let mut parts = JSMAP::new();
+#[test]
+fn to_varint_if_whole_works() -> Result<(), NP_Error> {
+    assert_eq!(NP_Dec::new(42, 0).to_varint_if_whole(), Some(42));
+    assert_eq!(NP_Dec::new(-4200, 2).to_varint_if_whole(), Some(-42));
+    assert_eq!(NP_Dec::new(0, 30).to_varint_if_whole(), Some(0));
+    assert_eq!(NP_Dec::new(i64::MIN, 0).to_varint_if_whole(), Some(i64::MIN));
 
-                        parts.insert("num".to_owned(), NP_JSON::Integer(y.num));
-                        parts.insert("exp".to_owned(), NP_JSON::Integer(exp as i64));
-                        object.insert("value".to_owned(), NP_JSON::Float(y.to_float()));
-                        object.insert("parts".to_owned(), NP_JSON::Dictionary(parts));
-                        
-                        NP_JSON::Dictionary(object)
-                    },
-                    None => {
-                        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Dec_Data) };
+    assert_eq!(NP_Dec::new(4201, 2).to_varint_if_whole(), None);
+    assert_eq!(NP_Dec::new(-1, 3).to_varint_if_whole(), None);
+    assert_eq!(NP_Dec::new(1, 30).to_varint_if_whole(), None);
 
-                        if let Some(d) = data.default {
-                            let mut object = JSMAP::new();
-                            let mut parts = JSMAP::new();
+    Ok(())
+}
 
-                            parts.insert("num".to_owned(), NP_JSON::Integer(d.num.clone()));
-                            parts.insert("exp".to_owned(), NP_JSON::Integer(data.exp as i64));
-                            object.insert("value".to_owned(), NP_JSON::Float(d.to_float()));
-                            object.insert("parts".to_owned(), NP_JSON::Dictionary(parts));
-                            
-                            NP_JSON::Dictionary(object)
-                        } else {
-                            NP_JSON::Null
-                        }
-                         
-                    }
-                }
-            },
-            Err(_e) => {
-                NP_JSON::Null
-            }
-        }
-    }
+#[test]
+fn json_numbers_set_exactly() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("dec({exp: 2})")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set_with_json(&[], r#"{"value": 0.07}"#)?;
+    assert_eq!(buffer.get::<NP_Dec>(&[])?.unwrap().export(), (7, 2));
+    buffer.set_with_json(&[], r#"{"value": 203.293}"#)?;
+    assert_eq!(buffer.get::<NP_Dec>(&[])?.unwrap().export(), (20329, 2));
+    buffer.set_with_json(&[], r#"{"value": -12}"#)?;
+    assert_eq!(buffer.get::<NP_Dec>(&[])?.unwrap().export(), (-1200, 2));
+
+    // more digits than an f64 can hold
+    let factory = crate::NP_Factory::new("dec({exp: 17})")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set_with_json(&[], r#"{"value": 0.12345678901234567}"#)?;
+    assert_eq!(buffer.get::<NP_Dec>(&[])?.unwrap().export(), (12345678901234567, 17));
 
-    fn get_size(_depth:usize, cursor: &NP_Cursor, memory: &NP_Memory) -> Result<usize, NP_Error> {
-        
-        let c_value = || { cursor.get_value(memory) };
+    // the schema rounding mode applies to extra digits
+    let factory = crate::NP_Factory::new(r#"dec({exp: 1, rounding: "half_even"})"#)?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set_with_json(&[], r#"{"value": 0.25}"#)?;
+    assert_eq!(buffer.get::<NP_Dec>(&[])?.unwrap().export(), (2, 1));
 
-        if c_value().get_addr_value() == 0 {
-            Ok(0) 
-        } else {
-            Ok(core::mem::size_of::<i64>())
-        }
-    }
+    let factory = crate::NP_Factory::new("dec({exp: 18})")?;
+    let mut buffer = factory.new_buffer(None);
+    assert!(buffer.set_with_json(&[], r#"{"value": 100}"#).is_err());
 
+    // parsed floats keep their text but compare by value
+    let parsed = crate::json_flex::json_decode(String::from(r#"{"value": 1.50}"#))?;
+    assert_eq!(parsed["value"], NP_JSON::Float(1.5, Some(String::from("1.50"))));
+    assert_eq!(parsed["value"], NP_JSON::Float(1.5, None));
 
-    fn schema_to_idl(schema: &Vec<NP_Parsed_Schema>, address: usize)-> Result<String, NP_Error> {
-        let data = unsafe { &*(*schema[address].data as *const NP_Dec_Data) };
+    Ok(())
+}
 
-        let mut result = String::from("dec({exp: ");
-        result.push_str(data.exp.to_string().as_str());
-        if let Some(x) = data.default {
-            result.push_str(", default: ");
-            result.push_str(x.to_float().to_string().as_str());
-        }
-        result.push_str("})");
-        Ok(result)
-       
+#[test]
+fn is_integer_and_frac_work() -> Result<(), NP_Error> {
+    // whole numbers
+    for value in [NP_Dec::new(5, 0), NP_Dec::new(500, 2), NP_Dec::new(-500, 2), NP_Dec::new(0, 4), NP_Dec::new(i64::MIN, 0)].iter() {
+        assert!(value.is_integer());
+        assert_eq!(value.frac().export(), (0, value.exp));
     }
 
-    fn from_idl_to_schema(mut schema: Vec<NP_Parsed_Schema>, _name: &str, idl: &JS_Schema, args: &Vec<JS_AST>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
+    // pure fractions
+    assert!(!NP_Dec::new(5, 2).is_integer());
+    assert_eq!(NP_Dec::new(5, 2).frac().export(), (5, 2));
+    assert_eq!(NP_Dec::new(-5, 2).frac().export(), (-5, 2));
+    assert_eq!(NP_Dec::new(123, 30).frac().export(), (123, 30));
 
-        let mut exp: Option<u8> = None;
-        let mut default: Option<f64> = None;
-        if args.len() > 0 {
-            match &args[0] {
-                JS_AST::object { properties } => {
-                    for (key, value) in properties {
-                        match idl.get_str(key).trim() {
-                            "exp" => {
-                                match value {
-                                    JS_AST::number { addr } => {
-                                        match idl.get_str(addr).trim().parse::<u8>() {
-                                            Ok(x) => {
-                                                exp = Some(x);
-                                            },
-                                            Err(_e) => return Err(NP_Error::new("Error parsing exponent of decimal value!"))
-                                        }
-                                    },
-                                    _ => { }
-                                }
-                            },
-                            "default" => {
-                                match value {
-                                    JS_AST::number { addr } => {
-                                        match idl.get_str(addr).trim().parse::<f64>() {
-                                            Ok(x) => {
-                                                default = Some(x);
-                                            },
-                                            Err(_e) => return Err(NP_Error::new("Error parsing exponent of decimal default!"))
-                                        }
-                                    },
-                                    _ => { }
-                                }
-                            },
-                            _ => { }
-                        }
-                    }
-                },
-                _ => { }
-            }
-        }
+    // mixed values
+    assert!(!NP_Dec::new(-275, 2).is_integer());
+    assert_eq!(NP_Dec::new(-275, 2).frac().export(), (-75, 2));
+    assert_eq!(NP_Dec::new(1001, 3).frac().export(), (1, 3));
+    assert_eq!(NP_Dec::new(i64::MAX, 18).frac().export(), (i64::MAX % 1_000_000_000_000_000_000, 18));
 
-        let mut schema_data: Vec<u8> = Vec::new();
-        schema_data.push(NP_TypeKeys::Decimal as u8);
+    Ok(())
+}
 
-        
+#[test]
+fn json_exact_text_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("struct({fields: {price: dec({exp: 2}), tax: dec({exp: 3, default: 0.5})}})")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&["price"], NP_Dec::new(10, 2))?;
 
-        let exp = if let Some(x) = exp {
-            schema_data.push(x as u8);
-            x
-        } else {
-            return Err(NP_Error::new("Decimal type requires 'exp' property!"))
-        };
+    let json = buffer.json_encode(&["price"])?;
+    assert_eq!(json["value"]["exact"], NP_JSON::String(String::from("0.10")));
+    assert_eq!(json["value"]["value"], NP_JSON::Float(0.1, None));
 
-        let mult = 10i64.pow(exp as u32);
+    // defaults get the exact text too
+    let json = buffer.json_encode(&["tax"])?;
+    assert_eq!(json["value"]["exact"], NP_JSON::String(String::from("0.500")));
 
-        let default = match default {
-            Some(x) => {
-                schema_data.push(1);
-                let value = x * (mult as f64);
-                schema_data.extend((value as i64).to_be_bytes().to_vec());
-                Some(NP_Dec::new(value as i64, exp))
-            },
-            _ => {
-                schema_data.push(0);
-                None
-            }
-        };
+    // the extra field doesn't get in the way of reading the JSON back
+    let mut copy = factory.new_buffer(None);
+    copy.set_with_json(&["price"], buffer.json_encode(&["price"])?.stringify())?;
+    assert_eq!(copy.get::<NP_Dec>(&["price"])?, Some(NP_Dec::new(10, 2)));
 
-        schema.push(NP_Parsed_Schema {
-            val: NP_Value_Kind::Fixed(8),
-            i: NP_TypeKeys::Decimal,
-            sortable: true,
-            data: Arc::new(Box::into_raw(Box::new(NP_Dec_Data { exp, default })) as *const u8)
-        });
+    Ok(())
+}
 
-        return Ok((true, schema_data, schema))
+#[test]
+fn saturating_int_conversions_work() -> Result<(), NP_Error> {
+    // at the i32 boundaries
+    assert_eq!(NP_Dec::new(i32::MAX as i64, 0).to_i32_saturating(), i32::MAX);
+    assert_eq!(NP_Dec::new(i32::MIN as i64, 0).to_i32_saturating(), i32::MIN);
+    assert_eq!(NP_Dec::new(i32::MAX as i64 * 100 + 99, 2).to_i32_saturating(), i32::MAX);
+    assert_eq!(NP_Dec::new(i32::MIN as i64 * 100 - 99, 2).to_i32_saturating(), i32::MIN);
+
+    // beyond them
+    assert_eq!(NP_Dec::new(i32::MAX as i64 + 1, 0).to_i32_saturating(), i32::MAX);
+    assert_eq!(NP_Dec::new(i32::MIN as i64 - 1, 0).to_i32_saturating(), i32::MIN);
+    assert_eq!(NP_Dec::new(i64::MAX, 0).to_i32_saturating(), i32::MAX);
+    assert_eq!(NP_Dec::new(i64::MIN, 0).to_i32_saturating(), i32::MIN);
+    assert_eq!(NP_Dec::new(i64::MIN, 2).to_i32_saturating(), i32::MIN);
+
+    // fractions truncate toward zero
+    assert_eq!(NP_Dec::new(-99, 2).to_i32_saturating(), 0);
+    assert_eq!(NP_Dec::new(-199, 2).to_i32_saturating(), -1);
+    assert_eq!(NP_Dec::new(199, 2).to_i32_saturating(), 1);
+    assert_eq!(NP_Dec::new(i64::MAX, 30).to_i32_saturating(), 0);
+
+    // i64 covers every whole part
+    assert_eq!(NP_Dec::new(i64::MAX, 0).to_i64_saturating(), i64::MAX);
+    assert_eq!(NP_Dec::new(i64::MIN, 0).to_i64_saturating(), i64::MIN);
+    assert_eq!(NP_Dec::new(i64::MAX, 2).to_i64_saturating(), i64::MAX / 100);
+    assert_eq!(NP_Dec::new(i64::MIN, 2).to_i64_saturating(), i64::MIN / 100);
+    assert_eq!(NP_Dec::new(-250, 2).to_i64_saturating(), -2);
+
+    Ok(())
+}
 
+#[test]
+fn sum_rounded_works() -> Result<(), NP_Error> {
+    // 0.125 forty times is exactly 5.000
+    let items: Vec<NP_Dec> = (0..40).map(|_| NP_Dec::new(125, 3)).collect();
+
+    let total = NP_Dec::sum_rounded(&items, 2, NP_RoundMode::HalfEven).unwrap();
+    assert_eq!(total.export(), (500, 2));
+
+    // rounding each item to 0.12 first drifts by 0.005 per item
+    let mut per_item = NP_Dec::new(0, 2);
+    for item in items.iter() {
+        let mut rounded = *item;
+        rounded.shift_exp_rounded(2, NP_RoundMode::HalfEven);
+        per_item += rounded;
     }
+    assert_eq!(per_item.export(), (480, 2));
+    assert!(total.abs_diff(&NP_Dec::new(5, 0)) < per_item.abs_diff(&NP_Dec::new(5, 0)));
+
+    // mixed exps are summed exactly at the finest one
+    let mixed = [NP_Dec::new(1, 0), NP_Dec::new(5, 1), NP_Dec::new(-4, 3)];
+    assert_eq!(NP_Dec::sum_rounded(&mixed, 3, NP_RoundMode::Truncate).unwrap().export(), (1496, 3));
+    assert_eq!(NP_Dec::sum_rounded(&mixed, 2, NP_RoundMode::HalfUp).unwrap().export(), (150, 2));
+    assert_eq!(NP_Dec::sum_rounded(&mixed, 2, NP_RoundMode::Truncate).unwrap().export(), (149, 2));
+    assert_eq!(NP_Dec::sum_rounded(&mixed, 5, NP_RoundMode::Truncate).unwrap().export(), (149600, 5));
+
+    // negative halves
+    let negative = [NP_Dec::new(-5, 1), NP_Dec::new(-20, 1)];
+    assert_eq!(NP_Dec::sum_rounded(&negative, 0, NP_RoundMode::HalfUp).unwrap().export(), (-3, 0));
+    assert_eq!(NP_Dec::sum_rounded(&negative, 0, NP_RoundMode::HalfEven).unwrap().export(), (-2, 0));
+
+    // partial sums can go past i64 as long as the total fits
+    let big = [NP_Dec::new(i64::MAX, 0), NP_Dec::new(i64::MAX, 0), NP_Dec::new(-i64::MAX, 0)];
+    assert_eq!(NP_Dec::sum_rounded(&big, 0, NP_RoundMode::Truncate).unwrap().export(), (i64::MAX, 0));
+    assert_eq!(NP_Dec::sum_rounded(&[NP_Dec::new(i64::MIN, 0)], 0, NP_RoundMode::Truncate).unwrap().export(), (i64::MIN, 0));
+
+    // overflow
+    assert_eq!(NP_Dec::sum_rounded(&big[..2], 0, NP_RoundMode::Truncate), None);
+    assert_eq!(NP_Dec::sum_rounded(&[NP_Dec::new(i64::MAX, 0)], 1, NP_RoundMode::Truncate), None);
+    assert_eq!(NP_Dec::sum_rounded(&[NP_Dec::new(1, 0), NP_Dec::new(1, 60)], 0, NP_RoundMode::Truncate), None);
+
+    // empty
+    assert_eq!(NP_Dec::sum_rounded(&[], 2, NP_RoundMode::HalfUp).unwrap().export(), (0, 2));
 
-    fn from_json_to_schema(mut schema: Vec<NP_Parsed_Schema>, json_schema: &Box<NP_JSON>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
+    Ok(())
+}
 
-        let mut schema_data: Vec<u8> = Vec::new();
-        schema_data.push(NP_TypeKeys::Decimal as u8);
+#[test]
+fn checked_ops_work() -> Result<(), NP_Error> {
+    let a = NP_Dec::new(2203, 3); // 2.203
+    let b = NP_Dec::new(15, 1); // 1.5
+
+    // same results as the operators when nothing overflows
+    assert!(a.checked_add(b).unwrap().repr_eq(&(a + b)));
+    assert!(a.checked_sub(b).unwrap().repr_eq(&(a - b)));
+    assert!(b.checked_add(a).unwrap().repr_eq(&(b + a)));
+
+    assert!(a.checked_mul(b).unwrap().repr_eq(&(a * b)));
+    assert!(b.checked_mul(a).unwrap().repr_eq(&(b * a)));
+    assert!(a.checked_div(b).unwrap().repr_eq(&(a / b)));
+    assert!(b.checked_div(a).unwrap().repr_eq(&(b / a)));
+    let (c, d) = (NP_Dec::new(-15, 1), NP_Dec::new(2, 0));
+    assert!(c.checked_mul(d).unwrap().repr_eq(&(c * d)));
+    assert!(c.checked_div(d).unwrap().repr_eq(&(c / d)));
+    assert!(d.checked_mul(c).unwrap().repr_eq(&(d * c)));
+    assert!(d.checked_div(c).unwrap().repr_eq(&(d / c)));
+
+    // overflow in the op or while rescaling
+    assert_eq!(NP_Dec::new(i64::MAX, 2).checked_add(NP_Dec::new(1, 2)), None);
+    assert_eq!(NP_Dec::new(i64::MIN, 2).checked_sub(NP_Dec::new(1, 2)), None);
+    assert_eq!(NP_Dec::new(i64::MAX, 0).checked_mul(NP_Dec::new(2, 0)), None);
+    assert_eq!(NP_Dec::new(1, 0).checked_div(NP_Dec::new(0, 3)), None);
+    assert_eq!(NP_Dec::new(1, 2).checked_mul(NP_Dec::new(i64::MAX, 0)), None);
+    assert_eq!(NP_Dec::new(1, 2).checked_div(NP_Dec::new(i64::MAX, 0)), None);
+    assert_eq!(NP_Dec::new(1, 18).checked_add(NP_Dec::new(10, 0)), None);
+
+    // an accumulation loop stops instead of wrapping
+    let mut total = NP_Dec::new(0, 2);
+    let mut overflowed = false;
+    for _ in 0..4 {
+        match total.checked_add(NP_Dec::new(i64::MAX / 3, 2)) {
+            Some(x) => total = x,
+            None => overflowed = true
+        }
+    }
+    assert!(overflowed);
+    assert_eq!(total.export(), ((i64::MAX / 3) * 3, 2));
 
-        let exp: u8;
+    Ok(())
+}
 
-        match json_schema["exp"] {
-            NP_JSON::Integer(x) => {
-                if x > 255 || x < 0 {
-                    return Err(NP_Error::new("Decimal 'exp' property must be between 0 and 255!"))
-                }
-                exp = x as u8;
-                schema_data.push(x as u8);
-            },
-            _ => {
-                return Err(NP_Error::new("Decimal type requires 'exp' property!"))
-            }
-        }
+#[test]
+fn display_from_str_round_trip() -> Result<(), NP_Error> {
+    use core::str::FromStr;
+
+    let values = [
+        NP_Dec::new(2039756, 3),
+        NP_Dec::new(-5, 2),
+        NP_Dec::new(0, 4),
+        NP_Dec::new(100, 0),
+        NP_Dec::new(1, 255),
+        NP_Dec::new(i64::MAX, 18),
+        NP_Dec::new(i64::MIN, 18),
+        NP_Dec::new(i64::MIN, 0),
+    ];
+
+    // the text keeps every digit, so num and exp come back unchanged
+    for value in values.iter() {
+        let text = value.to_string();
+        assert!(NP_Dec::from_str(text.as_str())?.repr_eq(value), "{}", text);
+    }
 
-        let mult = 10i64.pow(exp as u32);
+    assert_eq!(NP_Dec::new(-5, 2).to_string(), "-0.05");
+    assert_eq!(NP_Dec::new(0, 4).to_string(), "0.0000");
+    assert!(NP_Dec::from_str("12.500")?.repr_eq(&NP_Dec::new(12500, 3)));
 
-        let default = match json_schema["default"] {
-            NP_JSON::Float(x) => {
-                schema_data.push(1);
-                let value = x * (mult as f64);
-                schema_data.extend((value as i64).to_be_bytes().to_vec());
-                Some(NP_Dec::new(value as i64, exp))
-            },
-            NP_JSON::Integer(x) => {
-                schema_data.push(1);
-                let value = x * (mult as i64);
-                schema_data.extend((value as i64).to_be_bytes().to_vec());
-                Some(NP_Dec::new(value as i64, exp))
-            },
-            _ => {
-                schema_data.push(0);
-                // schema_data.extend(0i64.to_be_bytes().to_vec())
-                None
-            }
-        };
+    for garbage in ["", "-", ".", "1.2.3", "abc", "1e5", "--1", "9223372036854775808"].iter() {
+        assert!(NP_Dec::from_str(garbage).is_err(), "{}", garbage);
+    }
 
-        schema.push(NP_Parsed_Schema {
-            val: NP_Value_Kind::Fixed(8),
-            i: NP_TypeKeys::Decimal,
-            sortable: true,
-            data: Arc::new(Box::into_raw(Box::new(NP_Dec_Data { exp, default })) as *const u8)
-        });
+    Ok(())
+}
 
-        return Ok((true, schema_data, schema))
-   
+#[test]
+fn shift_exp_rounded_works() -> Result<(), NP_Error> {
+    let cases: [(i64, u8, u8, NP_RoundMode, (i64, u8)); 12] = [
+        (125, 2, 1, NP_RoundMode::Truncate, (12, 1)),
+        (125, 2, 1, NP_RoundMode::HalfUp, (13, 1)),
+        (125, 2, 1, NP_RoundMode::HalfEven, (12, 1)),
+        (135, 2, 1, NP_RoundMode::HalfEven, (14, 1)),
+        (-125, 2, 1, NP_RoundMode::HalfUp, (-13, 1)),
+        (-125, 2, 1, NP_RoundMode::HalfEven, (-12, 1)),
+        (-129, 2, 1, NP_RoundMode::Truncate, (-12, 1)),
+        // digits past the first dropped one break the tie
+        (12501, 4, 1, NP_RoundMode::HalfEven, (13, 1)),
+        // carries
+        (99, 1, 0, NP_RoundMode::HalfUp, (10, 0)),
+        (-995, 3, 2, NP_RoundMode::HalfEven, (-100, 2)),
+        // raising the exp never rounds
+        (15, 1, 3, NP_RoundMode::HalfUp, (1500, 3)),
+        (i64::MAX, 1, 0, NP_RoundMode::HalfUp, (i64::MAX / 10 + 1, 0)),
+    ];
+
+    for (num, exp, new_exp, mode, expected) in cases.iter() {
+        let mut value = NP_Dec::new(*num, *exp);
+        assert_eq!(value.shift_exp_rounded(*new_exp, *mode).export(), *expected);
+        assert_eq!(value.export(), *expected);
     }
 
-    fn from_bytes_to_schema(mut schema: Vec<NP_Parsed_Schema>, address: usize, bytes: &[u8]) -> (bool, Vec<NP_Parsed_Schema>) {
-        let exp = bytes[address + 1];
+    // Truncate matches shift_exp
+    assert!(NP_Dec::new(-2679, 3).shift_exp_rounded(1, NP_RoundMode::Truncate).repr_eq(&NP_Dec::new(-2679, 3).shift_exp(1)));
 
-        let default = if bytes[address + 2] == 0 {
-            None
-        } else {
-            let mut slice = 0i64.to_be_bytes();
-            slice.copy_from_slice(&bytes[(address + 3)..address + 11]);
-            let value = i64::from_be_bytes(slice);
-            Some(NP_Dec::new(value, exp))
-        };
+    Ok(())
+}
 
-        schema.push(NP_Parsed_Schema {
-            val: NP_Value_Kind::Fixed(8),
-            i: NP_TypeKeys::Decimal,
-            sortable: true,
-            data: Arc::new(Box::into_raw(Box::new(NP_Dec_Data { exp, default })) as *const u8)
-        });
+#[test]
+fn to_aligned_string_works() -> Result<(), NP_Error> {
+    // padding
+    assert_eq!(NP_Dec::new(15, 1).to_aligned_string(8, 2), "    1.50");
+    assert_eq!(NP_Dec::new(7, 0).to_aligned_string(5, 0), "    7");
+    assert_eq!(NP_Dec::new(7, 0).to_aligned_string(0, 2), "7.00");
+
+    // rounding, including negatives and carries
+    assert_eq!(NP_Dec::new(2675, 3).to_aligned_string(6, 2), "  2.68");
+    assert_eq!(NP_Dec::new(-2675, 3).to_aligned_string(6, 2), " -2.68");
+    assert_eq!(NP_Dec::new(9996, 3).to_aligned_string(6, 2), " 10.00");
+    assert_eq!(NP_Dec::new(-4, 3).to_aligned_string(6, 2), "  0.00");
+    assert_eq!(NP_Dec::new(5, 1).to_aligned_string(3, 0), "  1");
+
+    // wide values overflow the field instead of being cut
+    assert_eq!(NP_Dec::new(-1234567, 2).to_aligned_string(4, 2), "-12345.67");
+    assert_eq!(NP_Dec::new(i64::MAX, 0).to_aligned_string(4, 2), "9223372036854775807.00");
+
+    // every column has the same width
+    let column: Vec<String> = [NP_Dec::new(1, 0), NP_Dec::new(-25055, 3), NP_Dec::new(100000, 1)].iter().map(|x| x.to_aligned_string(10, 2)).collect();
+    assert!(column.iter().all(|x| x.len() == 10));
 
-        (true, schema)
-    }
+    Ok(())
 }
 
+#[cfg(feature = "serde")]
 #[test]
-fn schema_parsing_works_idl() -> Result<(), NP_Error> {
-    let schema = "dec({exp: 3, default: 203.293})";
-    let factory = crate::NP_Factory::new(schema)?;
-    assert_eq!(schema, factory.schema.to_idl()?);
-    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
-    assert_eq!(schema, factory2.schema.to_idl()?);
+fn serde_round_trip_works() -> Result<(), NP_Error> {
+    let value = NP_Dec::new(2039756, 3);
+    let text = serde_json::to_string(&value).unwrap();
+    assert_eq!(text, r#"{"num":2039756,"exp":3}"#);
+
+    for value in [value, NP_Dec::new(i64::MIN, 18), NP_Dec::new(-5, 0), NP_Dec::new(250, 2)].iter() {
+        let parsed: NP_Dec = serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap();
+        assert!(parsed.repr_eq(value));
+    }
+
+    assert!(serde_json::from_str::<NP_Dec>(r#"{"num":1,"exp":256}"#).is_err());
+    assert!(serde_json::from_str::<NP_Dec>(r#"{"num":1.5,"exp":1}"#).is_err());
 
-    let schema = "dec({exp: 3})";
-    let factory = crate::NP_Factory::new(schema)?;
-    assert_eq!(schema, factory.schema.to_idl()?);
-    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
-    assert_eq!(schema, factory2.schema.to_idl()?);
-    
     Ok(())
 }
 
 #[test]
-fn schema_parsing_works() -> Result<(), NP_Error> {
-    let schema = "{\"type\":\"decimal\",\"exp\":3,\"default\":203.293}";
-    let factory = crate::NP_Factory::new_json(schema)?;
-    assert_eq!(schema, factory.schema.to_json()?.stringify());
-    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
-    assert_eq!(schema, factory2.schema.to_json()?.stringify());
+fn sign_helpers_work() -> Result<(), NP_Error> {
+    for (num, signum) in [(i64::MIN, -1i64), (-1, -1), (0, 0), (1, 1), (i64::MAX, 1)].iter() {
+        let value = NP_Dec::new(*num, 4);
+        assert_eq!(value.signum(), *signum);
+        assert_eq!(value.is_negative(), *signum < 0);
+        assert_eq!(value.is_positive(), *signum > 0);
+
+        // abs keeps the exp and never goes negative
+        let abs = value.abs();
+        assert_eq!(abs.exp, 4);
+        assert!(!abs.is_negative());
+    }
+
+    assert!(NP_Dec::new(-2039756, 3).abs().repr_eq(&NP_Dec::new(2039756, 3)));
+    assert!(NP_Dec::new(i64::MIN, 0).abs().repr_eq(&NP_Dec::new(i64::MAX, 0)));
+    assert!(NP_Dec::new(0, 2).abs().repr_eq(&NP_Dec::new(0, 2)));
+
+    // abs of the difference agrees with abs_diff when the exps match
+    let (a, b) = (NP_Dec::new(995, 2), NP_Dec::new(1205, 2));
+    assert!((a - b).abs().repr_eq(&a.abs_diff(&b)));
 
-    let schema = "{\"type\":\"decimal\",\"exp\":3}";
-    let factory = crate::NP_Factory::new_json(schema)?;
-    assert_eq!(schema, factory.schema.to_json()?.stringify());
-    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
-    assert_eq!(schema, factory2.schema.to_json()?.stringify());
-    
     Ok(())
 }
 
 #[test]
-fn default_value_works() -> Result<(), NP_Error> {
-    let schema = "{\"type\":\"decimal\",\"exp\":3,\"default\":203.293}";
-    let factory = crate::NP_Factory::new_json(schema)?;
-    let buffer = factory.new_buffer(None);
-    assert_eq!(buffer.get::<NP_Dec>(&[])?.unwrap(), NP_Dec::new(203293, 3));
-    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
-    assert_eq!(schema, factory2.schema.to_json()?.stringify());
+fn neg_rem_work() -> Result<(), NP_Error> {
+    // neg keeps the exp and saturates at the bottom of the range
+    assert!((-NP_Dec::new(2049, 2)).repr_eq(&NP_Dec::new(-2049, 2)));
+    assert!((-NP_Dec::new(-2049, 2)).repr_eq(&NP_Dec::new(2049, 2)));
+    assert!((-NP_Dec::new(0, 2)).repr_eq(&NP_Dec::new(0, 2)));
+    assert!((-NP_Dec::new(i64::MIN, 1)).repr_eq(&NP_Dec::new(i64::MAX, 1)));
+
+    // matching exps
+    assert!((NP_Dec::new(2049, 2) % NP_Dec::new(100, 2)).repr_eq(&NP_Dec::new(49, 2)));
+
+    // right side is shifted to the exp of the left side
+    assert!((NP_Dec::new(2049, 2) % NP_Dec::new(1, 0)).repr_eq(&NP_Dec::new(49, 2)));
+    assert!((NP_Dec::new(2049, 2) % NP_Dec::new(50, 1)).repr_eq(&NP_Dec::new(49, 2)));
+
+    // sign follows the left operand, like i64
+    assert!((NP_Dec::new(-2049, 2) % NP_Dec::new(100, 2)).repr_eq(&NP_Dec::new(-49, 2)));
+    assert!((NP_Dec::new(2049, 2) % NP_Dec::new(-100, 2)).repr_eq(&NP_Dec::new(49, 2)));
+
+    let mut value = NP_Dec::new(2049, 2);
+    value %= NP_Dec::new(5, 0);
+    assert!(value.repr_eq(&NP_Dec::new(49, 2)));
 
     Ok(())
 }
 
+#[test]
+fn eq_ord_hash_work() -> Result<(), NP_Error> {
+    use core::hash::{Hash, Hasher};
+    use alloc::collections::BTreeMap;
+
+    // FNV-1a, only needs to be stable inside this test
+    struct TestHasher(u64);
+    impl Hasher for TestHasher {
+        fn finish(&self) -> u64 { self.0 }
+        fn write(&mut self, bytes: &[u8]) {
+            for b in bytes {
+                self.0 ^= *b as u64;
+                self.0 = self.0.wrapping_mul(0x100000001b3);
+            }
+        }
+    }
+
+    let hash = |value: &NP_Dec| {
+        let mut hasher = TestHasher(0xcbf29ce484222325);
+        value.hash(&mut hasher);
+        hasher.finish()
+    };
+
+    // equal values hash the same regardless of exp
+    let equal = [NP_Dec::new(202, 1), NP_Dec::new(2020, 2), NP_Dec::new(2020000, 5)];
+    for a in equal.iter() {
+        for b in equal.iter() {
+            assert_eq!(a.cmp(b), core::cmp::Ordering::Equal);
+            assert_eq!(hash(a), hash(b));
+        }
+    }
+    assert_eq!(hash(&NP_Dec::new(0, 0)), hash(&NP_Dec::new(0, 7)));
+    assert_ne!(hash(&NP_Dec::new(202, 1)), hash(&NP_Dec::new(2021, 2)));
+
+    // sorting works across exps, including values that can't be lined up in an i64
+    let mut values = vec![NP_Dec::new(1, 0), NP_Dec::new(-25, 1), NP_Dec::new(1, 21), NP_Dec::new(i64::MIN, 0), NP_Dec::new(i64::MAX, 18)];
+    values.sort();
+    let sorted: Vec<(i64, u8)> = values.iter().map(|v| v.export()).collect();
+    assert_eq!(sorted, vec![(i64::MIN, 0), (-25, 1), (1, 21), (1, 0), (i64::MAX, 18)]);
+
+    // equal values share a map key
+    let mut map: BTreeMap<NP_Dec, u8> = BTreeMap::new();
+    map.insert(NP_Dec::new(202, 1), 1);
+    map.insert(NP_Dec::new(2020, 2), 2);
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.get(&NP_Dec::new(20200, 3)), Some(&2));
+
+    Ok(())
+}
 
 #[test]
-fn set_clear_value_and_compaction_works() -> Result<(), NP_Error> {
-    let schema = "{\"type\":\"decimal\",\"exp\": 3}";
-    let factory = crate::NP_Factory::new_json(schema)?;
-    let mut buffer = factory.new_buffer(None);
-    buffer.set(&[], NP_Dec::new(203293, 3))?;
-    assert_eq!(buffer.get::<NP_Dec>(&[])?.unwrap(), NP_Dec::new(203293, 3));
-    buffer.del(&[])?;
-    assert_eq!(buffer.get::<NP_Dec>(&[])?, None);
+fn checked_rem_works() -> Result<(), NP_Error> {
+    // same as the operator when the exps match
+    for (a, b) in [(2049i64, 100i64), (-2049, 100), (2049, -100), (7, 7), (0, 3)].iter() {
+        let (a, b) = (NP_Dec::new(*a, 2), NP_Dec::new(*b, 2));
+        assert!(a.checked_rem(b).unwrap().repr_eq(&(a % b)));
+    }
 
-    buffer.compact(None)?;
-    assert_eq!(buffer.calc_bytes()?.current_buffer, 6usize);
+    // the right side isn't truncated away
+    assert!(NP_Dec::new(2049, 2).checked_rem(NP_Dec::new(5, 3)).unwrap().repr_eq(&NP_Dec::new(0, 2)));
+    assert!(NP_Dec::new(100, 2).checked_rem(NP_Dec::new(15, 3)).unwrap().repr_eq(&NP_Dec::new(1, 2))); // 0.010 at 2 places
+    assert!(NP_Dec::new(2049, 2).checked_rem(NP_Dec::new(3, 1)).unwrap().repr_eq(&NP_Dec::new(9, 2))); // 20.49 % 0.3 = 0.09
+
+    // cases the operator panics on
+    assert_eq!(NP_Dec::new(2049, 2).checked_rem(NP_Dec::new(0, 2)), None);
+    assert!(NP_Dec::new(i64::MIN, 0).checked_rem(NP_Dec::new(-1, 0)).unwrap().repr_eq(&NP_Dec::new(0, 0)));
+
+    // lining up values far apart in exp
+    assert!(NP_Dec::new(i64::MAX, 0).checked_rem(NP_Dec::new(1, 2)).unwrap().repr_eq(&NP_Dec::new(0, 0)));
+    assert_eq!(NP_Dec::new(i64::MAX, 0).checked_rem(NP_Dec::new(1, 40)), None);
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,912 @@
+//! Wide decimal type backed by an i128, for values that don't fit in `dec`
+//!
+//! Works the same as [`dec`](../dec/index.html) but the `num` is an i128, stored as 16 sortable bytes.  Use it when you need more digits than an i64 can hold at your precision.
+//!
+//! ```
+//! use no_proto::error::NP_Error;
+//! use no_proto::NP_Factory;
+//! use no_proto::pointer::dec128::NP_Dec128;
+//!
+//! let factory: NP_Factory = NP_Factory::new("dec128({exp: 20})")?;
+//!
+//! let mut new_buffer = factory.new_buffer(None);
+//! new_buffer.set(&[], NP_Dec128::new(123456789_000000000000000000001, 20))?;
+//!
+//! assert_eq!(new_buffer.get::<NP_Dec128>(&[])?.unwrap().to_string(), "1234567890.00000000000000000001");
+//!
+//! # Ok::<(), NP_Error>(())
+//! ```
+//!
+
+use crate::schema::NP_Value_Kind;
+use alloc::{string::String, sync::Arc};
+use alloc::prelude::v1::Box;
+use crate::{idl::{JS_AST, JS_Schema}, schema::NP_Dec128_Data, utils::to_signed};
+use crate::schema::{NP_Parsed_Schema};
+use alloc::vec::Vec;
+use crate::utils::to_unsigned;
+use crate::json_flex::{JSMAP, NP_JSON};
+use crate::schema::{NP_Schema, NP_TypeKeys};
+use crate::{pointer::NP_Value, error::NP_Error};
+use crate::pointer::dec::NP_Dec;
+use alloc::borrow::ToOwned;
+use super::{NP_Cursor};
+use crate::NP_Memory;
+use alloc::string::ToString;
+
+/// Holds wide fixed point decimal data.
+///
+/// Check out documentation [here](../dec128/index.html).
+///
+#[derive(Clone, Copy, Debug)]
+pub struct NP_Dec128 {
+    /// The number being stored, does not include decimal point data
+    pub num: i128,
+    /// The exponent of this number
+    pub exp: u8
+}
+
+impl<'value> super::NP_Scalar<'value> for NP_Dec128 {
+    fn schema_default(schema: &NP_Parsed_Schema) -> Option<Self> where Self: Sized {
+        let data = unsafe { &*(*schema.data as *const NP_Dec128_Data) };
+        Some(NP_Dec128 { exp: data.exp, num: 0})
+    }
+
+    fn np_max_value(cursor: &NP_Cursor, memory: &NP_Memory) -> Option<Self> {
+        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Dec128_Data) };
+        Some(NP_Dec128::new(i128::MAX, data.exp))
+    }
+
+    fn np_min_value(cursor: &NP_Cursor, memory: &NP_Memory) -> Option<Self> {
+        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Dec128_Data) };
+        Some(NP_Dec128::new(i128::MIN, data.exp))
+    }
+}
+
+impl NP_Dec128 {
+
+    /// The largest `exp` a `dec128` schema can have, `10^38` is the largest power of ten an i128 can hold.
+    pub const MAX_EXP: u8 = 38;
+
+    /// Generate a new NP_Dec128 value
+    ///
+    /// First argument is the `num` value, second is the `exp` or exponent.
+    ///
+    /// ```
+    /// use no_proto::pointer::dec128::NP_Dec128;
+    ///
+    /// let my_num = NP_Dec128::new(2203, 3); // value is 2.203
+    /// assert_eq!(my_num.export(), (2203, 3));
+    /// ```
+    pub fn new(num: i128, exp: u8) -> Self {
+        NP_Dec128 { num, exp }
+    }
+
+    /// Get the `num` and `exp` values of this NP_Dec128
+    pub fn export(&self) -> (i128, u8) {
+        (self.num, self.exp)
+    }
+
+    /// Convert an NP_Dec128 into a native floating point value.
+    ///
+    /// DO NOT use this to perform calculations, only to export/display the value.
+    ///
+    pub fn to_float(&self) -> f64 {
+        let m = self.num as f64;
+        let mut step = self.exp;
+        let mut s = 1f64;
+        while step > 0 {
+            s *= 10f64;
+            step -= 1;
+        }
+        m / s
+    }
+
+    /// Shift the exponent of this NP_Dec128 to a new value.
+    ///
+    /// Works like `NP_Dec::shift_exp`, lowering the `exp` truncates precision.  If raising the `exp` would overflow the i128 value `num` saturates at `i128::MAX` (or `i128::MIN` for negative values) instead of wrapping, use `checked_shift_exp` to catch that.
+    ///
+    /// ```
+    /// use no_proto::pointer::dec128::NP_Dec128;
+    ///
+    /// let mut my_num = NP_Dec128::new(2203, 3); // value is 2.203
+    /// my_num.shift_exp(1);
+    /// assert_eq!(my_num.export(), (22, 1));
+    ///
+    /// let mut big_num = NP_Dec128::new(i128::MAX, 0);
+    /// big_num.shift_exp(2);
+    /// assert_eq!(big_num.export(), (i128::MAX, 2));
+    /// ```
+    pub fn shift_exp(&mut self, new_exp: u8) -> NP_Dec128 {
+        *self = match self.checked_shift_exp(new_exp) {
+            Some(x) => x,
+            None => NP_Dec128::new(if self.num < 0 { i128::MIN } else { i128::MAX }, new_exp)
+        };
+
+        *self
+    }
+
+    /// Get a copy of this NP_Dec128 at a new `exp`, returns `None` if raising the `exp` overflows the i128 value.
+    ///
+    /// ```
+    /// use no_proto::pointer::dec128::NP_Dec128;
+    ///
+    /// assert_eq!(NP_Dec128::new(2203, 3).checked_shift_exp(5).unwrap().export(), (220300, 5));
+    /// assert_eq!(NP_Dec128::new(2203, 3).checked_shift_exp(1).unwrap().export(), (22, 1));
+    /// assert_eq!(NP_Dec128::new(i128::MAX, 0).checked_shift_exp(1), None);
+    /// ```
+    pub fn checked_shift_exp(&self, new_exp: u8) -> Option<NP_Dec128> {
+        let mut num = self.num;
+
+        if new_exp > self.exp {
+            for _ in self.exp..new_exp {
+                num = num.checked_mul(10)?;
+            }
+        } else {
+            for _ in new_exp..self.exp {
+                num /= 10;
+            }
+        }
+
+        Some(NP_Dec128::new(num, new_exp))
+    }
+
+    /// Checked multiplication, returns `None` instead of overflowing.
+    ///
+    /// Gives the same result as the `*` operator, the product keeps the `exp` of `self`.
+    ///
+    /// ```
+    /// use no_proto::pointer::dec128::NP_Dec128;
+    ///
+    /// assert_eq!(NP_Dec128::new(25, 1).checked_mul(NP_Dec128::new(150, 2)).unwrap().export(), (37, 1)); // 2.5 * 1.5
+    /// assert_eq!(NP_Dec128::new(i128::MAX, 0).checked_mul(NP_Dec128::new(2, 0)), None);
+    /// ```
+    pub fn checked_mul(self, other: NP_Dec128) -> Option<NP_Dec128> {
+        let mut num = self.num.checked_mul(other.num)?;
+
+        for _ in 0..other.exp {
+            num /= 10;
+        }
+
+        Some(NP_Dec128::new(num, self.exp))
+    }
+
+    /// Checked division, returns `None` if `other` is zero or the result overflows.
+    ///
+    /// Gives the same result as the `/` operator, the quotient keeps the `exp` of `self`.
+    ///
+    /// ```
+    /// use no_proto::pointer::dec128::NP_Dec128;
+    ///
+    /// assert_eq!(NP_Dec128::new(100, 1).checked_div(NP_Dec128::new(40, 1)).unwrap().export(), (25, 1)); // 10.0 / 4.0
+    /// assert_eq!(NP_Dec128::new(1, 0).checked_div(NP_Dec128::new(0, 0)), None);
+    /// assert_eq!(NP_Dec128::new(i128::MAX, 0).checked_div(NP_Dec128::new(1, 1)), None);
+    /// ```
+    pub fn checked_div(self, other: NP_Dec128) -> Option<NP_Dec128> {
+        if other.num == 0 {
+            return None;
+        }
+
+        // trailing zeros in `other` don't change the value, dropping them keeps the scaled dividend small
+        let (mut divisor, mut divisor_exp) = (other.num, other.exp);
+        while divisor_exp > 0 && divisor % 10 == 0 {
+            divisor /= 10;
+            divisor_exp -= 1;
+        }
+
+        let mut num = self.num;
+        for _ in 0..divisor_exp {
+            num = num.checked_mul(10)?;
+        }
+
+        Some(NP_Dec128::new(num.checked_div(divisor)?, self.exp))
+    }
+
+    /// Get a copy of another NP_Dec128 shifted to the `exp` of this one.
+    pub fn match_exp(&self, other: &NP_Dec128) -> NP_Dec128 {
+        let mut other_copy = other.clone();
+
+        if other_copy.exp != self.exp {
+            other_copy.shift_exp(self.exp);
+        }
+
+        other_copy
+    }
+
+    /// Parse an exact decimal string like `"-1234.5678"` at a given `exp`.
+    ///
+    /// Digits past `exp` are truncated.  Returns an error if the string isn't a decimal number or the value doesn't fit.
+    ///
+    /// ```
+    /// use no_proto::pointer::dec128::NP_Dec128;
+    /// use no_proto::error::NP_Error;
+    ///
+    /// assert_eq!(NP_Dec128::from_str_exp("-12.3456", 2)?.export(), (-1234, 2));
+    /// assert_eq!(NP_Dec128::from_str_exp("5", 3)?.export(), (5000, 3));
+    /// assert!(NP_Dec128::from_str_exp("5.a", 3).is_err());
+    ///
+    /// # Ok::<(), NP_Error>(())
+    /// ```
+    pub fn from_str_exp(value: &str, exp: u8) -> Result<NP_Dec128, NP_Error> {
+        let value = value.trim();
+
+        let (negative, digits) = match value.strip_prefix('-') {
+            Some(x) => (true, x),
+            None => (false, value)
+        };
+
+        let (whole, frac) = match digits.find('.') {
+            Some(idx) => (&digits[..idx], &digits[(idx + 1)..]),
+            None => (digits, "")
+        };
+
+        if (whole.len() == 0 && frac.len() == 0) || !whole.bytes().chain(frac.bytes()).all(|x| x.is_ascii_digit()) {
+            let mut err = String::from("Can't parse decimal value: ");
+            err.push_str(value);
+            return Err(NP_Error::new(err))
+        }
+
+        let overflow = || NP_Error::new("Decimal value is too large for dec128!");
+
+        let mut num: i128 = 0;
+        let frac_bytes = frac.as_bytes();
+
+        for digit in whole.bytes().chain((0..(exp as usize)).map(|idx| *frac_bytes.get(idx).unwrap_or(&b'0'))) {
+            num = num.checked_mul(10).ok_or_else(overflow)?;
+            num = num.checked_add((digit - b'0') as i128).ok_or_else(overflow)?;
+        }
+
+        Ok(NP_Dec128::new(if negative { -num } else { num }, exp))
+    }
+}
+
+/// Widen an NP_Dec into an NP_Dec128, this never loses precision.
+impl From<NP_Dec> for NP_Dec128 {
+    fn from(value: NP_Dec) -> Self {
+        NP_Dec128::new(value.num as i128, value.exp)
+    }
+}
+
+/// Print the exact decimal value of an NP_Dec128
+///
+/// ```
+/// use no_proto::pointer::dec128::NP_Dec128;
+///
+/// assert_eq!(NP_Dec128::new(-5, 2).to_string(), "-0.05");
+/// assert_eq!(NP_Dec128::new(i128::MAX, 0).to_string(), "170141183460469231731687303715884105727");
+/// ```
+impl core::fmt::Display for NP_Dec128 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let digits = self.num.unsigned_abs().to_string();
+        let exp = self.exp as usize;
+
+        if self.num < 0 {
+            f.write_str("-")?;
+        }
+
+        if exp == 0 {
+            return f.write_str(&digits);
+        }
+
+        if digits.len() > exp {
+            let (whole, frac) = digits.split_at(digits.len() - exp);
+            f.write_str(whole)?;
+            f.write_str(".")?;
+            f.write_str(frac)
+        } else {
+            f.write_str("0.")?;
+            for _ in digits.len()..exp {
+                f.write_str("0")?;
+            }
+            f.write_str(&digits)
+        }
+    }
+}
+
+/// Check if two NP_Dec128 are equal, values with different `exp` are compared exactly
+impl core::cmp::PartialEq for NP_Dec128 {
+    fn eq(&self, other: &NP_Dec128) -> bool {
+        self.cmp_aligned(other) == core::cmp::Ordering::Equal
+    }
+}
+
+/// Compare two NP_Dec128, values with different `exp` are compared exactly
+///
+/// ```
+/// use no_proto::pointer::dec128::NP_Dec128;
+///
+/// assert!(NP_Dec128::new(20201, 2) > NP_Dec128::new(202, 0));
+/// assert!(NP_Dec128::new(20201, 2) == NP_Dec128::new(2020100, 4));
+///
+/// // lining these up doesn't fit in an i128
+/// assert!(NP_Dec128::new(i128::MAX, 0) > NP_Dec128::new(1, 38));
+/// ```
+impl core::cmp::PartialOrd for NP_Dec128 {
+    fn partial_cmp(&self, other: &NP_Dec128) -> Option<core::cmp::Ordering> {
+        Some(self.cmp_aligned(other))
+    }
+}
+
+impl NP_Dec128 {
+    /// Compare the values of two NP_Dec128 without losing precision or overflowing.
+    fn cmp_aligned(&self, other: &NP_Dec128) -> core::cmp::Ordering {
+        if self.exp == other.exp {
+            return self.num.cmp(&other.num);
+        }
+
+        // scale the side with the smaller exp up to the larger exp
+        let (low, high, flipped) = if self.exp < other.exp { (self, other, false) } else { (other, self, true) };
+
+        let mut scaled = low.num;
+        let mut step = high.exp - low.exp;
+        let mut overflowed = false;
+
+        while step > 0 && scaled != 0 {
+            match scaled.checked_mul(10) {
+                Some(x) => scaled = x,
+                None => { overflowed = true; break; }
+            }
+            step -= 1;
+        }
+
+        let ordering = if overflowed {
+            // scaled is bigger than any i128 could be, only the sign matters
+            if scaled > 0 { core::cmp::Ordering::Greater } else { core::cmp::Ordering::Less }
+        } else {
+            scaled.cmp(&high.num)
+        };
+
+        if flipped { ordering.reverse() } else { ordering }
+    }
+}
+
+impl core::ops::Add for NP_Dec128 { // a + b
+    type Output = NP_Dec128;
+    fn add(mut self, other: NP_Dec128) -> NP_Dec128 {
+        self.num += self.match_exp(&other).num;
+        self
+    }
+}
+
+impl core::ops::AddAssign for NP_Dec128 { // a += b
+    fn add_assign(&mut self, other: NP_Dec128) {
+        self.num += self.match_exp(&other).num;
+    }
+}
+
+impl core::ops::Sub for NP_Dec128 { // a - b
+    type Output = NP_Dec128;
+    fn sub(mut self, other: NP_Dec128) -> NP_Dec128 {
+        self.num -= self.match_exp(&other).num;
+        self
+    }
+}
+
+impl core::ops::SubAssign for NP_Dec128 { // a -= b
+    fn sub_assign(&mut self, other: NP_Dec128) {
+        self.num -= self.match_exp(&other).num;
+    }
+}
+
+/// Multiply two NP_Dec128, the result keeps the `exp` of the left side
+///
+/// # Panics
+///
+/// Panics if the product of the two `num` values doesn't fit in an i128, `checked_mul` returns `None` instead.
+///
+/// ```
+/// use no_proto::pointer::dec128::NP_Dec128;
+///
+/// let result = NP_Dec128::new(25, 1) * NP_Dec128::new(150, 2); // 2.5 * 1.5
+/// assert_eq!(result.export(), (37, 1)); // 3.7
+/// ```
+impl core::ops::Mul for NP_Dec128 { // a * b
+    type Output = NP_Dec128;
+    fn mul(self, other: NP_Dec128) -> NP_Dec128 {
+        match self.checked_mul(other) {
+            Some(x) => x,
+            None => panic!("NP_Dec128 multiplication overflowed")
+        }
+    }
+}
+
+impl core::ops::MulAssign for NP_Dec128 { // a *= b
+    fn mul_assign(&mut self, other: NP_Dec128) {
+        *self = *self * other;
+    }
+}
+
+/// Divide two NP_Dec128, the result keeps the `exp` of the left side
+///
+/// # Panics
+///
+/// Panics if the right side is zero or the scaled left side doesn't fit in an i128, `checked_div` returns `None` instead.
+///
+/// ```
+/// use no_proto::pointer::dec128::NP_Dec128;
+///
+/// let result = NP_Dec128::new(100, 1) / NP_Dec128::new(40, 1); // 10.0 / 4.0
+/// assert_eq!(result.export(), (25, 1)); // 2.5
+/// ```
+impl core::ops::Div for NP_Dec128 { // a / b
+    type Output = NP_Dec128;
+    fn div(self, other: NP_Dec128) -> NP_Dec128 {
+        match self.checked_div(other) {
+            Some(x) => x,
+            None => panic!("NP_Dec128 division by zero or overflow")
+        }
+    }
+}
+
+impl core::ops::DivAssign for NP_Dec128 { // a /= b
+    fn div_assign(&mut self, other: NP_Dec128) {
+        *self = *self / other;
+    }
+}
+
+impl Default for NP_Dec128 {
+    fn default() -> Self {
+        NP_Dec128::new(0, 0)
+    }
+}
+
+impl NP_Dec128 {
+    /// JSON representation of a value, `num` is a string since it may not fit in a JSON number
+    fn json_value(value: &NP_Dec128, exp: u8) -> NP_JSON {
+        let mut object = JSMAP::new();
+        let mut parts = JSMAP::new();
+
+        parts.insert("num".to_owned(), NP_JSON::String(value.num.to_string()));
+        parts.insert("exp".to_owned(), NP_JSON::Integer(exp as i64));
+        object.insert("value".to_owned(), NP_JSON::Float(value.to_float(), None));
+        object.insert("parts".to_owned(), NP_JSON::Dictionary(parts));
+
+        NP_JSON::Dictionary(object)
+    }
+
+    /// Schema bytes are the type key, exp, default flag and (optional) 16 default bytes
+    fn schema_bytes(exp: u8, default: &Option<NP_Dec128>) -> Vec<u8> {
+        let mut schema_data: Vec<u8> = Vec::new();
+        schema_data.push(NP_TypeKeys::Decimal128 as u8);
+        schema_data.push(exp);
+
+        match default {
+            Some(x) => {
+                schema_data.push(1);
+                schema_data.extend_from_slice(&x.num.to_be_bytes());
+            },
+            None => {
+                schema_data.push(0);
+            }
+        }
+
+        schema_data
+    }
+
+    fn push_schema(mut schema: Vec<NP_Parsed_Schema>, exp: u8, default: Option<NP_Dec128>) -> Vec<NP_Parsed_Schema> {
+        schema.push(NP_Parsed_Schema {
+            val: NP_Value_Kind::Fixed(16),
+            i: NP_TypeKeys::Decimal128,
+            sortable: true,
+            data: Arc::new(Box::into_raw(Box::new(NP_Dec128_Data { exp, default })) as *const u8)
+        });
+        schema
+    }
+}
+
+impl<'value> NP_Value<'value> for NP_Dec128 {
+
+    fn type_idx() -> (&'value str, NP_TypeKeys) { ("decimal128", NP_TypeKeys::Decimal128) }
+    fn self_type_idx(&self) -> (&'value str, NP_TypeKeys) { ("decimal128", NP_TypeKeys::Decimal128) }
+
+    fn schema_to_json(schema: &Vec<NP_Parsed_Schema>, address: usize)-> Result<NP_JSON, NP_Error> {
+        let mut schema_json = JSMAP::new();
+        schema_json.insert("type".to_owned(), NP_JSON::String(Self::type_idx().0.to_string()));
+
+        let data = unsafe { &*(*schema[address].data as *const NP_Dec128_Data) };
+
+        schema_json.insert("exp".to_owned(), NP_JSON::Integer(data.exp as i64));
+
+        if let Some(d) = data.default {
+            schema_json.insert("default".to_owned(), NP_JSON::String(d.to_string()));
+        }
+
+        Ok(NP_JSON::Dictionary(schema_json))
+    }
+
+    fn default_value(_depth: usize, addr: usize, schema: &Vec<NP_Parsed_Schema>) -> Option<Self> {
+        let data = unsafe { &*(*schema[addr].data as *const NP_Dec128_Data) };
+        data.default
+    }
+
+    fn set_from_json<'set>(_depth: usize, apply_null: bool, cursor: NP_Cursor, memory: &'set NP_Memory, value: &Box<NP_JSON>) -> Result<(), NP_Error> where Self: 'set + Sized {
+        match &**value {
+            NP_JSON::Null => {
+                // null writes the schema default if there is one, otherwise clears the value
+                if apply_null {
+                    let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Dec128_Data) };
+                    match data.default {
+                        Some(default) => { Self::set_value(cursor, memory, default)?; },
+                        None => { NP_Cursor::delete(cursor, memory)?; }
+                    }
+                }
+            },
+            NP_JSON::Dictionary(map) => {
+                if let Some(NP_JSON::Dictionary(parts)) = map.get("parts") {
+                    let num = match parts.get("num") {
+                        Some(NP_JSON::Integer(num)) => *num as i128,
+                        Some(NP_JSON::String(num)) => match num.trim().parse::<i128>() {
+                            Ok(x) => x,
+                            Err(_e) => return Err(NP_Error::new("Decimal `parts.num` property must be an integer!"))
+                        },
+                        _ => return Err(NP_Error::new("Decimal types require a `parts.num` property!"))
+                    };
+                    if let Some(NP_JSON::Integer(exp)) = parts.get("exp") {
+                        if *exp < 0 || *exp > NP_Dec128::MAX_EXP as i64 {
+                            return Err(NP_Error::new("Decimal `parts.exp` property must be between 0 and 38!"))
+                        }
+                        Self::set_value(cursor, memory, NP_Dec128::new(num, *exp as u8))?;
+                    } else {
+                        return Err(NP_Error::new("Decimal types require a `parts.exp` property!"))
+                    }
+                } else {
+                    return Err(NP_Error::new("Decimal types require a `parts` property!"))
+                }
+            },
+            _ => { NP_Cursor::json_type_mismatch(memory, "object", value)?; }
+        }
+
+        Ok(())
+    }
+
+    fn set_value<'set>(cursor: NP_Cursor, memory: &'set NP_Memory, value: Self) -> Result<NP_Cursor, NP_Error> where Self: 'set + Sized {
+
+        let c_value = || { cursor.get_value(memory) };
+
+        let mut value_address = c_value().get_addr_value() as usize;
+
+        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Dec128_Data) };
+
+        if value.exp > NP_Dec128::MAX_EXP {
+            return Err(NP_Error::new("Decimal exp must be between 0 and 38!"))
+        }
+
+        let cloned_value = match value.checked_shift_exp(data.exp) {
+            Some(x) => x,
+            None => return Err(NP_Error::new("Decimal value doesn't fit dec128 at the schema exp!"))
+        };
+
+        let mut be_bytes = cloned_value.num.to_be_bytes();
+
+        // convert to unsigned
+        be_bytes[0] = to_unsigned(be_bytes[0]);
+
+        if value_address != 0 { // existing value, replace
+            let write_bytes = memory.write_bytes();
+
+            // overwrite existing values in buffer
+            for x in 0..be_bytes.len() {
+                write_bytes[value_address + x] = be_bytes[x];
+            }
+
+        } else { // new value
+            value_address = memory.malloc_borrow(&be_bytes)?;
+            cursor.get_value_mut(memory).set_addr_value(value_address as u32);
+        }
+
+        Ok(cursor)
+    }
+
+    fn into_value(cursor: &NP_Cursor, memory: &'value NP_Memory) -> Result<Option<Self>, NP_Error> where Self: Sized {
+
+        let value_addr = cursor.get_value(memory).get_addr_value() as usize;
+
+        // empty value
+        if value_addr == 0 {
+            return Ok(None);
+        }
+
+        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Dec128_Data) };
+
+        Ok(match memory.get_16_bytes(value_addr) {
+            Some(x) => {
+                let mut be_bytes = x.clone();
+                be_bytes[0] = to_signed(be_bytes[0]);
+                Some(NP_Dec128::new(i128::from_be_bytes(be_bytes), data.exp))
+            },
+            None => None
+        })
+    }
+
+    fn to_json(_depth:usize, cursor: &NP_Cursor, memory: &'value NP_Memory) -> NP_JSON {
+
+        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Dec128_Data) };
+
+        match Self::into_value(cursor, memory) {
+            Ok(Some(x)) => NP_Dec128::json_value(&x, data.exp),
+            Ok(None) => {
+                match data.default {
+                    Some(d) => NP_Dec128::json_value(&d, data.exp),
+                    None => NP_JSON::Null
+                }
+            },
+            Err(_e) => NP_JSON::Null
+        }
+    }
+
+    fn get_size(_depth:usize, cursor: &NP_Cursor, memory: &NP_Memory) -> Result<usize, NP_Error> {
+        if cursor.get_value(memory).get_addr_value() == 0 {
+            Ok(0)
+        } else {
+            Ok(core::mem::size_of::<i128>())
+        }
+    }
+
+    fn schema_to_idl(schema: &Vec<NP_Parsed_Schema>, address: usize)-> Result<String, NP_Error> {
+        let data = unsafe { &*(*schema[address].data as *const NP_Dec128_Data) };
+
+        let mut result = String::from("dec128({exp: ");
+        result.push_str(data.exp.to_string().as_str());
+        if let Some(x) = data.default {
+            result.push_str(", default: ");
+            result.push_str(x.to_string().as_str());
+        }
+        if NP_Schema::is_required(&schema[address]) {
+            result.push_str(", required: true");
+        }
+        result.push_str("})");
+        Ok(result)
+    }
+
+    fn from_idl_to_schema(schema: Vec<NP_Parsed_Schema>, _name: &str, idl: &JS_Schema, args: &Vec<JS_AST>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
+
+        let mut exp: Option<u8> = None;
+        let mut default: Option<&str> = None;
+
+        if args.len() > 0 {
+            match &args[0] {
+                JS_AST::object { properties } => {
+                    for (key, value) in properties {
+                        match idl.get_str(key).trim() {
+                            "exp" => {
+                                match value {
+                                    JS_AST::number { addr } => {
+                                        match idl.get_str(addr).trim().parse::<u8>() {
+                                            Ok(x) if x <= NP_Dec128::MAX_EXP => {
+                                                exp = Some(x);
+                                            },
+                                            Ok(_) => return Err(NP_Error::new("Decimal 'exp' property must be between 0 and 38!")),
+                                            Err(_e) => return Err(NP_Error::new("Error parsing exponent of decimal value!"))
+                                        }
+                                    },
+                                    _ => { }
+                                }
+                            },
+                            "default" => {
+                                match value {
+                                    JS_AST::number { addr } => {
+                                        default = Some(idl.get_str(addr));
+                                    },
+                                    _ => { }
+                                }
+                            },
+                            _ => { }
+                        }
+                    }
+                },
+                _ => { }
+            }
+        }
+
+        let exp = match exp {
+            Some(x) => x,
+            None => return Err(NP_Error::new("Decimal type requires 'exp' property!"))
+        };
+
+        let default = match default {
+            Some(x) => Some(NP_Dec128::from_str_exp(x, exp)?),
+            None => None
+        };
+
+        Ok((true, NP_Dec128::schema_bytes(exp, &default), NP_Dec128::push_schema(schema, exp, default)))
+    }
+
+    fn from_json_to_schema(schema: Vec<NP_Parsed_Schema>, json_schema: &Box<NP_JSON>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
+
+        let exp: u8 = match json_schema["exp"] {
+            NP_JSON::Integer(x) => {
+                if x > NP_Dec128::MAX_EXP as i64 || x < 0 {
+                    return Err(NP_Error::new("Decimal 'exp' property must be between 0 and 38!"))
+                }
+                x as u8
+            },
+            _ => {
+                return Err(NP_Error::new("Decimal type requires 'exp' property!"))
+            }
+        };
+
+        let default = match &json_schema["default"] {
+            NP_JSON::String(x) => Some(NP_Dec128::from_str_exp(&x, exp)?),
+            NP_JSON::Integer(x) => Some(NP_Dec128::from_str_exp(&x.to_string(), exp)?),
+            NP_JSON::Float(x, _) => Some(NP_Dec128::from_str_exp(&x.to_string(), exp)?),
+            _ => None
+        };
+
+        Ok((true, NP_Dec128::schema_bytes(exp, &default), NP_Dec128::push_schema(schema, exp, default)))
+    }
+
+    fn from_bytes_to_schema(schema: Vec<NP_Parsed_Schema>, address: usize, bytes: &[u8]) -> (bool, Vec<NP_Parsed_Schema>) {
+        // the JSON and IDL parsers never write a larger exp, this keeps hand made schema bytes from overflowing
+        let exp = u8::min(bytes[address + 1], NP_Dec128::MAX_EXP);
+
+        let default = if bytes[address + 2] == 0 {
+            None
+        } else {
+            let mut slice = 0i128.to_be_bytes();
+            slice.copy_from_slice(&bytes[(address + 3)..(address + 19)]);
+            Some(NP_Dec128::new(i128::from_be_bytes(slice), exp))
+        };
+
+        (true, NP_Dec128::push_schema(schema, exp, default))
+    }
+}
+
+#[test]
+fn schema_parsing_works_idl() -> Result<(), NP_Error> {
+    let schema = "dec128({exp: 3, default: 203.293})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+
+    let schema = "dec128({exp: 20})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+
+    Ok(())
+}
+
+#[test]
+fn schema_parsing_works() -> Result<(), NP_Error> {
+    let schema = "{\"type\":\"decimal128\",\"exp\":30,\"default\":\"-1.000000000000000000000000000001\"}";
+    let factory = crate::NP_Factory::new_json(schema)?;
+    assert_eq!(schema, factory.schema.to_json()?.stringify());
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_json()?.stringify());
+
+    Ok(())
+}
+
+#[test]
+fn set_clear_value_and_compaction_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("dec128({exp: 10})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    // 10^28, well past i64::MAX at this exp
+    let big = NP_Dec128::new(10i128.pow(28) + 1, 10);
+    buffer.set(&[], big)?;
+    assert_eq!(buffer.get::<NP_Dec128>(&[])?, Some(big));
+    assert_eq!(buffer.get::<NP_Dec128>(&[])?.unwrap().export(), (10i128.pow(28) + 1, 10));
+
+    let negative = NP_Dec128::new(-(10i128.pow(30)), 10);
+    buffer.set(&[], negative)?;
+    assert_eq!(buffer.get::<NP_Dec128>(&[])?.unwrap().export(), (-(10i128.pow(30)), 10));
+
+    // json keeps the full value
+    let json = buffer.json_encode(&[])?;
+    assert_eq!(json["value"]["parts"]["num"], NP_JSON::String(String::from("-1000000000000000000000000000000")));
+    buffer.set_with_json(&[], r#"{"value": {"parts": {"num": "123456789012345678901234567890", "exp": 10}}}"#)?;
+    assert_eq!(buffer.get::<NP_Dec128>(&[])?.unwrap().to_string(), "12345678901234567890.1234567890");
+
+    buffer.del(&[])?;
+    assert_eq!(buffer.get::<NP_Dec128>(&[])?, None);
+
+    buffer.compact(None)?;
+    assert_eq!(buffer.calc_bytes()?.current_buffer, 6usize);
+
+    Ok(())
+}
+
+#[test]
+fn arithmetic_works() -> Result<(), NP_Error> {
+    let a = NP_Dec128::new(i64::MAX as i128, 2);
+    let b = NP_Dec128::new(i64::MAX as i128, 0);
+
+    // sums past the i64 range
+    let sum = a + b;
+    assert_eq!(sum.export(), (i64::MAX as i128 * 101, 2));
+    assert!(sum > b);
+
+    let mut diff = sum;
+    diff -= b;
+    assert_eq!(diff, a);
+
+    let product = NP_Dec128::new(3_000_000_000_000_000_000, 0) * NP_Dec128::new(25, 1);
+    assert_eq!(product.export(), (7_500_000_000_000_000_000, 0));
+
+    let quotient = NP_Dec128::new(10i128.pow(30), 3) / NP_Dec128::new(4, 0);
+    assert_eq!(quotient.export(), (10i128.pow(30) / 4, 3));
+
+    // widening an NP_Dec keeps the value
+    assert_eq!(NP_Dec128::from(NP_Dec::new(-2203, 3)), NP_Dec128::new(-2203, 3));
+
+    Ok(())
+}
+
+#[test]
+fn exp_limit_works() -> Result<(), NP_Error> {
+    assert!(crate::NP_Factory::new("dec128({exp: 38})").is_ok());
+    assert!(matches!(crate::NP_Factory::new("dec128({exp: 39})"), Err(NP_Error::Custom { message }) if message == "Decimal 'exp' property must be between 0 and 38!"));
+    assert!(matches!(crate::NP_Factory::new_json(r#"{"type":"decimal128","exp":200}"#), Err(NP_Error::Custom { message }) if message == "Decimal 'exp' property must be between 0 and 38!"));
+
+    // bytes from elsewhere are capped instead of overflowing later
+    let mut bytes = crate::NP_Factory::new("dec128({exp: 2})")?.export_schema_bytes().to_vec();
+    bytes[1] = 200;
+    let factory = crate::NP_Factory::new_bytes(&bytes)?;
+    assert_eq!(factory.schema.to_idl()?, "dec128({exp: 38})");
+
+    // comparing values far apart in exp doesn't overflow
+    assert!(NP_Dec128::new(i128::MAX, 0) > NP_Dec128::new(i128::MAX, 38));
+    assert!(NP_Dec128::new(i128::MIN, 0) < NP_Dec128::new(i128::MIN, 38));
+    assert!(NP_Dec128::new(-1, 0) < NP_Dec128::new(1, 200));
+    assert!(NP_Dec128::new(1, 0) != NP_Dec128::new(1, 200));
+    assert!(NP_Dec128::new(0, 0) == NP_Dec128::new(0, 200));
+    assert!(NP_Dec128::new(25, 1) == NP_Dec128::new(2500, 3));
+
+    Ok(())
+}
+
+#[test]
+fn overflow_is_reported() -> Result<(), NP_Error> {
+    let overflow = |err: NP_Error| matches!(err, NP_Error::Custom { message } if message == "Decimal value doesn't fit dec128 at the schema exp!");
+
+    let factory = crate::NP_Factory::new("dec128({exp: 10})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    // raising the exp to the schema exp doesn't fit
+    assert!(overflow(buffer.set(&[], NP_Dec128::new(i128::MAX, 0)).unwrap_err()));
+    assert!(overflow(buffer.set(&[], NP_Dec128::new(i128::MIN / 5, 9)).unwrap_err()));
+    assert_eq!(buffer.get::<NP_Dec128>(&[])?, None);
+    buffer.set(&[], NP_Dec128::new(i128::MAX / 10, 9))?;
+    assert_eq!(buffer.get::<NP_Dec128>(&[])?.unwrap().export(), (i128::MAX / 10 * 10, 10));
+
+    // exps past the limit are refused
+    assert!(matches!(buffer.set(&[], NP_Dec128::new(1, 39)).unwrap_err(), NP_Error::Custom { message } if message == "Decimal exp must be between 0 and 38!"));
+    for exp in ["-1", "39", "300"].iter() {
+        let mut json = String::from(r#"{"value": {"parts": {"num": "1", "exp": "#);
+        json.push_str(exp);
+        json.push_str("}}}");
+        assert!(matches!(buffer.set_with_json(&[], json).unwrap_err(), NP_Error::Custom { message } if message.starts_with("Decimal `parts.exp` property must be between 0 and 38!")));
+    }
+    assert_eq!(buffer.get::<NP_Dec128>(&[])?.unwrap().export(), (i128::MAX / 10 * 10, 10));
+
+    // shift_exp saturates, checked_shift_exp reports
+    assert_eq!(NP_Dec128::new(i128::MIN, 0).shift_exp(2).export(), (i128::MIN, 2));
+    assert_eq!(NP_Dec128::new(1, 0).checked_shift_exp(38).unwrap().export(), (10i128.pow(38), 38));
+    assert_eq!(NP_Dec128::new(2, 0).checked_shift_exp(38), None);
+
+    // multiply and divide
+    assert_eq!(NP_Dec128::new(i128::MAX / 2, 0).checked_mul(NP_Dec128::new(3, 0)), None);
+    assert_eq!(NP_Dec128::new(10, 0).checked_mul(NP_Dec128::new(5, 38)).unwrap().export(), (0, 0));
+    assert_eq!(NP_Dec128::new(10, 0).checked_mul(NP_Dec128::new(5, 200)).unwrap().export(), (0, 0));
+    assert_eq!(NP_Dec128::new(i128::MAX, 0).checked_div(NP_Dec128::new(5, 1)), None);
+    assert_eq!(NP_Dec128::new(1, 0).checked_div(NP_Dec128::new(0, 3)), None);
+    assert_eq!(NP_Dec128::new(i128::MIN, 0).checked_div(NP_Dec128::new(-1, 0)), None);
+    assert_eq!(NP_Dec128::new(6, 0).checked_div(NP_Dec128::new(3 * 10i128.pow(37), 37)).unwrap().export(), (2, 0));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "NP_Dec128 multiplication overflowed")]
+fn mul_overflow_panics() {
+    let _ = NP_Dec128::new(i128::MAX, 0) * NP_Dec128::new(2, 0);
+}
+
+#[test]
+#[should_panic(expected = "NP_Dec128 division by zero or overflow")]
+fn div_overflow_panics() {
+    let _ = NP_Dec128::new(i128::MAX, 0) / NP_Dec128::new(5, 1);
+}
@@ -277,8 +277,8 @@ impl NP_Geo {
      /// 
      pub fn into_json(&self) -> NP_JSON {
         let mut result_json = JSMAP::new();
-        result_json.insert("lat".to_owned(), NP_JSON::Float(self.lat));
-        result_json.insert("lng".to_owned(), NP_JSON::Float(self.lng));
+        result_json.insert("lat".to_owned(), NP_JSON::Float(self.lat, None));
+        result_json.insert("lng".to_owned(), NP_JSON::Float(self.lng, None));
         NP_JSON::Dictionary(result_json)
      }
 
@@ -351,7 +351,7 @@ fn geo_default_value(size: u8, json: &NP_JSON) -> Result<Option<NP_Geo_Bytes>, N
                         NP_JSON::Integer(y) => {
                             lat = *y as f64;
                         },
-                        NP_JSON::Float(y) => {
+                        NP_JSON::Float(y, _) => {
                             lat = *y as f64;
                         },
                         _ => {}
@@ -368,7 +368,7 @@ fn geo_default_value(size: u8, json: &NP_JSON) -> Result<Option<NP_Geo_Bytes>, N
                         NP_JSON::Integer(y) => {
                             lng = *y as f64;
                         },
-                        NP_JSON::Float(y) => {
+                        NP_JSON::Float(y, _) => {
                             lng = *y as f64;
                         },
                         _ => {}
@@ -415,7 +415,7 @@ impl<'value> NP_Value<'value> for NP_Geo {
                     value.lat = *lat as f64;
                 }
 
-                if let Some(NP_JSON::Float(lat)) = map.get("lat") {
+                if let Some(NP_JSON::Float(lat, _)) = map.get("lat") {
                     value.lat = *lat as f64;
                 }
 
@@ -423,13 +423,13 @@ impl<'value> NP_Value<'value> for NP_Geo {
                     value.lng = *lng as f64;
                 }
 
-                if let Some(NP_JSON::Float(lng)) = map.get("lng") {
+                if let Some(NP_JSON::Float(lng, _)) = map.get("lng") {
                     value.lng = *lng as f64;
                 }
 
                 Self::set_value(cursor, memory, value)?;
             },
-            _ => { }
+            _ => { NP_Cursor::json_type_mismatch(memory, "object", value)?; }
         }
 
         Ok(())
@@ -449,8 +449,8 @@ impl<'value> NP_Value<'value> for NP_Geo {
     
         if let Some(d) = &data.default {
             let mut default_map = JSMAP::new();
-            default_map.insert("lat".to_owned(), NP_JSON::Float(d.lat));
-            default_map.insert("lng".to_owned(), NP_JSON::Float(d.lng));
+            default_map.insert("lat".to_owned(), NP_JSON::Float(d.lat, None));
+            default_map.insert("lng".to_owned(), NP_JSON::Float(d.lng, None));
             schema_json.insert("default".to_owned(), NP_JSON::Dictionary(default_map));
         }
 
@@ -660,8 +660,8 @@ impl<'value> NP_Value<'value> for NP_Geo {
                     Some(y) => {
                         let mut object = JSMAP::new();
 
-                        object.insert("lat".to_owned(), NP_JSON::Float(y.lat));
-                        object.insert("lng".to_owned(), NP_JSON::Float(y.lng));
+                        object.insert("lat".to_owned(), NP_JSON::Float(y.lat, None));
+                        object.insert("lng".to_owned(), NP_JSON::Float(y.lng, None));
                         
                         NP_JSON::Dictionary(object)
                     },
@@ -672,8 +672,8 @@ impl<'value> NP_Value<'value> for NP_Geo {
                         if let Some(d) = &data.default {
                             let mut object = JSMAP::new();
 
-                            object.insert("lat".to_owned(), NP_JSON::Float(d.lat));
-                            object.insert("lng".to_owned(), NP_JSON::Float(d.lng));
+                            object.insert("lat".to_owned(), NP_JSON::Float(d.lat, None));
+                            object.insert("lng".to_owned(), NP_JSON::Float(d.lng, None));
                             
                             NP_JSON::Dictionary(object)
                         } else {
@@ -700,14 +700,27 @@ impl<'value> NP_Value<'value> for NP_Geo {
             _  => { String::from("geo4(")  }
         };
     
+        let mut properties: Vec<String> = Vec::new();
+
         if let Some(d) = &data.default {
-            schema_idl.push_str("{default: {");
-            schema_idl.push_str("lat: ");
-            schema_idl.push_str(d.lat.to_string().as_str());
-            schema_idl.push_str(", ");
-            schema_idl.push_str("lng: ");
-            schema_idl.push_str(d.lng.to_string().as_str());
-            schema_idl.push_str("}}");
+            let mut def = String::from("default: {");
+            def.push_str("lat: ");
+            def.push_str(d.lat.to_string().as_str());
+            def.push_str(", ");
+            def.push_str("lng: ");
+            def.push_str(d.lng.to_string().as_str());
+            def.push_str("}");
+            properties.push(def);
+        }
+
+        if NP_Schema::is_required(&schema[address]) {
+            properties.push(String::from("required: true"));
+        }
+
+        if properties.len() > 0 {
+            schema_idl.push_str("{");
+            schema_idl.push_str(properties.join(", ").as_str());
+            schema_idl.push_str("}");
         }
 
         schema_idl.push_str(")");
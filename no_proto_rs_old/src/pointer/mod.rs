@@ -18,6 +18,8 @@ pub mod numbers;
 pub mod bool;
 pub mod geo;
 pub mod dec;
+pub mod dec128;
+pub mod sorted_uints;
 pub mod ulid;
 pub mod uuid;
 pub mod option;
@@ -28,14 +30,14 @@ pub mod portal;
 use core::{fmt::{Debug}};
 
 use alloc::prelude::v1::Box;
-use crate::{idl::{JS_AST, JS_Schema}, pointer::dec::NP_Dec, schema::{NP_Portal_Data, NP_Schema_Addr}, utils::opt_err};
+use crate::{idl::{JS_AST, JS_Schema}, pointer::dec::NP_Dec, pointer::dec128::NP_Dec128, pointer::sorted_uints::NP_SortedUints, schema::{NP_Portal_Data, NP_Schema_Addr, NP_Struct_Data}, utils::opt_err};
 use crate::NP_Parsed_Schema;
 use crate::{json_flex::NP_JSON};
 use crate::memory::{NP_Memory};
 use crate::NP_Error;
 use crate::{schema::{NP_TypeKeys}, collection::{map::NP_Map, struc::NP_Struct, list::NP_List, tuple::NP_Tuple}};
 
-use alloc::{string::String, vec::Vec, borrow::ToOwned};
+use alloc::{string::String, vec::Vec, borrow::ToOwned, string::ToString};
 use bytes::NP_Bytes;
 
 use self::{date::NP_Date, geo::NP_Geo, option::NP_Enum, portal::NP_Portal, ulid::{NP_ULID}, uuid::{NP_UUID}};
@@ -402,6 +404,7 @@ impl<'cursor> NP_Cursor {
             NP_TypeKeys::Float      => {        f32::set_value(cursor, memory, opt_err(      f32::np_max_value(&cursor, memory))?)?; } ,
             NP_TypeKeys::Double     => {        f64::set_value(cursor, memory, opt_err(      f64::np_max_value(&cursor, memory))?)?; } ,
             NP_TypeKeys::Decimal    => {     NP_Dec::set_value(cursor, memory, opt_err(   NP_Dec::np_max_value(&cursor, memory))?)?; } ,
+            NP_TypeKeys::Decimal128 => {  NP_Dec128::set_value(cursor, memory, opt_err(NP_Dec128::np_max_value(&cursor, memory))?)?; } ,
             NP_TypeKeys::Geo        => {     NP_Geo::set_value(cursor, memory, opt_err(   NP_Geo::np_max_value(&cursor, memory))?)?; } ,
             NP_TypeKeys::Date       => {    NP_Date::set_value(cursor, memory, opt_err(  NP_Date::np_max_value(&cursor, memory))?)?; } ,
             NP_TypeKeys::Enum       => {    NP_Enum::set_value(cursor, memory, opt_err(  NP_Enum::np_max_value(&cursor, memory))?)?; } ,
@@ -465,6 +468,7 @@ impl<'cursor> NP_Cursor {
             NP_TypeKeys::Float      => {        f32::set_value(cursor, memory, opt_err(      f32::np_min_value(&cursor, memory))?)?; } ,
             NP_TypeKeys::Double     => {        f64::set_value(cursor, memory, opt_err(      f64::np_min_value(&cursor, memory))?)?; } ,
             NP_TypeKeys::Decimal    => {     NP_Dec::set_value(cursor, memory, opt_err(   NP_Dec::np_min_value(&cursor, memory))?)?; } ,
+            NP_TypeKeys::Decimal128 => {  NP_Dec128::set_value(cursor, memory, opt_err(NP_Dec128::np_min_value(&cursor, memory))?)?; } ,
             NP_TypeKeys::Geo        => {     NP_Geo::set_value(cursor, memory, opt_err(   NP_Geo::np_min_value(&cursor, memory))?)?; } ,
             NP_TypeKeys::Date       => {    NP_Date::set_value(cursor, memory, opt_err(  NP_Date::np_min_value(&cursor, memory))?)?; } ,
             NP_TypeKeys::Enum       => {    NP_Enum::set_value(cursor, memory, opt_err(  NP_Enum::np_min_value(&cursor, memory))?)?; } ,
@@ -529,6 +533,8 @@ impl<'cursor> NP_Cursor {
             NP_TypeKeys::Float          => {       f32::to_json(depth, cursor, memory) },
             NP_TypeKeys::Double         => {       f64::to_json(depth, cursor, memory) },
             NP_TypeKeys::Decimal        => {    NP_Dec::to_json(depth, cursor, memory) },
+            NP_TypeKeys::Decimal128     => { NP_Dec128::to_json(depth, cursor, memory) },
+            NP_TypeKeys::SortedUints    => { NP_SortedUints::to_json(depth, cursor, memory) },
             NP_TypeKeys::Boolean        => {      bool::to_json(depth, cursor, memory) },
             NP_TypeKeys::Geo            => {    NP_Geo::to_json(depth, cursor, memory) },
             NP_TypeKeys::Uuid           => {   NP_UUID::to_json(depth, cursor, memory) },
@@ -545,6 +551,80 @@ impl<'cursor> NP_Cursor {
 
     }
 
+    /// Walk every scalar at or below this cursor, calling `callback` with the path to each scalar and it's cursor.
+    /// 
+    /// Collections that haven't been set are skipped, scalars are passed to the callback whether they are set or not.
+    /// 
+    pub fn for_each_leaf<F>(depth: usize, cursor: NP_Cursor, memory: &NP_Memory, path: &mut Vec<String>, callback: &mut F) -> Result<(), NP_Error> where F: FnMut(&Vec<String>, &NP_Cursor) -> Result<(), NP_Error> {
+
+        if depth > 255 { return Err(NP_Error::RecursionLimit) }
+
+        let schema = memory.get_schema(cursor.schema_addr);
+
+        match schema.i {
+            NP_TypeKeys::Struct | NP_TypeKeys::Tuple | NP_TypeKeys::List | NP_TypeKeys::Map => {
+                if cursor.get_value(memory).get_addr_value() == 0 {
+                    return Ok(())
+                }
+            },
+            _ => {}
+        }
+
+        match schema.i {
+            NP_TypeKeys::None | NP_TypeKeys::Any => { },
+            NP_TypeKeys::Struct     => {
+                let mut struc = NP_Struct::new_iter(&cursor, memory);
+                while let Some((_index, key, item)) = struc.step_iter(memory) {
+                    if let Some(item_cursor) = item {
+                        path.push(String::from(key));
+                        NP_Cursor::for_each_leaf(depth + 1, item_cursor, memory, path, callback)?;
+                        path.pop();
+                    }
+                }
+            },
+            NP_TypeKeys::Tuple      => {
+                let mut tuple = NP_Tuple::new_iter(&cursor, memory);
+                while let Some((index, item)) = tuple.step_iter(memory, false) {
+                    if let Some(item_cursor) = item {
+                        path.push(index.to_string());
+                        NP_Cursor::for_each_leaf(depth + 1, item_cursor, memory, path, callback)?;
+                        path.pop();
+                    }
+                }
+            },
+            NP_TypeKeys::List       => {
+                let mut list = NP_List::new_iter(&cursor, memory, true, 0);
+                while let Some((index, item)) = list.step_iter(memory) {
+                    if let Some(item_cursor) = item {
+                        path.push(index.to_string());
+                        NP_Cursor::for_each_leaf(depth + 1, item_cursor, memory, path, callback)?;
+                        path.pop();
+                    }
+                }
+            },
+            NP_TypeKeys::Map        => {
+                let mut map = NP_Map::new_iter(&cursor, memory);
+                while let Some((key, item_cursor)) = map.step_iter(memory) {
+                    path.push(String::from(key));
+                    NP_Cursor::for_each_leaf(depth + 1, item_cursor, memory, path, callback)?;
+                    path.pop();
+                }
+            },
+            NP_TypeKeys::Portal     => {
+                let portal_data = unsafe { &*(*schema.data as *const NP_Portal_Data) };
+                let mut portal_cursor = cursor.clone();
+                portal_cursor.schema_addr = portal_data.schema;
+                portal_cursor.parent_schema_addr = portal_data.parent_schema;
+                NP_Cursor::for_each_leaf(depth + 1, portal_cursor, memory, path, callback)?;
+            },
+            _ => {
+                callback(path, &cursor)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Compact from old cursor and memory into new cursor and memory
     /// 
     pub fn compact(depth: usize, from_cursor: NP_Cursor, from_memory: &NP_Memory, to_cursor: NP_Cursor, to_memory: &NP_Memory) -> Result<NP_Cursor, NP_Error> {
@@ -566,6 +646,8 @@ impl<'cursor> NP_Cursor {
             NP_TypeKeys::Float         => {       f32::do_compact(depth, from_cursor, from_memory, to_cursor, to_memory) }
             NP_TypeKeys::Double        => {       f64::do_compact(depth, from_cursor, from_memory, to_cursor, to_memory) }
             NP_TypeKeys::Decimal       => {    NP_Dec::do_compact(depth, from_cursor, from_memory, to_cursor, to_memory) }
+            NP_TypeKeys::Decimal128    => { NP_Dec128::do_compact(depth, from_cursor, from_memory, to_cursor, to_memory) }
+            NP_TypeKeys::SortedUints   => { NP_SortedUints::do_compact(depth, from_cursor, from_memory, to_cursor, to_memory) }
             NP_TypeKeys::Boolean       => {      bool::do_compact(depth, from_cursor, from_memory, to_cursor, to_memory) }
             NP_TypeKeys::Geo           => {    NP_Geo::do_compact(depth, from_cursor, from_memory, to_cursor, to_memory) }
             NP_TypeKeys::Uuid          => {   NP_UUID::do_compact(depth, from_cursor, from_memory, to_cursor, to_memory) }
@@ -611,6 +693,8 @@ impl<'cursor> NP_Cursor {
             NP_TypeKeys::Float       => {        f32::set_value(cursor, memory, opt_err(f32::schema_default(schema))?)?; },
             NP_TypeKeys::Double      => {        f64::set_value(cursor, memory, opt_err(f64::schema_default(schema))?)?; },
             NP_TypeKeys::Decimal     => {     NP_Dec::set_value(cursor, memory, opt_err(NP_Dec::schema_default(schema))?)?; },
+            NP_TypeKeys::Decimal128  => {  NP_Dec128::set_value(cursor, memory, opt_err(NP_Dec128::schema_default(schema))?)?; },
+            NP_TypeKeys::SortedUints => { NP_SortedUints::set_value(cursor, memory, opt_err(NP_SortedUints::schema_default(schema))?)?; },
             NP_TypeKeys::Boolean     => {       bool::set_value(cursor, memory, opt_err(bool::schema_default(schema))?)?; },
             NP_TypeKeys::Geo         => {     NP_Geo::set_value(cursor, memory, opt_err(NP_Geo::schema_default(schema))?)?; },
             NP_TypeKeys::Uuid        => {    NP_UUID::set_value(cursor, memory, opt_err(NP_UUID::schema_default(schema))?)?; },
@@ -622,6 +706,21 @@ impl<'cursor> NP_Cursor {
         Ok(())
     }
 
+    /// Called by scalar types when a JSON value isn't a shape they accept.
+    /// 
+    /// The value is ignored unless the buffer uses strict JSON, then an error is returned.  `null` is always ignored.
+    pub fn json_type_mismatch(memory: &NP_Memory, expected: &str, json: &NP_JSON) -> Result<(), NP_Error> {
+        if memory.options.strict_json && *json != NP_JSON::Null {
+            let mut err = String::from("expected ");
+            err.push_str(expected);
+            err.push_str(", got ");
+            err.push_str(json.kind());
+            return Err(NP_Error::new(err))
+        }
+
+        Ok(())
+    }
+
     /// Set a JSON value into the buffer
     pub fn set_from_json(depth: usize, apply_null: bool, cursor: NP_Cursor, memory: &NP_Memory, json: &Box<NP_JSON>) -> Result<(), NP_Error> {
 
@@ -654,6 +753,8 @@ impl<'cursor> NP_Cursor {
             NP_TypeKeys::Float          => {       f32::set_from_json(depth, apply_null, cursor, memory, json) },
             NP_TypeKeys::Double         => {       f64::set_from_json(depth, apply_null, cursor, memory, json) },
             NP_TypeKeys::Decimal        => {    NP_Dec::set_from_json(depth, apply_null, cursor, memory, json) },
+            NP_TypeKeys::Decimal128     => { NP_Dec128::set_from_json(depth, apply_null, cursor, memory, json) },
+            NP_TypeKeys::SortedUints    => { NP_SortedUints::set_from_json(depth, apply_null, cursor, memory, json) },
             NP_TypeKeys::Boolean        => {      bool::set_from_json(depth, apply_null, cursor, memory, json) },
             NP_TypeKeys::Geo            => {    NP_Geo::set_from_json(depth, apply_null, cursor, memory, json) },
             NP_TypeKeys::Uuid           => {   NP_UUID::set_from_json(depth, apply_null, cursor, memory, json) },
@@ -669,6 +770,129 @@ impl<'cursor> NP_Cursor {
         }
     }
 
+    /// Set a JSON document into the buffer like `set_from_json`, but walk the collections here so an error can report the path it happened at.
+    /// 
+    /// `null` values are skipped, unknown struct fields are ignored.
+    pub fn set_from_json_with_path(depth: usize, cursor: NP_Cursor, memory: &NP_Memory, json: &NP_JSON, path: &mut Vec<String>) -> Result<(), NP_Error> {
+
+        if depth > 255 { return Err(NP_Error::RecursionLimit) }
+
+        if *json == NP_JSON::Null { return Ok(()) }
+
+        let schema = memory.get_schema(cursor.schema_addr);
+
+        match schema.i {
+            NP_TypeKeys::Struct => {
+                if let NP_JSON::Dictionary(_) = json { } else {
+                    return NP_Cursor::json_type_mismatch(memory, "object", json).map_err(|e| NP_Cursor::with_json_path(e, path));
+                }
+
+                if cursor.parent_type == NP_Cursor_Parent::Tuple {
+                    memory.write_bytes()[cursor.buff_addr - 1] = 1;
+                }
+
+                let data = unsafe { &*(*schema.data as *const NP_Struct_Data) };
+
+                for field in data.fields.iter() {
+                    let value = &json[field.col.as_str()];
+                    if *value == NP_JSON::Null { continue; }
+
+                    path.push(field.col.clone());
+                    match NP_Struct::select(cursor, schema, &field.col, true, false, memory)? {
+                        Some(x) => { NP_Cursor::set_from_json_with_path(depth + 1, x, memory, value, path)?; },
+                        None => return Err(NP_Cursor::with_json_path(NP_Error::new("Failed to find field value!"), path))
+                    }
+                    path.pop();
+                }
+
+                Ok(())
+            },
+            NP_TypeKeys::Map => {
+                let json_map = match json {
+                    NP_JSON::Dictionary(x) => x,
+                    _ => return NP_Cursor::json_type_mismatch(memory, "object", json).map_err(|e| NP_Cursor::with_json_path(e, path))
+                };
+
+                if cursor.parent_type == NP_Cursor_Parent::Tuple {
+                    memory.write_bytes()[cursor.buff_addr - 1] = 1;
+                }
+
+                for (key, value) in json_map.values.iter() {
+                    path.push(key.clone());
+                    if let Some(x) = NP_Map::select(cursor, key, true, false, memory)? {
+                        NP_Cursor::set_from_json_with_path(depth + 1, x, memory, value, path)?;
+                    }
+                    path.pop();
+                }
+
+                Ok(())
+            },
+            NP_TypeKeys::List => {
+                let items = match json {
+                    NP_JSON::Array(x) => x,
+                    _ => return NP_Cursor::json_type_mismatch(memory, "array", json).map_err(|e| NP_Cursor::with_json_path(e, path))
+                };
+
+                if cursor.parent_type == NP_Cursor_Parent::Tuple {
+                    memory.write_bytes()[cursor.buff_addr - 1] = 1;
+                }
+
+                for (idx, value) in items.iter().enumerate() {
+                    path.push(idx.to_string());
+                    match NP_List::select(cursor, idx, true, false, memory)? {
+                        Some((_, Some(x))) => { NP_Cursor::set_from_json_with_path(depth + 1, x, memory, value, path)?; },
+                        Some((_, None)) => { },
+                        None => return Err(NP_Cursor::with_json_path(NP_Error::new("Failed to find field value!"), path))
+                    }
+                    path.pop();
+                }
+
+                Ok(())
+            },
+            NP_TypeKeys::Tuple => {
+                let items = match json {
+                    NP_JSON::Array(x) => x,
+                    _ => return NP_Cursor::json_type_mismatch(memory, "array", json).map_err(|e| NP_Cursor::with_json_path(e, path))
+                };
+
+                if cursor.parent_type == NP_Cursor_Parent::Tuple {
+                    memory.write_bytes()[cursor.buff_addr - 1] = 1;
+                }
+
+                for (idx, value) in items.iter().enumerate() {
+                    path.push(idx.to_string());
+                    match NP_Tuple::select(cursor, schema, idx, true, false, memory)? {
+                        Some(x) => { NP_Cursor::set_from_json_with_path(depth + 1, x, memory, value, path)?; },
+                        None => return Err(NP_Cursor::with_json_path(NP_Error::new("Failed to find column value!"), path))
+                    }
+                    path.pop();
+                }
+
+                Ok(())
+            },
+            _ => {
+                NP_Cursor::set_from_json(depth, false, cursor, memory, &Box::new(json.clone())).map_err(|e| NP_Cursor::with_json_path(e, path))
+            }
+        }
+    }
+
+    /// Add the path a JSON error happened at to the end of it's message
+    fn with_json_path(err: NP_Error, path: &Vec<String>) -> NP_Error {
+        match err {
+            NP_Error::Custom { mut message } => {
+                message.push_str(" (at path: ");
+                if path.len() == 0 {
+                    message.push_str("root");
+                } else {
+                    message.push_str(path.join(".").as_str());
+                }
+                message.push(')');
+                NP_Error::Custom { message }
+            },
+            _ => err
+        }
+    }
+
     /// Delete the value at this cursor
     /// 
     /// Returns `true` if something was deleted, `false` otherwise.
@@ -724,6 +948,8 @@ impl<'cursor> NP_Cursor {
             NP_TypeKeys::Float        => {       f32::get_size(depth, cursor, memory) },
             NP_TypeKeys::Double       => {       f64::get_size(depth, cursor, memory) },
             NP_TypeKeys::Decimal      => {    NP_Dec::get_size(depth, cursor, memory) },
+            NP_TypeKeys::Decimal128   => { NP_Dec128::get_size(depth, cursor, memory) },
+            NP_TypeKeys::SortedUints  => { NP_SortedUints::get_size(depth, cursor, memory) },
             NP_TypeKeys::Boolean      => {      bool::get_size(depth, cursor, memory) },
             NP_TypeKeys::Geo          => {    NP_Geo::get_size(depth, cursor, memory) },
             NP_TypeKeys::Uuid         => {   NP_UUID::get_size(depth, cursor, memory) },
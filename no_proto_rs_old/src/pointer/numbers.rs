@@ -35,6 +35,7 @@ use crate::schema::NP_Value_Kind;
 use core::{str::FromStr};
 
 use crate::pointer::JS_AST;
+use crate::pointer::dec::{NP_Dec, NP_RoundMode};
 use alloc::string::String;
 use alloc::prelude::v1::Box;
 use crate::schema::NP_Parsed_Schema;
@@ -42,7 +43,7 @@ use alloc::vec::Vec;
 use crate::utils::to_unsigned;
 use crate::utils::to_signed;
 use crate::error::NP_Error;
-use crate::{schema::NP_TypeKeys, pointer::NP_Value, json_flex::NP_JSON, json_flex::JSMAP};
+use crate::{schema::{NP_Schema, NP_TypeKeys}, pointer::NP_Value, json_flex::NP_JSON, json_flex::JSMAP};
 
 use alloc::string::ToString;
 use alloc::{borrow::ToOwned};
@@ -62,10 +63,334 @@ pub enum NP_NumType {
 }
 
 
+/// Byte order to use when decoding the raw bytes of a number.
+/// 
+/// NoProto always writes numbers as big endian, this is only needed to read data written by something else.  See `NP_Buffer::get_with_endian`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endian {
+    /// Big endian, the byte order NoProto uses
+    Big,
+    /// Little endian
+    Little
+}
+
+/// How numbers that don't fit the schema type are written.
+/// 
+/// Set for a whole factory with `NP_Factory::with_overflow_policy`, it's used when JSON values are written into number fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumericOverflowPolicy {
+    /// Cast the value like `as` does, integers wrap around
+    Wrap,
+    /// Clamp the value to the closest number the type can hold
+    Saturate,
+    /// Refuse to write the value and return an error
+    Error
+}
+
+/// Timestamp format for `i64` fields, set with the `format` schema property.
+/// 
+/// The value is still stored as a plain `i64`, the format only changes JSON.  `to_json` writes the value as an ISO 8601 UTC string like `"2024-01-02T03:04:05.678Z"` and `set_from_json` accepts those strings as well as plain numbers.  Values outside the years 0000 to 9999 are written as plain numbers.
+/// 
+/// ```
+/// use no_proto::error::NP_Error;
+/// use no_proto::NP_Factory;
+/// 
+/// let factory: NP_Factory = NP_Factory::new(r#"i64({format: "unix_millis"})"#)?;
+/// 
+/// let mut new_buffer = factory.new_buffer(None);
+/// new_buffer.set(&[], 1704164645678i64)?;
+/// assert_eq!(new_buffer.json_encode(&[])?.stringify(), r#"{"value":"2024-01-02T03:04:05.678Z"}"#);
+/// 
+/// new_buffer.set_with_json(&[], r#"{"value": "2024-01-02T03:04:06Z"}"#)?;
+/// assert_eq!(new_buffer.get::<i64>(&[])?, Some(1704164646000));
+/// 
+/// # Ok::<(), NP_Error>(()) 
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NP_TimestampFormat {
+    /// Seconds since the unix epoch, `"2024-01-02T03:04:05Z"`
+    UnixSeconds,
+    /// Milliseconds since the unix epoch, `"2024-01-02T03:04:05.678Z"`
+    UnixMillis
+}
+
+impl NP_TimestampFormat {
+    /// Name of this format in schemas
+    pub(crate) fn schema_name(&self) -> &'static str {
+        match self {
+            NP_TimestampFormat::UnixSeconds => "unix_seconds",
+            NP_TimestampFormat::UnixMillis => "unix_millis"
+        }
+    }
+
+    /// Parse the name of a format from a schema
+    pub(crate) fn from_schema_name(name: &str) -> Result<Self, NP_Error> {
+        match name {
+            "unix_seconds" => Ok(NP_TimestampFormat::UnixSeconds),
+            "unix_millis" => Ok(NP_TimestampFormat::UnixMillis),
+            _ => Err(NP_Error::new("Number 'format' property must be one of 'unix_seconds' or 'unix_millis'!"))
+        }
+    }
+
+    /// Format stored in the schema bytes, zero is no format
+    pub(crate) fn to_schema_bits(format: Option<Self>) -> u8 {
+        match format {
+            None => 0,
+            Some(NP_TimestampFormat::UnixSeconds) => 1,
+            Some(NP_TimestampFormat::UnixMillis) => 2
+        }
+    }
+
+    /// Format read from the schema bytes, unknown values have no format
+    pub(crate) fn from_schema_bits(bits: u8) -> Option<Self> {
+        match bits {
+            1 => Some(NP_TimestampFormat::UnixSeconds),
+            2 => Some(NP_TimestampFormat::UnixMillis),
+            _ => None
+        }
+    }
+
+    /// Write a timestamp as an ISO 8601 UTC string, `None` if the year is outside 0000 to 9999
+    fn to_iso(&self, value: i64) -> Option<String> {
+        let (secs, millis) = match self {
+            NP_TimestampFormat::UnixSeconds => (value, None),
+            NP_TimestampFormat::UnixMillis => (value.div_euclid(1000), Some(value.rem_euclid(1000)))
+        };
+
+        let (year, month, day) = civil_from_days(secs.div_euclid(86400));
+        if year < 0 || year > 9999 {
+            return None;
+        }
+
+        let time = secs.rem_euclid(86400);
+        let mut iso = format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}", year, month, day, time / 3600, (time / 60) % 60, time % 60);
+        if let Some(ms) = millis {
+            iso.push_str(format!(".{:03}", ms).as_str());
+        }
+        iso.push('Z');
+        Some(iso)
+    }
+
+    /// Read an ISO 8601 UTC string like `2024-01-02T03:04:05Z`, unix millis also take up to 3 fraction digits
+    fn from_iso(&self, iso: &str) -> Result<i64, NP_Error> {
+        let bad_format = || NP_Error::new(match self {
+            NP_TimestampFormat::UnixSeconds => "Timestamp must look like 2024-01-02T03:04:05Z!",
+            NP_TimestampFormat::UnixMillis => "Timestamp must look like 2024-01-02T03:04:05.678Z!"
+        });
+
+        let bytes = iso.trim().as_bytes();
+        if bytes.len() < 20 || bytes[bytes.len() - 1] != b'Z' {
+            return Err(bad_format());
+        }
+
+        let number = |start: usize, len: usize| -> Result<i64, NP_Error> {
+            let digits = &bytes[start..(start + len)];
+            if digits.iter().all(|b| b.is_ascii_digit()) {
+                Ok(digits.iter().fold(0i64, |acc, b| acc * 10 + (b - b'0') as i64))
+            } else {
+                Err(bad_format())
+            }
+        };
+
+        for (idx, sep) in [(4, b'-'), (7, b'-'), (10, b'T'), (13, b':'), (16, b':')].iter() {
+            if bytes[*idx] != *sep {
+                return Err(bad_format());
+            }
+        }
+
+        let (year, month, day) = (number(0, 4)?, number(5, 2)?, number(8, 2)?);
+        let (hour, minute, second) = (number(11, 2)?, number(14, 2)?, number(17, 2)?);
+
+        if month < 1 || month > 12 || day < 1 || day > days_in_month(year, month) || hour > 23 || minute > 59 || second > 59 {
+            return Err(bad_format());
+        }
+
+        // anything between the seconds and the `Z` has to be a fraction
+        let fraction = &bytes[19..(bytes.len() - 1)];
+        let millis = match (self, fraction.len()) {
+            (_, 0) => 0,
+            (NP_TimestampFormat::UnixMillis, 2..=4) if fraction[0] == b'.' => {
+                let digits = fraction.len() - 1;
+                let mut ms = number(20, digits)?;
+                for _ in digits..3 { ms *= 10; }
+                ms
+            },
+            _ => return Err(bad_format())
+        };
+
+        let secs = days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second;
+
+        Ok(match self {
+            NP_TimestampFormat::UnixSeconds => secs,
+            NP_TimestampFormat::UnixMillis => secs * 1000 + millis
+        })
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 => if is_leap_year(year) { 29 } else { 28 },
+        4 | 6 | 9 | 11 => 30,
+        _ => 31
+    }
+}
+
+/// Days since 1970-01-01 for a date in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Date in the proleptic Gregorian calendar for a count of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (year_of_era + era * 400 + if month <= 2 { 1 } else { 0 }, month, day)
+}
+
+/// A number default written as the `"min"` or `"max"` of the field instead of a literal.
+/// 
+/// The bound is the schema `min` or `max` when the field has one, otherwise the smallest or largest value of the type.  Schemas keep the sentinel when they are exported, the resolved number is what readers see.
+/// 
+/// ```
+/// use no_proto::error::NP_Error;
+/// use no_proto::NP_Factory;
+/// 
+/// let factory: NP_Factory = NP_Factory::new(r#"struct({fields: {
+///     retries: u8({default: "max"}),
+///     floor: i32({default: "min", min: -40})
+/// }})"#)?;
+/// 
+/// let new_buffer = factory.new_buffer(None);
+/// assert_eq!(new_buffer.get::<u8>(&["retries"])?, Some(255));
+/// assert_eq!(new_buffer.get::<i32>(&["floor"])?, Some(-40));
+/// 
+/// # Ok::<(), NP_Error>(()) 
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NP_NumberBound {
+    /// The smallest value the field can hold
+    Min,
+    /// The largest value the field can hold
+    Max
+}
+
+impl NP_NumberBound {
+    /// Name of this bound in schemas
+    pub(crate) fn schema_name(&self) -> &'static str {
+        match self {
+            NP_NumberBound::Min => "min",
+            NP_NumberBound::Max => "max"
+        }
+    }
+
+    /// Parse the name of a bound from a schema
+    pub(crate) fn from_schema_name(name: &str) -> Result<Self, NP_Error> {
+        match name {
+            "min" => Ok(NP_NumberBound::Min),
+            "max" => Ok(NP_NumberBound::Max),
+            _ => Err(NP_Error::new("Number 'default' property must be a number, 'min' or 'max'!"))
+        }
+    }
+
+    /// Bound stored in the schema bytes, zero is a literal default
+    pub(crate) fn to_schema_bits(bound: Option<Self>) -> u8 {
+        match bound {
+            None => 0,
+            Some(NP_NumberBound::Min) => 1,
+            Some(NP_NumberBound::Max) => 2
+        }
+    }
+
+    /// Bound read from the schema bytes
+    pub(crate) fn from_schema_bits(bits: u8) -> Option<Self> {
+        match bits {
+            1 => Some(NP_NumberBound::Min),
+            2 => Some(NP_NumberBound::Max),
+            _ => None
+        }
+    }
+
+    /// The value this bound stands for, the schema range wins over the type range
+    pub(crate) fn resolve<T>(&self, min: Option<T>, max: Option<T>, type_min: T, type_max: T) -> T {
+        match self {
+            NP_NumberBound::Min => min.unwrap_or(type_min),
+            NP_NumberBound::Max => max.unwrap_or(type_max)
+        }
+    }
+}
+
+/// Schema byte after the type key holds the default flag in bit 0, `i64` types keep their timestamp format above it
+pub(crate) const TIMESTAMP_SHIFT: u8 = 1;
+
+/// Native number types that can be decoded straight from their raw bytes.
+/// 
+/// Implemented for `i8` through `i64`, `u8` through `u64`, `f32` and `f64`.
+pub trait NP_Number: Sized {
+    /// Decode a number from the start of `bytes` using the provided byte order.
+    /// 
+    /// Big endian signed values have their sign bit flipped back the same way NoProto does when reading them.  Little endian values are decoded as plain two's complement with no flip.
+    /// 
+    /// Returns `None` if there aren't enough bytes.
+    fn np_from_raw_bytes(bytes: &[u8], endian: Endian) -> Option<Self>;
+}
+
+/// Writes for numbers that don't fit the field type, used by `NP_Buffer::set_saturating` and `NP_Buffer::set_wrapping`.
+/// 
+/// Both write through `set_value` like any other number.
+pub(crate) trait NP_NumberCast {
+    /// Clamp the value to the closest number the field can hold, including the schema `min` and `max`, then write it
+    fn set_saturating(cursor: NP_Cursor, memory: &NP_Memory, value: i128) -> Result<NP_Cursor, NP_Error>;
+    /// Keep the low bits of the value like an `as` cast, then write it
+    fn set_wrapping(cursor: NP_Cursor, memory: &NP_Memory, value: i128) -> Result<NP_Cursor, NP_Error>;
+}
 
 macro_rules! noproto_number {
     ($t:ty, $str1: tt, $str2: tt, $tkey: expr, $numType: expr) => {
 
+        impl NP_Number for $t {
+            fn np_from_raw_bytes(bytes: &[u8], endian: Endian) -> Option<Self> {
+                let mut raw_bytes = <$t>::default().to_be_bytes();
+
+                let size = raw_bytes.len();
+
+                if bytes.len() < size {
+                    return None;
+                }
+
+                raw_bytes.copy_from_slice(&bytes[..size]);
+
+                match endian {
+                    Endian::Big => {
+                        match $numType {
+                            NP_NumType::signed => {
+                                raw_bytes[0] = to_signed(raw_bytes[0]);
+                            },
+                            _ => {}
+                        };
+                        Some(<$t>::from_be_bytes(raw_bytes))
+                    },
+                    Endian::Little => {
+                        Some(<$t>::from_le_bytes(raw_bytes))
+                    }
+                }
+            }
+        }
+
         impl<'value> super::NP_Scalar<'value> for $t {
             fn schema_default(_schema: &NP_Parsed_Schema) -> Option<Self> where Self: Sized {
                 Some(Self::default())
@@ -86,15 +411,91 @@ macro_rules! noproto_number {
 
             fn self_type_idx(&self) -> (&'value str, NP_TypeKeys) { ($str1, $tkey) }
 
-            fn set_from_json<'set>(_depth: usize, _apply_null: bool, cursor: NP_Cursor, memory: &'set NP_Memory, value: &Box<NP_JSON>) -> Result<(), NP_Error> where Self: 'set + Sized {
+            fn set_from_json<'set>(_depth: usize, apply_null: bool, cursor: NP_Cursor, memory: &'set NP_Memory, value: &Box<NP_JSON>) -> Result<(), NP_Error> where Self: 'set + Sized {
+                // saturating also stops at the schema range, the same as `set_saturating`
+                let saturate_to_schema = |mut value: $t| -> $t {
+                    if let NumericOverflowPolicy::Saturate = memory.options.overflow_policy {
+                        let (min, max) = <$t>::np_get_range(cursor.schema_addr, memory.get_schemas());
+                        if let Some(x) = min { if value < x { value = x; } }
+                        if let Some(x) = max { if value > x { value = x; } }
+                    }
+                    value
+                };
+
+                // JSON numbers and numbers sent as strings share the same overflow and range handling
+                let set_int = |int: i128| -> Result<(), NP_Error> {
+                    let (min, max) = (<$t>::MIN as i128, <$t>::MAX as i128);
+                    let in_range = int >= min && int <= max;
+                    let value = match (in_range, memory.options.overflow_policy) {
+                        (true, _) | (false, NumericOverflowPolicy::Wrap) => int as $t,
+                        (false, NumericOverflowPolicy::Saturate) => if int < min { <$t>::MIN } else { <$t>::MAX },
+                        (false, NumericOverflowPolicy::Error) => return Err(np_overflow_error(&int, Self::type_idx().0))
+                    };
+                    Self::set_value(cursor, memory, saturate_to_schema(value))?;
+                    Ok(())
+                };
+
+                let set_float = |float: f64| -> Result<(), NP_Error> {
+                    // `<$t>::MAX as f64` rounds up for 64 bit integers, so integer types check against the exact power of two above their max
+                    let bits = core::mem::size_of::<$t>() * 8;
+                    let in_range = match $numType {
+                        NP_NumType::unsigned => float >= 0.0 && float < (1u128 << bits) as f64,
+                        NP_NumType::signed => float >= -((1u128 << (bits - 1)) as f64) && float < (1u128 << (bits - 1)) as f64,
+                        NP_NumType::floating => float >= <$t>::MIN as f64 && float <= <$t>::MAX as f64
+                    };
+                    let value = match (in_range, memory.options.overflow_policy) {
+                        (true, _) | (false, NumericOverflowPolicy::Wrap) => float as $t,
+                        (false, NumericOverflowPolicy::Saturate) => if float < 0.0 { <$t>::MIN } else { <$t>::MAX },
+                        (false, NumericOverflowPolicy::Error) => return Err(np_overflow_error(&float, Self::type_idx().0))
+                    };
+                    // a fraction would be dropped when writing into an integer type
+                    let is_integer = match $numType { NP_NumType::floating => false, _ => true };
+                    if memory.options.lossless_json && in_range && is_integer && value as f64 != float {
+                        return Err(np_lossy_error(&float, Self::type_idx().0));
+                    }
+                    Self::set_value(cursor, memory, saturate_to_schema(value))?;
+                    Ok(())
+                };
+
                 match **value {
-                    NP_JSON::Integer(int) => {
-                        Self::set_value(cursor, memory, int as $t)?;
+                    NP_JSON::Integer(int) => { set_int(int as i128)?; },
+                    NP_JSON::Float(float, _) => { set_float(float)?; },
+                    NP_JSON::Null => {
+                        // null writes the schema default if there is one, otherwise clears the value
+                        if apply_null {
+                            match (memory.options.null_clears, <$t>::np_get_default(cursor.schema_addr, memory.get_schemas())) {
+                                (false, Some(default)) => { Self::set_value(cursor, memory, default)?; },
+                                _ => { NP_Cursor::delete(cursor, memory)?; }
+                            }
+                        }
                     },
-                    NP_JSON::Float(float) => {
-                        Self::set_value(cursor, memory, float as $t)?;
+                    NP_JSON::String(ref text) => {
+                        // numbers too large for a JSON number are often sent as strings
+                        let is_integer = match $numType { NP_NumType::floating => false, _ => true };
+                        if let (true, Ok(int)) = (is_integer, text.trim().parse::<i128>()) {
+                            return set_int(int);
+                        }
+                        if let Ok(float) = text.trim().parse::<f64>() {
+                            if float.is_finite() {
+                                return set_float(float);
+                            }
+                        }
+                        match <$t>::np_timestamp_format(cursor.schema_addr, memory.get_schemas()) {
+                            Some(format) => {
+                                // text that isn't a valid timestamp is treated like any other mismatched value
+                                match format.from_iso(text) {
+                                    Ok(x) => { set_int(x as i128)?; },
+                                    Err(e) => if memory.options.strict_json { return Err(e) }
+                                }
+                            },
+                            None => if memory.options.strict_json {
+                                return Err(np_parse_error(text, Self::type_idx().0))
+                            } else {
+                                NP_Cursor::json_type_mismatch(memory, "number", value)?;
+                            }
+                        }
                     },
-                    _ => {}
+                    _ => { NP_Cursor::json_type_mismatch(memory, "number", value)?; }
                 }
 
                 Ok(())
@@ -104,7 +505,9 @@ macro_rules! noproto_number {
                 let mut schema_json = JSMAP::new();
                 schema_json.insert("type".to_owned(), NP_JSON::String(Self::type_idx().0.to_string()));
             
-                if let Some(default) = <$t>::np_get_default(address, &schema) {
+                if let Some(bound) = <$t>::np_default_bound(address, &schema) {
+                    schema_json.insert("default".to_owned(), NP_JSON::String(bound.schema_name().to_owned()));
+                } else if let Some(default) = <$t>::np_get_default(address, &schema) {
                     let default_val = default;
                     match $numType {
                         NP_NumType::signed => {
@@ -114,11 +517,34 @@ macro_rules! noproto_number {
                             schema_json.insert("default".to_owned(), NP_JSON::Integer(default_val as i64));
                         },
                         NP_NumType::floating => {
-                            schema_json.insert("default".to_owned(), NP_JSON::Float(default_val as f64));
+                            schema_json.insert("default".to_owned(), NP_JSON::Float(default_val as f64, None));
                         }
                     };
                     
                 }
+
+                let (min, max) = <$t>::np_get_range(address, &schema);
+                for (key, bound) in [("min", min), ("max", max)].iter() {
+                    if let Some(x) = bound {
+                        schema_json.insert((*key).to_owned(), match $numType {
+                            NP_NumType::floating => NP_JSON::Float(*x as f64, None),
+                            NP_NumType::unsigned => unsigned_to_json(*x as u64),
+                            NP_NumType::signed => NP_JSON::Integer(*x as i64)
+                        });
+                    }
+                }
+
+                if <$t>::np_whole_as_int(address, &schema) {
+                    schema_json.insert("whole_as_int".to_owned(), NP_JSON::True);
+                }
+
+                if let Some(places) = <$t>::np_display_places(address, &schema) {
+                    schema_json.insert("display_places".to_owned(), NP_JSON::Integer(places as i64));
+                }
+
+                if let Some(format) = <$t>::np_timestamp_format(address, &schema) {
+                    schema_json.insert("format".to_owned(), NP_JSON::String(format.schema_name().to_owned()));
+                }
         
                 Ok(NP_JSON::Dictionary(schema_json))
             }
@@ -126,12 +552,55 @@ macro_rules! noproto_number {
             fn schema_to_idl(schema: &Vec<NP_Parsed_Schema>, address: usize)-> Result<String, NP_Error> {
                 let mut result = String::from($str2);
 
-                if let Some(default) = <$t>::np_get_default(address, &schema) {
-                    result.push_str("({default: ");
-                    result.push_str(default.to_string().as_str());
-                    result.push_str("})");
-                } else {
+                let mut properties: Vec<String> = Vec::new();
+
+                if let Some(bound) = <$t>::np_default_bound(address, &schema) {
+                    let mut def = String::from("default: \"");
+                    def.push_str(bound.schema_name());
+                    def.push_str("\"");
+                    properties.push(def);
+                } else if let Some(default) = <$t>::np_get_default(address, &schema) {
+                    let mut def = String::from("default: ");
+                    def.push_str(default.to_string().as_str());
+                    properties.push(def);
+                }
+
+                let (min, max) = <$t>::np_get_range(address, &schema);
+                for (key, bound) in [("min: ", min), ("max: ", max)].iter() {
+                    if let Some(x) = bound {
+                        let mut prop = String::from(*key);
+                        prop.push_str(x.to_string().as_str());
+                        properties.push(prop);
+                    }
+                }
+
+                if <$t>::np_whole_as_int(address, &schema) {
+                    properties.push(String::from("whole_as_int: true"));
+                }
+
+                if let Some(places) = <$t>::np_display_places(address, &schema) {
+                    let mut prop = String::from("display_places: ");
+                    prop.push_str(places.to_string().as_str());
+                    properties.push(prop);
+                }
+
+                if let Some(format) = <$t>::np_timestamp_format(address, &schema) {
+                    let mut prop = String::from("format: \"");
+                    prop.push_str(format.schema_name());
+                    prop.push_str("\"");
+                    properties.push(prop);
+                }
+
+                if NP_Schema::is_required(&schema[address]) {
+                    properties.push(String::from("required: true"));
+                }
+
+                if properties.len() == 0 {
                     result.push_str("()");
+                } else {
+                    result.push_str("({");
+                    result.push_str(properties.join(", ").as_str());
+                    result.push_str("})");
                 }
 
                 Ok(result)
@@ -141,6 +610,12 @@ macro_rules! noproto_number {
                 
                 let mut default: Option<$t> = None;
                 let mut default_str: Option<String> = None;
+                let mut whole_as_int = false;
+                let mut display_places: Option<i64> = None;
+                let mut format: Option<NP_TimestampFormat> = None;
+                let mut min_str: Option<String> = None;
+                let mut max_str: Option<String> = None;
+                let mut default_bound: Option<NP_NumberBound> = None;
 
                 if args.len() > 0 {
                     match &args[0] {
@@ -159,6 +634,48 @@ macro_rules! noproto_number {
                                                     Err(_e) => {  }
                                                 }
                                             },
+                                            JS_AST::string { addr } => {
+                                                default_bound = Some(NP_NumberBound::from_schema_name(idl.get_str(addr).trim())?);
+                                            },
+                                            _ => { }
+                                        }
+                                    },
+                                    "whole_as_int" => {
+                                        match value {
+                                            JS_AST::bool { state } => {
+                                                whole_as_int = *state;
+                                            },
+                                            _ => { }
+                                        }
+                                    },
+                                    "display_places" => {
+                                        match value {
+                                            JS_AST::number { addr } => {
+                                                match idl.get_str(addr).trim().parse::<i64>() {
+                                                    Ok(x) => { display_places = Some(x); },
+                                                    Err(_e) => { return Err(NP_Error::new("Float 'display_places' property must be a whole number!")) }
+                                                }
+                                            },
+                                            _ => { }
+                                        }
+                                    },
+                                    "format" => {
+                                        match value {
+                                            JS_AST::string { addr } => {
+                                                format = Some(NP_TimestampFormat::from_schema_name(idl.get_str(addr).trim())?);
+                                            },
+                                            _ => { }
+                                        }
+                                    },
+                                    "min" => {
+                                        match value {
+                                            JS_AST::number { addr } => { min_str = Some(String::from(idl.get_str(addr).trim())); },
+                                            _ => { }
+                                        }
+                                    },
+                                    "max" => {
+                                        match value {
+                                            JS_AST::number { addr } => { max_str = Some(String::from(idl.get_str(addr).trim())); },
                                             _ => { }
                                         }
                                     },
@@ -170,6 +687,15 @@ macro_rules! noproto_number {
                     }
                 }
 
+                let min: Option<$t> = np_parse_range(&min_str, "min", Self::type_idx().0)?;
+                let max: Option<$t> = np_parse_range(&max_str, "max", Self::type_idx().0)?;
+
+                if let Some(bound) = default_bound {
+                    let x = bound.resolve(min, max, <$t>::MIN, <$t>::MAX);
+                    default_str = Some(x.to_string());
+                    default = Some(x);
+                }
+
                 let mut schema_data: Vec<u8> = Vec::new();
                 schema_data.push($tkey as u8);
 
@@ -180,6 +706,33 @@ macro_rules! noproto_number {
                     schema_data.push(0);
                 }
 
+                let display_places = match $numType {
+                    NP_NumType::floating => {
+                        let places = np_check_display_places(display_places)?;
+                        schema_data.push(np_float_options_byte(whole_as_int, places));
+                        places
+                    },
+                    _ => None
+                };
+
+                if format.is_some() && $tkey != NP_TypeKeys::Int64 {
+                    return Err(NP_Error::new("Only i64 types support the 'format' property!"))
+                }
+                schema_data[1] |= NP_TimestampFormat::to_schema_bits(format) << TIMESTAMP_SHIFT;
+
+                schema_data[1] |= NP_NumberBound::to_schema_bits(default_bound) << DEFAULT_BOUND_SHIFT;
+
+                // min and max go after everything else so older readers still find the float options
+                np_check_schema_range(default, min, max)?;
+                if let Some(x) = min {
+                    schema_data[1] |= RANGE_MIN_BIT;
+                    schema_data.extend_from_slice(&x.to_be_bytes());
+                }
+                if let Some(x) = max {
+                    schema_data[1] |= RANGE_MAX_BIT;
+                    schema_data.extend_from_slice(&x.to_be_bytes());
+                }
+
                 let use_schema = NP_Parsed_Schema {
                     i: $tkey,
                     val: NP_Value_Kind::Fixed(core::mem::size_of::<Self>() as u32),
@@ -189,34 +742,34 @@ macro_rules! noproto_number {
                     },
                     data: Arc::new(match $tkey {
                         NP_TypeKeys::Int8 => {
-                            Box::into_raw(Box::new(NP_i8_Data { default: i8::np_unwrap_default(default_str) })) as *const u8
+                            Box::into_raw(Box::new(NP_i8_Data { default: i8::np_unwrap_default(default_str), default_bound, min: i8::np_unwrap_default(min_str.clone()), max: i8::np_unwrap_default(max_str.clone()) })) as *const u8
                         },
                         NP_TypeKeys::Int16 => {
-                            Box::into_raw(Box::new(NP_i16_Data { default: i16::np_unwrap_default(default_str) })) as *const u8
+                            Box::into_raw(Box::new(NP_i16_Data { default: i16::np_unwrap_default(default_str), default_bound, min: i16::np_unwrap_default(min_str.clone()), max: i16::np_unwrap_default(max_str.clone()) })) as *const u8
                         },
                         NP_TypeKeys::Int32 => {
-                            Box::into_raw(Box::new(NP_i32_Data { default: i32::np_unwrap_default(default_str) })) as *const u8
+                            Box::into_raw(Box::new(NP_i32_Data { default: i32::np_unwrap_default(default_str), default_bound, min: i32::np_unwrap_default(min_str.clone()), max: i32::np_unwrap_default(max_str.clone()) })) as *const u8
                         },
                         NP_TypeKeys::Int64 => {
-                            Box::into_raw(Box::new(NP_i64_Data { default: i64::np_unwrap_default(default_str) })) as *const u8
+                            Box::into_raw(Box::new(NP_i64_Data { default: i64::np_unwrap_default(default_str), default_bound, min: i64::np_unwrap_default(min_str.clone()), max: i64::np_unwrap_default(max_str.clone()), format })) as *const u8
                         },
                         NP_TypeKeys::Uint8 => {
-                            Box::into_raw(Box::new(NP_u8_Data { default: u8::np_unwrap_default(default_str) })) as *const u8
+                            Box::into_raw(Box::new(NP_u8_Data { default: u8::np_unwrap_default(default_str), default_bound, min: u8::np_unwrap_default(min_str.clone()), max: u8::np_unwrap_default(max_str.clone()) })) as *const u8
                         },
                         NP_TypeKeys::Uint16 => {
-                            Box::into_raw(Box::new(NP_u16_Data { default: u16::np_unwrap_default(default_str) })) as *const u8
+                            Box::into_raw(Box::new(NP_u16_Data { default: u16::np_unwrap_default(default_str), default_bound, min: u16::np_unwrap_default(min_str.clone()), max: u16::np_unwrap_default(max_str.clone()) })) as *const u8
                         },
                         NP_TypeKeys::Uint32 => {
-                            Box::into_raw(Box::new(NP_u32_Data { default: u32::np_unwrap_default(default_str) })) as *const u8
+                            Box::into_raw(Box::new(NP_u32_Data { default: u32::np_unwrap_default(default_str), default_bound, min: u32::np_unwrap_default(min_str.clone()), max: u32::np_unwrap_default(max_str.clone()) })) as *const u8
                         },
                         NP_TypeKeys::Uint64 => {
-                            Box::into_raw(Box::new(NP_u64_Data { default: u64::np_unwrap_default(default_str) })) as *const u8
+                            Box::into_raw(Box::new(NP_u64_Data { default: u64::np_unwrap_default(default_str), default_bound, min: u64::np_unwrap_default(min_str.clone()), max: u64::np_unwrap_default(max_str.clone()) })) as *const u8
                         },
                         NP_TypeKeys::Float => {
-                            Box::into_raw(Box::new(NP_f32_Data { default: f32::np_unwrap_default(default_str) })) as *const u8
+                            Box::into_raw(Box::new(NP_f32_Data { default: f32::np_unwrap_default(default_str), default_bound, min: f32::np_unwrap_default(min_str.clone()), max: f32::np_unwrap_default(max_str.clone()), whole_as_int, display_places })) as *const u8
                         },
                         NP_TypeKeys::Double => {
-                            Box::into_raw(Box::new(NP_f64_Data { default: f64::np_unwrap_default(default_str) })) as *const u8
+                            Box::into_raw(Box::new(NP_f64_Data { default: f64::np_unwrap_default(default_str), default_bound, min: f64::np_unwrap_default(min_str.clone()), max: f64::np_unwrap_default(max_str.clone()), whole_as_int, display_places })) as *const u8
                         },
                         _ => { NULL() }
                     })
@@ -233,6 +786,9 @@ macro_rules! noproto_number {
     
             fn set_value<'set>(cursor: NP_Cursor, memory: &'set NP_Memory, value: Self) -> Result<NP_Cursor, NP_Error> where Self: 'set + Sized {
 
+                let (min, max) = <$t>::np_get_range(cursor.schema_addr, memory.get_schemas());
+                np_check_value_range(value, min, max, Self::type_idx().0)?;
+
                 let c_value = || { cursor.get_value(memory) };
 
                 let mut value_address = c_value().get_addr_value() as usize;
@@ -307,16 +863,19 @@ macro_rules! noproto_number {
                         match x {
                             Some(y) => {
                                 match $numType {
-                                    NP_NumType::floating => NP_JSON::Float(y as f64),
-                                    _ => NP_JSON::Integer(y as i64)
+                                    NP_NumType::floating => float_to_json(y as f64, <$t>::np_whole_as_int(cursor.schema_addr, &memory.get_schemas()), <$t>::np_display_places(cursor.schema_addr, &memory.get_schemas())),
+                                    NP_NumType::unsigned => unsigned_to_json(y as u64),
+                                    _ => signed_to_json(y as i64, <$t>::np_timestamp_format(cursor.schema_addr, &memory.get_schemas()))
                                 }
                             },
+                            None if memory.options.emit_null_for_unset => NP_JSON::Null,
                             None => {
                                 match <$t>::default_value(0, cursor.schema_addr, &memory.get_schemas()) {
                                     Some(v) => {
                                         match $numType {
-                                            NP_NumType::floating => { NP_JSON::Float(v as f64) },
-                                            _ => { NP_JSON::Integer(v as i64) }
+                                            NP_NumType::floating => { float_to_json(v as f64, <$t>::np_whole_as_int(cursor.schema_addr, &memory.get_schemas()), <$t>::np_display_places(cursor.schema_addr, &memory.get_schemas())) },
+                                            NP_NumType::unsigned => { unsigned_to_json(v as u64) },
+                                            _ => { signed_to_json(v as i64, <$t>::np_timestamp_format(cursor.schema_addr, &memory.get_schemas())) }
                                         }
                                     },
                                     None => NP_JSON::Null
@@ -343,23 +902,75 @@ macro_rules! noproto_number {
 
             fn from_json_to_schema(mut schema: Vec<NP_Parsed_Schema>, json_schema: &Box<NP_JSON>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
         
+                let min_str = np_range_str_from_json(&json_schema["min"], "min")?;
+                let max_str = np_range_str_from_json(&json_schema["max"], "max")?;
+                let min: Option<$t> = np_parse_range(&min_str, "min", Self::type_idx().0)?;
+                let max: Option<$t> = np_parse_range(&max_str, "max", Self::type_idx().0)?;
+
+                let default_bound = match &json_schema["default"] {
+                    NP_JSON::String(x) => Some(NP_NumberBound::from_schema_name(x.as_str())?),
+                    _ => None
+                };
+
+                let default = match default_bound {
+                    Some(bound) => Some(bound.resolve(min, max, <$t>::MIN, <$t>::MAX)),
+                    None => <$t>::np_get_default_from_json(&json_schema["default"])
+                };
+
                 let mut schema_data: Vec<u8> = Vec::new();
                 schema_data.push($tkey as u8);
-    
-                match json_schema["default"] {
-                    NP_JSON::Float(x) => {
-                        schema_data.push(1);
-                        schema_data.extend((x as $t).to_be_bytes().to_vec());
-                    },
-                    NP_JSON::Integer(x) => {
-                        schema_data.push(1);
-                        schema_data.extend((x as $t).to_be_bytes().to_vec());
+
+                if let Some(x) = default {
+                    schema_data.push(1);
+                    schema_data.extend_from_slice(&x.to_be_bytes());
+                } else {
+                    schema_data.push(0);
+                }
+
+                let whole_as_int = match json_schema["whole_as_int"] {
+                    NP_JSON::True => true,
+                    _ => false
+                };
+
+                let display_places = match json_schema["display_places"] {
+                    NP_JSON::Integer(x) => Some(x),
+                    NP_JSON::Null => None,
+                    _ => { return Err(NP_Error::new("Float 'display_places' property must be a whole number!")) }
+                };
+
+                let format = match &json_schema["format"] {
+                    NP_JSON::String(x) => Some(NP_TimestampFormat::from_schema_name(x.as_str())?),
+                    NP_JSON::Null => None,
+                    _ => { return Err(NP_Error::new("Number 'format' property must be one of 'unix_seconds' or 'unix_millis'!")) }
+                };
+
+                let display_places = match $numType {
+                    NP_NumType::floating => {
+                        let places = np_check_display_places(display_places)?;
+                        schema_data.push(np_float_options_byte(whole_as_int, places));
+                        places
                     },
-                    _ => {
-                        schema_data.push(0);
-                    }
+                    _ => None
                 };
 
+                if format.is_some() && $tkey != NP_TypeKeys::Int64 {
+                    return Err(NP_Error::new("Only i64 types support the 'format' property!"))
+                }
+                schema_data[1] |= NP_TimestampFormat::to_schema_bits(format) << TIMESTAMP_SHIFT;
+
+                schema_data[1] |= NP_NumberBound::to_schema_bits(default_bound) << DEFAULT_BOUND_SHIFT;
+
+                // min and max go after everything else so older readers still find the float options
+                np_check_schema_range(default, min, max)?;
+                if let Some(x) = min {
+                    schema_data[1] |= RANGE_MIN_BIT;
+                    schema_data.extend_from_slice(&x.to_be_bytes());
+                }
+                if let Some(x) = max {
+                    schema_data[1] |= RANGE_MAX_BIT;
+                    schema_data.extend_from_slice(&x.to_be_bytes());
+                }
+
                 let use_schema = NP_Parsed_Schema {
                     i: $tkey,
                     val: NP_Value_Kind::Fixed(core::mem::size_of::<Self>() as u32),
@@ -369,34 +980,34 @@ macro_rules! noproto_number {
                     },
                     data: Arc::new(match $tkey {
                         NP_TypeKeys::Int8 => {
-                            Box::into_raw(Box::new(NP_i8_Data { default: i8::np_get_default_from_json(&json_schema["default"]) })) as *const u8
+                            Box::into_raw(Box::new(NP_i8_Data { default: default.map(|x| x as i8), default_bound, min: i8::np_unwrap_default(min_str.clone()), max: i8::np_unwrap_default(max_str.clone()) })) as *const u8
                         },
                         NP_TypeKeys::Int16 => {
-                            Box::into_raw(Box::new(NP_i16_Data { default: i16::np_get_default_from_json(&json_schema["default"]) })) as *const u8
+                            Box::into_raw(Box::new(NP_i16_Data { default: default.map(|x| x as i16), default_bound, min: i16::np_unwrap_default(min_str.clone()), max: i16::np_unwrap_default(max_str.clone()) })) as *const u8
                         },
                         NP_TypeKeys::Int32 => {
-                            Box::into_raw(Box::new(NP_i32_Data { default: i32::np_get_default_from_json(&json_schema["default"]) })) as *const u8
+                            Box::into_raw(Box::new(NP_i32_Data { default: default.map(|x| x as i32), default_bound, min: i32::np_unwrap_default(min_str.clone()), max: i32::np_unwrap_default(max_str.clone()) })) as *const u8
                         },
                         NP_TypeKeys::Int64 => {
-                            Box::into_raw(Box::new(NP_i64_Data { default: i64::np_get_default_from_json(&json_schema["default"]) })) as *const u8
+                            Box::into_raw(Box::new(NP_i64_Data { default: default.map(|x| x as i64), default_bound, min: i64::np_unwrap_default(min_str.clone()), max: i64::np_unwrap_default(max_str.clone()), format })) as *const u8
                         },
                         NP_TypeKeys::Uint8 => {
-                            Box::into_raw(Box::new(NP_u8_Data { default: u8::np_get_default_from_json(&json_schema["default"]) })) as *const u8
+                            Box::into_raw(Box::new(NP_u8_Data { default: default.map(|x| x as u8), default_bound, min: u8::np_unwrap_default(min_str.clone()), max: u8::np_unwrap_default(max_str.clone()) })) as *const u8
                         },
                         NP_TypeKeys::Uint16 => {
-                            Box::into_raw(Box::new(NP_u16_Data { default: u16::np_get_default_from_json(&json_schema["default"]) })) as *const u8
+                            Box::into_raw(Box::new(NP_u16_Data { default: default.map(|x| x as u16), default_bound, min: u16::np_unwrap_default(min_str.clone()), max: u16::np_unwrap_default(max_str.clone()) })) as *const u8
                         },
                         NP_TypeKeys::Uint32 => {
-                            Box::into_raw(Box::new(NP_u32_Data { default: u32::np_get_default_from_json(&json_schema["default"]) })) as *const u8
+                            Box::into_raw(Box::new(NP_u32_Data { default: default.map(|x| x as u32), default_bound, min: u32::np_unwrap_default(min_str.clone()), max: u32::np_unwrap_default(max_str.clone()) })) as *const u8
                         },
                         NP_TypeKeys::Uint64 => {
-                            Box::into_raw(Box::new(NP_u64_Data { default: u64::np_get_default_from_json(&json_schema["default"]) })) as *const u8
+                            Box::into_raw(Box::new(NP_u64_Data { default: default.map(|x| x as u64), default_bound, min: u64::np_unwrap_default(min_str.clone()), max: u64::np_unwrap_default(max_str.clone()) })) as *const u8
                         },
                         NP_TypeKeys::Float => {
-                            Box::into_raw(Box::new(NP_f32_Data { default: f32::np_get_default_from_json(&json_schema["default"]) })) as *const u8
+                            Box::into_raw(Box::new(NP_f32_Data { default: default.map(|x| x as f32), default_bound, min: f32::np_unwrap_default(min_str.clone()), max: f32::np_unwrap_default(max_str.clone()), whole_as_int, display_places })) as *const u8
                         },
                         NP_TypeKeys::Double => {
-                            Box::into_raw(Box::new(NP_f64_Data { default: f64::np_get_default_from_json(&json_schema["default"]) })) as *const u8
+                            Box::into_raw(Box::new(NP_f64_Data { default: default.map(|x| x as f64), default_bound, min: f64::np_unwrap_default(min_str.clone()), max: f64::np_unwrap_default(max_str.clone()), whole_as_int, display_places })) as *const u8
                         },
                         _ => { NULL() }
                     })
@@ -419,34 +1030,44 @@ macro_rules! noproto_number {
                     },
                     data: Arc::new(match $tkey {
                         NP_TypeKeys::Int8 => {
-                            Box::into_raw(Box::new(NP_i8_Data { default: i8::np_get_default_from_bytes(address, bytes) })) as *const u8
+                            let (min, max) = i8::np_range_from_bytes(address, bytes);
+                            Box::into_raw(Box::new(NP_i8_Data { default: i8::np_get_default_from_bytes(address, bytes), default_bound: NP_NumberBound::from_schema_bits((bytes[address + 1] >> DEFAULT_BOUND_SHIFT) & 3), min, max })) as *const u8
                         },
                         NP_TypeKeys::Int16 => {
-                            Box::into_raw(Box::new(NP_i16_Data { default: i16::np_get_default_from_bytes(address, bytes) })) as *const u8
+                            let (min, max) = i16::np_range_from_bytes(address, bytes);
+                            Box::into_raw(Box::new(NP_i16_Data { default: i16::np_get_default_from_bytes(address, bytes), default_bound: NP_NumberBound::from_schema_bits((bytes[address + 1] >> DEFAULT_BOUND_SHIFT) & 3), min, max })) as *const u8
                         },
                         NP_TypeKeys::Int32 => {
-                            Box::into_raw(Box::new(NP_i32_Data { default: i32::np_get_default_from_bytes(address, bytes) })) as *const u8
+                            let (min, max) = i32::np_range_from_bytes(address, bytes);
+                            Box::into_raw(Box::new(NP_i32_Data { default: i32::np_get_default_from_bytes(address, bytes), default_bound: NP_NumberBound::from_schema_bits((bytes[address + 1] >> DEFAULT_BOUND_SHIFT) & 3), min, max })) as *const u8
                         },
                         NP_TypeKeys::Int64 => {
-                            Box::into_raw(Box::new(NP_i64_Data { default: i64::np_get_default_from_bytes(address, bytes) })) as *const u8
+                            let (min, max) = i64::np_range_from_bytes(address, bytes);
+                            Box::into_raw(Box::new(NP_i64_Data { default: i64::np_get_default_from_bytes(address, bytes), default_bound: NP_NumberBound::from_schema_bits((bytes[address + 1] >> DEFAULT_BOUND_SHIFT) & 3), min, max, format: NP_TimestampFormat::from_schema_bits((bytes[address + 1] >> TIMESTAMP_SHIFT) & 3) })) as *const u8
                         },
                         NP_TypeKeys::Uint8 => {
-                            Box::into_raw(Box::new(NP_u8_Data { default: u8::np_get_default_from_bytes(address, bytes) })) as *const u8
+                            let (min, max) = u8::np_range_from_bytes(address, bytes);
+                            Box::into_raw(Box::new(NP_u8_Data { default: u8::np_get_default_from_bytes(address, bytes), default_bound: NP_NumberBound::from_schema_bits((bytes[address + 1] >> DEFAULT_BOUND_SHIFT) & 3), min, max })) as *const u8
                         },
                         NP_TypeKeys::Uint16 => {
-                            Box::into_raw(Box::new(NP_u16_Data { default: u16::np_get_default_from_bytes(address, bytes) })) as *const u8
+                            let (min, max) = u16::np_range_from_bytes(address, bytes);
+                            Box::into_raw(Box::new(NP_u16_Data { default: u16::np_get_default_from_bytes(address, bytes), default_bound: NP_NumberBound::from_schema_bits((bytes[address + 1] >> DEFAULT_BOUND_SHIFT) & 3), min, max })) as *const u8
                         },
                         NP_TypeKeys::Uint32 => {
-                            Box::into_raw(Box::new(NP_u32_Data { default: u32::np_get_default_from_bytes(address, bytes) })) as *const u8
+                            let (min, max) = u32::np_range_from_bytes(address, bytes);
+                            Box::into_raw(Box::new(NP_u32_Data { default: u32::np_get_default_from_bytes(address, bytes), default_bound: NP_NumberBound::from_schema_bits((bytes[address + 1] >> DEFAULT_BOUND_SHIFT) & 3), min, max })) as *const u8
                         },
                         NP_TypeKeys::Uint64 => {
-                            Box::into_raw(Box::new(NP_u64_Data { default: u64::np_get_default_from_bytes(address, bytes) })) as *const u8
+                            let (min, max) = u64::np_range_from_bytes(address, bytes);
+                            Box::into_raw(Box::new(NP_u64_Data { default: u64::np_get_default_from_bytes(address, bytes), default_bound: NP_NumberBound::from_schema_bits((bytes[address + 1] >> DEFAULT_BOUND_SHIFT) & 3), min, max })) as *const u8
                         },
                         NP_TypeKeys::Float => {
-                            Box::into_raw(Box::new(NP_f32_Data { default: f32::np_get_default_from_bytes(address, bytes) })) as *const u8
+                            let (min, max) = f32::np_range_from_bytes(address, bytes);
+                            Box::into_raw(Box::new(NP_f32_Data { default: f32::np_get_default_from_bytes(address, bytes), default_bound: NP_NumberBound::from_schema_bits((bytes[address + 1] >> DEFAULT_BOUND_SHIFT) & 3), min, max, whole_as_int: np_float_options_from_bytes::<f32>(address, bytes) & 1 == 1, display_places: np_display_places_from_bytes::<f32>(address, bytes) })) as *const u8
                         },
                         NP_TypeKeys::Double => {
-                            Box::into_raw(Box::new(NP_f64_Data { default: f64::np_get_default_from_bytes(address, bytes) })) as *const u8
+                            let (min, max) = f64::np_range_from_bytes(address, bytes);
+                            Box::into_raw(Box::new(NP_f64_Data { default: f64::np_get_default_from_bytes(address, bytes), default_bound: NP_NumberBound::from_schema_bits((bytes[address + 1] >> DEFAULT_BOUND_SHIFT) & 3), min, max, whole_as_int: np_float_options_from_bytes::<f64>(address, bytes) & 1 == 1, display_places: np_display_places_from_bytes::<f64>(address, bytes) })) as *const u8
                         },
                         _ => { NULL() }
                     })
@@ -456,6 +1077,54 @@ macro_rules! noproto_number {
                 (schema[schema.len() - 1].sortable, schema)
             }
         }
+
+        impl NP_SchemaRange for $t {
+            fn np_range_from_bytes(address: usize, bytes: &[u8]) -> (Option<Self>, Option<Self>) {
+                let size = core::mem::size_of::<$t>();
+                let flags = bytes[address + 1];
+
+                // skip the default and the float options byte
+                let mut offset = address + 2;
+                if flags & 1 == 1 { offset += size; }
+                if let NP_NumType::floating = $numType { offset += 1; }
+
+                let mut read = |bit: u8| {
+                    if flags & bit == 0 { return None }
+                    let mut be_bytes = <$t>::default().to_be_bytes();
+                    be_bytes.copy_from_slice(&bytes[offset..(offset + size)]);
+                    offset += size;
+                    Some(<$t>::from_be_bytes(be_bytes))
+                };
+
+                let min = read(RANGE_MIN_BIT);
+                let max = read(RANGE_MAX_BIT);
+                (min, max)
+            }
+        }
+
+        impl NP_NumberCast for $t {
+            fn set_saturating(cursor: NP_Cursor, memory: &NP_Memory, value: i128) -> Result<NP_Cursor, NP_Error> {
+                let mut clamped = if value < <$t>::MIN as i128 {
+                    <$t>::MIN
+                } else if value > <$t>::MAX as i128 {
+                    <$t>::MAX
+                } else {
+                    value as $t
+                };
+
+                // the schema range is narrower than the type
+                let (min, max) = <$t>::np_get_range(cursor.schema_addr, memory.get_schemas());
+                if let Some(x) = min { if clamped < x { clamped = x; } }
+                if let Some(x) = max { if clamped > x { clamped = x; } }
+
+                Self::set_value(cursor, memory, clamped)
+            }
+
+            fn set_wrapping(cursor: NP_Cursor, memory: &NP_Memory, value: i128) -> Result<NP_Cursor, NP_Error> {
+                // `as` between integers keeps the low bits, the same as core's wrapping conversions
+                Self::set_value(cursor, memory, value as $t)
+            }
+        }
     }
 }
 
@@ -476,46 +1145,257 @@ noproto_number!(f32,  "float", "f32", NP_TypeKeys::Float , NP_NumType::floating)
 noproto_number!(f64, "double", "f64", NP_TypeKeys::Double, NP_NumType::floating);
 
 
-trait NP_BigEndian {
-    fn np_get_default_from_json(json: &NP_JSON) -> Option<Self> where Self: Sized;
-    fn np_get_default_from_bytes<'default>(address: usize, bytes: &'default [u8]) -> Option<Self> where Self: Sized;
-    fn np_get_default<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<Self> where Self: Sized;
-    fn np_unwrap_default(value: Option<String>) -> Option<Self> where Self: Sized + FromStr {
-        if let Some(x) = value {
-            if let Ok(y) = x.parse::<Self>() {
-                Some(y)
-            } else {
-                None
-            }
-        } else {
-            None
-        }
+/// Float values that are whole and within the safe integer range of an f64 are emitted as JSON integers when `whole_as_int` is set.
+///
+/// With `display_places` set the value is rounded (half up) to that many decimal places first, the stored value is untouched.
+fn float_to_json(value: f64, whole_as_int: bool, display_places: Option<u8>) -> NP_JSON {
+    let value = match display_places {
+        Some(places) => round_for_display(value, places),
+        None => value
+    };
+    if whole_as_int && value > -9007199254740992f64 && value < 9007199254740992f64 && (value as i64) as f64 == value {
+        NP_JSON::Integer(value as i64)
+    } else {
+        NP_JSON::Float(value, None)
     }
 }
 
+/// Unsigned values up to `u32` always fit a JSON integer, `u64` values above `i64::MAX` don't and are emitted as strings instead of wrapping negative.
+pub(crate) fn unsigned_to_json(value: u64) -> NP_JSON {
+    if value > i64::MAX as u64 {
+        NP_JSON::String(value.to_string())
+    } else {
+        NP_JSON::Integer(value as i64)
+    }
+}
 
-impl NP_BigEndian for i8 {
-    fn np_get_default<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<Self> where Self: Sized {
+/// Signed values are written as JSON integers, or as ISO 8601 strings for `i64` fields with a timestamp format.
+fn signed_to_json(value: i64, format: Option<NP_TimestampFormat>) -> NP_JSON {
+    match format.and_then(|f| f.to_iso(value)) {
+        Some(iso) => NP_JSON::String(iso),
+        None => NP_JSON::Integer(value)
+    }
+}
 
-        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_i8_Data) };
-        data.default
+/// Error for a JSON number that doesn't fit the schema type under `NumericOverflowPolicy::Error`.
+fn np_overflow_error<V: ToString>(value: &V, type_name: &str) -> NP_Error {
+    let mut err = String::from("Number out of range for type (");
+    err.push_str(type_name);
+    err.push_str("): ");
+    err.push_str(value.to_string().as_str());
+    NP_Error::new(err)
+}
+
+/// Error for a JSON number with a fraction going into an integer type while lossless JSON is on.
+fn np_lossy_error<V: ToString>(value: &V, type_name: &str) -> NP_Error {
+    let mut err = String::from("Number can't be stored exactly in type (");
+    err.push_str(type_name);
+    err.push_str("): ");
+    err.push_str(value.to_string().as_str());
+    NP_Error::new(err)
+}
+
+/// Error for JSON text that isn't a number of the field type.
+fn np_parse_error(text: &str, type_name: &str) -> NP_Error {
+    let mut err = String::from("Can't parse number for type (");
+    err.push_str(type_name);
+    err.push_str("): ");
+    err.push_str(text);
+    NP_Error::new(err)
+}
+
+/// Flag bits for a schema `min` and `max`, their values are stored after the default (and float options byte) in that order.
+pub(crate) const RANGE_MIN_BIT: u8 = 1 << 3;
+pub(crate) const RANGE_MAX_BIT: u8 = 1 << 4;
+
+/// Shift of the two flag bits that mark a default given as `"min"` or `"max"`.
+pub(crate) const DEFAULT_BOUND_SHIFT: u8 = 5;
+
+/// Read the `min` or `max` range bound out of the flag byte and the bytes after it.
+trait NP_SchemaRange: Sized {
+    fn np_range_from_bytes(address: usize, bytes: &[u8]) -> (Option<Self>, Option<Self>);
+}
+
+/// The text of a `min` or `max` property in a JSON schema, it's parsed into the number type later.
+fn np_range_str_from_json(json: &NP_JSON, name: &str) -> Result<Option<String>, NP_Error> {
+    match json {
+        NP_JSON::Integer(x) => Ok(Some(x.to_string())),
+        NP_JSON::Float(x, raw) => Ok(Some(match raw { Some(text) => text.clone(), None => x.to_string() })),
+        NP_JSON::String(x) => Ok(Some(x.clone())),
+        NP_JSON::Null => Ok(None),
+        _ => {
+            let mut err = String::from("Number '");
+            err.push_str(name);
+            err.push_str("' property must be a number!");
+            Err(NP_Error::new(err))
+        }
     }
+}
 
-    fn np_get_default_from_json(json: &NP_JSON) -> Option<Self> {
-        match json {
-            NP_JSON::Float(x) => {
-                Some(*x as Self)
-            },
-            NP_JSON::Integer(x) => {
-                Some(*x as Self)
-            },
-            _ => {
-                None
+fn np_parse_range<T: FromStr>(text: &Option<String>, name: &str, type_name: &str) -> Result<Option<T>, NP_Error> {
+    match text {
+        Some(x) => match x.parse::<T>() {
+            Ok(value) => Ok(Some(value)),
+            Err(_e) => {
+                let mut err = String::from("Number '");
+                err.push_str(name);
+                err.push_str("' property doesn't fit type (");
+                err.push_str(type_name);
+                err.push_str("): ");
+                err.push_str(x.as_str());
+                Err(NP_Error::new(err))
+            }
+        },
+        None => Ok(None)
+    }
+}
+
+pub(crate) fn np_check_schema_range<T: PartialOrd>(default: Option<T>, min: Option<T>, max: Option<T>) -> Result<(), NP_Error> {
+    if let (Some(low), Some(high)) = (&min, &max) {
+        if low > high {
+            return Err(NP_Error::new("Number 'min' property can't be greater than 'max'!"))
+        }
+    }
+    if let Some(value) = default {
+        if min.map(|x| value < x).unwrap_or(false) || max.map(|x| value > x).unwrap_or(false) {
+            return Err(NP_Error::new("Number 'default' property must be between 'min' and 'max'!"))
+        }
+    }
+    Ok(())
+}
+
+/// Values outside the schema `min` and `max` are refused, NaN is outside any range.
+fn np_check_value_range<T: PartialOrd + ToString>(value: T, min: Option<T>, max: Option<T>, type_name: &str) -> Result<(), NP_Error> {
+    let out_of_range = |side: &str, bound: &T| {
+        let mut err = String::from("Number out of schema range for type (");
+        err.push_str(type_name);
+        err.push_str("): ");
+        err.push_str(value.to_string().as_str());
+        err.push_str(side);
+        err.push_str(bound.to_string().as_str());
+        NP_Error::new(err)
+    };
+    if let Some(x) = &min {
+        if !(value >= *x) { return Err(out_of_range(" is below the min of ", x)) }
+    }
+    if let Some(x) = &max {
+        if !(value <= *x) { return Err(out_of_range(" is above the max of ", x)) }
+    }
+    Ok(())
+}
+
+/// Rounds through the shortest decimal form of the float, so `2.675` displays as `2.68` even though the nearest f64 is slightly below it.
+fn round_for_display(value: f64, places: u8) -> f64 {
+    if value.is_nan() || value.is_infinite() {
+        return value;
+    }
+
+    let dec = NP_Dec::from_float(value, places, NP_RoundMode::HalfUp);
+
+    // saturated, too large to round at this many places
+    if dec.num == i64::MAX || dec.num == i64::MIN {
+        return value;
+    }
+
+    dec.to_string().parse::<f64>().unwrap_or(value)
+}
+
+/// Largest `display_places` a float schema accepts, anything past this can't be rounded through an `NP_Dec`.
+const MAX_DISPLAY_PLACES: i64 = 18;
+
+/// Display places are stored in the float option byte above the `whole_as_int` bit, offset by one so zero means unset.
+const DISPLAY_PLACES_SHIFT: u8 = 1;
+
+pub(crate) fn np_check_display_places(places: Option<i64>) -> Result<Option<u8>, NP_Error> {
+    match places {
+        Some(x) => {
+            if x < 0 || x > MAX_DISPLAY_PLACES {
+                return Err(NP_Error::new("Float 'display_places' property must be between 0 and 18!"))
+            }
+            Ok(Some(x as u8))
+        },
+        None => Ok(None)
+    }
+}
+
+pub(crate) fn np_float_options_byte(whole_as_int: bool, display_places: Option<u8>) -> u8 {
+    let places = match display_places {
+        Some(x) => (x + 1) << DISPLAY_PLACES_SHIFT,
+        None => 0
+    };
+    places | if whole_as_int { 1 } else { 0 }
+}
+
+/// The option byte for float schemas sits right after the (optional) default value.
+fn np_float_options_from_bytes<T>(address: usize, bytes: &[u8]) -> u8 {
+    let option_addr = if bytes[address + 1] & 1 == 0 {
+        address + 2
+    } else {
+        address + 2 + core::mem::size_of::<T>()
+    };
+    *bytes.get(option_addr).unwrap_or(&0)
+}
+
+fn np_display_places_from_bytes<T>(address: usize, bytes: &[u8]) -> Option<u8> {
+    match np_float_options_from_bytes::<T>(address, bytes) >> DISPLAY_PLACES_SHIFT {
+        0 => None,
+        x => Some(x - 1)
+    }
+}
+
+trait NP_BigEndian {
+    fn np_whole_as_int<'default>(_schema_addr: usize, _ptr: &'default Vec<NP_Parsed_Schema>) -> bool { false }
+    fn np_display_places<'default>(_schema_addr: usize, _ptr: &'default Vec<NP_Parsed_Schema>) -> Option<u8> { None }
+    fn np_timestamp_format<'default>(_schema_addr: usize, _ptr: &'default Vec<NP_Parsed_Schema>) -> Option<NP_TimestampFormat> { None }
+    fn np_get_default_from_json(json: &NP_JSON) -> Option<Self> where Self: Sized;
+    fn np_get_default_from_bytes<'default>(address: usize, bytes: &'default [u8]) -> Option<Self> where Self: Sized;
+    fn np_get_default<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<Self> where Self: Sized;
+    fn np_get_range<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> (Option<Self>, Option<Self>) where Self: Sized;
+    fn np_default_bound<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<NP_NumberBound>;
+    fn np_unwrap_default(value: Option<String>) -> Option<Self> where Self: Sized + FromStr {
+        if let Some(x) = value {
+            if let Ok(y) = x.parse::<Self>() {
+                Some(y)
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+}
+
+
+impl NP_BigEndian for i8 {
+    fn np_get_default<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<Self> where Self: Sized {
+
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_i8_Data) };
+        data.default
+    }
+    fn np_get_range<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> (Option<Self>, Option<Self>) {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_i8_Data) };
+        (data.min, data.max)
+    }
+    fn np_default_bound<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<NP_NumberBound> {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_i8_Data) };
+        data.default_bound
+    }
+
+    fn np_get_default_from_json(json: &NP_JSON) -> Option<Self> {
+        match json {
+            NP_JSON::Float(x, _) => {
+                Some(*x as Self)
+            },
+            NP_JSON::Integer(x) => {
+                Some(*x as Self)
+            },
+            _ => {
+                None
             }
         }
     }
     fn np_get_default_from_bytes<'default>(address: usize, bytes: &'default [u8]) -> Option<Self> {
-        if bytes[address + 1] == 0 {
+        if bytes[address + 1] & 1 == 0 {
             None
         } else {
             let mut slice: [u8; 1] = Default::default();
@@ -550,6 +1430,13 @@ fn i8_schema_parsing_works_idl() -> Result<(), NP_Error> {
     let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
     assert_eq!(schema, factory2.schema.to_idl()?);
 
+    let schema = "i8({default: -128})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+    assert_eq!(factory2.new_buffer(None).get::<i8>(&[])?, Some(i8::MIN));
+
     let schema = "i8()";
     let factory = crate::NP_Factory::new(schema)?;
     assert_eq!(schema, factory.schema.to_idl()?);
@@ -592,9 +1479,17 @@ impl NP_BigEndian for i16 {
         let data = unsafe { &*(*ptr[schema_addr].data as *const NP_i16_Data) };
         data.default
     }
+    fn np_get_range<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> (Option<Self>, Option<Self>) {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_i16_Data) };
+        (data.min, data.max)
+    }
+    fn np_default_bound<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<NP_NumberBound> {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_i16_Data) };
+        data.default_bound
+    }
     fn np_get_default_from_json(json: &NP_JSON) -> Option<Self> {
         match json {
-            NP_JSON::Float(x) => {
+            NP_JSON::Float(x, _) => {
                 Some(*x as Self)
             },
             NP_JSON::Integer(x) => {
@@ -606,7 +1501,7 @@ impl NP_BigEndian for i16 {
         }
     }
     fn np_get_default_from_bytes<'default>(address: usize, bytes: &'default [u8]) -> Option<Self> {
-        if bytes[address + 1] == 0 {
+        if bytes[address + 1] & 1 == 0 {
             None
         } else {
             let mut slice: [u8; 2] = Default::default();
@@ -634,6 +1529,31 @@ fn i16_schema_parsing_works() -> Result<(), NP_Error> {
 }
 
 
+#[test]
+fn i16_schema_parsing_works_idl() -> Result<(), NP_Error> {
+    let schema = "i16({default: -98})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+    assert_eq!(factory2.new_buffer(None).get::<i16>(&[])?, Some(-98i16));
+
+    let schema = "i16({default: -32768})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+    assert_eq!(factory2.new_buffer(None).get::<i16>(&[])?, Some(i16::MIN));
+
+    let schema = "i16()";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+
+    Ok(())
+}
+
 #[test]
 fn i16_default_value_works() -> Result<(), NP_Error> {
     let schema = "{\"type\":\"int16\",\"default\":293}";
@@ -667,9 +1587,17 @@ impl NP_BigEndian for i32 {
         let data = unsafe { &*(*ptr[schema_addr].data as *const NP_i32_Data) };
         data.default
     }
+    fn np_get_range<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> (Option<Self>, Option<Self>) {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_i32_Data) };
+        (data.min, data.max)
+    }
+    fn np_default_bound<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<NP_NumberBound> {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_i32_Data) };
+        data.default_bound
+    }
     fn np_get_default_from_json(json: &NP_JSON) -> Option<Self> {
         match json {
-            NP_JSON::Float(x) => {
+            NP_JSON::Float(x, _) => {
                 Some(*x as Self)
             },
             NP_JSON::Integer(x) => {
@@ -681,7 +1609,7 @@ impl NP_BigEndian for i32 {
         }
     }
     fn np_get_default_from_bytes<'default>(address: usize, bytes: &'default [u8]) -> Option<Self> {
-        if bytes[address + 1] == 0 {
+        if bytes[address + 1] & 1 == 0 {
             None
         } else {
             let mut slice: [u8; 4] = Default::default();
@@ -709,6 +1637,31 @@ fn i32_schema_parsing_works() -> Result<(), NP_Error> {
 }
 
 
+#[test]
+fn i32_schema_parsing_works_idl() -> Result<(), NP_Error> {
+    let schema = "i32({default: -98})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+    assert_eq!(factory2.new_buffer(None).get::<i32>(&[])?, Some(-98i32));
+
+    let schema = "i32({default: -2147483648})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+    assert_eq!(factory2.new_buffer(None).get::<i32>(&[])?, Some(i32::MIN));
+
+    let schema = "i32()";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+
+    Ok(())
+}
+
 #[test]
 fn i32_default_value_works() -> Result<(), NP_Error> {
     let schema = "{\"type\":\"i32\",\"default\":293}";
@@ -740,9 +1693,21 @@ impl NP_BigEndian for i64 {
         let data = unsafe { &*(*ptr[schema_addr].data as *const NP_i64_Data) };
         data.default
     }
+    fn np_get_range<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> (Option<Self>, Option<Self>) {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_i64_Data) };
+        (data.min, data.max)
+    }
+    fn np_default_bound<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<NP_NumberBound> {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_i64_Data) };
+        data.default_bound
+    }
+    fn np_timestamp_format<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<NP_TimestampFormat> {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_i64_Data) };
+        data.format
+    }
     fn np_get_default_from_json(json: &NP_JSON) -> Option<Self> {
         match json {
-            NP_JSON::Float(x) => {
+            NP_JSON::Float(x, _) => {
                 Some(*x as Self)
             },
             NP_JSON::Integer(x) => {
@@ -754,7 +1719,7 @@ impl NP_BigEndian for i64 {
         }
     }
     fn np_get_default_from_bytes<'default>(address: usize, bytes: &'default [u8]) -> Option<Self> {
-        if bytes[address + 1] == 0 {
+        if bytes[address + 1] & 1 == 0 {
             None
         } else {
             let mut slice: [u8; 8] = Default::default();
@@ -782,6 +1747,31 @@ fn i64_schema_parsing_works() -> Result<(), NP_Error> {
 }
 
 
+#[test]
+fn i64_schema_parsing_works_idl() -> Result<(), NP_Error> {
+    let schema = "i64({default: -98})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+    assert_eq!(factory2.new_buffer(None).get::<i64>(&[])?, Some(-98i64));
+
+    let schema = "i64({default: -9223372036854775808})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+    assert_eq!(factory2.new_buffer(None).get::<i64>(&[])?, Some(i64::MIN));
+
+    let schema = "i64()";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+
+    Ok(())
+}
+
 #[test]
 fn i64_default_value_works() -> Result<(), NP_Error> {
     let schema = "{\"type\":\"int64\",\"default\":293}";
@@ -815,9 +1805,17 @@ impl NP_BigEndian for u8 {
         let data = unsafe { &*(*ptr[schema_addr].data as *const NP_u8_Data) };
         data.default
     }
+    fn np_get_range<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> (Option<Self>, Option<Self>) {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_u8_Data) };
+        (data.min, data.max)
+    }
+    fn np_default_bound<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<NP_NumberBound> {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_u8_Data) };
+        data.default_bound
+    }
     fn np_get_default_from_json(json: &NP_JSON) -> Option<Self> {
         match json {
-            NP_JSON::Float(x) => {
+            NP_JSON::Float(x, _) => {
                 Some(*x as Self)
             },
             NP_JSON::Integer(x) => {
@@ -829,7 +1827,7 @@ impl NP_BigEndian for u8 {
         }
     }
     fn np_get_default_from_bytes<'default>(address: usize, bytes: &'default [u8]) -> Option<Self> {
-        if bytes[address + 1] == 0 {
+        if bytes[address + 1] & 1 == 0 {
             None
         } else {
             let mut slice: [u8; 1] = Default::default();
@@ -888,9 +1886,17 @@ impl NP_BigEndian for u16 {
         let data = unsafe { &*(*ptr[schema_addr].data as *const NP_u16_Data) };
         data.default
     }
+    fn np_get_range<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> (Option<Self>, Option<Self>) {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_u16_Data) };
+        (data.min, data.max)
+    }
+    fn np_default_bound<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<NP_NumberBound> {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_u16_Data) };
+        data.default_bound
+    }
     fn np_get_default_from_json(json: &NP_JSON) -> Option<Self> {
         match json {
-            NP_JSON::Float(x) => {
+            NP_JSON::Float(x, _) => {
                 Some(*x as Self)
             },
             NP_JSON::Integer(x) => {
@@ -902,7 +1908,7 @@ impl NP_BigEndian for u16 {
         }
     }
     fn np_get_default_from_bytes<'default>(address: usize, bytes: &'default [u8]) -> Option<Self> {
-        if bytes[address + 1] == 0 {
+        if bytes[address + 1] & 1 == 0 {
             None
         } else {
             let mut slice: [u8; 2] = Default::default();
@@ -961,9 +1967,17 @@ impl NP_BigEndian for u32 {
         let data = unsafe { &*(*ptr[schema_addr].data as *const NP_u32_Data) };
         data.default
     }
+    fn np_get_range<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> (Option<Self>, Option<Self>) {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_u32_Data) };
+        (data.min, data.max)
+    }
+    fn np_default_bound<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<NP_NumberBound> {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_u32_Data) };
+        data.default_bound
+    }
     fn np_get_default_from_json(json: &NP_JSON) -> Option<Self> {
         match json {
-            NP_JSON::Float(x) => {
+            NP_JSON::Float(x, _) => {
                 Some(*x as Self)
             },
             NP_JSON::Integer(x) => {
@@ -975,7 +1989,7 @@ impl NP_BigEndian for u32 {
         }
     }
     fn np_get_default_from_bytes<'default>(address: usize, bytes: &'default [u8]) -> Option<Self> {
-        if bytes[address + 1] == 0 {
+        if bytes[address + 1] & 1 == 0 {
             None
         } else {
             let mut slice: [u8; 4] = Default::default();
@@ -1034,9 +2048,17 @@ impl NP_BigEndian for u64 {
         let data = unsafe { &*(*ptr[schema_addr].data as *const NP_u64_Data) };
         data.default
     }
+    fn np_get_range<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> (Option<Self>, Option<Self>) {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_u64_Data) };
+        (data.min, data.max)
+    }
+    fn np_default_bound<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<NP_NumberBound> {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_u64_Data) };
+        data.default_bound
+    }
     fn np_get_default_from_json(json: &NP_JSON) -> Option<Self> {
         match json {
-            NP_JSON::Float(x) => {
+            NP_JSON::Float(x, _) => {
                 Some(*x as Self)
             },
             NP_JSON::Integer(x) => {
@@ -1048,7 +2070,7 @@ impl NP_BigEndian for u64 {
         }
     }
     fn np_get_default_from_bytes<'default>(address: usize, bytes: &'default [u8]) -> Option<Self> {
-        if bytes[address + 1] == 0 {
+        if bytes[address + 1] & 1 == 0 {
             None
         } else {
             let mut slice: [u8; 8] = Default::default();
@@ -1106,9 +2128,25 @@ impl NP_BigEndian for f32 {
         let data = unsafe { &*(*ptr[schema_addr].data as *const NP_f32_Data) };
         data.default
     }
+    fn np_get_range<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> (Option<Self>, Option<Self>) {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_f32_Data) };
+        (data.min, data.max)
+    }
+    fn np_default_bound<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<NP_NumberBound> {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_f32_Data) };
+        data.default_bound
+    }
+    fn np_whole_as_int<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> bool {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_f32_Data) };
+        data.whole_as_int
+    }
+    fn np_display_places<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<u8> {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_f32_Data) };
+        data.display_places
+    }
     fn np_get_default_from_json(json: &NP_JSON) -> Option<Self> {
         match json {
-            NP_JSON::Float(x) => {
+            NP_JSON::Float(x, _) => {
                 Some(*x as Self)
             },
             NP_JSON::Integer(x) => {
@@ -1120,7 +2158,7 @@ impl NP_BigEndian for f32 {
         }
     }
     fn np_get_default_from_bytes<'default>(address: usize, bytes: &'default [u8]) -> Option<Self> {
-        if bytes[address + 1] == 0 {
+        if bytes[address + 1] & 1 == 0 {
             None
         } else {
             let mut slice: [u8; 4] = Default::default();
@@ -1179,9 +2217,25 @@ impl NP_BigEndian for f64 {
         let data = unsafe { &*(*ptr[schema_addr].data as *const NP_f64_Data) };
         data.default
     }
+    fn np_get_range<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> (Option<Self>, Option<Self>) {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_f64_Data) };
+        (data.min, data.max)
+    }
+    fn np_default_bound<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<NP_NumberBound> {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_f64_Data) };
+        data.default_bound
+    }
+    fn np_whole_as_int<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> bool {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_f64_Data) };
+        data.whole_as_int
+    }
+    fn np_display_places<'default>(schema_addr: usize, ptr: &'default Vec<NP_Parsed_Schema>) -> Option<u8> {
+        let data = unsafe { &*(*ptr[schema_addr].data as *const NP_f64_Data) };
+        data.display_places
+    }
     fn np_get_default_from_json(json: &NP_JSON) -> Option<Self> {
         match json {
-            NP_JSON::Float(x) => {
+            NP_JSON::Float(x, _) => {
                 Some(*x as Self)
             },
             NP_JSON::Integer(x) => {
@@ -1193,7 +2247,7 @@ impl NP_BigEndian for f64 {
         }
     }
     fn np_get_default_from_bytes<'default>(address: usize, bytes: &'default [u8]) -> Option<Self> {
-        if bytes[address + 1] == 0 {
+        if bytes[address + 1] & 1 == 0 {
             None
         } else {
             let mut slice: [u8; 8] = Default::default();
@@ -1244,4 +2298,770 @@ fn double_set_clear_value_and_compaction_works() -> Result<(), NP_Error> {
     assert_eq!(buffer.calc_bytes()?.current_buffer, 6usize);
 
     Ok(())
-}
\ No newline at end of file
+}
+#[test]
+fn double_whole_as_int_schema_parsing_works() -> Result<(), NP_Error> {
+    let schema = "{\"type\":\"double\",\"default\":2.5,\"whole_as_int\":true}";
+    let factory = crate::NP_Factory::new_json(schema)?;
+    assert_eq!(schema, factory.schema.to_json()?.stringify());
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_json()?.stringify());
+
+    let schema = "f64({whole_as_int: true})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+
+    Ok(())
+}
+
+#[test]
+fn double_whole_as_int_json_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("f64({whole_as_int: true})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    // whole values are emitted as JSON integers
+    buffer.set(&[], 2f64)?;
+    assert_eq!(buffer.json_encode(&[])?["value"], NP_JSON::Integer(2));
+    buffer.set(&[], -40f64)?;
+    assert_eq!(buffer.json_encode(&[])?["value"], NP_JSON::Integer(-40));
+
+    // fractional values stay floats
+    buffer.set(&[], 2.5f64)?;
+    assert_eq!(buffer.json_encode(&[])?["value"], NP_JSON::Float(2.5, None));
+
+    // reading the integer back in still stores a float
+    buffer.set_with_json(&[], "{\"value\":2}")?;
+    assert_eq!(buffer.get::<f64>(&[])?, Some(2f64));
+
+    // without the option whole floats stay floats
+    let factory = crate::NP_Factory::new("f32()")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&[], 2f32)?;
+    assert_eq!(buffer.json_encode(&[])?["value"], NP_JSON::Float(2.0, None));
+
+    Ok(())
+}
+
+#[test]
+fn apply_null_writes_default_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("list({of: i32({default: 56})})")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set_with_json(&[], r#"{"value": [1, 2]}"#)?;
+
+    // without apply_null the value is left alone
+    buffer.set_with_json(&[], r#"{"value": [null]}"#)?;
+    assert_eq!(buffer.get::<i32>(&["0"])?, Some(1));
+
+    // the default is written into the buffer, not just read back as a fallback
+    buffer.set_with_json_apply_null(&[], r#"{"value": [null]}"#)?;
+    assert_eq!(buffer.get::<i32>(&["0"])?, Some(56));
+    assert_eq!(buffer.get_with_endian::<i32>(&["0"], Endian::Big)?, Some(56));
+    assert_eq!(buffer.get::<i32>(&["1"])?, Some(2));
+
+    Ok(())
+}
+
+#[test]
+fn apply_null_clears_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("list({of: u16()})")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set_with_json(&[], r#"{"value": [1, 2, 3]}"#)?;
+    buffer.set_with_json(&[], r#"{"value": [null, 20]}"#)?;
+    assert_eq!(buffer.get::<u16>(&["0"])?, Some(1));
+    assert_eq!(buffer.get::<u16>(&["1"])?, Some(20));
+
+    buffer.set_with_json_apply_null(&[], r#"{"value": [null, 30]}"#)?;
+    assert_eq!(buffer.get::<u16>(&["0"])?, None);
+    assert_eq!(buffer.get::<u16>(&["1"])?, Some(30));
+    assert_eq!(buffer.get::<u16>(&["2"])?, Some(3));
+
+    Ok(())
+}
+
+#[test]
+fn null_clears_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("list({of: i32({default: 56})})")?.with_null_clears(true);
+    let mut buffer = factory.new_buffer(None);
+    buffer.set_with_json(&[], r#"{"value": [1, 2]}"#)?;
+
+    // the default isn't written, the value is cleared and reads back as unset
+    buffer.set_with_json_apply_null(&[], r#"{"value": [null]}"#)?;
+    assert_eq!(buffer.get::<i32>(&["0"])?, None);
+    assert_eq!(buffer.get::<i32>(&["1"])?, Some(2));
+
+    // turning it back off writes the default again
+    let factory = factory.with_null_clears(false);
+    let mut buffer = factory.new_buffer(None);
+    buffer.set_with_json(&[], r#"{"value": [1]}"#)?;
+    buffer.set_with_json_apply_null(&[], r#"{"value": [null]}"#)?;
+    assert_eq!(buffer.get::<i32>(&["0"])?, Some(56));
+
+    Ok(())
+}
+
+#[test]
+fn overflow_policy_works() -> Result<(), NP_Error> {
+    use crate::pointer::numbers::NumericOverflowPolicy;
+
+    // wrap is the default
+    let factory = crate::NP_Factory::new("u8()")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set_with_json(&[], r#"{"value": 300}"#)?;
+    assert_eq!(buffer.get::<u8>(&[])?, Some(44));
+    buffer.set_with_json(&[], r#"{"value": -1}"#)?;
+    assert_eq!(buffer.get::<u8>(&[])?, Some(255));
+
+    let factory = crate::NP_Factory::new("i8()")?.with_overflow_policy(NumericOverflowPolicy::Saturate);
+    let mut buffer = factory.new_buffer(None);
+    buffer.set_with_json(&[], r#"{"value": 300}"#)?;
+    assert_eq!(buffer.get::<i8>(&[])?, Some(127));
+    buffer.set_with_json(&[], r#"{"value": -300}"#)?;
+    assert_eq!(buffer.get::<i8>(&[])?, Some(-128));
+    buffer.set_with_json(&[], r#"{"value": -10000000000.5}"#)?;
+    assert_eq!(buffer.get::<i8>(&[])?, Some(-128));
+    buffer.set_with_json(&[], r#"{"value": 12}"#)?;
+    assert_eq!(buffer.get::<i8>(&[])?, Some(12));
+
+    let factory = crate::NP_Factory::new("u16()")?.with_overflow_policy(NumericOverflowPolicy::Error);
+    let mut buffer = factory.new_buffer(None);
+    buffer.set_with_json(&[], r#"{"value": 500}"#)?;
+    assert!(buffer.set_with_json(&[], r#"{"value": 70000}"#).is_err());
+    assert!(buffer.set_with_json(&[], r#"{"value": -1}"#).is_err());
+    assert!(buffer.set_with_json(&[], r#"{"value": 70000.5}"#).is_err());
+    assert_eq!(buffer.get::<u16>(&[])?, Some(500));
+
+    // the policy carries over to copies
+    let mut copy = buffer.copy_buffer();
+    assert!(copy.set_with_json(&[], r#"{"value": 70000}"#).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn strict_json_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("struct({fields: {count: u32(), name: string(), flag: bool()}})")?;
+
+    // mismatched values are ignored by default
+    let mut buffer = factory.new_buffer(None);
+    buffer.set_with_json(&[], r#"{"value": {"count": "twelve", "name": 5, "flag": "yes"}}"#)?;
+    assert_eq!(buffer.get::<u32>(&["count"])?, None);
+
+    let factory = factory.with_strict_json(true);
+    let mut buffer = factory.new_buffer(None);
+    assert!(matches!(buffer.set_with_json(&[], r#"{"value": {"count": "twelve"}}"#), Err(NP_Error::Custom { message }) if message == "Can't parse number for type (uint32): twelve"));
+    assert!(matches!(buffer.set_with_json(&[], r#"{"value": {"count": [12]}}"#), Err(NP_Error::Custom { message }) if message == "expected number, got array"));
+    assert!(matches!(buffer.set_with_json(&[], r#"{"value": {"name": 5}}"#), Err(NP_Error::Custom { message }) if message == "expected string, got number"));
+    assert!(matches!(buffer.set_with_json(&[], r#"{"value": {"flag": [true]}}"#), Err(NP_Error::Custom { message }) if message == "expected boolean, got array"));
+
+    // matching values and nulls are fine
+    buffer.set_with_json(&[], r#"{"value": {"count": 12, "name": null, "flag": true}}"#)?;
+    assert_eq!(buffer.get::<u32>(&["count"])?, Some(12));
+    assert_eq!(buffer.get::<bool>(&["flag"])?, Some(true));
+
+    Ok(())
+}
+
+#[test]
+fn get_or_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("struct({fields: {retries: u32({default: 5}), ratio: f64()}})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    // unset, schema default is ignored
+    assert_eq!(buffer.get_or(&["retries"], 3u32)?, 3);
+    assert_eq!(buffer.get_or(&["ratio"], 0.5f64)?, 0.5);
+
+    // set
+    buffer.set(&["retries"], 8u32)?;
+    buffer.set(&["ratio"], 2.25f64)?;
+    assert_eq!(buffer.get_or(&["retries"], 3u32)?, 8);
+    assert_eq!(buffer.get_or(&["ratio"], 0.5f64)?, 2.25);
+
+    // deleted values go back to the fallback
+    buffer.del(&["retries"])?;
+    assert_eq!(buffer.get_or(&["retries"], 3u32)?, 3);
+
+    Ok(())
+}
+
+#[test]
+fn can_widen_to_works() -> Result<(), NP_Error> {
+    let widens: [(NP_TypeKeys, &[NP_TypeKeys]); 10] = [
+        (NP_TypeKeys::Uint8,  &[NP_TypeKeys::Uint16, NP_TypeKeys::Uint32, NP_TypeKeys::Uint64, NP_TypeKeys::Int16, NP_TypeKeys::Int32, NP_TypeKeys::Int64, NP_TypeKeys::Float, NP_TypeKeys::Double]),
+        (NP_TypeKeys::Uint16, &[NP_TypeKeys::Uint32, NP_TypeKeys::Uint64, NP_TypeKeys::Int32, NP_TypeKeys::Int64, NP_TypeKeys::Float, NP_TypeKeys::Double]),
+        (NP_TypeKeys::Uint32, &[NP_TypeKeys::Uint64, NP_TypeKeys::Int64, NP_TypeKeys::Double]),
+        (NP_TypeKeys::Uint64, &[]),
+        (NP_TypeKeys::Int8,   &[NP_TypeKeys::Int16, NP_TypeKeys::Int32, NP_TypeKeys::Int64, NP_TypeKeys::Float, NP_TypeKeys::Double]),
+        (NP_TypeKeys::Int16,  &[NP_TypeKeys::Int32, NP_TypeKeys::Int64, NP_TypeKeys::Float, NP_TypeKeys::Double]),
+        (NP_TypeKeys::Int32,  &[NP_TypeKeys::Int64, NP_TypeKeys::Double]),
+        (NP_TypeKeys::Int64,  &[]),
+        (NP_TypeKeys::Float,  &[NP_TypeKeys::Double]),
+        (NP_TypeKeys::Double, &[])
+    ];
+
+    // check every pair of type keys
+    for from in 0..=27u8 {
+        for to in 0..=27u8 {
+            let (from, to) = (NP_TypeKeys::from(from), NP_TypeKeys::from(to));
+
+            let expected = from == to || widens.iter().any(|(key, targets)| *key == from && targets.contains(&to));
+
+            assert_eq!(from.can_widen_to(to), expected, "{:?} to {:?}", from, to);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn compare_and_set_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("struct({fields: {version: u64({default: 1}), score: f32()}})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    // the schema default doesn't count as a stored value
+    assert_eq!(buffer.compare_and_set(&["version"], Some(1u64), 5u64)?, false);
+    assert_eq!(buffer.compare_and_set(&["version"], None, 5u64)?, true);
+    assert_eq!(buffer.get::<u64>(&["version"])?, Some(5));
+
+    // matching expected
+    assert_eq!(buffer.compare_and_set(&["version"], Some(5u64), 6u64)?, true);
+    assert_eq!(buffer.get::<u64>(&["version"])?, Some(6));
+
+    // mismatching expected leaves the value alone
+    assert_eq!(buffer.compare_and_set(&["version"], Some(5u64), 7u64)?, false);
+    assert_eq!(buffer.compare_and_set(&["version"], None, 7u64)?, false);
+    assert_eq!(buffer.get::<u64>(&["version"])?, Some(6));
+
+    assert_eq!(buffer.compare_and_set(&["score"], None, 0.5f32)?, true);
+    assert_eq!(buffer.compare_and_set(&["score"], Some(0.5f32), 1.5f32)?, true);
+    assert_eq!(buffer.get::<f32>(&["score"])?, Some(1.5));
+
+    // wrong type for the schema
+    assert!(buffer.compare_and_set(&["version"], Some(6u32), 7u32).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn set_numeric_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("struct({fields: {count: u32(), small: u8(), signed: i16(), name: string()}})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    // u16 into a u32 field
+    buffer.set_numeric(&["count"], 65535u16)?;
+    assert_eq!(buffer.get::<u32>(&["count"])?, Some(65535));
+
+    // fits after conversion
+    buffer.set_numeric(&["small"], 255i64)?;
+    assert_eq!(buffer.get::<u8>(&["small"])?, Some(255));
+    buffer.set_numeric(&["signed"], -300i32)?;
+    assert_eq!(buffer.get::<i16>(&["signed"])?, Some(-300));
+
+    // too large for u8, existing value is untouched
+    assert!(buffer.set_numeric(&["small"], 256u32).is_err());
+    assert!(buffer.set_numeric(&["small"], -1i8).is_err());
+    assert_eq!(buffer.get::<u8>(&["small"])?, Some(255));
+
+    // non integer schema types are rejected
+    assert!(buffer.set_numeric(&["name"], 1u8).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn unsigned_max_to_json_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("u8()")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&[], u8::MAX)?;
+    assert_eq!(buffer.json_encode(&[])?.stringify(), "{\"value\":255}");
+
+    let factory = crate::NP_Factory::new("u16()")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&[], u16::MAX)?;
+    assert_eq!(buffer.json_encode(&[])?.stringify(), "{\"value\":65535}");
+
+    let factory = crate::NP_Factory::new("u32()")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&[], u32::MAX)?;
+    assert_eq!(buffer.json_encode(&[])?.stringify(), "{\"value\":4294967295}");
+
+    let factory = crate::NP_Factory::new("u64()")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&[], i64::MAX as u64)?;
+    assert_eq!(buffer.json_encode(&[])?.stringify(), "{\"value\":9223372036854775807}");
+    buffer.set(&[], i64::MAX as u64 + 1)?;
+    assert_eq!(buffer.json_encode(&[])?.stringify(), "{\"value\":\"9223372036854775808\"}");
+    buffer.set(&[], u64::MAX)?;
+    assert_eq!(buffer.json_encode(&[])?.stringify(), "{\"value\":\"18446744073709551615\"}");
+
+    Ok(())
+}
+
+#[test]
+fn set_dec_as_int_works() -> Result<(), NP_Error> {
+    use crate::pointer::dec::{NP_Dec, NP_RoundMode};
+
+    let factory = crate::NP_Factory::new("struct({fields: {small: u8(), signed: i32()}})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    // 2.5 half even rounds down to 2, half up rounds to 3, truncate drops the fraction
+    buffer.set_dec_as_int(&["small"], NP_Dec::new(25, 1), NP_RoundMode::HalfEven)?;
+    assert_eq!(buffer.get::<u8>(&["small"])?, Some(2));
+    buffer.set_dec_as_int(&["small"], NP_Dec::new(25, 1), NP_RoundMode::HalfUp)?;
+    assert_eq!(buffer.get::<u8>(&["small"])?, Some(3));
+    buffer.set_dec_as_int(&["small"], NP_Dec::new(2999, 3), NP_RoundMode::Truncate)?;
+    assert_eq!(buffer.get::<u8>(&["small"])?, Some(2));
+
+    buffer.set_dec_as_int(&["signed"], NP_Dec::new(-1255, 1), NP_RoundMode::HalfEven)?;
+    assert_eq!(buffer.get::<i32>(&["signed"])?, Some(-126));
+
+    // 255.5 rounds up past u8::MAX, the stored value doesn't change
+    assert!(buffer.set_dec_as_int(&["small"], NP_Dec::new(2555, 1), NP_RoundMode::HalfUp).is_err());
+    assert!(buffer.set_dec_as_int(&["small"], NP_Dec::new(-1, 0), NP_RoundMode::HalfUp).is_err());
+    assert_eq!(buffer.get::<u8>(&["small"])?, Some(2));
+
+    // truncating 255.5 fits
+    buffer.set_dec_as_int(&["small"], NP_Dec::new(2555, 1), NP_RoundMode::Truncate)?;
+    assert_eq!(buffer.get::<u8>(&["small"])?, Some(255));
+
+    Ok(())
+}
+
+#[test]
+fn float_display_places_works() -> Result<(), NP_Error> {
+    let schema = "{\"type\":\"double\",\"display_places\":2}";
+    let factory = crate::NP_Factory::new_json(schema)?;
+    assert_eq!(schema, factory.schema.to_json()?.stringify());
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_json()?.stringify());
+
+    let schema = "f32({default: 2.5, whole_as_int: true, display_places: 2})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+
+    // only the JSON output is rounded, the stored value keeps full precision
+    let factory = crate::NP_Factory::new("f64({display_places: 2})")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&[], 12.3456789f64)?;
+    assert_eq!(buffer.json_encode(&[])?["value"], NP_JSON::Float(12.35, None));
+    assert_eq!(buffer.get::<f64>(&[])?, Some(12.3456789f64));
+    buffer.set(&[], -2.675f64)?;
+    assert_eq!(buffer.json_encode(&[])?["value"], NP_JSON::Float(-2.68, None));
+
+    // zero places combines with whole_as_int
+    let factory = crate::NP_Factory::new("f64({whole_as_int: true, display_places: 0})")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&[], 7.6f64)?;
+    assert_eq!(buffer.json_encode(&[])?["value"], NP_JSON::Integer(8));
+
+    assert!(crate::NP_Factory::new("f64({display_places: 19})").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn next_seq_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("struct({fields: {id: u64(), name: string()}})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    // unset starts from zero
+    for expected in 1..=50u64 {
+        assert_eq!(buffer.next_seq(&["id"])?, expected);
+    }
+    assert_eq!(buffer.get::<u64>(&["id"])?, Some(50));
+
+    // incrementing in place doesn't grow the buffer
+    let size = buffer.calc_bytes()?.current_buffer;
+    buffer.next_seq(&["id"])?;
+    assert_eq!(buffer.calc_bytes()?.current_buffer, size);
+
+    buffer.set(&["id"], u64::MAX - 1)?;
+    assert_eq!(buffer.next_seq(&["id"])?, u64::MAX);
+    let err = buffer.next_seq(&["id"]).unwrap_err();
+    assert!(matches!(err, NP_Error::Custom { message } if message == "Sequence overflow, value is already u64::MAX!"));
+    assert_eq!(buffer.get::<u64>(&["id"])?, Some(u64::MAX));
+
+    assert!(buffer.next_seq(&["name"]).is_err());
+    assert!(buffer.next_seq(&["missing"]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn extreme_values_round_trip() -> Result<(), NP_Error> {
+
+    // set the value, check `get` and the JSON text, then feed that JSON into a fresh buffer and check it again
+    macro_rules! round_trip {
+        ($schema:expr, $t:ty, $value:expr) => {
+            round_trip!($schema, $t, $value, format!("{{\"value\":{}}}", $value))
+        };
+        ($schema:expr, $t:ty, $value:expr, $json:expr) => {{
+            let factory = crate::NP_Factory::new($schema)?;
+            let mut buffer = factory.new_buffer(None);
+            buffer.set(&[], $value)?;
+            assert_eq!(buffer.get::<$t>(&[])?, Some($value));
+            let json = buffer.json_encode(&[])?.stringify();
+            assert_eq!(json, $json);
+            let mut from_json = factory.new_buffer(None);
+            from_json.set_with_json(&[], json)?;
+            assert_eq!(from_json.get::<$t>(&[])?, Some($value));
+        }};
+    }
+
+    round_trip!("i8()", i8, i8::MIN);
+    round_trip!("i8()", i8, i8::MAX);
+    round_trip!("i8()", i8, -1i8);
+    round_trip!("i16()", i16, i16::MIN);
+    round_trip!("i16()", i16, i16::MAX);
+    round_trip!("i32()", i32, i32::MIN);
+    round_trip!("i32()", i32, i32::MAX);
+    round_trip!("i64()", i64, i64::MIN);
+    round_trip!("i64()", i64, i64::MAX);
+
+    round_trip!("u8()", u8, u8::MIN);
+    round_trip!("u8()", u8, u8::MAX);
+    round_trip!("u16()", u16, u16::MAX);
+    round_trip!("u32()", u32, u32::MAX);
+    round_trip!("u64()", u64, u64::MIN);
+    round_trip!("u64()", u64, i64::MAX as u64);
+
+    // floats are written through f64, whose shortest round trip text reads back to the same bits
+    round_trip!("f32()", f32, f32::MIN_POSITIVE, format!("{{\"value\":{}}}", f32::MIN_POSITIVE as f64));
+    round_trip!("f32()", f32, f32::EPSILON, format!("{{\"value\":{}}}", f32::EPSILON as f64));
+    round_trip!("f32()", f32, -1.5f32);
+    round_trip!("f64()", f64, f64::MIN_POSITIVE);
+    round_trip!("f64()", f64, f64::EPSILON);
+    round_trip!("f64()", f64, -1.5f64);
+
+    // u64 values above i64::MAX are emitted as strings, and read back from them
+    round_trip!("u64()", u64, u64::MAX, "{\"value\":\"18446744073709551615\"}");
+    round_trip!("u64()", u64, i64::MAX as u64 + 1, "{\"value\":\"9223372036854775808\"}");
+
+    // whole floats keep a decimal point so they're read back as floats, even the largest ones
+    round_trip!("f32()", f32, f32::MAX, format!("{{\"value\":{}.0}}", f32::MAX as f64));
+    round_trip!("f32()", f32, f32::MIN, format!("{{\"value\":{}.0}}", f32::MIN as f64));
+    round_trip!("f64()", f64, f64::MAX, format!("{{\"value\":{}.0}}", f64::MAX));
+    round_trip!("f64()", f64, f64::MIN, format!("{{\"value\":{}.0}}", f64::MIN));
+    round_trip!("f32()", f32, -3.0f32, "{\"value\":-3.0}");
+    round_trip!("f64()", f64, 1.0f64, "{\"value\":1.0}");
+    round_trip!("f64()", f64, 0.0f64, "{\"value\":0.0}");
+
+    Ok(())
+}
+
+#[test]
+fn emit_null_for_unset_works() -> Result<(), NP_Error> {
+    use crate::pointer::dec::NP_Dec;
+
+    let factory = crate::NP_Factory::new("struct({fields: {retries: u8({default: 3}), ratio: f64({default: 0.5}), price: dec({exp: 2, default: 1.5})}})")?;
+
+    let buffer = factory.new_buffer(None);
+    let json = buffer.json_encode(&[])?;
+    assert_eq!(json["value"]["retries"], NP_JSON::Integer(3));
+    assert_eq!(json["value"]["ratio"], NP_JSON::Float(0.5, None));
+    assert_eq!(json["value"]["price"]["parts"]["num"], NP_JSON::Integer(150));
+
+    let factory = factory.with_emit_null_for_unset(true);
+    let mut buffer = factory.new_buffer(None);
+    let json = buffer.json_encode(&[])?;
+    assert_eq!(json["value"]["retries"], NP_JSON::Null);
+    assert_eq!(json["value"]["ratio"], NP_JSON::Null);
+    assert_eq!(json["value"]["price"], NP_JSON::Null);
+
+    // a value explicitly set to the default is still written out
+    buffer.set(&["retries"], 3u8)?;
+    buffer.set(&["price"], NP_Dec::new(150, 2))?;
+    let json = buffer.json_encode(&[])?;
+    assert_eq!(json["value"]["retries"], NP_JSON::Integer(3));
+    assert_eq!(json["value"]["price"]["parts"]["num"], NP_JSON::Integer(150));
+
+    // `get` still falls back to the schema default
+    assert_eq!(buffer.get::<f64>(&["ratio"])?, Some(0.5));
+    assert_eq!(buffer.copy_buffer().json_encode(&["ratio"])?["value"], NP_JSON::Null);
+
+    Ok(())
+}
+
+#[test]
+fn timestamp_format_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new(r#"struct({fields: {created: i64({format: "unix_millis"}), day: i64({format: "unix_seconds", default: 951825600}), count: i64()}})"#)?;
+
+    // schema round trips through IDL, JSON and bytes
+    let idl = r#"struct({fields: {created: i64({format: "unix_millis"}), day: i64({default: 951825600, format: "unix_seconds"}), count: i64()}})"#;
+    assert_eq!(factory.schema.to_idl()?, idl);
+    assert_eq!(crate::NP_Factory::new_bytes(factory.export_schema_bytes())?.schema.to_idl()?, idl);
+    let json = factory.schema.to_json()?.stringify();
+    assert_eq!(crate::NP_Factory::new_json(json.as_str())?.export_schema_bytes(), factory.export_schema_bytes());
+
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&["created"], 1704164645678i64)?;
+    buffer.set(&["count"], 1704164645678i64)?;
+
+    let json = buffer.json_encode(&[])?;
+    assert_eq!(json["value"]["created"], NP_JSON::String(String::from("2024-01-02T03:04:05.678Z")));
+    assert_eq!(json["value"]["day"], NP_JSON::String(String::from("2000-02-29T12:00:00Z")));
+    assert_eq!(json["value"]["count"], NP_JSON::Integer(1704164645678));
+
+    // the ISO text goes back to the same integer
+    let mut copy = factory.new_buffer(None);
+    copy.set_with_json(&[], json.stringify())?;
+    assert_eq!(copy.get::<i64>(&["created"])?, Some(1704164645678));
+    assert_eq!(copy.get::<i64>(&["count"])?, Some(1704164645678));
+
+    // before the epoch, short fractions and plain numbers
+    copy.set_with_json(&["created"], r#"{"value": "1969-12-31T23:59:59.5Z"}"#)?;
+    assert_eq!(copy.get::<i64>(&["created"])?, Some(-500));
+    assert_eq!(copy.json_encode(&["created"])?["value"], NP_JSON::String(String::from("1969-12-31T23:59:59.500Z")));
+    copy.set_with_json(&["day"], r#"{"value": "0001-01-01T00:00:00Z"}"#)?;
+    assert_eq!(copy.get::<i64>(&["day"])?, Some(-62135596800));
+    copy.set_with_json(&["day"], r#"{"value": 253402300799}"#)?;
+    assert_eq!(copy.json_encode(&["day"])?["value"], NP_JSON::String(String::from("9999-12-31T23:59:59Z")));
+
+    // past year 9999 falls back to a number
+    copy.set(&["day"], 253402300800i64)?;
+    assert_eq!(copy.json_encode(&["day"])?["value"], NP_JSON::Integer(253402300800));
+
+    // bad text is skipped, or an error with strict JSON
+    copy.set_with_json(&["created"], r#"{"value": "yesterday"}"#)?;
+    assert_eq!(copy.get::<i64>(&["created"])?, Some(-500));
+    let strict = factory.with_strict_json(true);
+    let mut buffer = strict.new_buffer(None);
+    assert!(buffer.set_with_json(&["day"], r#"{"value": "2024-01-02T03:04:05.678Z"}"#).is_err());
+    assert!(buffer.set_with_json(&["created"], r#"{"value": "2023-02-29T00:00:00Z"}"#).is_err());
+    assert!(buffer.set_with_json(&["created"], r#"{"value": "2024-01-02 03:04:05Z"}"#).is_err());
+    assert!(buffer.set_with_json(&["count"], r#"{"value": "2024-01-02T03:04:05Z"}"#).is_err());
+
+    // only i64 takes a format
+    assert!(crate::NP_Factory::new(r#"i32({format: "unix_seconds"})"#).is_err());
+    assert!(crate::NP_Factory::new(r#"i64({format: "unix_days"})"#).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn set_saturating_and_wrapping_work() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("struct({fields: {a: i8(), b: u16(), c: i32(), d: u64(), e: f32()}})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    // the same results as core's saturating and wrapping conversions
+    for value in [i128::MIN, -70000, -129, -1, 0, 127, 128, 70000, u64::MAX as i128 + 1, i128::MAX].iter() {
+        buffer.set_saturating(&["a"], *value)?;
+        assert_eq!(buffer.get::<i8>(&["a"])?, Some((*value).max(i8::MIN as i128).min(i8::MAX as i128) as i8));
+        buffer.set_wrapping(&["a"], *value)?;
+        assert_eq!(buffer.get::<i8>(&["a"])?, Some(*value as i8));
+
+        buffer.set_saturating(&["b"], *value)?;
+        assert_eq!(buffer.get::<u16>(&["b"])?, Some((*value).max(0).min(u16::MAX as i128) as u16));
+        buffer.set_wrapping(&["b"], *value)?;
+        assert_eq!(buffer.get::<u16>(&["b"])?, Some(*value as u16));
+
+        buffer.set_saturating(&["c"], *value)?;
+        assert_eq!(buffer.get::<i32>(&["c"])?, Some((*value).max(i32::MIN as i128).min(i32::MAX as i128) as i32));
+        buffer.set_wrapping(&["c"], *value)?;
+        assert_eq!(buffer.get::<i32>(&["c"])?, Some(*value as i32));
+
+        buffer.set_saturating(&["d"], *value)?;
+        assert_eq!(buffer.get::<u64>(&["d"])?, Some((*value).max(0).min(u64::MAX as i128) as u64));
+        buffer.set_wrapping(&["d"], *value)?;
+        assert_eq!(buffer.get::<u64>(&["d"])?, Some(*value as u64));
+    }
+
+    // narrower input types work too
+    buffer.set_saturating(&["b"], 70000u32)?;
+    assert_eq!(buffer.get::<u16>(&["b"])?, Some(u16::MAX));
+    buffer.set_wrapping(&["b"], 70000u32)?;
+    assert_eq!(buffer.get::<u16>(&["b"])?, Some(4464));
+
+    // only integer fields
+    assert!(buffer.set_saturating(&["e"], 5u8).is_err());
+    assert!(buffer.set_wrapping(&["e"], 5u8).is_err());
+    assert!(buffer.set_saturating(&["missing"], 5u8).is_err());
+    assert_eq!(buffer.get::<f32>(&["e"])?, None);
+
+    Ok(())
+}
+
+#[test]
+fn range_constraints_work() -> Result<(), NP_Error> {
+    // schemas keep min and max through IDL, JSON and bytes
+    let schema = "u32({default: 20, min: 10, max: 1000})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+
+    let schema = "{\"type\":\"int16\",\"min\":-5,\"max\":5}";
+    let factory = crate::NP_Factory::new_json(schema)?;
+    assert_eq!(schema, factory.schema.to_json()?.stringify());
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_json()?.stringify());
+
+    let schema = "f64({min: 0.5, whole_as_int: true, display_places: 2})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+
+    // values outside the range are refused
+    let factory = crate::NP_Factory::new("struct({fields: {a: u32({min: 10, max: 1000}), b: f32({max: 1.5})}})")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&["a"], 10u32)?;
+    buffer.set(&["a"], 1000u32)?;
+    assert_eq!(buffer.get::<u32>(&["a"])?, Some(1000));
+    match buffer.set(&["a"], 5u32) {
+        Err(NP_Error::Custom { message }) => assert_eq!(message, "Number out of schema range for type (uint32): 5 is below the min of 10"),
+        _ => panic!()
+    }
+    assert!(buffer.set(&["a"], 1001u32).is_err());
+    assert!(buffer.set(&["b"], 1.75f32).is_err());
+    assert!(buffer.set(&["b"], f32::NAN).is_err());
+    assert_eq!(buffer.get::<u32>(&["a"])?, Some(1000));
+    assert_eq!(buffer.get::<f32>(&["b"])?, None);
+
+    // JSON writes go through the same check
+    assert!(buffer.set_with_json(&["a"], r#"{"value": 2000}"#).is_err());
+    buffer.set_with_json(&["a"], r#"{"value": 500}"#)?;
+    assert_eq!(buffer.get::<u32>(&["a"])?, Some(500));
+
+    // saturating writes clamp to the range
+    buffer.set_saturating(&["a"], 3u8)?;
+    assert_eq!(buffer.get::<u32>(&["a"])?, Some(10));
+    buffer.set_saturating(&["a"], -3i8)?;
+    assert_eq!(buffer.get::<u32>(&["a"])?, Some(10));
+
+    // so do JSON writes with the saturate policy, in or out of the type's own range
+    let saturate = factory.with_overflow_policy(NumericOverflowPolicy::Saturate);
+    let mut buffer = saturate.new_buffer(None);
+    buffer.set_with_json(&["a"], r#"{"value": 2000}"#)?;
+    assert_eq!(buffer.get::<u32>(&["a"])?, Some(1000));
+    buffer.set_with_json(&["a"], r#"{"value": -5}"#)?;
+    assert_eq!(buffer.get::<u32>(&["a"])?, Some(10));
+    buffer.set_with_json(&["a"], r#"{"value": "99999999999"}"#)?;
+    assert_eq!(buffer.get::<u32>(&["a"])?, Some(1000));
+    buffer.set_with_json(&["b"], r#"{"value": 2.5}"#)?;
+    assert_eq!(buffer.get::<f32>(&["b"])?, Some(1.5));
+
+    // bad ranges are schema errors
+    assert!(crate::NP_Factory::new("u8({min: 10, max: 5})").is_err());
+    assert!(crate::NP_Factory::new("u8({min: 300})").is_err());
+    assert!(crate::NP_Factory::new("u8({default: 2, min: 10})").is_err());
+    assert!(crate::NP_Factory::new_json("{\"type\":\"uint8\",\"max\":1.5}").is_err());
+    assert!(crate::NP_Factory::new_json("{\"type\":\"uint8\",\"max\":true}").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn default_bound_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new(r#"u8({default: "max"})"#)?;
+    assert_eq!(factory.new_buffer(None).get::<u8>(&[])?, Some(255));
+
+    // the sentinel survives IDL, JSON and bytes
+    let schema = r#"i16({default: "min"})"#;
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+    assert_eq!(factory2.new_buffer(None).get::<i16>(&[])?, Some(i16::MIN));
+
+    let schema = "{\"type\":\"uint32\",\"default\":\"max\",\"max\":1000}";
+    let factory = crate::NP_Factory::new_json(schema)?;
+    assert_eq!(schema, factory.schema.to_json()?.stringify());
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_json()?.stringify());
+    assert_eq!(factory2.new_buffer(None).get::<u32>(&[])?, Some(1000));
+
+    let schema = r#"f64({default: "max", whole_as_int: true})"#;
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    assert_eq!(factory.new_buffer(None).get::<f64>(&[])?, Some(f64::MAX));
+
+    // bounds and timestamp formats share the flag byte
+    let schema = r#"i64({default: "max", max: 1700000000, format: "unix_seconds"})"#;
+    let factory = crate::NP_Factory::new(schema)?;
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+
+    // literal defaults are still written as numbers
+    let factory = crate::NP_Factory::new("u8({default: 255})")?;
+    assert_eq!("u8({default: 255})", factory.schema.to_idl()?);
+
+    assert!(crate::NP_Factory::new(r#"u8({default: "biggest"})"#).is_err());
+    assert!(crate::NP_Factory::new_json("{\"type\":\"uint8\",\"default\":\"lots\"}").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn numbers_from_json_strings_work() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("struct({fields: {big: u64(), small: i8(), ratio: f32(), created: i64({format: \"unix_millis\"})}})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    buffer.set_with_json(&[], r#"{"value": {"big": "18446744073709551615", "small": " -128 ", "ratio": "0.25"}}"#)?;
+    assert_eq!(buffer.get::<u64>(&["big"])?, Some(u64::MAX));
+    assert_eq!(buffer.get::<i8>(&["small"])?, Some(-128));
+    assert_eq!(buffer.get::<f32>(&["ratio"])?, Some(0.25));
+
+    // timestamps take plain numbers as well as ISO text
+    buffer.set_with_json(&["created"], r#"{"value": "1704164646000"}"#)?;
+    assert_eq!(buffer.get::<i64>(&["created"])?, Some(1704164646000));
+
+    // text that isn't a number is ignored like any other mismatched value
+    buffer.set_with_json(&["big"], r#"{"value": "lots"}"#)?;
+    assert_eq!(buffer.get::<u64>(&["big"])?, Some(u64::MAX));
+
+    // numbers in strings follow the overflow policy, the same as JSON numbers
+    buffer.set_with_json(&["small"], r#"{"value": "300"}"#)?;
+    assert_eq!(buffer.get::<i8>(&["small"])?, Some(300i64 as i8));
+
+    let saturate = factory.with_overflow_policy(NumericOverflowPolicy::Saturate);
+    let mut buffer = saturate.new_buffer(None);
+    buffer.set_with_json(&["small"], r#"{"value": "300"}"#)?;
+    assert_eq!(buffer.get::<i8>(&["small"])?, Some(127));
+    buffer.set_with_json(&["big"], r#"{"value": "-1"}"#)?;
+    assert_eq!(buffer.get::<u64>(&["big"])?, Some(0));
+
+    // with strict JSON and the error policy bad text is an error, the old value is kept
+    let strict = saturate.with_overflow_policy(NumericOverflowPolicy::Error).with_strict_json(true);
+    let mut buffer = strict.new_buffer(None);
+    buffer.set_with_json(&["big"], r#"{"value": "18446744073709551615"}"#)?;
+    for bad in ["18446744073709551616", "-1"].iter() {
+        let json = format!("{{\"value\": \"{}\"}}", bad);
+        let err = buffer.set_with_json(&["big"], json).unwrap_err();
+        assert!(matches!(err, NP_Error::Custom { message } if message == format!("Number out of range for type (uint64): {}", bad)));
+    }
+    for bad in ["lots", "", "inf"].iter() {
+        let json = format!("{{\"value\": \"{}\"}}", bad);
+        let err = buffer.set_with_json(&["big"], json).unwrap_err();
+        assert!(matches!(err, NP_Error::Custom { message } if message == format!("Can't parse number for type (uint64): {}", bad)));
+    }
+    assert_eq!(buffer.get::<u64>(&["big"])?, Some(u64::MAX));
+
+    Ok(())
+}
+
+#[test]
+fn float_range_edges_work() -> Result<(), NP_Error> {
+    use crate::pointer::numbers::NumericOverflowPolicy;
+
+    // 2^63 and 2^64 are one past the max of i64 and u64, even though `MAX as f64` rounds up to them
+    let factory = crate::NP_Factory::new("struct({fields: {signed: i64(), unsigned: u64()}})")?.with_overflow_policy(NumericOverflowPolicy::Error);
+    let mut buffer = factory.new_buffer(None);
+    assert!(buffer.set_with_json(&["signed"], r#"{"value": 9223372036854775808.0}"#).is_err());
+    assert!(buffer.set_with_json(&["unsigned"], r#"{"value": 18446744073709551616.0}"#).is_err());
+    assert_eq!(buffer.get::<i64>(&["signed"])?, None);
+    assert_eq!(buffer.get::<u64>(&["unsigned"])?, None);
+
+    // the largest floats below them still fit
+    buffer.set_with_json(&["signed"], r#"{"value": 9223372036854774784.0}"#)?;
+    assert_eq!(buffer.get::<i64>(&["signed"])?, Some(9223372036854774784));
+    buffer.set_with_json(&["signed"], r#"{"value": -9223372036854775808.0}"#)?;
+    assert_eq!(buffer.get::<i64>(&["signed"])?, Some(i64::MIN));
+    buffer.set_with_json(&["unsigned"], r#"{"value": 18446744073709549568.0}"#)?;
+    assert_eq!(buffer.get::<u64>(&["unsigned"])?, Some(18446744073709549568));
+
+    Ok(())
+}
@@ -20,7 +20,7 @@ use crate::{JS_Schema, idl::JS_AST, schema::{NP_Enum_Data, NP_Value_Kind}};
 use crate::{memory::NP_Memory, schema::{NP_Parsed_Schema}};
 use alloc::{sync::Arc, vec::Vec};
 use crate::json_flex::{JSMAP, NP_JSON};
-use crate::schema::{NP_TypeKeys};
+use crate::schema::{NP_Schema, NP_TypeKeys};
 use crate::{pointer::NP_Value, error::NP_Error};
 use core::{fmt::{Debug}};
 
@@ -138,7 +138,7 @@ impl<'value> NP_Value<'value> for NP_Enum {
             NP_JSON::String(x) => {
                 Self::set_value(cursor, memory, Self::new(x.clone()))?;
             },
-            _ => { }
+            _ => { NP_Cursor::json_type_mismatch(memory, "string", value)?; }
         }
 
         Ok(())
@@ -216,6 +216,9 @@ impl<'value> NP_Value<'value> for NP_Enum {
         }
         result.push_str("]");
        
+        if NP_Schema::is_required(&schema[address]) {
+            result.push_str(", required: true");
+        }
 
         result.push_str("})");
 
@@ -55,7 +55,7 @@ use crate::{idl::{JS_AST, JS_Schema}, memory::NP_Memory, schema::{NP_Parsed_Sche
 use alloc::{sync::Arc, vec::Vec};
 
 use crate::json_flex::{JSMAP, NP_JSON};
-use crate::schema::{NP_TypeKeys};
+use crate::schema::{NP_Schema, NP_TypeKeys};
 use crate::{pointer::NP_Value, error::NP_Error};
 
 
@@ -105,7 +105,11 @@ impl<'value> NP_Value<'value> for NP_Portal {
 
         let mut result = String::from("portal({to: \"");
         result.push_str(data.path.as_str());
-        result.push_str("\"});");
+        result.push_str("\"");
+        if NP_Schema::is_required(&schema[address]) {
+            result.push_str(", required: true");
+        }
+        result.push_str("})");
         Ok(result)
        
     }
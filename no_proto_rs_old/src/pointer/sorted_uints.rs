@@ -0,0 +1,623 @@
+//! Delta encoded list of sorted unsigned integers
+//!
+//! Good for sequences that only go up like timestamps or ids.  The first value is stored at the full width of the `base` type and every value after it is stored as a varint of the difference from the value before it, so long runs of close values take one or two bytes each.
+//!
+//! Values can only be read in order, finding the value at a given position means scanning from the start.  Appending with [`NP_Buffer::sorted_uints_push`](../../buffer/struct.NP_Buffer.html#method.sorted_uints_push) is cheap, pushing a value smaller than the last one is an error.
+//!
+//! ```
+//! use no_proto::error::NP_Error;
+//! use no_proto::NP_Factory;
+//! use no_proto::pointer::sorted_uints::NP_SortedUints;
+//!
+//! let factory: NP_Factory = NP_Factory::new(r#"sorted_uints({base: "u64"})"#)?;
+//!
+//! let mut new_buffer = factory.new_buffer(None);
+//! new_buffer.sorted_uints_push(&[], 1_600_000_000_000)?;
+//! new_buffer.sorted_uints_push(&[], 1_600_000_000_250)?;
+//! new_buffer.sorted_uints_push(&[], 1_600_000_001_000)?;
+//!
+//! let values: Vec<u64> = new_buffer.sorted_uints_iter(&[])?.unwrap().collect::<Result<Vec<u64>, NP_Error>>()?;
+//! assert_eq!(values, vec![1_600_000_000_000, 1_600_000_000_250, 1_600_000_001_000]);
+//!
+//! // or read them all at once
+//! assert_eq!(new_buffer.get::<NP_SortedUints>(&[])?.unwrap().values, values);
+//!
+//! # Ok::<(), NP_Error>(())
+//! ```
+//!
+
+use alloc::{string::String, sync::Arc};
+use alloc::prelude::v1::Box;
+use crate::{idl::{JS_AST, JS_Schema}, schema::{NP_SortedUints_Data, NP_Value_Kind}};
+use crate::schema::{NP_Parsed_Schema};
+use alloc::vec::Vec;
+use crate::json_flex::{JSMAP, NP_JSON};
+use crate::schema::{NP_Schema, NP_TypeKeys};
+use crate::{pointer::NP_Value, error::NP_Error};
+use crate::utils::{read_varint, write_varint};
+use crate::pointer::numbers::unsigned_to_json;
+use alloc::borrow::ToOwned;
+use super::{NP_Cursor};
+use crate::NP_Memory;
+use alloc::string::ToString;
+
+/// Size of the block header, `[count u32][used u32][capacity u32][last value u64]`
+const HEADER_SIZE: usize = 20;
+
+/// Holds a sequence of sorted unsigned integers.
+///
+/// Check out documentation [here](../sorted_uints/index.html).
+///
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct NP_SortedUints {
+    /// The values, smallest first
+    pub values: Vec<u64>
+}
+
+impl NP_SortedUints {
+    /// Create a new sequence, the values must already be sorted
+    pub fn new(values: Vec<u64>) -> Self {
+        NP_SortedUints { values }
+    }
+
+    /// Width in bytes of the first value for a given base type
+    fn base_width(base: NP_TypeKeys) -> usize {
+        match base {
+            NP_TypeKeys::Uint8  => 1,
+            NP_TypeKeys::Uint16 => 2,
+            NP_TypeKeys::Uint32 => 4,
+            _ => 8
+        }
+    }
+
+    /// Largest value the base type can hold
+    fn base_max(base: NP_TypeKeys) -> u64 {
+        match base {
+            NP_TypeKeys::Uint8  => u8::MAX as u64,
+            NP_TypeKeys::Uint16 => u16::MAX as u64,
+            NP_TypeKeys::Uint32 => u32::MAX as u64,
+            _ => u64::MAX
+        }
+    }
+
+    fn base_from_str(base: &str) -> Result<NP_TypeKeys, NP_Error> {
+        match base {
+            "u8"  | "uint8"  => Ok(NP_TypeKeys::Uint8),
+            "u16" | "uint16" => Ok(NP_TypeKeys::Uint16),
+            "u32" | "uint32" => Ok(NP_TypeKeys::Uint32),
+            "u64" | "uint64" => Ok(NP_TypeKeys::Uint64),
+            _ => Err(NP_Error::new("sorted_uints 'base' property must be one of u8, u16, u32 or u64!"))
+        }
+    }
+
+    fn base_to_str(base: NP_TypeKeys) -> &'static str {
+        match base {
+            NP_TypeKeys::Uint8  => "u8",
+            NP_TypeKeys::Uint16 => "u16",
+            NP_TypeKeys::Uint32 => "u32",
+            _ => "u64"
+        }
+    }
+
+    /// Encode a single value onto the end of a sequence that currently ends with `last`
+    fn encode_next(base: NP_TypeKeys, last: Option<u64>, value: u64, out: &mut Vec<u8>) -> Result<(), NP_Error> {
+        if value > NP_SortedUints::base_max(base) {
+            let mut err = String::from("Value is too large for sorted_uints base type: ");
+            err.push_str(value.to_string().as_str());
+            return Err(NP_Error::new(err));
+        }
+
+        match last {
+            Some(last) => {
+                if value < last {
+                    let mut err = String::from("sorted_uints values must be pushed in order, got ");
+                    err.push_str(value.to_string().as_str());
+                    err.push_str(" after ");
+                    err.push_str(last.to_string().as_str());
+                    return Err(NP_Error::new(err));
+                }
+                write_varint(value - last, out);
+            },
+            None => {
+                let width = NP_SortedUints::base_width(base);
+                out.extend_from_slice(&value.to_be_bytes()[(8 - width)..]);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write a whole new block to the buffer and point the cursor at it
+    fn write_block(cursor: &NP_Cursor, memory: &NP_Memory, count: u32, last: u64, data: &[u8], capacity: usize) -> Result<(), NP_Error> {
+        let mut block: Vec<u8> = Vec::with_capacity(HEADER_SIZE + capacity);
+        block.extend_from_slice(&count.to_be_bytes());
+        block.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        block.extend_from_slice(&(capacity as u32).to_be_bytes());
+        block.extend_from_slice(&last.to_be_bytes());
+        block.extend_from_slice(data);
+        block.resize(HEADER_SIZE + capacity, 0);
+
+        let addr = memory.malloc(block)?;
+        cursor.get_value_mut(memory).set_addr_value(addr as u32);
+
+        Ok(())
+    }
+
+    /// Read `(count, used, capacity, last)` from the block header
+    fn read_header(value_addr: usize, memory: &NP_Memory) -> Result<(u32, usize, usize, u64), NP_Error> {
+        let bytes = memory.read_bytes();
+
+        if value_addr + HEADER_SIZE > bytes.len() {
+            return Err(NP_Error::new("sorted_uints header is out of bounds!"));
+        }
+
+        let count = u32::from_be_bytes(*memory.get_4_bytes(value_addr).unwrap_or(&[0; 4]));
+        let used = u32::from_be_bytes(*memory.get_4_bytes(value_addr + 4).unwrap_or(&[0; 4])) as usize;
+        let capacity = u32::from_be_bytes(*memory.get_4_bytes(value_addr + 8).unwrap_or(&[0; 4])) as usize;
+        let last = u64::from_be_bytes(*memory.get_8_bytes(value_addr + 12).unwrap_or(&[0; 8]));
+
+        if used > capacity || value_addr + HEADER_SIZE + capacity > bytes.len() {
+            return Err(NP_Error::new("sorted_uints data is out of bounds!"));
+        }
+
+        Ok((count, used, capacity, last))
+    }
+
+    /// Append a value at the cursor, creating the sequence if it doesn't exist.
+    ///
+    /// Writes in place while there is spare capacity, otherwise the block is copied somewhere with double the capacity.
+    #[doc(hidden)]
+    pub fn push(cursor: &NP_Cursor, memory: &NP_Memory, value: u64) -> Result<(), NP_Error> {
+        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_SortedUints_Data) };
+
+        let value_addr = cursor.get_value(memory).get_addr_value() as usize;
+
+        if value_addr == 0 {
+            let mut encoded: Vec<u8> = Vec::new();
+            NP_SortedUints::encode_next(data.base, None, value, &mut encoded)?;
+            let capacity = encoded.len();
+            return NP_SortedUints::write_block(cursor, memory, 1, value, &encoded, capacity);
+        }
+
+        let (count, used, capacity, last) = NP_SortedUints::read_header(value_addr, memory)?;
+
+        if count == u32::MAX {
+            return Err(NP_Error::new("sorted_uints is full!"));
+        }
+
+        let mut encoded: Vec<u8> = Vec::new();
+        NP_SortedUints::encode_next(data.base, if count == 0 { None } else { Some(last) }, value, &mut encoded)?;
+
+        if used + encoded.len() <= capacity {
+            let write_bytes = memory.write_bytes();
+            let data_start = value_addr + HEADER_SIZE + used;
+            write_bytes[data_start..(data_start + encoded.len())].copy_from_slice(&encoded);
+            write_bytes[value_addr..(value_addr + 4)].copy_from_slice(&(count + 1).to_be_bytes());
+            write_bytes[(value_addr + 4)..(value_addr + 8)].copy_from_slice(&((used + encoded.len()) as u32).to_be_bytes());
+            write_bytes[(value_addr + 12)..(value_addr + 20)].copy_from_slice(&value.to_be_bytes());
+            return Ok(());
+        }
+
+        let mut new_data: Vec<u8> = Vec::with_capacity(used + encoded.len());
+        new_data.extend_from_slice(&memory.read_bytes()[(value_addr + HEADER_SIZE)..(value_addr + HEADER_SIZE + used)]);
+        new_data.extend(encoded);
+
+        let new_capacity = usize::max(capacity * 2, new_data.len());
+
+        if new_capacity > u32::MAX as usize {
+            return Err(NP_Error::new("sorted_uints is full!"));
+        }
+
+        NP_SortedUints::write_block(cursor, memory, count + 1, value, &new_data, new_capacity)
+    }
+
+    /// Iterate the values at the cursor without collecting them, `None` if nothing is set.
+    #[doc(hidden)]
+    pub fn iter<'it>(cursor: &NP_Cursor, memory: &'it NP_Memory) -> Result<Option<NP_SortedUintsIter<'it>>, NP_Error> {
+        let value_addr = cursor.get_value(memory).get_addr_value() as usize;
+
+        if value_addr == 0 {
+            return Ok(None);
+        }
+
+        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_SortedUints_Data) };
+
+        let (count, used, _capacity, _last) = NP_SortedUints::read_header(value_addr, memory)?;
+
+        let start = value_addr + HEADER_SIZE;
+
+        Ok(Some(NP_SortedUintsIter {
+            bytes: &memory.read_bytes()[start..(start + used)],
+            width: NP_SortedUints::base_width(data.base),
+            remaining: count,
+            offset: 0,
+            last: None
+        }))
+    }
+
+    fn schema_bytes(base: NP_TypeKeys) -> Vec<u8> {
+        let mut schema_data: Vec<u8> = Vec::new();
+        schema_data.push(NP_TypeKeys::SortedUints as u8);
+        schema_data.push(base as u8);
+        schema_data
+    }
+
+    fn push_schema(mut schema: Vec<NP_Parsed_Schema>, base: NP_TypeKeys) -> Vec<NP_Parsed_Schema> {
+        schema.push(NP_Parsed_Schema {
+            val: NP_Value_Kind::Pointer,
+            i: NP_TypeKeys::SortedUints,
+            sortable: false,
+            data: Arc::new(Box::into_raw(Box::new(NP_SortedUints_Data { base })) as *const u8)
+        });
+        schema
+    }
+}
+
+/// Reads sorted_uints values in order, straight from the buffer.
+///
+/// Yields an error and stops if the stored data doesn't decode to exactly the number of values in the block header.
+///
+/// Created with [`NP_Buffer::sorted_uints_iter`](../../buffer/struct.NP_Buffer.html#method.sorted_uints_iter).
+///
+#[derive(Debug)]
+pub struct NP_SortedUintsIter<'it> {
+    bytes: &'it [u8],
+    width: usize,
+    remaining: u32,
+    offset: usize,
+    last: Option<u64>
+}
+
+impl<'it> NP_SortedUintsIter<'it> {
+    /// Stop iterating and report a block that doesn't hold `count` values
+    fn corrupt(&mut self) -> Option<Result<u64, NP_Error>> {
+        self.remaining = 0;
+        self.offset = self.bytes.len();
+        Some(Err(NP_Error::new("sorted_uints data doesn't match it's value count!")))
+    }
+}
+
+impl<'it> Iterator for NP_SortedUintsIter<'it> {
+    type Item = Result<u64, NP_Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            // bytes left over means the header count is short
+            if self.offset < self.bytes.len() {
+                return self.corrupt();
+            }
+            return None;
+        }
+
+        let value = match self.last {
+            Some(last) => {
+                let (delta, used) = match read_varint(&self.bytes[self.offset..]) {
+                    Some(x) => x,
+                    None => return self.corrupt()
+                };
+                self.offset += used;
+                match last.checked_add(delta) {
+                    Some(x) => x,
+                    None => return self.corrupt()
+                }
+            },
+            None => {
+                if self.offset + self.width > self.bytes.len() {
+                    return self.corrupt();
+                }
+                let mut be_bytes = [0u8; 8];
+                be_bytes[(8 - self.width)..].copy_from_slice(&self.bytes[self.offset..(self.offset + self.width)]);
+                self.offset += self.width;
+                u64::from_be_bytes(be_bytes)
+            }
+        };
+
+        self.remaining -= 1;
+        self.last = Some(value);
+
+        Some(Ok(value))
+    }
+}
+
+impl<'value> super::NP_Scalar<'value> for NP_SortedUints {
+    fn schema_default(_schema: &NP_Parsed_Schema) -> Option<Self> where Self: Sized {
+        Some(NP_SortedUints::default())
+    }
+
+    fn np_max_value(_cursor: &NP_Cursor, _memory: &NP_Memory) -> Option<Self> {
+        None
+    }
+
+    fn np_min_value(_cursor: &NP_Cursor, _memory: &NP_Memory) -> Option<Self> {
+        None
+    }
+}
+
+impl<'value> NP_Value<'value> for NP_SortedUints {
+
+    fn type_idx() -> (&'value str, NP_TypeKeys) { ("sorted_uints", NP_TypeKeys::SortedUints) }
+    fn self_type_idx(&self) -> (&'value str, NP_TypeKeys) { ("sorted_uints", NP_TypeKeys::SortedUints) }
+
+    fn schema_to_json(schema: &Vec<NP_Parsed_Schema>, address: usize)-> Result<NP_JSON, NP_Error> {
+        let data = unsafe { &*(*schema[address].data as *const NP_SortedUints_Data) };
+
+        let mut schema_json = JSMAP::new();
+        schema_json.insert("type".to_owned(), NP_JSON::String(Self::type_idx().0.to_string()));
+        schema_json.insert("base".to_owned(), NP_JSON::String(NP_SortedUints::base_to_str(data.base).to_string()));
+
+        Ok(NP_JSON::Dictionary(schema_json))
+    }
+
+    fn schema_to_idl(schema: &Vec<NP_Parsed_Schema>, address: usize)-> Result<String, NP_Error> {
+        let data = unsafe { &*(*schema[address].data as *const NP_SortedUints_Data) };
+
+        let mut result = String::from("sorted_uints({base: \"");
+        result.push_str(NP_SortedUints::base_to_str(data.base));
+        result.push_str("\"");
+        if NP_Schema::is_required(&schema[address]) {
+            result.push_str(", required: true");
+        }
+        result.push_str("})");
+        Ok(result)
+    }
+
+    fn from_idl_to_schema(schema: Vec<NP_Parsed_Schema>, _name: &str, idl: &JS_Schema, args: &Vec<JS_AST>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
+        let mut base: Option<NP_TypeKeys> = None;
+
+        if args.len() > 0 {
+            match &args[0] {
+                JS_AST::object { properties } => {
+                    for (key, value) in properties {
+                        match idl.get_str(key).trim() {
+                            "base" => {
+                                match value {
+                                    JS_AST::string { addr } => {
+                                        base = Some(NP_SortedUints::base_from_str(idl.get_str(addr).trim())?);
+                                    },
+                                    _ => { }
+                                }
+                            },
+                            _ => { }
+                        }
+                    }
+                },
+                _ => { }
+            }
+        }
+
+        let base = match base {
+            Some(x) => x,
+            None => return Err(NP_Error::new("sorted_uints type requires 'base' property!"))
+        };
+
+        Ok((false, NP_SortedUints::schema_bytes(base), NP_SortedUints::push_schema(schema, base)))
+    }
+
+    fn from_json_to_schema(schema: Vec<NP_Parsed_Schema>, json_schema: &Box<NP_JSON>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
+        let base = match &json_schema["base"] {
+            NP_JSON::String(x) => NP_SortedUints::base_from_str(x.trim())?,
+            _ => return Err(NP_Error::new("sorted_uints type requires 'base' property!"))
+        };
+
+        Ok((false, NP_SortedUints::schema_bytes(base), NP_SortedUints::push_schema(schema, base)))
+    }
+
+    fn from_bytes_to_schema(schema: Vec<NP_Parsed_Schema>, address: usize, bytes: &[u8]) -> (bool, Vec<NP_Parsed_Schema>) {
+        let base = match NP_TypeKeys::from(bytes[address + 1]) {
+            NP_TypeKeys::Uint8  => NP_TypeKeys::Uint8,
+            NP_TypeKeys::Uint16 => NP_TypeKeys::Uint16,
+            NP_TypeKeys::Uint32 => NP_TypeKeys::Uint32,
+            _ => NP_TypeKeys::Uint64
+        };
+
+        (false, NP_SortedUints::push_schema(schema, base))
+    }
+
+    fn set_value<'set>(cursor: NP_Cursor, memory: &'set NP_Memory, value: Self) -> Result<NP_Cursor, NP_Error> where Self: 'set + Sized {
+        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_SortedUints_Data) };
+
+        if value.values.len() > u32::MAX as usize {
+            return Err(NP_Error::new("sorted_uints is full!"));
+        }
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut last: Option<u64> = None;
+
+        for x in value.values.iter() {
+            NP_SortedUints::encode_next(data.base, last, *x, &mut encoded)?;
+            last = Some(*x);
+        }
+
+        let capacity = encoded.len();
+
+        NP_SortedUints::write_block(&cursor, memory, value.values.len() as u32, last.unwrap_or(0), &encoded, capacity)?;
+
+        Ok(cursor)
+    }
+
+    fn set_from_json<'set>(_depth: usize, apply_null: bool, cursor: NP_Cursor, memory: &'set NP_Memory, value: &Box<NP_JSON>) -> Result<(), NP_Error> where Self: 'set + Sized {
+        match &**value {
+            NP_JSON::Null => {
+                if apply_null {
+                    NP_Cursor::delete(cursor, memory)?;
+                }
+            },
+            NP_JSON::Array(items) => {
+                let mut values: Vec<u64> = Vec::with_capacity(items.len());
+
+                for item in items.iter() {
+                    match item {
+                        NP_JSON::Integer(x) if *x >= 0 => values.push(*x as u64),
+                        // values above i64::MAX are written out as strings
+                        NP_JSON::String(x) => match x.trim().parse::<u64>() {
+                            Ok(x) => values.push(x),
+                            Err(_) => return Err(NP_Error::new("sorted_uints values must be positive integers!"))
+                        },
+                        _ => return Err(NP_Error::new("sorted_uints values must be positive integers!"))
+                    }
+                }
+
+                Self::set_value(cursor, memory, NP_SortedUints::new(values))?;
+            },
+            _ => { NP_Cursor::json_type_mismatch(memory, "array", value)?; }
+        }
+
+        Ok(())
+    }
+
+    fn into_value(cursor: &NP_Cursor, memory: &'value NP_Memory) -> Result<Option<Self>, NP_Error> where Self: Sized {
+        match NP_SortedUints::iter(cursor, memory)? {
+            Some(iter) => Ok(Some(NP_SortedUints::new(iter.collect::<Result<Vec<u64>, NP_Error>>()?))),
+            None => Ok(None)
+        }
+    }
+
+    fn default_value(_depth: usize, _addr: usize, _schema: &Vec<NP_Parsed_Schema>) -> Option<Self> {
+        None
+    }
+
+    fn to_json(_depth:usize, cursor: &NP_Cursor, memory: &'value NP_Memory) -> NP_JSON {
+        match Self::into_value(cursor, memory) {
+            Ok(Some(x)) => NP_JSON::Array(x.values.iter().map(|v| unsigned_to_json(*v)).collect()),
+            _ => NP_JSON::Null
+        }
+    }
+
+    fn get_size(_depth:usize, cursor: &NP_Cursor, memory: &NP_Memory) -> Result<usize, NP_Error> {
+        let value_addr = cursor.get_value(memory).get_addr_value() as usize;
+
+        if value_addr == 0 {
+            return Ok(0);
+        }
+
+        let (_count, _used, capacity, _last) = NP_SortedUints::read_header(value_addr, memory)?;
+
+        Ok(HEADER_SIZE + capacity)
+    }
+}
+
+#[test]
+fn schema_parsing_works_idl() -> Result<(), NP_Error> {
+    let schema = "sorted_uints({base: \"u32\"})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+
+    assert!(crate::NP_Factory::new("sorted_uints({base: \"i64\"})").is_err());
+    assert!(crate::NP_Factory::new("sorted_uints()").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn schema_parsing_works() -> Result<(), NP_Error> {
+    let schema = "{\"type\":\"sorted_uints\",\"base\":\"u64\"}";
+    let factory = crate::NP_Factory::new_json(schema)?;
+    assert_eq!(schema, factory.schema.to_json()?.stringify());
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_json()?.stringify());
+
+    Ok(())
+}
+
+#[test]
+fn encode_decode_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("struct({fields: {times: sorted_uints({base: \"u64\"})}})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    assert!(buffer.sorted_uints_iter(&["times"])?.is_none());
+
+    let mut expected: Vec<u64> = Vec::new();
+    let mut time = u64::MAX - 1_000_000;
+    for step in 0..200u64 {
+        time += step * 7;
+        expected.push(time);
+        buffer.sorted_uints_push(&["times"], time)?;
+    }
+    // repeated values are allowed
+    buffer.sorted_uints_push(&["times"], time)?;
+    expected.push(time);
+
+    assert_eq!(buffer.sorted_uints_iter(&["times"])?.unwrap().collect::<Result<Vec<u64>, NP_Error>>()?, expected);
+    assert_eq!(buffer.get::<NP_SortedUints>(&["times"])?.unwrap().values, expected);
+
+    // the first value is 8 bytes, every small delta is 1 or 2
+    let size = buffer.calc_bytes()?.current_buffer;
+    buffer.compact(None)?;
+    assert!(buffer.calc_bytes()?.current_buffer < size);
+    assert!(buffer.calc_bytes()?.current_buffer < (201 * 8) / 2);
+    assert_eq!(buffer.get::<NP_SortedUints>(&["times"])?.unwrap().values, expected);
+
+    // set the whole sequence at once, or from json
+    buffer.set(&["times"], NP_SortedUints::new(vec![1, 5, 300, 70_000]))?;
+    assert_eq!(buffer.sorted_uints_iter(&["times"])?.unwrap().collect::<Result<Vec<u64>, NP_Error>>()?, vec![1, 5, 300, 70_000]);
+    buffer.set_with_json(&["times"], r#"{"value": [2, 4, 8]}"#)?;
+    assert_eq!(buffer.get::<NP_SortedUints>(&["times"])?.unwrap().values, vec![2, 4, 8]);
+    assert_eq!(buffer.json_encode(&["times"])?.stringify(), "{\"value\":[2,4,8]}");
+
+    // values past i64::MAX go through JSON as strings without wrapping
+    let big = vec![5, i64::MAX as u64 + 1, u64::MAX];
+    buffer.set(&["times"], NP_SortedUints::new(big.clone()))?;
+    let json = buffer.json_encode(&["times"])?.stringify();
+    assert_eq!(json, "{\"value\":[5,\"9223372036854775808\",\"18446744073709551615\"]}");
+    buffer.set_with_json(&["times"], r#"{"value": [1]}"#)?;
+    buffer.set_with_json(&["times"], json)?;
+    assert_eq!(buffer.get::<NP_SortedUints>(&["times"])?.unwrap().values, big);
+
+    Ok(())
+}
+
+#[test]
+fn out_of_order_rejected() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("sorted_uints({base: \"u8\"})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    buffer.sorted_uints_push(&[], 10)?;
+    buffer.sorted_uints_push(&[], 20)?;
+    assert!(buffer.sorted_uints_push(&[], 19).is_err());
+    // too big for the base type
+    assert!(buffer.sorted_uints_push(&[], 256).is_err());
+    // failed pushes leave the sequence alone
+    assert_eq!(buffer.sorted_uints_iter(&[])?.unwrap().collect::<Result<Vec<u64>, NP_Error>>()?, vec![10, 20]);
+
+    assert!(buffer.set(&[], NP_SortedUints::new(vec![3, 2])).is_err());
+    assert!(buffer.set_with_json(&[], r#"{"value": [1, -2]}"#).is_err());
+    assert!(buffer.set_with_json(&[], r#"{"value": [1, "two"]}"#).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn truncated_block_is_an_error() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("sorted_uints({base: \"u8\"})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    buffer.sorted_uints_push(&[], 10)?;
+    buffer.sorted_uints_push(&[], 20)?;
+    buffer.sorted_uints_push(&[], 30)?;
+
+    let (addr, _length) = buffer.value_location(&[])?.unwrap();
+
+    // drop the last delta from the data but keep the count at 3
+    let mut bytes = buffer.read_bytes().to_vec();
+    bytes[(addr + 4)..(addr + 8)].copy_from_slice(&2u32.to_be_bytes());
+    let truncated = factory.open_buffer(bytes);
+
+    let mut iter = truncated.sorted_uints_iter(&[])?.unwrap();
+    assert_eq!(iter.next().unwrap()?, 10);
+    assert_eq!(iter.next().unwrap()?, 20);
+    assert!(matches!(iter.next(), Some(Err(NP_Error::Custom { message })) if message == "sorted_uints data doesn't match it's value count!"));
+    assert!(iter.next().is_none());
+    assert!(truncated.get::<NP_SortedUints>(&[]).is_err());
+
+    // a count that's too small leaves data behind
+    let mut bytes = buffer.read_bytes().to_vec();
+    bytes[addr..(addr + 4)].copy_from_slice(&2u32.to_be_bytes());
+    let short_count = factory.open_buffer(bytes);
+
+    assert!(short_count.sorted_uints_iter(&[])?.unwrap().collect::<Result<Vec<u64>, NP_Error>>().is_err());
+    assert!(short_count.get::<NP_SortedUints>(&[]).is_err());
+
+    Ok(())
+}
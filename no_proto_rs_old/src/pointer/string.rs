@@ -23,7 +23,7 @@ use crate::{
     memory::NP_Memory,
     schema::{NP_Parsed_Schema},
 };
-use crate::{json_flex::NP_JSON, pointer::NP_Value, schema::NP_TypeKeys};
+use crate::{json_flex::NP_JSON, pointer::NP_Value, schema::{NP_Schema, NP_TypeKeys}};
 use alloc::vec::Vec;
 
 use super::{NP_Cursor, NP_Scalar};
@@ -177,6 +177,10 @@ impl<'value> NP_Value<'value> for String {
             _ => {}
         }
 
+        if NP_Schema::is_required(schema) {
+            properties.push(String::from("required: true"));
+        }
+
         if properties.len() == 0 {
             Ok(String::from("string()"))
         } else {
@@ -290,7 +294,7 @@ impl<'value> NP_Value<'value> for String {
             NP_JSON::String(value) => {
                 Self::set_value(cursor, memory, value.clone())?;
             },
-            _ => {}
+            _ => { NP_Cursor::json_type_mismatch(memory, "string", value)?; }
         }
 
         Ok(())
@@ -422,7 +426,7 @@ impl<'value> NP_Value<'value> for String {
                 schema_data.extend((x as u32).to_be_bytes().to_vec());
                 x as u32
             }
-            NP_JSON::Float(x) => {
+            NP_JSON::Float(x, _) => {
                 has_fixed_size = true;
                 if x < 1.0 {
                     return Err(NP_Error::new(
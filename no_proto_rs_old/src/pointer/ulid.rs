@@ -23,7 +23,7 @@ use crate::{idl::{JS_AST, JS_Schema}, memory::NP_Memory, schema::{NP_Parsed_Sche
 use alloc::{sync::Arc, vec::Vec};
 use crate::utils::to_base32;
 use crate::json_flex::{JSMAP, NP_JSON};
-use crate::schema::{NP_TypeKeys};
+use crate::schema::{NP_Schema, NP_TypeKeys};
 use crate::{pointer::NP_Value, error::NP_Error, utils::{Rand}};
 use core::{fmt::{Debug, Formatter}};
 
@@ -200,14 +200,18 @@ impl<'value> NP_Value<'value> for NP_ULID {
             NP_JSON::String(value) => {
                 Self::set_value(cursor, memory, NP_ULID::from_string(&value))?;
             },
-            _ => {}
+            _ => { NP_Cursor::json_type_mismatch(memory, "string", value)?; }
         }
 
         Ok(())
     }
 
-    fn schema_to_idl(_schema: &Vec<NP_Parsed_Schema>, _address: usize)-> Result<String, NP_Error> {
-        Ok(String::from("ulid()"))
+    fn schema_to_idl(schema: &Vec<NP_Parsed_Schema>, address: usize)-> Result<String, NP_Error> {
+        if NP_Schema::is_required(&schema[address]) {
+            Ok(String::from("ulid({required: true})"))
+        } else {
+            Ok(String::from("ulid()"))
+        }
     }
 
     fn from_idl_to_schema(schema: Vec<NP_Parsed_Schema>, _name: &str, _idl: &JS_Schema, _args: &Vec<JS_AST>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
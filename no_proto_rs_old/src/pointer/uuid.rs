@@ -23,14 +23,14 @@
 //! 
 
 use alloc::sync::Arc;
-use crate::{JS_Schema, idl::JS_AST, schema::{NP_Value_Kind, NULL}};
+use crate::{JS_Schema, idl::JS_AST, schema::{NP_Uuid_Data, NP_Value_Kind}};
 use alloc::prelude::v1::Box;
 use crate::pointer::NP_Scalar;
 use crate::{memory::NP_Memory, schema::{NP_Parsed_Schema}};
 use alloc::vec::Vec;
 use crate::json_flex::{JSMAP, NP_JSON};
-use crate::schema::{NP_TypeKeys};
-use crate::{pointer::NP_Value, error::NP_Error, utils::{Rand}};
+use crate::schema::{NP_Schema, NP_TypeKeys};
+use crate::{pointer::NP_Value, error::NP_Error, utils::{Rand, sha1}};
 use core::{fmt::{Debug, Formatter, Write}};
 
 use alloc::string::String;
@@ -55,10 +55,21 @@ pub struct NP_UUID {
 /// ULID alias for shared value
 pub type _NP_UUID<'a> = &'a NP_UUID;
 
+/// URL-safe base64 alphabet used for the compact UUID form
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Set on the schema version byte when the field is `auto`
+const AUTO_FLAG: u8 = 0b1000_0000;
+
+/// Set on the schema version byte when nil values are written to JSON as `null`
+const OMIT_NIL_FLAG: u8 = 0b0100_0000;
+
 impl NP_UUID {
 
     /// Generate a new UUID with a given random seed.  You should attempt to provide a seed with as much randomness as possible.
     /// 
+    /// The same seed always gives the same UUID, prefer `new_v4` (with the `getrandom` feature) when the UUID shouldn't be guessable.
+    /// 
     pub fn generate(random_seed: u32) -> Self {
 
 
@@ -79,6 +90,38 @@ impl NP_UUID {
         uuid
     }
 
+    /// Generate a random (version 4) UUID from the operating system's secure random source.
+    /// 
+    /// This is the recommended way to make random UUIDs, unlike `generate` the result can't be predicted from a seed.  Requires the `getrandom` feature.
+    /// 
+    /// # Panics
+    /// 
+    /// If the operating system can't provide random bytes.
+    /// 
+    /// ```
+    /// # #[cfg(feature = "getrandom")] {
+    /// use no_proto::pointer::uuid::NP_UUID;
+    /// 
+    /// let uuid = NP_UUID::new_v4();
+    /// assert_eq!(uuid.version(), 4);
+    /// # }
+    /// ```
+    /// 
+    #[cfg(feature = "getrandom")]
+    pub fn new_v4() -> Self {
+        let mut uuid = NP_UUID {
+            value: [0; 16]
+        };
+
+        getrandom::getrandom(&mut uuid.value).expect("Operating system random source failed!");
+
+        // version 4, RFC 4122 variant
+        uuid.value[6] = (uuid.value[6] & 0x0F) | 0x40;
+        uuid.value[8] = (uuid.value[8] & 0x3F) | 0x80;
+
+        uuid
+    }
+
     /// Generates a UUID with a provided random number generator.
     /// This is the preferrable way to generate a ULID, if you can provide a better RNG function than the psudorandom one built into this library, you should.
     /// 
@@ -98,8 +141,148 @@ impl NP_UUID {
         uuid
     }
 
+    /// Generate a time ordered (version 7) UUID from a unix timestamp in milliseconds and a random number generator.
+    /// 
+    /// The first 48 bits are the timestamp in big endian order, so the raw bytes of UUIDs made in different milliseconds sort by time, which keeps them in time order in sorted collections.  Only the low 48 bits of `unix_millis` are used, that covers dates until the year 10889.  The version and variant bits are set and every other bit comes from `random_fn`.
+    /// 
+    /// ```
+    /// use no_proto::pointer::uuid::NP_UUID;
+    /// 
+    /// let uuid = NP_UUID::generate_v7(1704164645678, || 0xFF);
+    /// assert_eq!(uuid.to_string(), "018CC820-DB2E-7FFF-BFFF-FFFFFFFFFFFF");
+    /// assert_eq!(uuid.version(), 7);
+    /// ```
+    /// 
+    pub fn generate_v7<F>(unix_millis: u64, random_fn: F) -> Self where F: Fn() -> u8 {
+        let mut uuid = NP_UUID {
+            value: [0; 16]
+        };
+
+        uuid.value[..6].copy_from_slice(&unix_millis.to_be_bytes()[2..]);
+
+        for x in 6..uuid.value.len() {
+            uuid.value[x] = random_fn();
+        }
+
+        // version 7, RFC 4122 variant
+        uuid.value[6] = (uuid.value[6] & 0x0F) | 0x70;
+        uuid.value[8] = (uuid.value[8] & 0x3F) | 0x80;
+
+        uuid
+    }
+
+    /// Generate a random (version 4) UUID from entropy you already have, like bytes from a CSPRNG buffer.
+    /// 
+    /// The first 16 bytes of `bytes` are used and anything after them is ignored.  The version and variant bits are set, every other bit comes from the entropy.  Fewer than 16 bytes is an error.
+    /// 
+    /// ```
+    /// use no_proto::pointer::uuid::NP_UUID;
+    /// use no_proto::error::NP_Error;
+    /// 
+    /// let uuid = NP_UUID::from_entropy(&[0u8; 16])?;
+    /// assert_eq!(uuid.to_string(), "00000000-0000-4000-8000-000000000000");
+    /// assert_eq!(uuid.version(), 4);
+    /// 
+    /// assert!(NP_UUID::from_entropy(&[0u8; 15]).is_err());
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn from_entropy(bytes: &[u8]) -> Result<NP_UUID, NP_Error> {
+        if bytes.len() < 16 {
+            return Err(NP_Error::new("UUID entropy must be at least 16 bytes!"))
+        }
+
+        let mut value = [0u8; 16];
+        value.copy_from_slice(&bytes[..16]);
+
+        NP_UUID::from_bytes_with_version(value, 4)
+    }
+
+    /// The DNS namespace UUID from RFC 4122, `6BA7B810-9DAD-11D1-80B4-00C04FD430C8`.
+    pub const NAMESPACE_DNS: NP_UUID = NP_UUID { value: [0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8] };
+
+    /// Generate a name based (version 5) UUID from a namespace and a name.
+    /// 
+    /// The same namespace and name always produce the same UUID.
+    /// 
+    /// ```
+    /// use no_proto::pointer::uuid::NP_UUID;
+    /// 
+    /// let uuid = NP_UUID::generate_v5(&NP_UUID::NAMESPACE_DNS, b"python.org");
+    /// assert_eq!(uuid.to_string(), "886313E1-3B8A-5372-9B90-0C9AEE199E5D");
+    /// assert_eq!(uuid.version(), 5);
+    /// ```
+    /// 
+    pub fn generate_v5(namespace: &NP_UUID, name: &[u8]) -> Self {
+        let mut data: Vec<u8> = Vec::with_capacity(16 + name.len());
+        data.extend_from_slice(&namespace.value);
+        data.extend_from_slice(name);
+
+        let hash = sha1(&data);
+
+        let mut uuid = NP_UUID {
+            value: [0; 16]
+        };
+
+        uuid.value.copy_from_slice(&hash[..16]);
+
+        // version 5, RFC 4122 variant
+        uuid.value[6] = (uuid.value[6] & 0x0F) | 0x50;
+        uuid.value[8] = (uuid.value[8] & 0x3F) | 0x80;
+
+        uuid
+    }
+
+    /// Generate a stable UUID for a name, handy for reproducible test data.
+    /// 
+    /// Same as `generate_v5` with the DNS namespace.
+    /// 
+    /// ```
+    /// use no_proto::pointer::uuid::NP_UUID;
+    /// 
+    /// assert_eq!(NP_UUID::from_name("alice"), NP_UUID::from_name("alice"));
+    /// assert_ne!(NP_UUID::from_name("alice"), NP_UUID::from_name("bob"));
+    /// ```
+    /// 
+    pub fn from_name(name: &str) -> Self {
+        NP_UUID::generate_v5(&NP_UUID::NAMESPACE_DNS, name.as_bytes())
+    }
+
+    /// Create a UUID from raw bytes with a custom version.
+    /// 
+    /// The version nibble is set to `version` and the variant bits to the RFC 4122 variant, every other bit is kept as is.  This is an escape hatch for systems that use the UUID layout with their own version meanings, `version` must be between 0 and 15 or an error is returned.
+    /// 
+    /// ```
+    /// use no_proto::pointer::uuid::NP_UUID;
+    /// use no_proto::error::NP_Error;
+    /// 
+    /// let uuid = NP_UUID::from_bytes_with_version([0xFF; 16], 9)?;
+    /// assert_eq!(uuid.to_string(), "FFFFFFFF-FFFF-9FFF-BFFF-FFFFFFFFFFFF");
+    /// assert_eq!(uuid.version(), 9);
+    /// 
+    /// assert!(NP_UUID::from_bytes_with_version([0; 16], 16).is_err());
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn from_bytes_with_version(bytes: [u8; 16], version: u8) -> Result<NP_UUID, NP_Error> {
+        if version > 15 {
+            return Err(NP_Error::new("UUID version must be between 0 and 15!"))
+        }
+
+        let mut uuid = NP_UUID { value: bytes };
+
+        uuid.value[6] = (uuid.value[6] & 0x0F) | (version << 4);
+        uuid.value[8] = (uuid.value[8] & 0x3F) | 0x80;
+
+        Ok(uuid)
+    }
+
     /// Create a UUID from a string representation
     /// 
+    /// This is lenient, pairs of characters that aren't hex become zero bytes.  Use `try_from_string` to get an error instead.
+    /// 
     pub fn from_string<S: AsRef<str>>(uuid: S) -> NP_UUID {
         let cleaned: String = String::from(uuid.as_ref()).replace("-", "").to_uppercase();
 
@@ -116,6 +299,199 @@ impl NP_UUID {
         NP_UUID { value }
     }
 
+    /// Create a UUID from a string representation, checking the input.
+    /// 
+    /// Dashes are ignored, what's left must be exactly 32 hex digits in upper or lower case or an error is returned.
+    /// 
+    /// ```
+    /// use no_proto::pointer::uuid::NP_UUID;
+    /// use no_proto::error::NP_Error;
+    /// 
+    /// let uuid = NP_UUID::try_from_string("886313e1-3b8a-5372-9b90-0c9aee199e5d")?;
+    /// assert_eq!(uuid.to_string(), "886313E1-3B8A-5372-9B90-0C9AEE199E5D");
+    /// 
+    /// assert!(NP_UUID::try_from_string("886313E1-3B8A-5372-9B90-0C9AEE199E5").is_err());
+    /// assert!(NP_UUID::try_from_string("886313E1-3B8A-5372-9B90-0C9AEE199E5G").is_err());
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn try_from_string<S: AsRef<str>>(uuid: S) -> Result<NP_UUID, NP_Error> {
+        let digits: Vec<u8> = uuid.as_ref().bytes().filter(|x| *x != b'-').collect();
+
+        let invalid = || {
+            let mut err = String::from("Invalid UUID string: ");
+            err.push_str(uuid.as_ref());
+            NP_Error::new(err)
+        };
+
+        if digits.len() != 32 {
+            return Err(invalid());
+        }
+
+        let hex = |digit: u8| (digit as char).to_digit(16).ok_or_else(invalid);
+
+        let mut value: [u8; 16] = [0; 16];
+
+        for x in 0..16usize {
+            value[x] = (hex(digits[x * 2])? * 16 + hex(digits[x * 2 + 1])?) as u8;
+        }
+
+        Ok(NP_UUID { value })
+    }
+
+    /// Generates the compact form of the UUID, 22 characters of URL-safe base64 without padding.
+    /// 
+    /// ```
+    /// use no_proto::pointer::uuid::NP_UUID;
+    /// 
+    /// let uuid = NP_UUID::from_string("886313E1-3B8A-5372-9B90-0C9AEE199E5D");
+    /// assert_eq!(uuid.to_base64(), "iGMT4TuKU3KbkAya7hmeXQ");
+    /// ```
+    /// 
+    pub fn to_base64(&self) -> String {
+        let mut result = String::with_capacity(22);
+
+        let mut buffer = 0u32;
+        let mut bits = 0u8;
+
+        for byte in self.value.iter() {
+            buffer = (buffer << 8) | *byte as u32;
+            bits += 8;
+            while bits >= 6 {
+                bits -= 6;
+                result.push(BASE64_CHARS[((buffer >> bits) & 0x3F) as usize] as char);
+            }
+        }
+
+        // last 2 bits, padded with zeros
+        if bits > 0 {
+            result.push(BASE64_CHARS[((buffer << (6 - bits)) & 0x3F) as usize] as char);
+        }
+
+        result
+    }
+
+    /// Parse a UUID from the compact form made by `to_base64`.
+    /// 
+    /// The input must be exactly 22 characters of URL-safe base64 without padding.
+    /// 
+    /// ```
+    /// use no_proto::pointer::uuid::NP_UUID;
+    /// use no_proto::error::NP_Error;
+    /// 
+    /// let uuid = NP_UUID::from_base64("iGMT4TuKU3KbkAya7hmeXQ")?;
+    /// assert_eq!(uuid.to_string(), "886313E1-3B8A-5372-9B90-0C9AEE199E5D");
+    /// 
+    /// # Ok::<(), NP_Error>(()) 
+    /// ```
+    /// 
+    pub fn from_base64(value: &str) -> Result<NP_UUID, NP_Error> {
+        let bytes = value.as_bytes();
+
+        if bytes.len() != 22 {
+            return Err(NP_Error::new("Base64 UUID must be 22 characters long!"))
+        }
+
+        let mut uuid = NP_UUID { value: [0; 16] };
+
+        let mut buffer = 0u32;
+        let mut bits = 0u8;
+        let mut idx = 0usize;
+
+        for byte in bytes.iter() {
+            let sextet = match BASE64_CHARS.iter().position(|x| x == byte) {
+                Some(x) => x as u32,
+                None => return Err(NP_Error::new("Base64 UUID contains an invalid character!"))
+            };
+            buffer = (buffer << 6) | sextet;
+            bits += 6;
+            if bits >= 8 {
+                bits -= 8;
+                uuid.value[idx] = ((buffer >> bits) & 0xFF) as u8;
+                idx += 1;
+            }
+        }
+
+        // the 4 leftover bits are padding and must be empty
+        if buffer & 0x0F != 0 {
+            return Err(NP_Error::new("Base64 UUID has trailing bits set!"))
+        }
+
+        Ok(uuid)
+    }
+
+    /// The nil UUID, every bit is zero.
+    /// 
+    /// Same value as `NP_UUID::default()`, handy as an "empty" sentinel.
+    /// 
+    /// ```
+    /// use no_proto::pointer::uuid::NP_UUID;
+    /// 
+    /// assert_eq!(NP_UUID::nil().to_string(), "00000000-0000-0000-0000-000000000000");
+    /// assert!(NP_UUID::nil().is_nil());
+    /// assert!(!NP_UUID::generate(50).is_nil());
+    /// ```
+    /// 
+    pub fn nil() -> Self {
+        NP_UUID { value: [0; 16] }
+    }
+
+    /// Check if this is the nil UUID.
+    pub fn is_nil(&self) -> bool {
+        self.value == [0; 16]
+    }
+
+    /// Get the version of this UUID, stored in the high nibble of the 7th byte.
+    /// 
+    /// ```
+    /// use no_proto::pointer::uuid::NP_UUID;
+    /// 
+    /// assert_eq!(NP_UUID::generate(50).version(), 4);
+    /// ```
+    /// 
+    pub fn version(&self) -> u8 {
+        self.value[6] >> 4
+    }
+
+    /// Check if the leading bytes of this UUID are equal to `prefix`.
+    /// 
+    /// Prefixes longer than 16 bytes never match.
+    /// 
+    /// ```
+    /// use no_proto::pointer::uuid::NP_UUID;
+    /// 
+    /// let uuid = NP_UUID::from_string("01890A5D-AC96-774B-BCCE-B302099A8057");
+    /// 
+    /// assert!(uuid.starts_with(&[0x01, 0x89, 0x0A]));
+    /// assert!(!uuid.starts_with(&[0x01, 0x89, 0x0B]));
+    /// ```
+    /// 
+    pub fn starts_with(&self, prefix: &[u8]) -> bool {
+        self.value.starts_with(prefix)
+    }
+
+    /// Check if the leading bytes of this UUID fall between `lo` and `hi`, inclusive.
+    /// 
+    /// The first `lo.len()` bytes of the UUID are compared against `lo` and the first `hi.len()` bytes against `hi`, byte by byte the same way UUIDs sort in buffers.  For time ordered UUIDs like v7 the first 6 bytes are a big endian millisecond timestamp, so passing the timestamp bytes of a start and end time matches every UUID created between them without decoding the timestamp.
+    /// 
+    /// ```
+    /// use no_proto::pointer::uuid::NP_UUID;
+    /// 
+    /// let uuid = NP_UUID::from_string("01890A5D-AC96-774B-BCCE-B302099A8057");
+    /// 
+    /// assert!(uuid.prefix_cmp(&[0x01, 0x89, 0x0A], &[0x01, 0x89, 0x0A]));
+    /// assert!(uuid.prefix_cmp(&[0x01, 0x89], &[0x01, 0x8A]));
+    /// assert!(!uuid.prefix_cmp(&[0x01, 0x89, 0x0B], &[0x01, 0x8A]));
+    /// ```
+    /// 
+    pub fn prefix_cmp(&self, lo: &[u8], hi: &[u8]) -> bool {
+        let lo_bytes = &self.value[..usize::min(lo.len(), self.value.len())];
+        let hi_bytes = &self.value[..usize::min(hi.len(), self.value.len())];
+
+        lo_bytes >= lo && hi_bytes <= hi
+    }
+
     /// Generates a stringified version of the UUID.
     /// 
     pub fn to_string(&self) -> String {
@@ -132,6 +508,49 @@ impl NP_UUID {
 
         result
     }
+
+    /// Generates a stringified version of the UUID with everything but the first segment masked out.
+    /// 
+    /// Useful for logs, the value stays correlatable without exposing the whole UUID.
+    /// 
+    /// ```
+    /// use no_proto::pointer::uuid::NP_UUID;
+    /// 
+    /// let uuid = NP_UUID::generate(50);
+    /// assert_eq!("48E6AAB0-****-****-****-************", uuid.to_redacted_string());
+    /// ```
+    /// 
+    pub fn to_redacted_string(&self) -> String {
+        self.to_redacted_string_with(4)
+    }
+
+    /// Generates a stringified version of the UUID that only shows the first `prefix_bytes` bytes, the rest are masked with `*`.
+    /// 
+    /// ```
+    /// use no_proto::pointer::uuid::NP_UUID;
+    /// 
+    /// let uuid = NP_UUID::generate(50);
+    /// assert_eq!("48E6AAB0-7DF5-****-****-************", uuid.to_redacted_string_with(6));
+    /// assert_eq!("********-****-****-****-************", uuid.to_redacted_string_with(0));
+    /// ```
+    /// 
+    pub fn to_redacted_string_with(&self, prefix_bytes: usize) -> String {
+
+        let mut result = String::with_capacity(36);
+
+        for x in 0..self.value.len() {
+            if x == 4 || x == 6 || x == 8 || x == 10 {
+                result.push_str("-");
+            }
+            if x < prefix_bytes {
+                write!(result, "{:02X}", self.value[x]).unwrap_or(());
+            } else {
+                result.push_str("**");
+            }
+        }
+
+        result
+    }
 }
 
 impl Debug for NP_UUID {
@@ -146,6 +565,32 @@ impl Default for NP_UUID {
      }
 }
 
+/// Convert a `uuid::Uuid` into an NP_UUID, requires the `uuid-crate` feature
+/// 
+/// ```
+/// # #[cfg(feature = "uuid-crate")] {
+/// use no_proto::pointer::uuid::NP_UUID;
+/// 
+/// let id = uuid::Uuid::from_bytes([0x9E, 0xE6, 0xAA, 0xB0, 0x2C, 0x94, 0x41, 0xFE, 0xFB, 0x88, 0x42, 0xF7, 0x32, 0x53, 0xF2, 0x17]);
+/// let np_id: NP_UUID = id.into();
+/// assert_eq!(np_id.to_string(), "9EE6AAB0-2C94-41FE-FB88-42F73253F217");
+/// # }
+/// ```
+#[cfg(feature = "uuid-crate")]
+impl From<uuid::Uuid> for NP_UUID {
+    fn from(uuid: uuid::Uuid) -> Self {
+        NP_UUID { value: *uuid.as_bytes() }
+    }
+}
+
+/// Convert an NP_UUID into a `uuid::Uuid`, requires the `uuid-crate` feature
+#[cfg(feature = "uuid-crate")]
+impl From<NP_UUID> for uuid::Uuid {
+    fn from(uuid: NP_UUID) -> Self {
+        uuid::Uuid::from_bytes(uuid.value)
+    }
+}
+
 impl<'value> NP_Scalar<'value> for NP_UUID {
     fn schema_default(_schema: &NP_Parsed_Schema) -> Option<Self> where Self: Sized {
         Some(Self::default())
@@ -166,10 +611,24 @@ impl<'value> NP_Value<'value> for NP_UUID {
     fn type_idx() -> (&'value str, NP_TypeKeys) { ("uuid", NP_TypeKeys::Uuid) }
     fn self_type_idx(&self) -> (&'value str, NP_TypeKeys) { ("uuid", NP_TypeKeys::Uuid) }
 
-    fn schema_to_json(_schema: &Vec<NP_Parsed_Schema>, _address: usize)-> Result<NP_JSON, NP_Error> {
+    fn schema_to_json(schema: &Vec<NP_Parsed_Schema>, address: usize)-> Result<NP_JSON, NP_Error> {
         let mut schema_json = JSMAP::new();
         schema_json.insert("type".to_owned(), NP_JSON::String(Self::type_idx().0.to_string()));
 
+        let data = unsafe { &*(*schema[address].data as *const NP_Uuid_Data) };
+
+        if let Some(version) = data.require_version {
+            schema_json.insert("require_version".to_owned(), NP_JSON::Integer(version as i64));
+        }
+
+        if data.auto {
+            schema_json.insert("auto".to_owned(), NP_JSON::True);
+        }
+
+        if data.omit_nil {
+            schema_json.insert("omit_nil".to_owned(), NP_JSON::True);
+        }
+
         Ok(NP_JSON::Dictionary(schema_json))
     }
 
@@ -181,21 +640,100 @@ impl<'value> NP_Value<'value> for NP_UUID {
 
         match &**value {
             NP_JSON::String(value) => {
-                Self::set_value(cursor, memory, NP_UUID::from_string(&value))?;
+                Self::set_value(cursor, memory, NP_UUID::try_from_string(&value)?)?;
             },
-            _ => {}
+            _ => { NP_Cursor::json_type_mismatch(memory, "string", value)?; }
         }
 
         Ok(())
     }
 
 
-    fn schema_to_idl(_schema: &Vec<NP_Parsed_Schema>, _address: usize)-> Result<String, NP_Error> {
-        Ok(String::from("uuid()"))
+    fn schema_to_idl(schema: &Vec<NP_Parsed_Schema>, address: usize)-> Result<String, NP_Error> {
+        let data = unsafe { &*(*schema[address].data as *const NP_Uuid_Data) };
+
+        let mut properties: Vec<String> = Vec::new();
+
+        if let Some(version) = data.require_version {
+            let mut def = String::from("require_version: ");
+            def.push_str(version.to_string().as_str());
+            properties.push(def);
+        }
+
+        if data.auto {
+            properties.push(String::from("auto: true"));
+        }
+
+        if data.omit_nil {
+            properties.push(String::from("omit_nil: true"));
+        }
+
+        if NP_Schema::is_required(&schema[address]) {
+            properties.push(String::from("required: true"));
+        }
+
+        if properties.len() == 0 {
+            Ok(String::from("uuid()"))
+        } else {
+            let mut final_str = String::from("uuid({");
+            final_str.push_str(properties.join(", ").as_str());
+            final_str.push_str("})");
+            Ok(final_str)
+        }
     }
 
-    fn from_idl_to_schema(schema: Vec<NP_Parsed_Schema>, _name: &str, _idl: &JS_Schema, _args: &Vec<JS_AST>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
-        Self::from_json_to_schema(schema, &Box::new(NP_JSON::Null))
+    fn from_idl_to_schema(mut schema: Vec<NP_Parsed_Schema>, _name: &str, idl: &JS_Schema, args: &Vec<JS_AST>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
+
+        let mut require_version: Option<u8> = None;
+        let mut auto = false;
+        let mut omit_nil = false;
+
+        if args.len() > 0 {
+            match &args[0] {
+                JS_AST::object { properties } => {
+                    for (key, value) in properties.iter() {
+                        match idl.get_str(key).trim() {
+                            "require_version" => {
+                                match value {
+                                    JS_AST::number { addr } => {
+                                        match idl.get_str(addr).trim().parse::<u8>() {
+                                            Ok(x) => {
+                                                require_version = Some(x);
+                                            },
+                                            Err(_e) => { return Err(NP_Error::new("require_version property must be an integer between 1 and 15!")) }
+                                        }
+                                    },
+                                    _ => { }
+                                }
+                            },
+                            "auto" => {
+                                if let JS_AST::bool { state } = value {
+                                    auto = *state;
+                                }
+                            },
+                            "omit_nil" => {
+                                if let JS_AST::bool { state } = value {
+                                    omit_nil = *state;
+                                }
+                            },
+                            _ => { }
+                        }
+                    }
+                },
+                _ => { }
+            }
+        }
+
+        let schema_bytes = NP_UUID::schema_bytes(require_version, auto, omit_nil)?;
+
+        schema.push(NP_Parsed_Schema {
+            val: NP_Value_Kind::Fixed(16),
+            i: NP_TypeKeys::Uuid,
+            sortable: true,
+            data: Arc::new(Box::into_raw(Box::new(NP_Uuid_Data { require_version, auto, omit_nil })) as *const u8)
+        });
+
+        return Ok((true, schema_bytes, schema))
     }
 
     fn default_value(_depth: usize, _scham_addr: usize,_schema: &Vec<NP_Parsed_Schema>) -> Option<Self> {
@@ -215,7 +753,12 @@ impl<'value> NP_Value<'value> for NP_UUID {
             Ok(x) => {
                 match x {
                     Some(y) => {
-                        NP_JSON::String(y.to_string())
+                        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Uuid_Data) };
+                        if data.omit_nil && y.is_nil() {
+                            NP_JSON::Null
+                        } else {
+                            NP_JSON::String(y.to_string())
+                        }
                     },
                     None => {
                         NP_JSON::Null
@@ -239,33 +782,95 @@ impl<'value> NP_Value<'value> for NP_UUID {
         }
     }
 
-    fn from_json_to_schema(mut schema: Vec<NP_Parsed_Schema>, _json_schema: &Box<NP_JSON>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
+    fn from_json_to_schema(mut schema: Vec<NP_Parsed_Schema>, json_schema: &Box<NP_JSON>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
+
+        let require_version = match json_schema["require_version"] {
+            NP_JSON::Integer(x) => {
+                if x < 1 || x > 15 {
+                    return Err(NP_Error::new("require_version property must be an integer between 1 and 15!"))
+                }
+                Some(x as u8)
+            },
+            _ => None
+        };
+
+        let auto = match json_schema["auto"] {
+            NP_JSON::True => true,
+            _ => false
+        };
+
+        let omit_nil = match json_schema["omit_nil"] {
+            NP_JSON::True => true,
+            _ => false
+        };
+
+        let schema_bytes = NP_UUID::schema_bytes(require_version, auto, omit_nil)?;
 
-       
-        let mut schema_bytes: Vec<u8> = Vec::new();
-        schema_bytes.push(NP_TypeKeys::Uuid as u8);
         schema.push(NP_Parsed_Schema {
             val: NP_Value_Kind::Fixed(16),
             i: NP_TypeKeys::Uuid,
             sortable: true,
-            data: Arc::new(NULL())
+            data: Arc::new(Box::into_raw(Box::new(NP_Uuid_Data { require_version, auto, omit_nil })) as *const u8)
         });
         return Ok((true, schema_bytes, schema))
     
     }
 
 
-    fn from_bytes_to_schema(mut schema: Vec<NP_Parsed_Schema>, _address: usize, _bytes: &[u8]) -> (bool, Vec<NP_Parsed_Schema>) {
+    fn from_bytes_to_schema(mut schema: Vec<NP_Parsed_Schema>, address: usize, bytes: &[u8]) -> (bool, Vec<NP_Parsed_Schema>) {
+
+        // `uuid()` is stored as just the type key, without the options byte
+        let options = bytes.get(address + 1).copied().unwrap_or(0);
+
+        let auto = options & AUTO_FLAG == AUTO_FLAG;
+        let omit_nil = options & OMIT_NIL_FLAG == OMIT_NIL_FLAG;
+
+        let require_version = match options & !(AUTO_FLAG | OMIT_NIL_FLAG) {
+            0 => None,
+            x => Some(x)
+        };
+
         schema.push(NP_Parsed_Schema {
             val: NP_Value_Kind::Fixed(16),
             i: NP_TypeKeys::Uuid,
             sortable: true,
-            data: Arc::new(NULL())
+            data: Arc::new(Box::into_raw(Box::new(NP_Uuid_Data { require_version, auto, omit_nil })) as *const u8)
         });
         (true, schema)
     }
 }
 
+impl NP_UUID {
+    /// schema bytes are the type key followed by the required version, zero if any version is allowed.  The high bit of the version byte is set for `auto` fields and the next one for `omit_nil` fields.  The version byte is left off when it would be zero.
+    fn schema_bytes(require_version: Option<u8>, auto: bool, omit_nil: bool) -> Result<Vec<u8>, NP_Error> {
+        let mut schema_bytes: Vec<u8> = Vec::new();
+        schema_bytes.push(NP_TypeKeys::Uuid as u8);
+
+        let mut flag = if auto { AUTO_FLAG } else { 0 };
+        if omit_nil { flag |= OMIT_NIL_FLAG; }
+
+        match require_version {
+            Some(x) => {
+                if x < 1 || x > 15 {
+                    return Err(NP_Error::new("require_version property must be an integer between 1 and 15!"))
+                }
+                if auto && x != 4 {
+                    return Err(NP_Error::new("auto uuids are version 4, require_version must be 4 or unset!"))
+                }
+                schema_bytes.push(x | flag);
+            },
+            None => {
+                // plain `uuid()` stays a single byte, the same as older schemas
+                if flag != 0 {
+                    schema_bytes.push(flag);
+                }
+            }
+        }
+
+        Ok(schema_bytes)
+    }
+}
+
 
 impl<'value> NP_Scalar<'value> for &NP_UUID {
     fn schema_default(_schema: &NP_Parsed_Schema) -> Option<Self> where Self: Sized {
@@ -291,6 +896,19 @@ impl<'value> NP_Value<'value> for &NP_UUID {
     }
 
     fn set_value<'set>(cursor: NP_Cursor, memory: &'set NP_Memory, value: Self) -> Result<NP_Cursor, NP_Error> where Self: 'set + Sized {
+
+        let data = unsafe { &*(*memory.get_schema(cursor.schema_addr).data as *const NP_Uuid_Data) };
+
+        if let Some(version) = data.require_version {
+            if value.version() != version {
+                let mut err = String::from("UUID version ");
+                err.push_str(value.version().to_string().as_str());
+                err.push_str(" does not match required version ");
+                err.push_str(version.to_string().as_str());
+                return Err(NP_Error::new(err));
+            }
+        }
+
         let c_value = || {cursor.get_value(memory)};
 
         let mut value_address = c_value().get_addr_value() as usize;
@@ -313,12 +931,12 @@ impl<'value> NP_Value<'value> for &NP_UUID {
         Ok(cursor)
     }
 
-    fn schema_to_idl(_schema: &Vec<NP_Parsed_Schema>, _address: usize)-> Result<String, NP_Error> {
-        Ok(String::from("uuid()"))
+    fn schema_to_idl(schema: &Vec<NP_Parsed_Schema>, address: usize)-> Result<String, NP_Error> {
+        NP_UUID::schema_to_idl(schema, address)
     }
 
-    fn from_idl_to_schema(schema: Vec<NP_Parsed_Schema>, _name: &str, _idl: &JS_Schema, _args: &Vec<JS_AST>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
-        Self::from_json_to_schema(schema, &Box::new(NP_JSON::Null))
+    fn from_idl_to_schema(schema: Vec<NP_Parsed_Schema>, name: &str, idl: &JS_Schema, args: &Vec<JS_AST>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
+        NP_UUID::from_idl_to_schema(schema, name, idl, args)
     }
 
     fn default_value(_depth: usize, _scham_addr: usize,_schema: &Vec<NP_Parsed_Schema>) -> Option<Self> {
@@ -376,6 +994,34 @@ fn schema_parsing_works_idl() -> Result<(), NP_Error> {
     Ok(())
 }
 
+#[test]
+fn old_schema_bytes_work() -> Result<(), NP_Error> {
+    // `uuid()` is a single type key byte
+    let factory = crate::NP_Factory::new_bytes(&[NP_TypeKeys::Uuid as u8])?;
+    assert_eq!("uuid()", factory.schema.to_idl()?);
+    assert_eq!(crate::NP_Factory::new("uuid()")?.export_schema_bytes(), &[NP_TypeKeys::Uuid as u8]);
+    assert_eq!(crate::NP_Factory::new("uuid({omit_nil: true})")?.export_schema_bytes().len(), 2);
+
+    // inside a struct the next field must not be read as uuid options
+    let count = crate::NP_Factory::new("u8()")?;
+    let count_bytes = count.export_schema_bytes();
+    let mut bytes: Vec<u8> = vec![NP_TypeKeys::Struct as u8, 2];
+    bytes.extend_from_slice(&[2, b'i', b'd', 0, 1, NP_TypeKeys::Uuid as u8]);
+    bytes.extend_from_slice(&[5, b'c', b'o', b'u', b'n', b't']);
+    bytes.extend_from_slice(&(count_bytes.len() as u16).to_be_bytes());
+    bytes.extend_from_slice(count_bytes);
+
+    let factory = crate::NP_Factory::new_bytes(&bytes)?;
+    assert_eq!("struct({fields: {id: uuid(), count: u8()}})", factory.schema.to_idl()?);
+
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&["id"], NP_UUID::nil())?;
+    buffer.set(&["count"], 3u8)?;
+    assert_eq!(buffer.get::<u8>(&["count"])?, Some(3));
+
+    Ok(())
+}
+
 #[test]
 fn schema_parsing_works() -> Result<(), NP_Error> {
     let schema = "{\"type\":\"uuid\"}";
@@ -406,4 +1052,385 @@ fn set_clear_value_and_compaction_works() -> Result<(), NP_Error> {
     assert_eq!(buffer.calc_bytes()?.current_buffer, 6usize);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[test]
+fn require_version_works() -> Result<(), NP_Error> {
+    let schema = "uuid({require_version: 7})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+    assert_eq!("{\"type\":\"uuid\",\"require_version\":7}", factory2.schema.to_json()?.stringify());
+
+    let mut buffer = factory.new_buffer(None);
+
+    // v4 uuids are rejected
+    let v4 = NP_UUID::generate(212);
+    assert_eq!(v4.version(), 4);
+    assert!(buffer.set(&[], &v4).is_err());
+    assert!(buffer.set_with_json(&[], "{\"value\":\"9EE6AAB0-2C94-41FE-FB88-42F73253F217\"}").is_err());
+    assert_eq!(buffer.get::<&NP_UUID>(&[])?, None);
+
+    // v7 uuids are accepted
+    let v7 = NP_UUID::from_string("017F22E2-79B0-7CC3-98C4-DC0C0C07398F");
+    buffer.set(&[], &v7)?;
+    assert_eq!(buffer.get::<&NP_UUID>(&[])?, Some(&v7));
+
+    assert!(crate::NP_Factory::new("uuid({require_version: 16})").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn redacted_string_works() -> Result<(), NP_Error> {
+    let uuid = NP_UUID::generate(212);
+    let full = uuid.to_string();
+
+    let redacted = uuid.to_redacted_string();
+    assert_eq!(redacted.len(), full.len());
+    assert_eq!(&redacted[..9], &full[..9]);
+    assert_eq!(redacted.matches('*').count(), 24);
+    assert_eq!(redacted, "9EE6AAB0-****-****-****-************");
+
+    let redacted = uuid.to_redacted_string_with(10);
+    assert_eq!(&redacted[..24], &full[..24]);
+    assert_eq!(redacted.matches('*').count(), 12);
+
+    assert_eq!(uuid.to_redacted_string_with(16), full);
+    assert_eq!(uuid.to_redacted_string_with(100), full);
+
+    Ok(())
+}
+
+#[test]
+fn from_name_works() -> Result<(), NP_Error> {
+    let alice = NP_UUID::from_name("alice");
+    assert_eq!(alice, NP_UUID::from_name("alice"));
+    assert_ne!(alice, NP_UUID::from_name("bob"));
+    assert_ne!(alice, NP_UUID::from_name("Alice"));
+    assert_eq!(alice.version(), 5);
+    assert_eq!(alice.value[8] & 0xC0, 0x80);
+
+    assert_eq!(NP_UUID::from_name("python.org").to_string(), "886313E1-3B8A-5372-9B90-0C9AEE199E5D");
+
+    // works with version requirements
+    let factory = crate::NP_Factory::new("uuid({require_version: 5})")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&[], &alice)?;
+    assert_eq!(buffer.get::<&NP_UUID>(&[])?, Some(&alice));
+
+    Ok(())
+}
+
+#[test]
+fn base64_works() -> Result<(), NP_Error> {
+    for seed in [1u32, 50, 212, 9000].iter() {
+        let uuid = NP_UUID::generate(*seed);
+        let encoded = uuid.to_base64();
+        assert_eq!(encoded.len(), 22);
+        assert_eq!(NP_UUID::from_base64(&encoded)?, uuid);
+    }
+
+    let max = NP_UUID { value: [255; 16] };
+    assert_eq!(max.to_base64(), "_____________________w");
+    assert_eq!(NP_UUID::from_base64("_____________________w")?, max);
+    assert_eq!(NP_UUID::from_base64("AAAAAAAAAAAAAAAAAAAAAA")?, NP_UUID { value: [0; 16] });
+
+    // wrong length, bad characters and dirty padding bits are rejected
+    assert!(NP_UUID::from_base64("iGMT4TuKU3KbkAya7hmeX").is_err());
+    assert!(NP_UUID::from_base64("iGMT4TuKU3KbkAya7hmeXQ==").is_err());
+    assert!(NP_UUID::from_base64("iGMT4TuKU3KbkAya7hme+Q").is_err());
+    assert!(NP_UUID::from_base64("_____________________x").is_err());
+
+    Ok(())
+}
+
+#[cfg(feature = "getrandom")]
+#[test]
+fn new_v4_works() -> Result<(), NP_Error> {
+    let first = NP_UUID::new_v4();
+    let second = NP_UUID::new_v4();
+    assert_ne!(first, second);
+
+    for uuid in [first, second].iter() {
+        assert_eq!(uuid.version(), 4);
+        assert_eq!(uuid.value[8] & 0xC0, 0x80);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn auto_schema_works() -> Result<(), NP_Error> {
+    let schema = "uuid({auto: true})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+    assert_eq!("{\"type\":\"uuid\",\"auto\":true}", factory2.schema.to_json()?.stringify());
+
+    let schema = "{\"type\":\"uuid\",\"require_version\":4,\"auto\":true}";
+    let factory = crate::NP_Factory::new_json(schema)?;
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_json()?.stringify());
+    assert_eq!("uuid({require_version: 4, auto: true})", factory2.schema.to_idl()?);
+
+    // generated uuids are v4
+    assert!(crate::NP_Factory::new("uuid({require_version: 7, auto: true})").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn fill_auto_uuids_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("struct({fields: {id: uuid({auto: true}), pair: tuple({values: [uuid(), uuid({auto: true})]}), others: list({of: uuid({auto: true})})}})")?;
+    let mut buffer = factory.new_buffer(None);
+
+    let mut seed = 0u32;
+    assert_eq!(buffer.fill_auto_uuids(|| { seed += 1; NP_UUID::generate(seed) })?, 2);
+    assert_eq!(buffer.get::<NP_UUID>(&["id"])?, Some(NP_UUID::generate(1)));
+    assert_eq!(buffer.get::<NP_UUID>(&["pair", "0"])?, None);
+    assert_eq!(buffer.get::<NP_UUID>(&["pair", "1"])?, Some(NP_UUID::generate(2)));
+    assert_eq!(buffer.get_length(&["others"])?, None);
+
+    // set values are kept
+    buffer.del(&["id"])?;
+    assert_eq!(buffer.fill_auto_uuids(|| NP_UUID::generate(99))?, 1);
+    assert_eq!(buffer.get::<NP_UUID>(&["id"])?, Some(NP_UUID::generate(99)));
+    assert_eq!(buffer.get::<NP_UUID>(&["pair", "1"])?, Some(NP_UUID::generate(2)));
+
+    Ok(())
+}
+
+#[cfg(feature = "getrandom")]
+#[test]
+fn new_buffer_with_defaults_works() -> Result<(), NP_Error> {
+    let factory = crate::NP_Factory::new("struct({fields: {id: uuid({auto: true}), name: string()}})")?;
+
+    let first = factory.new_buffer_with_defaults(None)?;
+    let second = factory.new_buffer_with_defaults(None)?;
+
+    let first_id = first.get::<NP_UUID>(&["id"])?.unwrap();
+    let second_id = second.get::<NP_UUID>(&["id"])?.unwrap();
+
+    assert_ne!(first_id, second_id);
+    assert_eq!(first_id.version(), 4);
+    assert_eq!(first.get::<&str>(&["name"])?, None);
+
+    Ok(())
+}
+
+#[cfg(feature = "uuid-crate")]
+#[test]
+fn uuid_crate_round_trip_works() -> Result<(), NP_Error> {
+    let np_id = NP_UUID::generate(212);
+
+    let id: uuid::Uuid = np_id.clone().into();
+    assert_eq!(id.as_bytes(), &np_id.value);
+    assert_eq!(id.get_version_num(), 4);
+    assert_eq!(id.get_version_num() as u8, np_id.version());
+    assert_eq!(id.to_hyphenated().to_string().to_uppercase(), np_id.to_string());
+
+    let back: NP_UUID = id.into();
+    assert_eq!(back, np_id);
+
+    // v5 and nil uuids keep their version and variant bits
+    let v5 = NP_UUID::from_name("example.com");
+    let id: uuid::Uuid = v5.clone().into();
+    assert_eq!(id.get_version_num(), 5);
+    assert_eq!(id.get_variant(), Some(uuid::Variant::RFC4122));
+    assert_eq!(NP_UUID::from(id), v5);
+
+    let nil: NP_UUID = uuid::Uuid::nil().into();
+    assert_eq!(nil.value, [0u8; 16]);
+
+    Ok(())
+}
+
+#[test]
+fn prefix_matching_works() -> Result<(), NP_Error> {
+    // v7 layout, 48 bit big endian unix millis then version and random bits
+    let v7_at = |millis: u64, rand: u8| {
+        let mut value = [rand; 16];
+        value[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+        value[6] = 0x70 | (rand & 0x0F);
+        value[8] = 0x80 | (rand & 0x3F);
+        NP_UUID { value }
+    };
+
+    let millis_prefix = |millis: u64| {
+        let mut prefix = [0u8; 6];
+        prefix.copy_from_slice(&millis.to_be_bytes()[2..]);
+        prefix
+    };
+
+    let start = 1_690_000_000_000u64;
+    let end = start + 60_000;
+
+    let before = v7_at(start - 1, 0xFF);
+    let first = v7_at(start, 0x00);
+    let middle = v7_at(start + 30_000, 0x5A);
+    let last = v7_at(end, 0xFF);
+    let after = v7_at(end + 1, 0x00);
+
+    let lo = millis_prefix(start);
+    let hi = millis_prefix(end);
+
+    assert!(!before.prefix_cmp(&lo, &hi));
+    assert!(first.prefix_cmp(&lo, &hi));
+    assert!(middle.prefix_cmp(&lo, &hi));
+    assert!(last.prefix_cmp(&lo, &hi));
+    assert!(!after.prefix_cmp(&lo, &hi));
+
+    assert!(middle.starts_with(&millis_prefix(start + 30_000)));
+    assert!(!middle.starts_with(&lo));
+    assert!(middle.starts_with(&[]));
+    assert!(middle.prefix_cmp(&[], &[]));
+
+    // the whole value works as both bounds, longer bounds never fit
+    assert!(middle.starts_with(&middle.value));
+    assert!(middle.prefix_cmp(&middle.value, &middle.value));
+    assert!(!middle.starts_with(&[0u8; 17]));
+    assert!(!middle.prefix_cmp(&[0xFF; 17], &[0xFF; 17]));
+
+    Ok(())
+}
+
+#[test]
+fn from_bytes_with_version_works() -> Result<(), NP_Error> {
+    let bytes: [u8; 16] = [0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0, 0x0F, 0xED, 0xCB, 0xA9, 0x87, 0x65, 0x43, 0x21];
+
+    for version in 0..=15u8 {
+        let uuid = NP_UUID::from_bytes_with_version(bytes, version)?;
+        assert_eq!(uuid.version(), version);
+        assert_eq!(uuid.value[6] & 0x0F, 0x0E);
+        assert_eq!(uuid.value[8], 0x8F);
+
+        // only the version and variant bits change
+        for idx in (0..16).filter(|idx| *idx != 6 && *idx != 8) {
+            assert_eq!(uuid.value[idx], bytes[idx]);
+        }
+    }
+
+    let err = NP_UUID::from_bytes_with_version(bytes, 16).unwrap_err();
+    assert!(matches!(err, NP_Error::Custom { message } if message == "UUID version must be between 0 and 15!"));
+    assert!(NP_UUID::from_bytes_with_version(bytes, 255).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn from_entropy_works() -> Result<(), NP_Error> {
+    let entropy: Vec<u8> = (0..20u8).map(|x| x.wrapping_mul(37).wrapping_add(200)).collect();
+
+    let uuid = NP_UUID::from_entropy(&entropy)?;
+    assert_eq!(uuid.version(), 4);
+    assert_eq!(uuid.value[6] >> 4, 4);
+    assert_eq!(uuid.value[8] >> 6, 0b10);
+
+    // every bit outside the version and variant comes from the entropy
+    for x in 0..16 {
+        match x {
+            6 => assert_eq!(uuid.value[x] & 0x0F, entropy[x] & 0x0F),
+            8 => assert_eq!(uuid.value[x] & 0x3F, entropy[x] & 0x3F),
+            _ => assert_eq!(uuid.value[x], entropy[x])
+        }
+    }
+
+    // only the first 16 bytes are used
+    assert_eq!(NP_UUID::from_entropy(&entropy[..16])?, uuid);
+    assert_eq!(NP_UUID::from_entropy(&[0xFF; 16])?.to_string(), "FFFFFFFF-FFFF-4FFF-BFFF-FFFFFFFFFFFF");
+
+    let err = NP_UUID::from_entropy(&entropy[..15]).unwrap_err();
+    assert!(matches!(err, NP_Error::Custom { message } if message == "UUID entropy must be at least 16 bytes!"));
+    assert!(NP_UUID::from_entropy(&[]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn generate_v7_sorts_by_time() -> Result<(), NP_Error> {
+    let seed = core::cell::Cell::new(7u32);
+    let random = || {
+        seed.set(seed.get().wrapping_mul(1103515245).wrapping_add(12345));
+        (seed.get() >> 16) as u8
+    };
+
+    let start = 1704164645678u64;
+    let mut uuids: Vec<(u64, NP_UUID)> = [5u64, 0, 3, 1000, 2, 1, 86400000, 4].iter().map(|x| (start + x, NP_UUID::generate_v7(start + x, &random))).collect();
+
+    for (millis, uuid) in uuids.iter() {
+        assert_eq!(uuid.version(), 7);
+        assert_eq!(uuid.value[8] >> 6, 0b10);
+        let mut time = [0u8; 8];
+        time[2..].copy_from_slice(&uuid.value[..6]);
+        assert_eq!(u64::from_be_bytes(time), *millis);
+        assert_eq!(NP_UUID::from_string(uuid.to_string()), *uuid);
+    }
+
+    // sorting the raw bytes gives timestamp order, whatever the random bits are
+    uuids.sort_by(|a, b| a.1.value.cmp(&b.1.value));
+    assert!(uuids.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+    // only the low 48 bits of the timestamp are kept
+    assert_eq!(&NP_UUID::generate_v7(u64::MAX, || 0).value[..6], &[0xFF; 6]);
+
+    Ok(())
+}
+
+#[test]
+fn try_from_string_works() -> Result<(), NP_Error> {
+    let uuid = NP_UUID::generate(99);
+    assert_eq!(NP_UUID::try_from_string(uuid.to_string())?, uuid);
+    assert_eq!(NP_UUID::try_from_string("886313e13b8a53729b900c9aee199e5d")?, NP_UUID::from_string("886313E1-3B8A-5372-9B90-0C9AEE199E5D"));
+
+    for bad in ["", "886313E1", "886313E1-3B8A-5372-9B90-0C9AEE199E5D0", "886313E1-3B8A-5372-9B90-0C9AEE199EZZ", "886313E1 3B8A 5372 9B90 0C9AEE199E5D", "+86313E1-3B8A-5372-9B90-0C9AEE199E5D"].iter() {
+        let err = NP_UUID::try_from_string(bad).unwrap_err();
+        assert!(matches!(err, NP_Error::Custom { message } if message == format!("Invalid UUID string: {}", bad)));
+    }
+
+    // JSON uses the checked parser
+    let factory = crate::NP_Factory::new("uuid()")?;
+    let mut buffer = factory.new_buffer(None);
+    assert!(buffer.set_with_json(&[], "{\"value\":\"9EE6AAB0-2C94-41FE-FB88-42F73253F2ZZ\"}").is_err());
+    assert_eq!(buffer.get::<&NP_UUID>(&[])?, None);
+    buffer.set_with_json(&[], "{\"value\":\"9ee6aab0-2c94-41fe-fb88-42f73253f217\"}")?;
+    assert_eq!(buffer.get::<&NP_UUID>(&[])?.unwrap().to_string(), "9EE6AAB0-2C94-41FE-FB88-42F73253F217");
+
+    Ok(())
+}
+
+#[test]
+fn nil_works() -> Result<(), NP_Error> {
+    assert_eq!(NP_UUID::nil(), NP_UUID::default());
+    assert!(NP_UUID::nil().is_nil());
+    assert!(!NP_UUID::from_bytes_with_version([0; 16], 4)?.is_nil());
+
+    // nil values are written as JSON strings unless the schema says otherwise
+    let factory = crate::NP_Factory::new("uuid()")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&[], NP_UUID::nil())?;
+    assert_eq!(buffer.json_encode(&[])?.stringify(), "{\"value\":\"00000000-0000-0000-0000-000000000000\"}");
+
+    let schema = "uuid({auto: true, omit_nil: true})";
+    let factory = crate::NP_Factory::new(schema)?;
+    assert_eq!(schema, factory.schema.to_idl()?);
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!(schema, factory2.schema.to_idl()?);
+    assert_eq!("{\"type\":\"uuid\",\"auto\":true,\"omit_nil\":true}", factory2.schema.to_json()?.stringify());
+
+    let factory = crate::NP_Factory::new_json("{\"type\":\"uuid\",\"require_version\":4,\"omit_nil\":true}")?;
+    let factory2 = crate::NP_Factory::new_bytes(factory.export_schema_bytes())?;
+    assert_eq!("uuid({require_version: 4, omit_nil: true})", factory2.schema.to_idl()?);
+
+    let factory = crate::NP_Factory::new("uuid({omit_nil: true})")?;
+    let mut buffer = factory.new_buffer(None);
+    buffer.set(&[], NP_UUID::nil())?;
+    assert_eq!(buffer.get::<NP_UUID>(&[])?, Some(NP_UUID::nil()));
+    assert_eq!(buffer.json_encode(&[])?.stringify(), "{\"value\":null}");
+    buffer.set(&[], NP_UUID::generate(50))?;
+    assert_eq!(buffer.json_encode(&[])?.stringify(), "{\"value\":\"48E6AAB0-7DF5-409F-4D57-4D969FA065EE\"}");
+
+    Ok(())
+}
@@ -181,6 +181,8 @@
 //! | [`enum`](#enum)                        | [`NP_Enum`](../pointer/option/struct.NP_Enum.html)                       | -                |✓                 | 1 byte          | Up to 255 string based options in schema.                                |
 //! | [`bool`](#bool)                        | [`bool`](https://doc.rust-lang.org/std/primitive.bool.html)              | -                |✓                 | 1 byte          |                                                                          |
 //! | [`decimal`](#decimal)                  | [`NP_Dec`](../pointer/dec/struct.NP_Dec.html)                            | -                |✓                 | 8 bytes         | Fixed point decimal number based on i64.                                 |
+//! | [`decimal128`](#decimal128)            | [`NP_Dec128`](../pointer/dec128/struct.NP_Dec128.html)                   | -                |✓                 | 16 bytes        | Fixed point decimal number based on i128.                                |
+//! | [`sorted_uints`](#sorted_uints)        | [`NP_SortedUints`](../pointer/sorted_uints/struct.NP_SortedUints.html)   | -                |𐄂                 | 20 bytes + data | Delta encoded sequence of increasing unsigned integers.                  |
 //! | [`geo4`](#geo4-geo8-geo16)             | [`NP_Geo`](../pointer/geo/struct.NP_Geo.html)                            | -                |✓                 | 4 bytes         | 1.1km resolution (city) geographic coordinate                            |
 //! | [`geo8`](#geo4-geo8-geo16)             | [`NP_Geo`](../pointer/geo/struct.NP_Geo.html)                            | -                |✓                 | 8 bytes         | 11mm resolution (marble) geographic coordinate                           |
 //! | [`geo16`](#geo4-geo8-geo16)            | [`NP_Geo`](../pointer/geo/struct.NP_Geo.html)                            | -                |✓                 | 16 bytes        | 110 microns resolution (grain of sand) geographic coordinate             |
@@ -219,6 +221,11 @@
 //! 
 //! Changing the `type` property of any value in the schame is unsafe.  It's only sometimes safe to modify properties besides `type`.
 //! 
+//! **Required**<br/>
+//! Any type can be marked with `required: true` in ES6 or `"required": true` in JSON.  Buffers aren't stopped from leaving these values unset, instead you can check for them before sending the buffer somewhere.  A value with a default in the schema is never missing.
+//! 
+//! - [How do you check a buffer for required values?](../buffer/struct.NP_Buffer.html#method.assert_complete)
+//! 
 //! # Schema Types
 //! 
 //! Every schema type maps exactly to a native data type in your code.
@@ -642,9 +649,67 @@
 //! decimal({exp: 3, default: 20.293})
 //! ```
 //! 
+//! Values with more decimal places than `exp` are truncated when they're written.  Set the optional `rounding` property to `"half_up"` or `"half_even"` (bankers rounding) to round them instead, `"truncate"` is the default.
+//! 
+//! ```text
+//! // JSON
+//! {
+//!     "type": "decimal",
+//!     "exp": 2,
+//!     "rounding": "half_even"
+//! }
+//! // ES6
+//! decimal({exp: 2, rounding: "half_even"})
+//! ```
+//! 
 //! More Details:
 //! - [Using NP_Dec data type](../pointer/dec/index.html)
 //! 
+//! ## decimal128
+//! Same as `decimal` but values are based on an i128 and take 16 bytes.  Use it when an i64 doesn't have enough digits for your `exp`.
+//! 
+//! - **Bytewise Sorting**: Supported
+//! - **Compaction**: Updates are done in place, never use additional space.
+//! - **Schema Mutations**: None
+//! 
+//! Defaults are given as strings in JSON so they aren't limited to the precision of a float.
+//! 
+//! ```text
+//! // JSON
+//! {
+//!     "type": "decimal128",
+//!     "exp": 20,
+//!     "default": "20.00000000000000000001"
+//! }
+//! // ES6
+//! dec128({exp: 20, default: 20.00000000000000000001})
+//! ```
+//! 
+//! More Details:
+//! - [Using NP_Dec128 data type](../pointer/dec128/index.html)
+//! 
+//! ## sorted_uints
+//! Stores a sequence of unsigned integers that never go down, like timestamps or ids.  The first value takes the full size of the `base` type, every value after it is a varint of the difference from the previous value.
+//! 
+//! - **Bytewise Sorting**: Unsupported
+//! - **Compaction**: Pushing values writes in place until the spare space runs out, then the sequence is copied with double the space.  Compaction removes the old copies and the spare space.
+//! - **Schema Mutations**: None
+//! 
+//! The `base` property is required and can be `u8`, `u16`, `u32` or `u64`.  Values can only be read in order, there is no fast random access.
+//! 
+//! ```text
+//! // JSON
+//! {
+//!     "type": "sorted_uints",
+//!     "base": "u64"
+//! }
+//! // ES6
+//! sorted_uints({base: "u64"})
+//! ```
+//! 
+//! More Details:
+//! - [Using NP_SortedUints data type](../pointer/sorted_uints/index.html)
+//! 
 //! ## geo4, ge8, geo16
 //! Allows you to store geographic coordinates with varying levels of accuracy and space usage.  
 //! 
@@ -717,6 +782,30 @@
 //! // no default supported
 //! ```
 //! 
+//! Setting `auto` to `true` marks the field to get a freshly generated v4 UUID when a buffer is created with `new_buffer_with_defaults` or filled with `fill_auto_uuids`.
+//! 
+//! ```text
+//! // JSON
+//! {
+//!     "type": "uuid",
+//!     "auto": true
+//! }
+//! // ES6
+//! uuid({auto: true})
+//! ```
+//! 
+//! Setting `omit_nil` to `true` writes nil (all zero) values as `null` when the buffer is exported to JSON.
+//! 
+//! ```text
+//! // JSON
+//! {
+//!     "type": "uuid",
+//!     "omit_nil": true
+//! }
+//! // ES6
+//! uuid({omit_nil: true})
+//! ```
+//! 
 //! More Details:
 //! - [Using NP_UUID data type](../pointer/uuid/index.html)
 //! 
@@ -819,7 +908,10 @@ use crate::{buffer::DEFAULT_ROOT_PTR_ADDR, json_flex::NP_JSON, memory::NP_Memory
 use crate::pointer::any::NP_Any;
 use crate::pointer::date::NP_Date;
 use crate::pointer::geo::NP_Geo;
-use crate::pointer::dec::NP_Dec;
+use crate::pointer::dec::{NP_Dec, NP_RoundMode};
+use crate::pointer::numbers::{NP_NumberBound, NP_TimestampFormat};
+use crate::pointer::dec128::NP_Dec128;
+use crate::pointer::sorted_uints::NP_SortedUints;
 use crate::collection::tuple::NP_Tuple;
 use crate::pointer::bytes::NP_Bytes;
 use crate::collection::{list::NP_List, struc::NP_Struct, map::NP_Map};
@@ -827,9 +919,19 @@ use crate::pointer::{option::NP_Enum, NP_Value};
 use crate::error::NP_Error;
 use alloc::vec::Vec;
 use alloc::boxed::Box;
+use alloc::borrow::ToOwned;
+
+/// Root level string properties that are preserved through schema parsing and export
+const ROOT_META_KEYS: [&str; 3] = ["$id", "title", "description"];
+
+/// Prefixed to root schema bytes that carry metadata, followed by the metadata length and block
+const ROOT_META_TAG: u8 = 253;
+
+/// Prefixed to the schema bytes of any type that is marked `required`
+const REQUIRED_TAG: u8 = 254;
 
 /// Simple enum to store the schema types
-#[derive(Debug, Clone, Eq, PartialEq, Copy)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Copy)]
 #[repr(u8)]
 #[allow(missing_docs)]
 pub enum NP_TypeKeys {
@@ -859,12 +961,14 @@ pub enum NP_TypeKeys {
     List       = 23,
     Tuple      = 24,
     Portal     = 25,
-    // Union      = 26
+    Decimal128 = 26,
+    SortedUints = 27,
+    // Union      = 28
 }
 
 impl From<u8> for NP_TypeKeys {
     fn from(value: u8) -> Self {
-        if value > 26 { return NP_TypeKeys::None; }
+        if value > 27 { return NP_TypeKeys::None; }
         unsafe { core::mem::transmute(value) }
     }
 }
@@ -888,6 +992,8 @@ impl NP_TypeKeys {
             NP_TypeKeys::Float      => {       f32::type_idx() }
             NP_TypeKeys::Double     => {       f64::type_idx() }
             NP_TypeKeys::Decimal    => {    NP_Dec::type_idx() }
+            NP_TypeKeys::Decimal128 => { NP_Dec128::type_idx() }
+            NP_TypeKeys::SortedUints => { NP_SortedUints::type_idx() }
             NP_TypeKeys::Boolean    => {      bool::type_idx() }
             NP_TypeKeys::Geo        => {    NP_Geo::type_idx() }
             NP_TypeKeys::Uuid       => {   NP_UUID::type_idx() }
@@ -901,6 +1007,57 @@ impl NP_TypeKeys {
             _ => ("", NP_TypeKeys::None)
         }
     }
+
+    /// Check if every value of this numeric type can be read as `other` without losing anything.
+    /// 
+    /// Every type widens to itself.  Beyond that only numbers widen:
+    /// - Unsigned integers widen to larger unsigned integers (u8 → u16 → u32 → u64)
+    /// - Signed integers widen to larger signed integers (i8 → i16 → i32 → i64)
+    /// - Unsigned integers widen to signed integers that are at least twice as wide (u32 → i64)
+    /// - Integers widen to floats when they fit in the mantissa, 16 bits or less for `float` and 32 bits or less for `double`
+    /// - `float` widens to `double`
+    /// 
+    /// ```
+    /// use no_proto::schema::NP_TypeKeys;
+    /// 
+    /// assert!(NP_TypeKeys::Uint8.can_widen_to(NP_TypeKeys::Uint32));
+    /// assert!(NP_TypeKeys::Uint32.can_widen_to(NP_TypeKeys::Int64));
+    /// assert!(NP_TypeKeys::Int32.can_widen_to(NP_TypeKeys::Double));
+    /// 
+    /// assert!(!NP_TypeKeys::Int64.can_widen_to(NP_TypeKeys::Double)); // only 53 bits of mantissa
+    /// assert!(!NP_TypeKeys::Int8.can_widen_to(NP_TypeKeys::Uint64)); // negatives don't fit
+    /// ```
+    pub fn can_widen_to(self, other: NP_TypeKeys) -> bool {
+        if self == other { return true }
+
+        // (is integer, is signed, bits of precision)
+        let describe = |key: NP_TypeKeys| -> Option<(bool, bool, u8)> {
+            match key {
+                NP_TypeKeys::Int8   => Some((true, true, 8)),
+                NP_TypeKeys::Int16  => Some((true, true, 16)),
+                NP_TypeKeys::Int32  => Some((true, true, 32)),
+                NP_TypeKeys::Int64  => Some((true, true, 64)),
+                NP_TypeKeys::Uint8  => Some((true, false, 8)),
+                NP_TypeKeys::Uint16 => Some((true, false, 16)),
+                NP_TypeKeys::Uint32 => Some((true, false, 32)),
+                NP_TypeKeys::Uint64 => Some((true, false, 64)),
+                // mantissa bits, including the implicit bit
+                NP_TypeKeys::Float  => Some((false, true, 24)),
+                NP_TypeKeys::Double => Some((false, true, 53)),
+                _ => None
+            }
+        };
+
+        match (describe(self), describe(other)) {
+            (Some((true, from_signed, from_bits)), Some((true, to_signed, to_bits))) => {
+                // negative values never fit in an unsigned type
+                if from_signed && !to_signed { false } else { from_bits < to_bits }
+            },
+            (Some((true, _, from_bits)), Some((false, _, mantissa))) => from_bits <= mantissa,
+            (Some((false, _, from_bits)), Some((false, _, to_bits))) => from_bits < to_bits,
+            _ => false
+        }
+    }
 }
 
 /// Schema Address (usize alias)
@@ -1002,70 +1159,105 @@ impl Default for NP_Parsed_Schema {
 #[doc(hidden)]
 #[derive(Debug, Clone)]
 pub struct NP_u8_Data {
-    pub default: Option<u8>
+    pub default: Option<u8>,
+    pub default_bound: Option<crate::pointer::numbers::NP_NumberBound>,
+    pub min: Option<u8>,
+    pub max: Option<u8>
 }
 
 #[allow(missing_docs)]
 #[doc(hidden)]
 #[derive(Debug, Clone)]
 pub struct NP_u16_Data {
-    pub default: Option<u16>
+    pub default: Option<u16>,
+    pub default_bound: Option<crate::pointer::numbers::NP_NumberBound>,
+    pub min: Option<u16>,
+    pub max: Option<u16>
 }
 
 #[allow(missing_docs)]
 #[doc(hidden)]
 #[derive(Debug, Clone)]
 pub struct NP_u32_Data {
-    pub default: Option<u32>
+    pub default: Option<u32>,
+    pub default_bound: Option<crate::pointer::numbers::NP_NumberBound>,
+    pub min: Option<u32>,
+    pub max: Option<u32>
 }
 
 #[allow(missing_docs)]
 #[doc(hidden)]
 #[derive(Debug, Clone)]
 pub struct NP_u64_Data {
-    pub default: Option<u64>
+    pub default: Option<u64>,
+    pub default_bound: Option<crate::pointer::numbers::NP_NumberBound>,
+    pub min: Option<u64>,
+    pub max: Option<u64>
 }
 
 #[allow(missing_docs)]
 #[doc(hidden)]
 #[derive(Debug, Clone)]
 pub struct NP_i8_Data {
-    pub default: Option<i8>
+    pub default: Option<i8>,
+    pub default_bound: Option<crate::pointer::numbers::NP_NumberBound>,
+    pub min: Option<i8>,
+    pub max: Option<i8>
 }
 
 #[allow(missing_docs)]
 #[doc(hidden)]
 #[derive(Debug, Clone)]
 pub struct NP_i16_Data {
-    pub default: Option<i16>
+    pub default: Option<i16>,
+    pub default_bound: Option<crate::pointer::numbers::NP_NumberBound>,
+    pub min: Option<i16>,
+    pub max: Option<i16>
 }
 
 #[allow(missing_docs)]
 #[doc(hidden)]
 #[derive(Debug, Clone)]
 pub struct NP_i32_Data {
-    pub default: Option<i32>
+    pub default: Option<i32>,
+    pub default_bound: Option<crate::pointer::numbers::NP_NumberBound>,
+    pub min: Option<i32>,
+    pub max: Option<i32>
 }
 
 #[allow(missing_docs)]
 #[doc(hidden)]
 #[derive(Debug, Clone)]
 pub struct NP_i64_Data {
-    pub default: Option<i64>
+    pub default: Option<i64>,
+    pub default_bound: Option<crate::pointer::numbers::NP_NumberBound>,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    pub format: Option<crate::pointer::numbers::NP_TimestampFormat>
 }
 
 #[allow(missing_docs)]
 #[doc(hidden)]
 #[derive(Debug, Clone)]
 pub struct NP_f32_Data {
-    pub default: Option<f32>
+    pub default: Option<f32>,
+    pub default_bound: Option<crate::pointer::numbers::NP_NumberBound>,
+    pub min: Option<f32>,
+    pub max: Option<f32>,
+    pub whole_as_int: bool,
+    pub display_places: Option<u8>
 }
 
 #[allow(missing_docs)]
 #[doc(hidden)]
 #[derive(Debug, Clone)]
 pub struct NP_f64_Data {
-    pub default: Option<f64>
+    pub default: Option<f64>,
+    pub default_bound: Option<crate::pointer::numbers::NP_NumberBound>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub whole_as_int: bool,
+    pub display_places: Option<u8>
 }
 
 #[allow(missing_docs)]
@@ -1101,14 +1293,39 @@ pub struct NP_Geo_Data {
     pub size: u8,
 }
 
+#[allow(missing_docs)]
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+pub struct NP_Uuid_Data {
+    pub require_version: Option<u8>,
+    pub auto: bool,
+    pub omit_nil: bool
+}
+
 #[allow(missing_docs)]
 #[doc(hidden)]
 #[derive(Debug, Clone)]
 pub struct NP_Dec_Data {
     pub default: Option<NP_Dec>,
+    pub exp: u8,
+    pub rounding: NP_RoundMode
+}
+
+#[allow(missing_docs)]
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+pub struct NP_Dec128_Data {
+    pub default: Option<NP_Dec128>,
     pub exp: u8
 }
 
+#[allow(missing_docs)]
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+pub struct NP_SortedUints_Data {
+    pub base: NP_TypeKeys
+}
+
 #[allow(missing_docs)]
 #[doc(hidden)]
 #[derive(Debug, Clone)]
@@ -1154,7 +1371,11 @@ impl Drop for NP_Parsed_Schema {
         match self.i {
             NP_TypeKeys::None => {}
             NP_TypeKeys::Any => {}
-            NP_TypeKeys::Uuid => {}
+            NP_TypeKeys::Uuid => {
+                if NULL() != *self.data && Arc::strong_count(&self.data) == 1 {
+                    drop(unsafe { Box::from_raw(*self.data as *mut NP_Uuid_Data) });
+                }
+            }
             NP_TypeKeys::Ulid => {}
             NP_TypeKeys::UTF8String => {
                 if NULL() != *self.data && Arc::strong_count(&self.data) == 1 {
@@ -1221,6 +1442,16 @@ impl Drop for NP_Parsed_Schema {
                     drop(unsafe { Box::from_raw(*self.data as *mut NP_Dec_Data) });
                 }
             }
+            NP_TypeKeys::Decimal128 => {
+                if NULL() != *self.data && Arc::strong_count(&self.data) == 1 {
+                    drop(unsafe { Box::from_raw(*self.data as *mut NP_Dec128_Data) });
+                }
+            }
+            NP_TypeKeys::SortedUints => {
+                if NULL() != *self.data && Arc::strong_count(&self.data) == 1 {
+                    drop(unsafe { Box::from_raw(*self.data as *mut NP_SortedUints_Data) });
+                }
+            }
             NP_TypeKeys::Boolean => {
                 if NULL() != *self.data && Arc::strong_count(&self.data) == 1 {
                     drop(unsafe { Box::from_raw(*self.data as *mut NP_Bool_Data) });
@@ -1291,6 +1522,41 @@ impl NP_Schema {
         NP_Schema::_type_to_idl(&self.parsed, 0)
     }
 
+    /// Is this type marked `required` in the schema?
+    pub fn is_required(schema: &NP_Parsed_Schema) -> bool {
+        match schema.all_props.get("required") {
+            Some(NP_Schema_Property::TRUE) => true,
+            _ => false
+        }
+    }
+
+    /// Does this type have a default value in the schema?
+    pub fn has_default(parsed_schema: &Vec<NP_Parsed_Schema>, address: usize) -> bool {
+        match parsed_schema[address].i {
+            NP_TypeKeys::UTF8String => {    String::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Bytes      => {  NP_Bytes::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Int8       => {        i8::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Int16      => {       i16::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Int32      => {       i32::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Int64      => {       i64::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Uint8      => {        u8::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Uint16     => {       u16::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Uint32     => {       u32::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Uint64     => {       u64::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Float      => {       f32::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Double     => {       f64::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Decimal    => {    NP_Dec::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Decimal128 => { NP_Dec128::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Boolean    => {      bool::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Geo        => {    NP_Geo::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Uuid       => {   NP_UUID::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Ulid       => {   NP_ULID::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Date       => {   NP_Date::default_value(0, address, parsed_schema).is_some() }
+            NP_TypeKeys::Enum       => {   NP_Enum::default_value(0, address, parsed_schema).is_some() }
+            _ => false
+        }
+    }
+
     /// Recursive function parse schema into IDL
     #[doc(hidden)]
     pub fn _type_to_idl(parsed_schema: &Vec<NP_Parsed_Schema>, address: usize) -> Result<String, NP_Error> {
@@ -1309,6 +1575,8 @@ impl NP_Schema {
             NP_TypeKeys::Float         => {       f32::schema_to_idl(parsed_schema, address) }
             NP_TypeKeys::Double        => {       f64::schema_to_idl(parsed_schema, address) }
             NP_TypeKeys::Decimal       => {    NP_Dec::schema_to_idl(parsed_schema, address) }
+            NP_TypeKeys::Decimal128    => { NP_Dec128::schema_to_idl(parsed_schema, address) }
+            NP_TypeKeys::SortedUints   => { NP_SortedUints::schema_to_idl(parsed_schema, address) }
             NP_TypeKeys::Boolean       => {      bool::schema_to_idl(parsed_schema, address) } 
             NP_TypeKeys::Geo           => {    NP_Geo::schema_to_idl(parsed_schema, address) }
             NP_TypeKeys::Uuid          => {   NP_UUID::schema_to_idl(parsed_schema, address) }
@@ -1327,12 +1595,35 @@ impl NP_Schema {
 
     /// Get a JSON represenatation of this schema
     pub fn to_json(&self) -> Result<NP_JSON, NP_Error> {
-        NP_Schema::_type_to_json(&self.parsed, 0)
+        let mut json = NP_Schema::_type_to_json(&self.parsed, 0)?;
+
+        if let (Some(root), NP_JSON::Dictionary(map)) = (self.parsed.get(0), &mut json) {
+            for key in ROOT_META_KEYS.iter() {
+                if let Some(NP_Schema_Property::STRING { source }) = root.all_props.get(key) {
+                    map.insert((*key).to_owned(), NP_JSON::String(source.clone()));
+                }
+            }
+        }
+
+        Ok(json)
     }
 
     /// Recursive function parse schema into JSON
     #[doc(hidden)]
     pub fn _type_to_json(parsed_schema: &Vec<NP_Parsed_Schema>, address: usize) -> Result<NP_JSON, NP_Error> {
+        let mut json = NP_Schema::_kind_to_json(parsed_schema, address)?;
+
+        if NP_Schema::is_required(&parsed_schema[address]) {
+            if let NP_JSON::Dictionary(map) = &mut json {
+                map.insert("required".to_owned(), NP_JSON::True);
+            }
+        }
+
+        Ok(json)
+    }
+
+    /// Get the JSON for a single type, without shared properties
+    fn _kind_to_json(parsed_schema: &Vec<NP_Parsed_Schema>, address: usize) -> Result<NP_JSON, NP_Error> {
         match parsed_schema[address].i {
             NP_TypeKeys::Any           => {    NP_Any::schema_to_json(parsed_schema, address) }
             NP_TypeKeys::UTF8String    => {    String::schema_to_json(parsed_schema, address) }
@@ -1348,6 +1639,8 @@ impl NP_Schema {
             NP_TypeKeys::Float         => {       f32::schema_to_json(parsed_schema, address) }
             NP_TypeKeys::Double        => {       f64::schema_to_json(parsed_schema, address) }
             NP_TypeKeys::Decimal       => {    NP_Dec::schema_to_json(parsed_schema, address) }
+            NP_TypeKeys::Decimal128    => { NP_Dec128::schema_to_json(parsed_schema, address) }
+            NP_TypeKeys::SortedUints   => { NP_SortedUints::schema_to_json(parsed_schema, address) }
             NP_TypeKeys::Boolean       => {      bool::schema_to_json(parsed_schema, address) } 
             NP_TypeKeys::Geo           => {    NP_Geo::schema_to_json(parsed_schema, address) }
             NP_TypeKeys::Uuid          => {   NP_UUID::schema_to_json(parsed_schema, address) }
@@ -1419,7 +1712,39 @@ impl NP_Schema {
     // }
 
     /// Generate a schema from a parsed IDL
+    /// 
+    /// Any type can be marked `required: true`, see [`NP_Buffer::assert_complete`](../buffer/struct.NP_Buffer.html#method.assert_complete).
+    /// 
     pub fn from_idl(parsed: Vec<NP_Parsed_Schema>, idl: &JS_Schema, ast: &JS_AST) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
+        let schema_addr = parsed.len();
+
+        let (sortable, mut schema_bytes, mut parsed) = NP_Schema::_type_from_idl(parsed, idl, ast)?;
+
+        let mut required = false;
+
+        if let JS_AST::method { args, .. } = ast {
+            if let Some(JS_AST::object { properties }) = args.get(0) {
+                for (key, value) in properties {
+                    if idl.get_str(key).trim() == "required" {
+                        if let JS_AST::bool { state } = value {
+                            required = *state;
+                        }
+                    }
+                }
+            }
+        }
+
+        if required {
+            schema_bytes.insert(0, REQUIRED_TAG);
+            parsed[schema_addr].all_props.insert("required", NP_Schema_Property::TRUE)?;
+        }
+
+        Ok((sortable, schema_bytes, parsed))
+    }
+
+    /// Parse a single type from IDL
+    #[doc(hidden)]
+    pub fn _type_from_idl(parsed: Vec<NP_Parsed_Schema>, idl: &JS_Schema, ast: &JS_AST) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
         
         match ast {
             JS_AST::method { name, args } => {
@@ -1453,6 +1778,9 @@ impl NP_Schema {
                     "double"   => {       f64::from_idl_to_schema(parsed, type_name, idl, args) },
                     "decimal"  => {    NP_Dec::from_idl_to_schema(parsed, type_name, idl, args) },
                     "dec"      => {    NP_Dec::from_idl_to_schema(parsed, type_name, idl, args) },
+                    "decimal128" => { NP_Dec128::from_idl_to_schema(parsed, type_name, idl, args) },
+                    "dec128"   => { NP_Dec128::from_idl_to_schema(parsed, type_name, idl, args) },
+                    "sorted_uints" => { NP_SortedUints::from_idl_to_schema(parsed, type_name, idl, args) },
                     "bool"     => {      bool::from_idl_to_schema(parsed, type_name, idl, args) },
                     "boolean"  => {      bool::from_idl_to_schema(parsed, type_name, idl, args) },
                     "geo4"     => {    NP_Geo::from_idl_to_schema(parsed, type_name, idl, args) },
@@ -1482,7 +1810,80 @@ impl NP_Schema {
     }
 
     /// Parse a schema out of schema bytes
-    pub fn from_bytes(mut cache: Vec<NP_Parsed_Schema>, address: usize, bytes: &[u8]) -> (bool, Vec<NP_Parsed_Schema>) {
+    pub fn from_bytes(cache: Vec<NP_Parsed_Schema>, address: usize, bytes: &[u8]) -> (bool, Vec<NP_Parsed_Schema>) {
+        let is_root = address == 0 && cache.len() == 0;
+
+        if is_root && bytes.get(0) == Some(&ROOT_META_TAG) {
+            let (meta, root_addr) = NP_Schema::_root_meta_from_bytes(bytes);
+
+            let (sortable, mut parsed) = NP_Schema::from_bytes(cache, root_addr, bytes);
+
+            if parsed.len() > 0 {
+                for (key, value) in meta {
+                    // keys come from ROOT_META_KEYS, the hashmap can't reject them
+                    let _ = parsed[0].all_props.insert(key, NP_Schema_Property::STRING { source: value });
+                }
+            }
+
+            return (sortable, parsed)
+        }
+
+        let schema_addr = cache.len();
+
+        let required = bytes.get(address) == Some(&REQUIRED_TAG);
+
+        let (sortable, mut parsed) = NP_Schema::_type_from_bytes(cache, if required { address + 1 } else { address }, bytes);
+
+        if required {
+            // a fresh key, the hashmap can't reject it
+            let _ = parsed[schema_addr].all_props.insert("required", NP_Schema_Property::TRUE);
+        }
+
+        (sortable, parsed)
+    }
+
+    /// Read root metadata out of the header at the start of schema bytes, returns the metadata and the address of the root type.
+    /// 
+    /// The header is `[ROOT_META_TAG][entries length u16]` followed by a list of `[key index u8][value length u16][value]` entries.  Entries that don't parse cleanly are ignored.
+    fn _root_meta_from_bytes(bytes: &[u8]) -> (Vec<(&'static str, String)>, usize) {
+        let mut result: Vec<(&'static str, String)> = Vec::new();
+
+        if bytes.len() < 3 {
+            return (result, bytes.len());
+        }
+
+        let block_len = u16::from_be_bytes([bytes[1], bytes[2]]) as usize;
+        let root_addr = usize::min(bytes.len(), 3 + block_len);
+
+        let block = &bytes[3..root_addr];
+
+        let mut idx = 0usize;
+        while idx < block.len() {
+            if idx + 3 > block.len() {
+                return (Vec::new(), root_addr);
+            }
+            let key = match ROOT_META_KEYS.get(block[idx] as usize) {
+                Some(key) => *key,
+                None => return (Vec::new(), root_addr)
+            };
+            let value_len = u16::from_be_bytes([block[idx + 1], block[idx + 2]]) as usize;
+            idx += 3;
+            if idx + value_len > block.len() {
+                return (Vec::new(), root_addr);
+            }
+            match core::str::from_utf8(&block[idx..(idx + value_len)]) {
+                Ok(value) => result.push((key, String::from(value))),
+                Err(_) => return (Vec::new(), root_addr)
+            }
+            idx += value_len;
+        }
+
+        (result, root_addr)
+    }
+
+    /// Parse a single type out of schema bytes
+    #[doc(hidden)]
+    pub fn _type_from_bytes(mut cache: Vec<NP_Parsed_Schema>, address: usize, bytes: &[u8]) -> (bool, Vec<NP_Parsed_Schema>) {
         let this_type = NP_TypeKeys::from(bytes[address]);
         match this_type {
             NP_TypeKeys::None       => {  cache.push(NP_Parsed_Schema::default());  (false, cache) }
@@ -1500,6 +1901,8 @@ impl NP_Schema {
             NP_TypeKeys::Float      => {          f32::from_bytes_to_schema(cache, address, bytes) }
             NP_TypeKeys::Double     => {          f64::from_bytes_to_schema(cache, address, bytes) }
             NP_TypeKeys::Decimal    => {       NP_Dec::from_bytes_to_schema(cache, address, bytes) }
+            NP_TypeKeys::Decimal128 => {    NP_Dec128::from_bytes_to_schema(cache, address, bytes) }
+            NP_TypeKeys::SortedUints => { NP_SortedUints::from_bytes_to_schema(cache, address, bytes) }
             NP_TypeKeys::Boolean    => {         bool::from_bytes_to_schema(cache, address, bytes) }
             NP_TypeKeys::Geo        => {       NP_Geo::from_bytes_to_schema(cache, address, bytes) }
             NP_TypeKeys::Uuid       => {      NP_UUID::from_bytes_to_schema(cache, address, bytes) }
@@ -1521,7 +1924,60 @@ impl NP_Schema {
     /// 
     /// If you need a quick way to convert JSON to schema bytes without firing up an NP_Factory, this will do the trick.
     /// 
+    /// Root level `$id`, `title` and `description` strings are preserved in the schema bytes and emitted again by `to_json`.
+    /// 
+    /// Any type can have `"required": true`, see [`NP_Buffer::assert_complete`](../buffer/struct.NP_Buffer.html#method.assert_complete).
+    /// 
     pub fn from_json(schema: Vec<NP_Parsed_Schema>, json_schema: &Box<NP_JSON>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
+        let is_root = schema.len() == 0;
+
+        let schema_addr = schema.len();
+
+        let (sortable, mut schema_bytes, mut parsed) = NP_Schema::_type_from_json(schema, json_schema)?;
+
+        if let NP_JSON::True = json_schema["required"] {
+            schema_bytes.insert(0, REQUIRED_TAG);
+            parsed[schema_addr].all_props.insert("required", NP_Schema_Property::TRUE)?;
+        }
+
+        if is_root {
+            let mut block: Vec<u8> = Vec::new();
+
+            for (key_idx, key) in ROOT_META_KEYS.iter().enumerate() {
+                match &json_schema[*key] {
+                    NP_JSON::String(value) => {
+                        if value.len() > u16::MAX as usize {
+                            let mut err_msg = String::from("Root schema property is too long! ");
+                            err_msg.push_str(key);
+                            return Err(NP_Error::new(err_msg.as_str()))
+                        }
+                        block.push(key_idx as u8);
+                        block.extend_from_slice(&(value.len() as u16).to_be_bytes());
+                        block.extend_from_slice(value.as_bytes());
+                        parsed[0].all_props.insert(key, NP_Schema_Property::STRING { source: value.clone() })?;
+                    },
+                    _ => { }
+                }
+            }
+
+            if block.len() > 0 {
+                if block.len() > u16::MAX as usize {
+                    return Err(NP_Error::new("Root schema properties are too long!"))
+                }
+                let mut header: Vec<u8> = Vec::with_capacity(block.len() + 3);
+                header.push(ROOT_META_TAG);
+                header.extend_from_slice(&(block.len() as u16).to_be_bytes());
+                header.extend(block);
+                schema_bytes.splice(0..0, header);
+            }
+        }
+
+        Ok((sortable, schema_bytes, parsed))
+    }
+
+    /// Parse a single type from a JSON object
+    #[doc(hidden)]
+    pub fn _type_from_json(schema: Vec<NP_Parsed_Schema>, json_schema: &Box<NP_JSON>) -> Result<(bool, Vec<u8>, Vec<NP_Parsed_Schema>), NP_Error> {
 
         match &json_schema["type"] {
             NP_JSON::String(x) => {
@@ -1554,6 +2010,9 @@ impl NP_Schema {
                     "f64"      => {       f64::from_json_to_schema(schema, &json_schema) },
                     "double"   => {       f64::from_json_to_schema(schema, &json_schema) },
                     "dec"      => {    NP_Dec::from_json_to_schema(schema, &json_schema) },
+                    "decimal128" => { NP_Dec128::from_json_to_schema(schema, &json_schema) },
+                    "dec128"   => { NP_Dec128::from_json_to_schema(schema, &json_schema) },
+                    "sorted_uints" => { NP_SortedUints::from_json_to_schema(schema, &json_schema) },
                     "decimal"  => {    NP_Dec::from_json_to_schema(schema, &json_schema) },
                     "bool"     => {      bool::from_json_to_schema(schema, &json_schema) },
                     "boolean"  => {      bool::from_json_to_schema(schema, &json_schema) },
@@ -1586,3 +2045,229 @@ impl NP_Schema {
         }
     }
 }
+
+/// Build the compact schema bytes for a scalar type directly, without going through JSON or IDL.
+/// 
+/// The bytes are the same ones `NP_Factory::export_schema_bytes` produces and can be loaded with `NP_Factory::new_bytes`.  Each type starts with it's `NP_TypeKeys` byte, the layouts built here are:
+/// 
+/// | Type | Bytes |
+/// |------|-------|
+/// | integers | `[key][flags][default, big endian, only if set][min][max]`, bit 0 of flags is the default flag, an `i64` with a timestamp `format` keeps it in bits 1-2, bits 3 and 4 flag a `min` and `max` stored at the very end, bits 5-6 mark a `"min"` or `"max"` default |
+/// | `float` / `double` | integer layout with `[options]` between the default and the range, bit 0 is `whole_as_int` and the bits above it hold `display_places + 1` (0 when unset) |
+/// | `decimal` | `[key][exp][flags][default num as i64, big endian, only if set]`, bit 0 of flags is the default flag and the bits above it hold the rounding mode |
+/// 
+/// Integers get their range, timestamp format and `"min"`/`"max"` defaults from [`range`](#method.range), [`timestamp_format`](#method.timestamp_format) and [`default_bound`](#method.default_bound).  Float ranges and bound defaults aren't supported here yet, build those schemas from JSON or IDL.
+/// 
+/// A required type is prefixed with a `254` byte.
+/// 
+/// ```
+/// use no_proto::error::NP_Error;
+/// use no_proto::NP_Factory;
+/// use no_proto::schema::{NP_SchemaBytesBuilder, NP_TypeKeys};
+/// use no_proto::pointer::dec::{NP_Dec, NP_RoundMode};
+/// use no_proto::pointer::numbers::NP_NumberBound;
+/// 
+/// let bytes = NP_SchemaBytesBuilder::integer(NP_TypeKeys::Uint16, Some(20))?.finish();
+/// assert_eq!(bytes, vec![NP_TypeKeys::Uint16 as u8, 1, 0, 20]);
+/// 
+/// let bytes = NP_SchemaBytesBuilder::decimal(2, Some(NP_Dec::new(15, 1)), NP_RoundMode::Truncate)?.required().finish();
+/// assert_eq!(bytes, vec![254, NP_TypeKeys::Decimal as u8, 2, 1, 0, 0, 0, 0, 0, 0, 0, 150]);
+/// 
+/// let factory = NP_Factory::new_bytes(&bytes)?;
+/// assert_eq!(factory.export_schema_bytes(), &bytes[..]);
+/// 
+/// let bytes = NP_SchemaBytesBuilder::integer(NP_TypeKeys::Uint8, None)?.range(Some(10), Some(20))?.default_bound(NP_NumberBound::Max)?.finish();
+/// assert_eq!(bytes, vec![NP_TypeKeys::Uint8 as u8, 1 | (1 << 3) | (1 << 4) | (2 << 5), 20, 10, 20]);
+/// 
+/// # Ok::<(), NP_Error>(()) 
+/// ```
+#[derive(Debug, Clone)]
+pub struct NP_SchemaBytesBuilder {
+    bytes: Vec<u8>,
+    required: bool
+}
+
+impl NP_SchemaBytesBuilder {
+
+    /// An integer type (`i8` through `u64`) with an optional default, errors if the default doesn't fit the type.
+    pub fn integer(kind: NP_TypeKeys, default: Option<i128>) -> Result<Self, NP_Error> {
+        use core::convert::TryFrom;
+
+        let out_of_range = || NP_Error::new("Integer default does not fit the schema type!");
+
+        let default_bytes: Option<Vec<u8>> = match (kind, default) {
+            (NP_TypeKeys::Int8, Some(x))   => Some(i8::try_from(x).map_err(|_| out_of_range())?.to_be_bytes().to_vec()),
+            (NP_TypeKeys::Int16, Some(x))  => Some(i16::try_from(x).map_err(|_| out_of_range())?.to_be_bytes().to_vec()),
+            (NP_TypeKeys::Int32, Some(x))  => Some(i32::try_from(x).map_err(|_| out_of_range())?.to_be_bytes().to_vec()),
+            (NP_TypeKeys::Int64, Some(x))  => Some(i64::try_from(x).map_err(|_| out_of_range())?.to_be_bytes().to_vec()),
+            (NP_TypeKeys::Uint8, Some(x))  => Some(u8::try_from(x).map_err(|_| out_of_range())?.to_be_bytes().to_vec()),
+            (NP_TypeKeys::Uint16, Some(x)) => Some(u16::try_from(x).map_err(|_| out_of_range())?.to_be_bytes().to_vec()),
+            (NP_TypeKeys::Uint32, Some(x)) => Some(u32::try_from(x).map_err(|_| out_of_range())?.to_be_bytes().to_vec()),
+            (NP_TypeKeys::Uint64, Some(x)) => Some(u64::try_from(x).map_err(|_| out_of_range())?.to_be_bytes().to_vec()),
+            (NP_TypeKeys::Int8, None) | (NP_TypeKeys::Int16, None) | (NP_TypeKeys::Int32, None) | (NP_TypeKeys::Int64, None) |
+            (NP_TypeKeys::Uint8, None) | (NP_TypeKeys::Uint16, None) | (NP_TypeKeys::Uint32, None) | (NP_TypeKeys::Uint64, None) => None,
+            _ => return Err(NP_Error::new("NP_SchemaBytesBuilder::integer requires an integer type!"))
+        };
+
+        Ok(Self { bytes: Self::with_default(kind, default_bytes), required: false })
+    }
+
+    /// A `float` type, `display_places` can be at most 18.
+    pub fn float(default: Option<f32>, whole_as_int: bool, display_places: Option<u8>) -> Result<Self, NP_Error> {
+        let places = crate::pointer::numbers::np_check_display_places(display_places.map(|x| x as i64))?;
+        let mut bytes = Self::with_default(NP_TypeKeys::Float, default.map(|x| x.to_be_bytes().to_vec()));
+        bytes.push(crate::pointer::numbers::np_float_options_byte(whole_as_int, places));
+        Ok(Self { bytes, required: false })
+    }
+
+    /// A `double` type, `display_places` can be at most 18.
+    pub fn double(default: Option<f64>, whole_as_int: bool, display_places: Option<u8>) -> Result<Self, NP_Error> {
+        let places = crate::pointer::numbers::np_check_display_places(display_places.map(|x| x as i64))?;
+        let mut bytes = Self::with_default(NP_TypeKeys::Double, default.map(|x| x.to_be_bytes().to_vec()));
+        bytes.push(crate::pointer::numbers::np_float_options_byte(whole_as_int, places));
+        Ok(Self { bytes, required: false })
+    }
+
+    /// A `decimal` type, the default must be exactly representable with `exp` decimal places.
+    pub fn decimal(exp: u8, default: Option<NP_Dec>, rounding: NP_RoundMode) -> Result<Self, NP_Error> {
+        let mut bytes: Vec<u8> = vec![NP_TypeKeys::Decimal as u8, exp];
+
+        let flags = rounding.to_schema_bits() << crate::pointer::dec::ROUNDING_SHIFT;
+
+        match default {
+            Some(value) => {
+                let num = match value.to_minor_units(exp) {
+                    Some(x) => x,
+                    None => return Err(NP_Error::new("Decimal default can't be stored exactly with the schema 'exp'!"))
+                };
+                bytes.push(flags | 1);
+                bytes.extend_from_slice(&num.to_be_bytes());
+            },
+            None => {
+                bytes.push(flags);
+            }
+        }
+
+        Ok(Self { bytes, required: false })
+    }
+
+    /// Set the `min` and `max` of an integer type, errors if either doesn't fit the type or the default is outside them.
+    pub fn range(mut self, min: Option<i128>, max: Option<i128>) -> Result<Self, NP_Error> {
+        let (size, type_min, type_max) = Self::integer_layout(&self.bytes)?;
+
+        if self.bytes[1] & (crate::pointer::numbers::RANGE_MIN_BIT | crate::pointer::numbers::RANGE_MAX_BIT) != 0 {
+            return Err(NP_Error::new("NP_SchemaBytesBuilder::range can only be set once!"))
+        }
+        if self.bytes[1] >> crate::pointer::numbers::DEFAULT_BOUND_SHIFT != 0 {
+            return Err(NP_Error::new("NP_SchemaBytesBuilder::range must be set before default_bound!"))
+        }
+        if min.map(|x| x < type_min || x > type_max).unwrap_or(false) || max.map(|x| x < type_min || x > type_max).unwrap_or(false) {
+            return Err(NP_Error::new("Integer range does not fit the schema type!"))
+        }
+
+        let default = Self::integer_default(&self.bytes, size, type_min);
+        crate::pointer::numbers::np_check_schema_range(default, min, max)?;
+
+        if let Some(x) = min {
+            self.bytes[1] |= crate::pointer::numbers::RANGE_MIN_BIT;
+            self.bytes.extend_from_slice(&x.to_be_bytes()[(16 - size)..]);
+        }
+        if let Some(x) = max {
+            self.bytes[1] |= crate::pointer::numbers::RANGE_MAX_BIT;
+            self.bytes.extend_from_slice(&x.to_be_bytes()[(16 - size)..]);
+        }
+
+        Ok(self)
+    }
+
+    /// Set the timestamp `format` of an `i64` type.
+    pub fn timestamp_format(mut self, format: NP_TimestampFormat) -> Result<Self, NP_Error> {
+        if self.bytes[0] != NP_TypeKeys::Int64 as u8 {
+            return Err(NP_Error::new("Only i64 types support the 'format' property!"))
+        }
+        self.bytes[1] &= !(3 << crate::pointer::numbers::TIMESTAMP_SHIFT);
+        self.bytes[1] |= NP_TimestampFormat::to_schema_bits(Some(format)) << crate::pointer::numbers::TIMESTAMP_SHIFT;
+        Ok(self)
+    }
+
+    /// Use the `min` or `max` of an integer type as it's default, set the [`range`](#method.range) first so the bound resolves against it.
+    pub fn default_bound(mut self, bound: NP_NumberBound) -> Result<Self, NP_Error> {
+        let (size, type_min, type_max) = Self::integer_layout(&self.bytes)?;
+
+        if self.bytes[1] & 1 == 1 {
+            return Err(NP_Error::new("NP_SchemaBytesBuilder::default_bound can't replace an existing default!"))
+        }
+
+        // the range values sit at the end, in order, after the (missing) default
+        let mut range = self.bytes[2..].chunks(size).map(|chunk| Self::integer_from_be(chunk, type_min));
+        let min = if self.bytes[1] & crate::pointer::numbers::RANGE_MIN_BIT != 0 { range.next() } else { None };
+        let max = if self.bytes[1] & crate::pointer::numbers::RANGE_MAX_BIT != 0 { range.next() } else { None };
+
+        let value = bound.resolve(min, max, type_min, type_max);
+        self.bytes[1] |= 1 | (NP_NumberBound::to_schema_bits(Some(bound)) << crate::pointer::numbers::DEFAULT_BOUND_SHIFT);
+        let tail = self.bytes.split_off(2);
+        self.bytes.extend_from_slice(&value.to_be_bytes()[(16 - size)..]);
+        self.bytes.extend(tail);
+
+        Ok(self)
+    }
+
+    /// Mark this type as required, see [`NP_Buffer::assert_complete`](../buffer/struct.NP_Buffer.html#method.assert_complete).
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    /// Get the finished schema bytes.
+    pub fn finish(self) -> Vec<u8> {
+        if self.required {
+            let mut bytes = Vec::with_capacity(self.bytes.len() + 1);
+            bytes.push(REQUIRED_TAG);
+            bytes.extend(self.bytes);
+            bytes
+        } else {
+            self.bytes
+        }
+    }
+
+    /// Byte size and value range of the integer type these bytes are for.
+    fn integer_layout(bytes: &[u8]) -> Result<(usize, i128, i128), NP_Error> {
+        match NP_TypeKeys::from(bytes[0]) {
+            NP_TypeKeys::Int8   => Ok((1, i8::MIN as i128, i8::MAX as i128)),
+            NP_TypeKeys::Int16  => Ok((2, i16::MIN as i128, i16::MAX as i128)),
+            NP_TypeKeys::Int32  => Ok((4, i32::MIN as i128, i32::MAX as i128)),
+            NP_TypeKeys::Int64  => Ok((8, i64::MIN as i128, i64::MAX as i128)),
+            NP_TypeKeys::Uint8  => Ok((1, 0, u8::MAX as i128)),
+            NP_TypeKeys::Uint16 => Ok((2, 0, u16::MAX as i128)),
+            NP_TypeKeys::Uint32 => Ok((4, 0, u32::MAX as i128)),
+            NP_TypeKeys::Uint64 => Ok((8, 0, u64::MAX as i128)),
+            _ => Err(NP_Error::new("Only integer types support a range or default bound in NP_SchemaBytesBuilder!"))
+        }
+    }
+
+    fn integer_default(bytes: &[u8], size: usize, type_min: i128) -> Option<i128> {
+        if bytes[1] & 1 == 1 { Some(Self::integer_from_be(&bytes[2..(2 + size)], type_min)) } else { None }
+    }
+
+    fn integer_from_be(bytes: &[u8], type_min: i128) -> i128 {
+        // sign extend signed types
+        let fill = if type_min < 0 && bytes[0] & 0x80 != 0 { 0xFF } else { 0 };
+        let mut be_bytes = [fill; 16];
+        be_bytes[(16 - bytes.len())..].copy_from_slice(bytes);
+        i128::from_be_bytes(be_bytes)
+    }
+
+    fn with_default(kind: NP_TypeKeys, default: Option<Vec<u8>>) -> Vec<u8> {
+        let mut bytes: Vec<u8> = vec![kind as u8];
+        match default {
+            Some(value) => {
+                bytes.push(1);
+                bytes.extend(value);
+            },
+            None => {
+                bytes.push(0);
+            }
+        }
+        bytes
+    }
+}